@@ -0,0 +1,391 @@
+//! `fom check`: checks each document in turn, up to the requested level, and
+//! prints each problem found as `file:line:column: error: path: message`, and
+//! each warning as `file:line:column: warning: path: message`. Level 3
+//! then checks the documents together, as the modules of one object model,
+//! and level 4 runs the lints over each of them, including those for the NETN
+//! naming conventions with `--netn`. The files, levels and lints are those of
+//! the `fom-check.toml` configuration of the project, unless the arguments
+//! name others.
+
+use clap::ArgMatches;
+use fom_tools_lib::config::{CheckConfig, ConfigError, CONFIG_FILE_NAME};
+use fom_tools_lib::conformance::{check_conformance, Profile, Severity};
+use fom_tools_lib::dom::Element;
+use fom_tools_lib::lint::{Level as LintLevel, LintRegistry};
+use fom_tools_lib::netn::NetnPack;
+use fom_tools_lib::schema::Schema;
+use fom_tools_lib::{
+    parse_object_model_with_options, FomErrorKind, NamespacePolicy, ParseOptions, SchemaVersion,
+};
+use std::env;
+use std::fmt::Display;
+use std::fs::File;
+use std::path::Path;
+use xml::common::Position;
+
+/// A level of conformance, as listed in docs/fom-check/design.md.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    /// The document is well-formed XML.
+    Xml = 1,
+    /// The document conforms to the IEEE 1516.2-2010 DIF schema. One of IEEE
+    /// 1516.2-2025 is checked against the same schema, as the 2010 structure
+    /// plus the extendable record encodings, since the 2025 schema is not
+    /// bundled.
+    Dif = 2,
+    /// The documents, together, conform to IEEE 1516.2-2010.
+    Conformance = 3,
+    /// The documents follow the style conventions that the lints check.
+    Style = 4,
+}
+
+/// Check the files named by the arguments, or those that the configuration
+/// includes, and return the exit code: 0 if no problems were found, and 1
+/// otherwise. Warnings are not problems.
+pub fn run(matches: &ArgMatches) -> i32 {
+    if let Some(id) = matches.value_of("explain") {
+        let mut registry = LintRegistry::new();
+        registry.register_pack(&NetnPack::new());
+        return explain(&registry, id);
+    }
+    let mut config = match read_config(matches) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    if matches.is_present("netn") && !config.packs.iter().any(|pack| pack == "netn") {
+        config.packs.push(String::from("netn"));
+    }
+    let level = matches
+        .value_of("level")
+        .and_then(|level| level.parse().ok())
+        .or(config.level);
+    let level = match level {
+        Some(1) => Level::Xml,
+        Some(2) => Level::Dif,
+        Some(3) => Level::Conformance,
+        _ => Level::Style,
+    };
+    let files: Vec<String> = match matches.values_of("FILES") {
+        Some(files) => files
+            .filter(|file| {
+                let excluded = config.is_excluded(Path::new(file));
+                if excluded {
+                    eprintln!("{}: not checked, as the configuration excludes it", file);
+                }
+                !excluded
+            })
+            .map(String::from)
+            .collect(),
+        None => config
+            .included_files()
+            .iter()
+            .map(|file| display_path(file))
+            .collect(),
+    };
+    if files.is_empty() {
+        eprintln!(
+            "no files to check: name them, or include them in {}",
+            CONFIG_FILE_NAME
+        );
+        return 1;
+    }
+    let mut problems = 0;
+    let mut warnings = 0;
+    let mut modules = Vec::new();
+    for file in &files {
+        let checked = check_file(file, level, config.omt_version);
+        problems += checked.problems;
+        warnings += checked.warnings;
+        modules.extend(checked.module);
+    }
+    // Levels 3 and 4 need documents that pass levels 1 and 2. A document that
+    // could not be validated against the DIF schema has already been counted
+    // as a problem, but is still checked at level 4. The schemas of level 3
+    // cannot check it either, so the modules are checked without it.
+    let valid = modules.len() == files.len();
+    if level >= Level::Conformance {
+        if !valid {
+            eprintln!("not checked at level 3 or above, as there are problems with the documents");
+        } else {
+            let profile = if matches.is_present("fdd") || config.fdd {
+                Profile::Fdd
+            } else {
+                Profile::Omt
+            };
+            for module in modules.iter().filter(|module| !module.validated) {
+                println!(
+                    "{}: note: not checked at level 3, as it could not be validated against \
+                     the DIF schema",
+                    module.file
+                );
+            }
+            let roots: Vec<&Element> = modules.iter().map(|module| &module.root).collect();
+            for finding in check_conformance(&roots, profile) {
+                println!("{}:{}", modules[finding.document].file, finding);
+                if let Some(related) = &finding.related {
+                    let file = related
+                        .document
+                        .map_or("HLAstandardMIM.xml", |i| &modules[i].file);
+                    println!(
+                        "{}:{}: note: {}: defined here",
+                        file, related.position, related.path
+                    );
+                }
+                match finding.severity {
+                    Severity::Error => problems += 1,
+                    Severity::Warning => warnings += 1,
+                }
+            }
+        }
+    }
+    if level >= Level::Style && valid {
+        for Module {
+            file,
+            root,
+            validated,
+        } in &modules
+        {
+            // The namespace of a document that could not be validated has
+            // already been reported, as have the problems in reading it.
+            let options = ParseOptions {
+                unknown_namespace: NamespacePolicy::Warn,
+                ..ParseOptions::default()
+            };
+            let fom = match parse_object_model_with_options(root, &options) {
+                Ok(parsed) => parsed.object_model,
+                Err(errors) => {
+                    for error in errors.iter().filter(|_| *validated) {
+                        report(file, error.position, "error", &error.path, error.message());
+                        problems += 1;
+                    }
+                    continue;
+                }
+            };
+            for diagnostic in config.registry(Path::new(file)).run(&fom) {
+                println!("{}:{}", file, diagnostic);
+                match diagnostic.level {
+                    LintLevel::Deny => problems += 1,
+                    _ => warnings += 1,
+                }
+            }
+        }
+    }
+    if warnings > 0 {
+        eprintln!("{}", plural(warnings, "warning"));
+    }
+    if problems > 0 {
+        eprintln!("{} found", plural(problems, "problem"));
+        1
+    } else {
+        0
+    }
+}
+
+/// Return the configuration named by the arguments, or else the one found in
+/// the current directory or its ancestors, or else the default one.
+fn read_config(matches: &ArgMatches) -> Result<CheckConfig, ConfigError> {
+    if matches.is_present("no-config") {
+        return Ok(CheckConfig::default());
+    }
+    if let Some(file) = matches.value_of("config") {
+        return CheckConfig::read(file);
+    }
+    let discovered = env::current_dir()
+        .ok()
+        .and_then(|directory| CheckConfig::discover(&directory));
+    match discovered {
+        Some(file) => CheckConfig::read(display_path(&file)),
+        None => Ok(CheckConfig::default()),
+    }
+}
+
+/// Return the path relative to the current directory, if it is within it.
+fn display_path(path: &Path) -> String {
+    let relative = env::current_dir()
+        .ok()
+        .and_then(|directory| path.strip_prefix(directory).ok().map(Path::to_path_buf));
+    relative
+        .as_deref()
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Print the explanation of the lint with the provided ID, and return the
+/// exit code.
+fn explain(registry: &LintRegistry, id: &str) -> i32 {
+    match registry.get(id) {
+        Some(lint) => {
+            println!(
+                "{} ({}, {} by default): {}",
+                lint.id(),
+                lint.category(),
+                lint.default_level(),
+                lint.description()
+            );
+            println!();
+            println!("{}", lint.explanation());
+            0
+        }
+        None => {
+            eprintln!("unknown lint '{}'", id);
+            1
+        }
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+}
+
+/// Print a problem or warning found in the file, in the form of the findings
+/// of level 3 and the diagnostics of level 4.
+fn report(file: &str, position: impl Display, severity: &str, path: &str, message: impl Display) {
+    println!("{}:{}: {}: {}: {}", file, position, severity, path, message);
+}
+
+/// A document that has passed levels 1 and 2, to be checked at levels 3 and 4.
+struct Module {
+    file: String,
+    root: Element,
+    /// Whether the document was validated against the DIF schema. One in a
+    /// namespace the schema does not accept is read into an object model at
+    /// level 2 instead, and the problems found in reading it are reported
+    /// there. It is checked at level 4, but not at level 3.
+    validated: bool,
+}
+
+/// What checking a file at levels 1 and 2 found.
+struct Checked {
+    problems: usize,
+    warnings: usize,
+    /// The document, if it is to be checked at levels 3 and 4.
+    module: Option<Module>,
+}
+
+/// Check the file up to the level, at most level 2, and print the problems
+/// found.
+fn check_file(file: &str, level: Level, omt_version: Option<SchemaVersion>) -> Checked {
+    let mut checked = Checked {
+        problems: 0,
+        warnings: 0,
+        module: None,
+    };
+    let document = match File::open(file) {
+        Ok(document) => document,
+        Err(e) => {
+            println!("{}: error: {}", file, e);
+            checked.problems += 1;
+            return checked;
+        }
+    };
+    let root = match Element::parse(document) {
+        Ok(root) => root,
+        Err(e) => {
+            let position = e.position();
+            println!(
+                "{}:{}:{}: error: {}",
+                file,
+                position.row + 1,
+                position.column + 1,
+                e.msg()
+            );
+            checked.problems += 1;
+            return checked;
+        }
+    };
+    if level < Level::Dif {
+        return checked;
+    }
+    let root_path = format!("/{}", root.name);
+    let version = root
+        .namespace
+        .as_deref()
+        .and_then(SchemaVersion::from_namespace);
+    if let (Some(version), Some(target)) = (version, omt_version) {
+        if version != target {
+            let message = format!(
+                "the document is written against {}, rather than the target {}",
+                version, target
+            );
+            // The document is still checked, against the schema of its own
+            // version.
+            report(file, root.position, "error", &root_path, message);
+            checked.problems += 1;
+        }
+    }
+    let schema = Schema::dif();
+    if !schema.accepts_namespace(root.namespace.as_deref()) {
+        let message = "not checked against the DIF schema, which accepts documents of \
+                       IEEE 1516.2-2010, and of IEEE 1516.2-2025 as the 2010 structure \
+                       plus the extendable record encodings";
+        report(file, root.position, "error", &root_path, message);
+        checked.problems += 1;
+        // Report what reading the document finds instead. Its namespace has
+        // been reported already.
+        let options = ParseOptions {
+            unknown_namespace: NamespacePolicy::Warn,
+            ..ParseOptions::default()
+        };
+        let is_namespace = |kind: &FomErrorKind| {
+            matches!(
+                kind,
+                FomErrorKind::UnknownNamespace | FomErrorKind::MissingNamespace
+            )
+        };
+        match parse_object_model_with_options(&root, &options) {
+            Ok(parsed) => {
+                for warning in parsed.warnings.iter().filter(|w| !is_namespace(&w.kind)) {
+                    report(
+                        file,
+                        warning.position,
+                        "warning",
+                        &warning.path,
+                        warning.message(),
+                    );
+                    checked.warnings += 1;
+                }
+            }
+            Err(errors) => {
+                for error in errors.iter() {
+                    report(file, error.position, "error", &error.path, error.message());
+                    checked.problems += 1;
+                }
+            }
+        }
+        checked.module = Some(Module {
+            file: String::from(file),
+            root,
+            validated: false,
+        });
+        return checked;
+    }
+    if version == Some(SchemaVersion::Ieee1516_2025) {
+        let message = "checked against the IEEE 1516.2-2010 DIF schema, as the 2010 \
+                       structure plus the extendable record encodings, rather than against \
+                       the IEEE 1516.2-2025 schema";
+        report(file, root.position, "note", &root_path, message);
+    }
+    let violations = schema.validate(&root);
+    for violation in &violations {
+        report(
+            file,
+            violation.position,
+            "error",
+            &violation.path,
+            violation.message(),
+        );
+    }
+    checked.problems += violations.len();
+    if violations.is_empty() {
+        checked.module = Some(Module {
+            file: String::from(file),
+            root,
+            validated: true,
+        });
+    }
+    checked
+}
@@ -1,10 +1,61 @@
-use std::fs::File;
+use clap::{App, AppSettings, Arg, SubCommand};
+use std::process;
+
+mod check;
 
 fn main() {
-    println!("Hello, world!");
-    // let fom_filename = "modules/NETN-FOM-3.0-rc1/modules/NETN-BASE.xml";
-    let fom_filename = "modules/RPR-FOM_v2.0/RPR-Base_v2.0.xml";
-    if let Ok(fom_file) = File::open(fom_filename) {
-        let _ = fom_tools_lib::parse(fom_file);
+    let matches = App::new("fom")
+        .about("Process HLA object models and object model modules")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("check")
+                .about("Check object models for conformance with the HLA OMT standard")
+                .arg(
+                    Arg::with_name("level")
+                        .long("level")
+                        .takes_value(true)
+                        .possible_values(&["1", "2", "3", "4"])
+                        .help("The highest level of checks to run: 1 for well-formed XML, 2 for the DIF schema, 3 for conformance of the documents together, 4 for lints (the default)"),
+                )
+                .arg(
+                    Arg::with_name("fdd")
+                        .long("fdd")
+                        .help("Check conformance against the FDD schema, rather than the OMT conformance schema"),
+                )
+                .arg(
+                    Arg::with_name("netn")
+                        .long("netn")
+                        .help("Also run the lints for the NETN naming conventions"),
+                )
+                .arg(
+                    Arg::with_name("config")
+                        .long("config")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .conflicts_with("no-config")
+                        .help("Read the configuration from FILE, rather than the fom-check.toml file of the current directory or its ancestors"),
+                )
+                .arg(
+                    Arg::with_name("no-config")
+                        .long("no-config")
+                        .help("Ignore any fom-check.toml file"),
+                )
+                .arg(
+                    Arg::with_name("explain")
+                        .long("explain")
+                        .takes_value(true)
+                        .value_name("ID")
+                        .help("Explain the lint with the provided ID"),
+                )
+                .arg(
+                    Arg::with_name("FILES")
+                        .multiple(true)
+                        .help("The object model documents to check, rather than those the configuration includes"),
+                ),
+        )
+        .get_matches();
+
+    if let Some(matches) = matches.subcommand_matches("check") {
+        process::exit(check::run(matches));
     }
 }
@@ -0,0 +1,148 @@
+//! Reads each bundled module with each parse backend and reports the time
+//! taken and the most memory allocated at once while reading it. Only the
+//! modules whose path contains one of the arguments are read, if any are
+//! given.
+//!
+//! The time is the median of repeated reads of the module from memory. The
+//! memory is that allocated above what was allocated before the read began,
+//! and includes the object model that was read, whose size is also reported.
+
+use fom_tools_lib::{parse_reader_with_options, ParseBackend, ParseOptions, Parsed};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The system allocator, keeping count of the memory allocated and of the most
+/// allocated at once since the count was last reset.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, p: *mut u8, layout: Layout) {
+        System.dealloc(p, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, p: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let p = System.realloc(p, layout, new_size);
+        if !p.is_null() {
+            if new_size > layout.size() {
+                let grown = new_size - layout.size();
+                let allocated = ALLOCATED.fetch_add(grown, Ordering::Relaxed) + grown;
+                PEAK.fetch_max(allocated, Ordering::Relaxed);
+            } else {
+                ALLOCATED.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        p
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Reads are repeated until this much time has passed, or `MAX_READS` reads
+/// have been made.
+const MEASURE_FOR: Duration = Duration::from_secs(2);
+const MAX_READS: usize = 50;
+
+fn bundled_modules() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+    let mut modules = vec![root.join("omt").join("HLAstandardMIM.xml")];
+    for directory in ["RPR-FOM_v2.0", "NETN-FOM-3.0"] {
+        let mut paths: Vec<PathBuf> = root
+            .join("modules")
+            .join(directory)
+            .read_dir()
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension() == Some("xml".as_ref()))
+            .collect();
+        paths.sort();
+        modules.extend(paths);
+    }
+    modules
+}
+
+fn parse(document: &[u8], backend: ParseBackend) -> Parsed {
+    let options = ParseOptions {
+        backend,
+        ..ParseOptions::default()
+    };
+    parse_reader_with_options(document, &options).unwrap()
+}
+
+/// Return the most memory allocated at once while reading the document, and
+/// the memory still allocated once it has been read, which is that of the
+/// object model.
+fn measure_memory(document: &[u8], backend: ParseBackend) -> (usize, usize) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let parsed = parse(document, backend);
+    let retained = ALLOCATED.load(Ordering::Relaxed) - before;
+    drop(parsed);
+    (PEAK.load(Ordering::Relaxed) - before, retained)
+}
+
+/// Return the median time taken to read the document.
+fn measure_time(document: &[u8], backend: ParseBackend) -> Duration {
+    let mut times = Vec::new();
+    let started = Instant::now();
+    while times.len() < MAX_READS && (times.len() < 3 || started.elapsed() < MEASURE_FOR) {
+        let start = Instant::now();
+        parse(document, backend);
+        times.push(start.elapsed());
+    }
+    times.sort();
+    times[times.len() / 2]
+}
+
+fn kib(bytes: usize) -> String {
+    format!("{:.0} KiB", bytes as f64 / 1024.0)
+}
+
+fn main() {
+    // Cargo passes `--bench`, which is not a filter.
+    let filters: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+
+    println!(
+        "{:<28} {:>9} {:>10} {:>10} {:>11} {:>11} {:>10}",
+        "module", "size", "dom", "streaming", "dom", "streaming", "model"
+    );
+    for path in bundled_modules() {
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        if !filters.is_empty() && !filters.iter().any(|filter| name.contains(filter.as_str())) {
+            continue;
+        }
+        let document = std::fs::read(&path).unwrap();
+        let dom_time = measure_time(&document, ParseBackend::Dom);
+        let streaming_time = measure_time(&document, ParseBackend::Streaming);
+        let (dom_memory, model) = measure_memory(&document, ParseBackend::Dom);
+        let (streaming_memory, _) = measure_memory(&document, ParseBackend::Streaming);
+        println!(
+            "{:<28} {:>9} {:>10.2?} {:>10.2?} {:>11} {:>11} {:>10}",
+            name,
+            kib(document.len()),
+            dom_time,
+            streaming_time,
+            kib(dom_memory),
+            kib(streaming_memory),
+            kib(model),
+        );
+    }
+}
@@ -0,0 +1,1473 @@
+//! Construction of object models in code, for tools that generate FOM modules
+//! from other sources rather than reading them from documents.
+//!
+//! An `ObjectModelBuilder` checks each part of the object model as it is
+//! added: that its name is a valid OMT name, that it does not use the `HLA`
+//! prefix reserved for the standard, and that it is not already taken, and
+//! that the superclass, datatypes, dimensions and transportation types it
+//! refers to have already been added, or are those of the HLA standard MIM.
+//! Parts are therefore added after the parts they refer to, which also keeps
+//! datatypes from containing themselves. The problems found are recorded as
+//! each part is added, and `build` reports every one of them, rather than just
+//! the first.
+//!
+//! ```
+//! use fom_tools_lib::builder::*;
+//! use fom_tools_lib::{to_xml_string, ModelType, WriteOptions};
+//!
+//! let fom = ObjectModelBuilder::new("Vehicles", ModelType::FOM)
+//!     .version("1.0")
+//!     .simple_data(SimpleDataBuilder::new("SpeedMetersPerSecond", "HLAfloat32BE").units("m/s"))
+//!     .object_class(
+//!         "HLAobjectRoot",
+//!         ObjectClassBuilder::new("Vehicle")
+//!             .attribute(AttributeBuilder::new("Speed", "SpeedMetersPerSecond")),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! let xml = to_xml_string(&fom, &WriteOptions::default()).unwrap();
+//! ```
+
+use crate::datatypes::NOT_APPLICABLE;
+use crate::*;
+use std::collections::HashSet;
+use std::fmt;
+
+/// The reason a part of an object model could not be added by a builder.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildErrorKind {
+    /// The name is empty, or has characters other than letters, digits,
+    /// hyphens and underscores, or does not start with a letter or underscore.
+    InvalidName,
+    /// The name starts with `HLA`, in any case, which the OMT reserves for the
+    /// parts of object models defined by the HLA standard.
+    ReservedName,
+    /// Another part of the same kind, in the same scope, has the name.
+    DuplicateName,
+    /// The named class, datatype, dimension or transportation is not part of
+    /// the object model, nor of the HLA standard MIM.
+    UndeclaredReference,
+}
+
+/// A problem with a part of an object model being built.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildError {
+    pub kind: BuildErrorKind,
+    /// The location of the part within the object model, in the form used by
+    /// `FomError`, e.g. `/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Car]`.
+    pub path: String,
+    /// The offending name.
+    pub value: String,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.path)?;
+        match self.kind {
+            BuildErrorKind::InvalidName => write!(f, "invalid name '{}'", self.value),
+            BuildErrorKind::ReservedName => write!(f, "reserved name '{}'", self.value),
+            BuildErrorKind::DuplicateName => write!(f, "duplicate name '{}'", self.value),
+            BuildErrorKind::UndeclaredReference => {
+                write!(f, "'{}' is not declared", self.value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// All of the problems found while building an object model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildErrors(pub Vec<BuildError>);
+
+impl BuildErrors {
+    pub fn iter(&self) -> std::slice::Iter<'_, BuildError> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for BuildErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BuildErrors {}
+
+/// The kinds of part of an object model that other parts refer to by name.
+#[derive(Debug, Clone, Copy)]
+enum Reference {
+    DataType,
+    Dimension,
+    Transportation,
+}
+
+const OBJECT_ROOT: &str = "HLAobjectRoot";
+const INTERACTION_ROOT: &str = "HLAinteractionRoot";
+
+fn reference(name: &str) -> Option<ReferenceType> {
+    Some(ReferenceType {
+        value: String::from(name),
+    })
+}
+
+fn text(value: &str) -> Option<String> {
+    Some(String::from(value))
+}
+
+/// Return why the provided name cannot name a part of an object model, if it
+/// cannot.
+fn check_name(name: &str) -> Option<BuildErrorKind> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        Some(BuildErrorKind::InvalidName)
+    } else if name
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("hla"))
+    {
+        Some(BuildErrorKind::ReservedName)
+    } else {
+        None
+    }
+}
+
+/// Builds an object class, with its attributes and subclasses, to be added to
+/// an `ObjectModelBuilder`.
+#[derive(Debug, Clone)]
+pub struct ObjectClassBuilder {
+    class: ObjectClassType,
+}
+
+impl ObjectClassBuilder {
+    /// Start an object class that can be published and subscribed.
+    pub fn new(name: &str) -> Self {
+        Self {
+            class: ObjectClassType {
+                name: String::from(name),
+                sharing: Some(SharingType::PublishSubscribe),
+                semantics: None,
+                attributes: None,
+                object_classes: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn sharing(mut self, sharing: SharingType) -> Self {
+        self.class.sharing = Some(sharing);
+        self
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.class.semantics = text(semantics);
+        self
+    }
+
+    pub fn attribute(mut self, attribute: AttributeBuilder) -> Self {
+        let attributes = self.class.attributes.get_or_insert_with(Vec::new);
+        attributes.push(attribute.attribute);
+        self
+    }
+
+    pub fn subclass(mut self, class: ObjectClassBuilder) -> Self {
+        let classes = self.class.object_classes.get_or_insert_with(Vec::new);
+        classes.push(class.class);
+        self
+    }
+}
+
+/// Builds an attribute of an object class.
+#[derive(Debug, Clone)]
+pub struct AttributeBuilder {
+    attribute: AttributeType,
+}
+
+impl AttributeBuilder {
+    /// Start an attribute of the named datatype, that can be published and
+    /// subscribed, and that is sent reliably in the order it is received.
+    pub fn new(name: &str, data_type: &str) -> Self {
+        Self {
+            attribute: AttributeType {
+                name: String::from(name),
+                data_type: reference(data_type),
+                update_type: None,
+                update_condition: None,
+                onwership: None,
+                sharing: Some(SharingType::PublishSubscribe),
+                dimensions: None,
+                transportation: reference("HLAreliable"),
+                order: Some(OrderType::Receive),
+                semantics: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn update_type(mut self, update_type: UpdateType) -> Self {
+        self.attribute.update_type = Some(update_type);
+        self
+    }
+
+    pub fn update_condition(mut self, update_condition: &str) -> Self {
+        self.attribute.update_condition = text(update_condition);
+        self
+    }
+
+    pub fn ownership(mut self, ownership: OwnershipType) -> Self {
+        self.attribute.onwership = Some(ownership);
+        self
+    }
+
+    pub fn sharing(mut self, sharing: SharingType) -> Self {
+        self.attribute.sharing = Some(sharing);
+        self
+    }
+
+    pub fn dimension(mut self, dimension: &str) -> Self {
+        let dimensions = self.attribute.dimensions.get_or_insert_with(Vec::new);
+        dimensions.extend(reference(dimension));
+        self
+    }
+
+    pub fn transportation(mut self, transportation: &str) -> Self {
+        self.attribute.transportation = reference(transportation);
+        self
+    }
+
+    pub fn order(mut self, order: OrderType) -> Self {
+        self.attribute.order = Some(order);
+        self
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.attribute.semantics = text(semantics);
+        self
+    }
+}
+
+/// Builds an interaction class, with its parameters and subclasses, to be
+/// added to an `ObjectModelBuilder`.
+#[derive(Debug, Clone)]
+pub struct InteractionClassBuilder {
+    class: InteractionClassType,
+}
+
+impl InteractionClassBuilder {
+    /// Start an interaction class that can be published and subscribed, and
+    /// that is sent reliably in the order it is received.
+    pub fn new(name: &str) -> Self {
+        Self {
+            class: InteractionClassType {
+                name: String::from(name),
+                sharing: Some(SharingType::PublishSubscribe),
+                dimensions: None,
+                transportation: reference("HLAreliable"),
+                order: Some(OrderType::Receive),
+                semantics: None,
+                parameters: None,
+                interaction_classes: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn sharing(mut self, sharing: SharingType) -> Self {
+        self.class.sharing = Some(sharing);
+        self
+    }
+
+    pub fn dimension(mut self, dimension: &str) -> Self {
+        let dimensions = self.class.dimensions.get_or_insert_with(Vec::new);
+        dimensions.extend(reference(dimension));
+        self
+    }
+
+    pub fn transportation(mut self, transportation: &str) -> Self {
+        self.class.transportation = reference(transportation);
+        self
+    }
+
+    pub fn order(mut self, order: OrderType) -> Self {
+        self.class.order = Some(order);
+        self
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.class.semantics = text(semantics);
+        self
+    }
+
+    pub fn parameter(mut self, parameter: ParameterBuilder) -> Self {
+        let parameters = self.class.parameters.get_or_insert_with(Vec::new);
+        parameters.push(parameter.parameter);
+        self
+    }
+
+    pub fn subclass(mut self, class: InteractionClassBuilder) -> Self {
+        let classes = self.class.interaction_classes.get_or_insert_with(Vec::new);
+        classes.push(class.class);
+        self
+    }
+}
+
+/// Builds a parameter of an interaction class.
+#[derive(Debug, Clone)]
+pub struct ParameterBuilder {
+    parameter: ParameterType,
+}
+
+impl ParameterBuilder {
+    /// Start a parameter of the named datatype.
+    pub fn new(name: &str, data_type: &str) -> Self {
+        Self {
+            parameter: ParameterType {
+                name: String::from(name),
+                data_type: reference(data_type),
+                semantics: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.parameter.semantics = text(semantics);
+        self
+    }
+}
+
+/// Builds a basic data representation.
+#[derive(Debug, Clone)]
+pub struct BasicDataBuilder {
+    data_type: BasicDataType,
+}
+
+impl BasicDataBuilder {
+    /// Start a representation of the provided size in bits.
+    pub fn new(name: &str, size: u32, endian: EndianType) -> Self {
+        Self {
+            data_type: BasicDataType {
+                name: String::from(name),
+                size: Some(SizeType {
+                    size: TypedValue::new(size),
+                }),
+                interpretation: None,
+                endian: Some(endian),
+                encoding: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn interpretation(mut self, interpretation: &str) -> Self {
+        self.data_type.interpretation = text(interpretation);
+        self
+    }
+
+    pub fn encoding(mut self, encoding: &str) -> Self {
+        self.data_type.encoding = text(encoding);
+        self
+    }
+}
+
+/// Builds a simple datatype.
+#[derive(Debug, Clone)]
+pub struct SimpleDataBuilder {
+    data_type: SimpleDataType,
+}
+
+impl SimpleDataBuilder {
+    /// Start a simple datatype of the named basic data representation.
+    pub fn new(name: &str, representation: &str) -> Self {
+        Self {
+            data_type: SimpleDataType {
+                name: String::from(name),
+                representation: reference(representation),
+                units: None,
+                resolution: None,
+                accuracy: None,
+                semantics: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn units(mut self, units: &str) -> Self {
+        self.data_type.units = text(units);
+        self
+    }
+
+    pub fn resolution(mut self, resolution: MeasureType) -> Self {
+        self.data_type.resolution = Some(TypedValue::new(resolution));
+        self
+    }
+
+    pub fn accuracy(mut self, accuracy: MeasureType) -> Self {
+        self.data_type.accuracy = Some(TypedValue::new(accuracy));
+        self
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.data_type.semantics = text(semantics);
+        self
+    }
+}
+
+/// Builds an enumerated datatype.
+#[derive(Debug, Clone)]
+pub struct EnumeratedDataBuilder {
+    data_type: EnumeratedDataType,
+}
+
+impl EnumeratedDataBuilder {
+    /// Start an enumerated datatype of the named basic data representation.
+    pub fn new(name: &str, representation: &str) -> Self {
+        Self {
+            data_type: EnumeratedDataType {
+                name: String::from(name),
+                representation: reference(representation),
+                semantics: None,
+                enumerators: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.data_type.semantics = text(semantics);
+        self
+    }
+
+    pub fn enumerator(mut self, name: &str, value: i128) -> Self {
+        let enumerators = self.data_type.enumerators.get_or_insert_with(Vec::new);
+        enumerators.push(EnumeratorType {
+            name: String::from(name),
+            value: vec![TypedValue::new(value)],
+            annotations: None,
+            span: SourceSpan::default(),
+        });
+        self
+    }
+}
+
+/// Builds an array datatype.
+#[derive(Debug, Clone)]
+pub struct ArrayDataBuilder {
+    data_type: ArrayDataType,
+}
+
+impl ArrayDataBuilder {
+    /// Start an array of elements of the named datatype. Arrays of dynamic
+    /// cardinality are encoded as `HLAvariableArray`, and others as
+    /// `HLAfixedArray`.
+    pub fn new(name: &str, data_type: &str, cardinality: Cardinality) -> Self {
+        let encoding = match cardinality {
+            Cardinality::Fixed(_) => ArrayDataTypeEncodingType::HlaFixedArray,
+            _ => ArrayDataTypeEncodingType::HlaVariableArray,
+        };
+        Self {
+            data_type: ArrayDataType {
+                name: String::from(name),
+                data_type: reference(data_type),
+                cardinality: Some(TypedValue::new(cardinality)),
+                encoding: Some(encoding),
+                semantics: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn encoding(mut self, encoding: ArrayDataTypeEncodingType) -> Self {
+        self.data_type.encoding = Some(encoding);
+        self
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.data_type.semantics = text(semantics);
+        self
+    }
+}
+
+/// Builds a fixed record datatype.
+#[derive(Debug, Clone)]
+pub struct FixedRecordDataBuilder {
+    data_type: FixedRecordDataType,
+}
+
+impl FixedRecordDataBuilder {
+    /// Start a fixed record datatype encoded as `HLAfixedRecord`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            data_type: FixedRecordDataType {
+                name: String::from(name),
+                encoding: Some(FixedRecordEncodingType::HlaFixedRecord),
+                semantics: None,
+                fields: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn encoding(mut self, encoding: FixedRecordEncodingType) -> Self {
+        self.data_type.encoding = Some(encoding);
+        self
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.data_type.semantics = text(semantics);
+        self
+    }
+
+    pub fn field(mut self, name: &str, data_type: &str) -> Self {
+        let fields = self.data_type.fields.get_or_insert_with(Vec::new);
+        fields.push(FieldType {
+            name: String::from(name),
+            data_type: reference(data_type),
+            semantics: None,
+            annotations: None,
+            span: SourceSpan::default(),
+        });
+        self
+    }
+}
+
+/// Builds a variant record datatype.
+#[derive(Debug, Clone)]
+pub struct VariantRecordDataBuilder {
+    data_type: VariantRecordDataType,
+}
+
+impl VariantRecordDataBuilder {
+    /// Start a variant record datatype encoded as `HLAvariantRecord`, whose
+    /// discriminant has the provided name and is of the named enumerated
+    /// datatype.
+    pub fn new(name: &str, discriminant: &str, data_type: &str) -> Self {
+        Self {
+            data_type: VariantRecordDataType {
+                name: String::from(name),
+                discriminant: text(discriminant),
+                data_type: reference(data_type),
+                alternatives: None,
+                encoding: Some(VariantRecordEncodingType::HlaVariantRecord),
+                semantics: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+        }
+    }
+
+    pub fn encoding(mut self, encoding: VariantRecordEncodingType) -> Self {
+        self.data_type.encoding = Some(encoding);
+        self
+    }
+
+    pub fn semantics(mut self, semantics: &str) -> Self {
+        self.data_type.semantics = text(semantics);
+        self
+    }
+
+    /// Add an alternative, of the named datatype, for the named enumerators of
+    /// the discriminant. The enumerators are written as in a document, e.g.
+    /// `Car, Truck` or `HLAother`.
+    pub fn alternative(mut self, enumerator: &str, name: &str, data_type: &str) -> Self {
+        let alternatives = self.data_type.alternatives.get_or_insert_with(Vec::new);
+        alternatives.push(AlternativeType {
+            enumerator: text(enumerator),
+            name: text(name),
+            data_type: reference(data_type),
+            semantics: None,
+            annotations: None,
+            span: SourceSpan::default(),
+        });
+        self
+    }
+}
+
+/// Builds an object model in the IEEE 1516.2-2010 namespace.
+#[derive(Debug, Clone)]
+pub struct ObjectModelBuilder {
+    fom: ObjectModelType,
+    data_type_names: HashSet<String>,
+    errors: Vec<BuildError>,
+}
+
+impl ObjectModelBuilder {
+    /// Start an object model of the provided name and type.
+    pub fn new(name: &str, model_type: ModelType) -> Self {
+        let mut builder = Self {
+            fom: ObjectModelType {
+                namespace: text(IEEE1516_2010_NAMESPACE),
+                schema_location: Some(vec![SchemaLocationType {
+                    namespace: String::from(IEEE1516_2010_NAMESPACE),
+                    location: String::from(
+                        "http://standards.ieee.org/downloads/1516/1516.2-2010/IEEE1516-DIF-2010.xsd",
+                    ),
+                }]),
+                model_identification: Some(ModelIdentificationType {
+                    name: text(name),
+                    model_type: Some(model_type),
+                    version: None,
+                    modification_date: None,
+                    security_classification: None,
+                    release_restriction: None,
+                    purpose: None,
+                    application_domain: None,
+                    description: None,
+                    use_limitation: None,
+                    use_history: None,
+                    keywords: None,
+                    poc: None,
+                    references: None,
+                    other: None,
+                    glyph: None,
+                    annotations: None,
+                    span: SourceSpan::default(),
+                }),
+                service_utilization: None,
+                objects: None,
+                interactions: None,
+                dimensions: None,
+                time: None,
+                tags: None,
+                synchronizations: None,
+                transportations: None,
+                switches: None,
+                update_rates: None,
+                data_types: None,
+                notes: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            },
+            data_type_names: HashSet::new(),
+            errors: Vec::new(),
+        };
+        if name.trim().is_empty() {
+            builder.error(
+                BuildErrorKind::InvalidName,
+                String::from("/objectModel/modelIdentification/name"),
+                name,
+            );
+        }
+        builder
+    }
+
+    fn error(&mut self, kind: BuildErrorKind, path: String, value: &str) {
+        self.errors.push(BuildError {
+            kind,
+            path,
+            value: String::from(value),
+        });
+    }
+
+    /// Check the name of a part, at the provided path, among the names already
+    /// taken in its scope, and take it.
+    fn check_new_name(&mut self, path: &str, name: &str, taken: &mut HashSet<String>) {
+        if let Some(kind) = check_name(name) {
+            self.error(kind, String::from(path), name);
+        } else if !taken.insert(String::from(name)) {
+            self.error(BuildErrorKind::DuplicateName, String::from(path), name);
+        }
+    }
+
+    /// Check that the part referred to at the provided path has already been
+    /// added, or is part of the HLA standard MIM.
+    fn check_reference(&mut self, kind: Reference, path: String, reference: &ReferenceType) {
+        let name = reference.value.as_str();
+        let declared = match kind {
+            Reference::DataType => {
+                name == NOT_APPLICABLE
+                    || self.data_type_names.contains(name)
+                    || standard_mim()
+                        .declared_data_types()
+                        .iter()
+                        .any(|data_type| data_type.name() == name)
+            }
+            Reference::Dimension => [&self.fom, standard_mim()]
+                .iter()
+                .flat_map(|fom| fom.dimensions.iter())
+                .flat_map(|dimensions| dimensions.dimensions.iter().flatten())
+                .any(|dimension| dimension.name == name),
+            Reference::Transportation => [&self.fom, standard_mim()]
+                .iter()
+                .flat_map(|fom| fom.transportations.iter())
+                .flat_map(|transportations| transportations.transportations.iter().flatten())
+                .any(|transportation| transportation.name == name),
+        };
+        if !declared {
+            self.error(BuildErrorKind::UndeclaredReference, path, name);
+        }
+    }
+
+    /// Check that the datatype referred to at the provided path, if any, has
+    /// already been added, or is one of the HLA standard MIM.
+    fn check_data_type_reference(&mut self, path: String, reference: Option<&ReferenceType>) {
+        if let Some(reference) = reference {
+            self.check_reference(Reference::DataType, path, reference);
+        }
+    }
+
+    fn identification(&mut self) -> &mut ModelIdentificationType {
+        self.fom
+            .model_identification
+            .as_mut()
+            .expect("the builder creates the model identification")
+    }
+
+    pub fn version(mut self, version: &str) -> Self {
+        self.identification().version = text(version);
+        self
+    }
+
+    /// Set the date of the last modification, in the form `YYYY-MM-DD`.
+    pub fn modification_date(mut self, date: &str) -> Self {
+        self.identification().modification_date = text(date);
+        self
+    }
+
+    pub fn security_classification(mut self, classification: SecurityClassificationType) -> Self {
+        self.identification().security_classification = Some(classification);
+        self
+    }
+
+    pub fn purpose(mut self, purpose: &str) -> Self {
+        self.identification().purpose = text(purpose);
+        self
+    }
+
+    pub fn application_domain(mut self, domain: ApplicationDomainType) -> Self {
+        self.identification().application_domain = Some(domain);
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.identification().description = text(description);
+        self
+    }
+
+    /// Add a point of contact.
+    pub fn poc(mut self, poc_type: PocTypeType, name: &str, organization: &str) -> Self {
+        let pocs = self.identification().poc.get_or_insert_with(Vec::new);
+        pocs.push(PocType {
+            poc_type: Some(poc_type),
+            poc_name: text(name),
+            poc_org: text(organization),
+            poc_telephones: None,
+            poc_emails: None,
+            annotations: None,
+            span: SourceSpan::default(),
+        });
+        self
+    }
+
+    /// Add an object class, and its subclasses, as a subclass of the class
+    /// with the provided qualified name, e.g. `HLAobjectRoot.Vehicle`. The
+    /// root class `HLAobjectRoot` is added when first needed. The datatypes,
+    /// dimensions and transportation types of the attributes must already
+    /// have been added.
+    pub fn object_class(mut self, superclass: &str, class: ObjectClassBuilder) -> Self {
+        let objects = self.fom.objects.get_or_insert_with(|| ObjectsType {
+            root_object_class: None,
+            annotations: None,
+            span: SourceSpan::default(),
+        });
+        let root = objects
+            .root_object_class
+            .get_or_insert_with(|| ObjectClassBuilder::new(OBJECT_ROOT).class);
+        let root = std::mem::replace(root, ObjectClassBuilder::new(OBJECT_ROOT).class);
+        let root = self.add_class(root, superclass, class.class, "/objectModel/objects");
+        self.fom.objects.as_mut().unwrap().root_object_class = Some(root);
+        self
+    }
+
+    /// Add an interaction class, and its subclasses, as a subclass of the
+    /// class with the provided qualified name, e.g. `HLAinteractionRoot`. The
+    /// root class `HLAinteractionRoot` is added when first needed. The
+    /// dimensions and transportation types of the classes, and the datatypes
+    /// of the parameters, must already have been added.
+    pub fn interaction_class(mut self, superclass: &str, class: InteractionClassBuilder) -> Self {
+        let root = match self.fom.interactions.take() {
+            Some(interactions) => interactions.interactions,
+            None => InteractionClassBuilder::new(INTERACTION_ROOT).class,
+        };
+        let root = self.add_class(root, superclass, class.class, "/objectModel/interactions");
+        self.fom.interactions = Some(InteractionsType {
+            interactions: root,
+            annotations: None,
+            span: SourceSpan::default(),
+        });
+        self
+    }
+
+    /// Add the class below the superclass of the provided qualified name in
+    /// the tree of the provided root, and return the root.
+    fn add_class<T: BuildableClass>(
+        &mut self,
+        mut root: T,
+        superclass: &str,
+        class: T,
+        table: &str,
+    ) -> T {
+        let mut names = superclass.split('.').peekable();
+        if names.peek() == Some(&root.name()) {
+            names.next();
+        }
+        let mut path = format!("{}/{}[{}]", table, T::ELEMENT, root.name());
+        // The members of the superclasses, which the class must not redeclare.
+        let mut inherited: HashSet<String> = root.member_names().collect();
+        let mut parent = &mut root;
+        for name in names {
+            match parent
+                .subclasses_mut()
+                .iter_mut()
+                .position(|c| c.name() == name)
+            {
+                Some(i) => {
+                    parent = &mut parent.subclasses_mut()[i];
+                    path = format!("{}/{}[{}]", path, T::ELEMENT, name);
+                    inherited.extend(parent.member_names());
+                }
+                None => {
+                    self.error(BuildErrorKind::UndeclaredReference, path, superclass);
+                    return root;
+                }
+            }
+        }
+
+        let mut siblings: HashSet<String> = parent
+            .subclasses_mut()
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect();
+        self.check_class(&path, &class, &mut siblings, &inherited);
+        parent.subclasses_mut().push(class);
+        root
+    }
+
+    /// Check the names and references of the class, its members and its
+    /// subclasses.
+    fn check_class<T: BuildableClass>(
+        &mut self,
+        parent_path: &str,
+        class: &T,
+        siblings: &mut HashSet<String>,
+        inherited: &HashSet<String>,
+    ) {
+        let path = format!("{}/{}[{}]", parent_path, T::ELEMENT, class.name());
+        self.check_new_name(&path, class.name(), siblings);
+        let mut members = inherited.clone();
+        for member in class.member_names() {
+            let member_path = format!("{}/{}[{}]", path, T::MEMBER_ELEMENT, member);
+            self.check_new_name(&member_path, &member, &mut members);
+        }
+        for (kind, reference_path, reference) in class.references() {
+            self.check_reference(kind, format!("{}/{}", path, reference_path), reference);
+        }
+        let mut subclasses = HashSet::new();
+        for subclass in class.subclasses() {
+            self.check_class(&path, subclass, &mut subclasses, &members);
+        }
+    }
+
+    fn data_types(&mut self) -> &mut DataTypesType {
+        self.fom.data_types.get_or_insert_with(|| DataTypesType {
+            basic_data_representations: None,
+            simple_data_types: None,
+            enumerated_data_types: None,
+            array_data_types: None,
+            fixed_record_data_types: None,
+            variand_record_data_types: None,
+            annotations: None,
+            span: SourceSpan::default(),
+        })
+    }
+
+    /// Check the name of a datatype, at the provided path, which must differ
+    /// from those of every other datatype, whatever its table. The name is
+    /// taken once the references of the datatype have been checked, so that a
+    /// datatype cannot refer to itself.
+    fn check_data_type_name(&mut self, path: &str, name: &str) {
+        let mut taken = std::mem::take(&mut self.data_type_names);
+        self.check_new_name(path, name, &mut taken);
+        self.data_type_names = taken;
+    }
+
+    /// Check the names of the parts of a datatype, such as its fields, which
+    /// must differ from each other.
+    fn check_part_names<'n>(
+        &mut self,
+        path: &str,
+        element: &str,
+        names: impl Iterator<Item = &'n str>,
+    ) {
+        let mut taken = HashSet::new();
+        for name in names {
+            let part_path = format!("{}/{}[{}]", path, element, name);
+            self.check_new_name(&part_path, name, &mut taken);
+        }
+    }
+
+    pub fn basic_data(mut self, data_type: BasicDataBuilder) -> Self {
+        let data_type = data_type.data_type;
+        let path = data_type_path("basicDataRepresentations", "basicData", &data_type.name);
+        self.check_data_type_name(&path, &data_type.name);
+        let table = self
+            .data_types()
+            .basic_data_representations
+            .get_or_insert_with(|| BasicDataRepresentationsType {
+                basic_datas: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            });
+        table
+            .basic_datas
+            .get_or_insert_with(Vec::new)
+            .push(data_type);
+        self
+    }
+
+    /// Add a simple datatype, whose representation must already have been
+    /// added, or be one of the MIM.
+    pub fn simple_data(mut self, data_type: SimpleDataBuilder) -> Self {
+        let data_type = data_type.data_type;
+        let path = data_type_path("simpleDataTypes", "simpleData", &data_type.name);
+        self.check_data_type_reference(
+            format!("{}/representation", path),
+            data_type.representation.as_ref(),
+        );
+        self.check_data_type_name(&path, &data_type.name);
+        let table =
+            self.data_types()
+                .simple_data_types
+                .get_or_insert_with(|| SimpleDataTypesType {
+                    simple_datas: None,
+                    annotations: None,
+                    span: SourceSpan::default(),
+                });
+        table
+            .simple_datas
+            .get_or_insert_with(Vec::new)
+            .push(data_type);
+        self
+    }
+
+    /// Add an enumerated datatype, whose representation must already have been
+    /// added, or be one of the MIM.
+    pub fn enumerated_data(mut self, data_type: EnumeratedDataBuilder) -> Self {
+        let data_type = data_type.data_type;
+        let path = data_type_path("enumeratedDataTypes", "enumeratedData", &data_type.name);
+        self.check_data_type_reference(
+            format!("{}/representation", path),
+            data_type.representation.as_ref(),
+        );
+        self.check_data_type_name(&path, &data_type.name);
+        let enumerators = data_type.enumerators.iter().flatten();
+        self.check_part_names(&path, "enumerator", enumerators.map(|e| e.name.as_str()));
+        let table = self
+            .data_types()
+            .enumerated_data_types
+            .get_or_insert_with(|| EnumeratedDataTypesType {
+                enumerated_datas: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            });
+        table
+            .enumerated_datas
+            .get_or_insert_with(Vec::new)
+            .push(data_type);
+        self
+    }
+
+    /// Add an array datatype, whose element datatype must already have been
+    /// added, or be one of the MIM.
+    pub fn array_data(mut self, data_type: ArrayDataBuilder) -> Self {
+        let data_type = data_type.data_type;
+        let path = data_type_path("arrayDataTypes", "arrayData", &data_type.name);
+        self.check_data_type_reference(format!("{}/dataType", path), data_type.data_type.as_ref());
+        self.check_data_type_name(&path, &data_type.name);
+        let table = self
+            .data_types()
+            .array_data_types
+            .get_or_insert_with(|| ArrayDataTypesType {
+                array_datas: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            });
+        table
+            .array_datas
+            .get_or_insert_with(Vec::new)
+            .push(data_type);
+        self
+    }
+
+    /// Add a fixed record datatype, the datatypes of whose fields must already
+    /// have been added, or be those of the MIM.
+    pub fn fixed_record_data(mut self, data_type: FixedRecordDataBuilder) -> Self {
+        let data_type = data_type.data_type;
+        let path = data_type_path("fixedRecordDataTypes", "fixedRecordData", &data_type.name);
+        for field in data_type.fields.iter().flatten() {
+            self.check_data_type_reference(
+                format!("{}/field[{}]/dataType", path, field.name),
+                field.data_type.as_ref(),
+            );
+        }
+        self.check_data_type_name(&path, &data_type.name);
+        let fields = data_type.fields.iter().flatten();
+        self.check_part_names(&path, "field", fields.map(|f| f.name.as_str()));
+        let table = self
+            .data_types()
+            .fixed_record_data_types
+            .get_or_insert_with(|| FixedRecordDataTypesType {
+                fixed_record_datas: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            });
+        table
+            .fixed_record_datas
+            .get_or_insert_with(Vec::new)
+            .push(data_type);
+        self
+    }
+
+    /// Add a variant record datatype, the datatypes of whose discriminant and
+    /// alternatives must already have been added, or be those of the MIM.
+    pub fn variant_record_data(mut self, data_type: VariantRecordDataBuilder) -> Self {
+        let data_type = data_type.data_type;
+        let path = data_type_path(
+            "variantRecordDataTypes",
+            "variantRecordData",
+            &data_type.name,
+        );
+        self.check_data_type_reference(format!("{}/dataType", path), data_type.data_type.as_ref());
+        for alternative in data_type.alternatives.iter().flatten() {
+            let segment = match &alternative.name {
+                Some(name) => format!("alternative[{}]", name),
+                None => String::from("alternative"),
+            };
+            self.check_data_type_reference(
+                format!("{}/{}/dataType", path, segment),
+                alternative.data_type.as_ref(),
+            );
+        }
+        self.check_data_type_name(&path, &data_type.name);
+        if let Some(discriminant) = &data_type.discriminant {
+            if let Some(kind) = check_name(discriminant) {
+                self.error(kind, format!("{}/discriminant", path), discriminant);
+            }
+        }
+        let alternatives = data_type.alternatives.iter().flatten();
+        let names = alternatives.filter_map(|a| a.name.as_deref());
+        self.check_part_names(&path, "alternative", names);
+        let table = self
+            .data_types()
+            .variand_record_data_types
+            .get_or_insert_with(|| VariantRecordDataTypesType {
+                variant_record_datas: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            });
+        table
+            .variant_record_datas
+            .get_or_insert_with(Vec::new)
+            .push(data_type);
+        self
+    }
+
+    /// Add a dimension of the named datatype, whose values are normalized to
+    /// the range from zero up to the upper bound. The datatype must already
+    /// have been added, or be one of the MIM.
+    pub fn dimension(mut self, name: &str, data_type: &str, upper_bound: u64) -> Self {
+        let path = format!("/objectModel/dimensions/dimension[{}]", name);
+        let data_type = reference(data_type);
+        self.check_data_type_reference(format!("{}/dataType", path), data_type.as_ref());
+        let dimensions = self.fom.dimensions.iter().flat_map(|d| d.dimensions.iter());
+        let mut taken: HashSet<String> = dimensions.flatten().map(|d| d.name.clone()).collect();
+        self.check_new_name(&path, name, &mut taken);
+        let table = self.fom.dimensions.get_or_insert_with(|| DimensionsType {
+            dimensions: None,
+            annotations: None,
+            span: SourceSpan::default(),
+        });
+        table
+            .dimensions
+            .get_or_insert_with(Vec::new)
+            .push(DimensionType {
+                name: String::from(name),
+                data_type,
+                upper_bound: Some(TypedValue::new(upper_bound)),
+                normalization: None,
+                value: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            });
+        self
+    }
+
+    /// Add a transportation type, in addition to the `HLAreliable` and
+    /// `HLAbestEffort` types of the standard.
+    pub fn transportation(mut self, name: &str, reliable: ReliableType, semantics: &str) -> Self {
+        let path = format!("/objectModel/transportations/transportation[{}]", name);
+        let transportations = self
+            .fom
+            .transportations
+            .iter()
+            .flat_map(|t| t.transportations.iter());
+        let mut taken: HashSet<String> =
+            transportations.flatten().map(|t| t.name.clone()).collect();
+        self.check_new_name(&path, name, &mut taken);
+        let table = self
+            .fom
+            .transportations
+            .get_or_insert_with(|| TransportationsType {
+                transportations: None,
+                annotations: None,
+                span: SourceSpan::default(),
+            });
+        table
+            .transportations
+            .get_or_insert_with(Vec::new)
+            .push(TransportationType {
+                name: String::from(name),
+                reliable: Some(reliable),
+                semantics: text(semantics),
+                annotations: None,
+                span: SourceSpan::default(),
+            });
+        self
+    }
+
+    /// Return the object model, unless a problem was found with one of its
+    /// parts as it was added.
+    pub fn build(self) -> Result<ObjectModelType, BuildErrors> {
+        if self.errors.is_empty() {
+            Ok(self.fom)
+        } else {
+            Err(BuildErrors(self.errors))
+        }
+    }
+}
+
+/// Return the path of the datatype with the provided name in the named table.
+fn data_type_path(table: &str, element: &str, name: &str) -> String {
+    format!("/objectModel/dataTypes/{}/{}[{}]", table, element, name)
+}
+
+/// The classes of object and interaction class trees, as the builder adds
+/// them.
+trait BuildableClass: ClassTree {
+    /// The name of the elements of classes of the tree.
+    const ELEMENT: &'static str;
+    /// The name of the elements of the members of the classes.
+    const MEMBER_ELEMENT: &'static str;
+
+    fn subclasses_mut(&mut self) -> &mut Vec<Self>;
+
+    fn member_names(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.members().iter().map(|m| m.name().to_string()))
+    }
+
+    /// Return the references of the class and of its members, with the kind
+    /// of part each refers to and its path relative to that of the class.
+    fn references(&self) -> Vec<(Reference, String, &ReferenceType)>;
+}
+
+impl BuildableClass for ObjectClassType {
+    const ELEMENT: &'static str = "objectClass";
+    const MEMBER_ELEMENT: &'static str = "attribute";
+
+    fn subclasses_mut(&mut self) -> &mut Vec<Self> {
+        self.object_classes.get_or_insert_with(Vec::new)
+    }
+
+    fn references(&self) -> Vec<(Reference, String, &ReferenceType)> {
+        let mut references = Vec::new();
+        for attribute in self.attributes.iter().flatten() {
+            let path = format!("attribute[{}]", attribute.name);
+            if let Some(data_type) = &attribute.data_type {
+                references.push((Reference::DataType, format!("{}/dataType", path), data_type));
+            }
+            for dimension in attribute.dimensions.iter().flatten() {
+                let dimension_path = format!("{}/dimensions/dimension", path);
+                references.push((Reference::Dimension, dimension_path, dimension));
+            }
+            if let Some(transportation) = &attribute.transportation {
+                let transportation_path = format!("{}/transportation", path);
+                references.push((
+                    Reference::Transportation,
+                    transportation_path,
+                    transportation,
+                ));
+            }
+        }
+        references
+    }
+}
+
+impl BuildableClass for InteractionClassType {
+    const ELEMENT: &'static str = "interactionClass";
+    const MEMBER_ELEMENT: &'static str = "parameter";
+
+    fn subclasses_mut(&mut self) -> &mut Vec<Self> {
+        self.interaction_classes.get_or_insert_with(Vec::new)
+    }
+
+    fn references(&self) -> Vec<(Reference, String, &ReferenceType)> {
+        let mut references = Vec::new();
+        for dimension in self.dimensions.iter().flatten() {
+            let path = String::from("dimensions/dimension");
+            references.push((Reference::Dimension, path, dimension));
+        }
+        if let Some(transportation) = &self.transportation {
+            let path = String::from("transportation");
+            references.push((Reference::Transportation, path, transportation));
+        }
+        for parameter in self.parameters.iter().flatten() {
+            if let Some(data_type) = &parameter.data_type {
+                let path = format!("parameter[{}]/dataType", parameter.name);
+                references.push((Reference::DataType, path, data_type));
+            }
+        }
+        references
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Schema;
+
+    fn vehicles() -> ObjectModelBuilder {
+        ObjectModelBuilder::new("Vehicles", ModelType::FOM)
+            .version("1.0")
+            .modification_date("2026-10-16")
+            .security_classification(SecurityClassificationType::Unclassified)
+            .purpose("Testing the builder")
+            .application_domain(ApplicationDomainType::Engineering)
+            .description("Vehicles and their crashes")
+            .poc(PocTypeType::PrimaryAuthor, "A. Author", "Example")
+            .basic_data(BasicDataBuilder::new(
+                "UnsignedInteger12BE",
+                12,
+                EndianType::Big,
+            ))
+            .simple_data(SimpleDataBuilder::new("Speed", "HLAfloat32BE").units("m/s"))
+            .enumerated_data(
+                EnumeratedDataBuilder::new("VehicleKind", "HLAoctet")
+                    .enumerator("Car", 0)
+                    .enumerator("Truck", 1),
+            )
+            .fixed_record_data(
+                FixedRecordDataBuilder::new("Waypoint")
+                    .field("X", "HLAfloat64BE")
+                    .field("Y", "HLAfloat64BE"),
+            )
+            .array_data(ArrayDataBuilder::new(
+                "Route",
+                "Waypoint",
+                Cardinality::Dynamic,
+            ))
+            .variant_record_data(
+                VariantRecordDataBuilder::new("Cargo", "Kind", "VehicleKind").alternative(
+                    "Truck",
+                    "Load",
+                    "HLAinteger32BE",
+                ),
+            )
+            .dimension("Zone", "HLAinteger32BE", 8)
+            .object_class(
+                "HLAobjectRoot",
+                ObjectClassBuilder::new("Vehicle")
+                    .attribute(
+                        AttributeBuilder::new("Speed", "Speed")
+                            .update_type(UpdateType::Periodic)
+                            .dimension("Zone")
+                            .transportation("HLAbestEffort"),
+                    )
+                    .subclass(
+                        ObjectClassBuilder::new("Car")
+                            .attribute(AttributeBuilder::new("Route", "Route")),
+                    ),
+            )
+            .object_class(
+                "Vehicle",
+                ObjectClassBuilder::new("Truck").attribute(AttributeBuilder::new("Cargo", "Cargo")),
+            )
+            .interaction_class(
+                "HLAinteractionRoot",
+                InteractionClassBuilder::new("Crash")
+                    .parameter(ParameterBuilder::new("Kind", "VehicleKind")),
+            )
+    }
+
+    #[test]
+    fn test_build_object_model() {
+        let fom = vehicles().build().unwrap();
+        assert!(fom.object_class("Vehicle.Car").is_some());
+        assert!(fom.object_class("HLAobjectRoot.Vehicle.Truck").is_some());
+        assert!(fom.interaction_class("Crash").is_some());
+
+        // Written and read back unchanged, without any warnings.
+        let xml = to_xml_string(&fom, &WriteOptions::default()).unwrap();
+        let parsed = parse_reader_with_options(xml.as_bytes(), &ParseOptions::default()).unwrap();
+        assert!(parsed.warnings.is_empty());
+        assert!(fom.same_model(&parsed.object_model));
+
+        // And written as a document that conforms to the DIF schema.
+        let violations = Schema::dif().validate_reader(xml.as_bytes()).unwrap();
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    #[test]
+    fn test_references_are_checked_as_parts_are_added() {
+        let check = |builder: ObjectModelBuilder| -> Vec<String> {
+            match builder.build() {
+                Ok(_) => Vec::new(),
+                Err(errors) => errors.iter().map(|e| e.to_string()).collect(),
+            }
+        };
+        let builder = || ObjectModelBuilder::new("Dangling", ModelType::FOM);
+
+        assert_eq!(
+            vec!["/objectModel/dataTypes/simpleDataTypes/simpleData[Speed]/representation: 'Float32' is not declared"],
+            check(builder().simple_data(SimpleDataBuilder::new("Speed", "Float32")))
+        );
+        assert_eq!(
+            vec!["/objectModel/dataTypes/enumeratedDataTypes/enumeratedData[Kind]/representation: 'Octet' is not declared"],
+            check(builder().enumerated_data(EnumeratedDataBuilder::new("Kind", "Octet")))
+        );
+        assert_eq!(
+            vec!["/objectModel/dataTypes/fixedRecordDataTypes/fixedRecordData[Waypoint]/field[Next]/dataType: 'Route' is not declared"],
+            check(
+                builder()
+                    .fixed_record_data(FixedRecordDataBuilder::new("Waypoint").field("Next", "Route"))
+                    .array_data(ArrayDataBuilder::new("Route", "Waypoint", Cardinality::Dynamic))
+            ),
+            "datatypes are added before the parts that refer to them"
+        );
+        assert_eq!(
+            vec!["/objectModel/dataTypes/arrayDataTypes/arrayData[Route]/dataType: 'Route' is not declared"],
+            check(builder().array_data(ArrayDataBuilder::new("Route", "Route", Cardinality::Dynamic))),
+            "a datatype cannot contain itself"
+        );
+        assert_eq!(
+            vec![
+                "/objectModel/dataTypes/variantRecordDataTypes/variantRecordData[Cargo]/dataType: 'Kind' is not declared",
+                "/objectModel/dataTypes/variantRecordDataTypes/variantRecordData[Cargo]/alternative[Load]/dataType: 'Mass' is not declared",
+            ],
+            check(builder().variant_record_data(
+                VariantRecordDataBuilder::new("Cargo", "Kind", "Kind").alternative("Truck", "Load", "Mass")
+            ))
+        );
+        assert_eq!(
+            vec!["/objectModel/dimensions/dimension[Zone]/dataType: 'ZoneNumber' is not declared"],
+            check(builder().dimension("Zone", "ZoneNumber", 8))
+        );
+        assert_eq!(
+            vec![
+                "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Car]/attribute[Speed]/dataType: 'Speed' is not declared",
+                "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Car]/attribute[Speed]/dimensions/dimension: 'Zone' is not declared",
+                "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Car]/attribute[Speed]/transportation: 'Radio' is not declared",
+            ],
+            check(builder().object_class(
+                "HLAobjectRoot",
+                ObjectClassBuilder::new("Vehicle").subclass(ObjectClassBuilder::new("Car").attribute(
+                    AttributeBuilder::new("Speed", "Speed")
+                        .dimension("Zone")
+                        .transportation("Radio")
+                )),
+            ))
+        );
+        assert_eq!(
+            vec![
+                "/objectModel/interactions/interactionClass[HLAinteractionRoot]/interactionClass[Crash]/dimensions/dimension: 'Zone' is not declared",
+                "/objectModel/interactions/interactionClass[HLAinteractionRoot]/interactionClass[Crash]/transportation: 'Radio' is not declared",
+                "/objectModel/interactions/interactionClass[HLAinteractionRoot]/interactionClass[Crash]/parameter[Speed]/dataType: 'Speed' is not declared",
+            ],
+            check(builder().interaction_class(
+                "HLAinteractionRoot",
+                InteractionClassBuilder::new("Crash")
+                    .dimension("Zone")
+                    .transportation("Radio")
+                    .parameter(ParameterBuilder::new("Speed", "Speed")),
+            ))
+        );
+
+        // The datatypes and transportation types of the MIM, and parts added
+        // earlier, may be referred to.
+        assert_eq!(
+            Vec::<String>::new(),
+            check(
+                builder()
+                    .transportation("Radio", ReliableType::No, "Sent over the radio")
+                    .dimension("Zone", "HLAinteger32BE", 8)
+                    .object_class(
+                        "HLAobjectRoot",
+                        ObjectClassBuilder::new("Vehicle").attribute(
+                            AttributeBuilder::new("Name", "HLAunicodeString")
+                                .dimension("Zone")
+                                .transportation("Radio")
+                        ),
+                    )
+                    .interaction_class(
+                        "HLAinteractionRoot",
+                        InteractionClassBuilder::new("Crash").transportation("HLAbestEffort"),
+                    )
+            )
+        );
+    }
+
+    #[test]
+    fn test_build_reports_every_problem() {
+        let errors = vehicles()
+            .simple_data(SimpleDataBuilder::new("Speed", "HLAfloat64BE"))
+            .enumerated_data(
+                EnumeratedDataBuilder::new("Color", "HLAoctet")
+                    .enumerator("Red", 0)
+                    .enumerator("Red", 1),
+            )
+            .object_class(
+                "Vehicle",
+                ObjectClassBuilder::new("Bus")
+                    .attribute(AttributeBuilder::new("Speed", "Speed"))
+                    .attribute(AttributeBuilder::new("Seats", "SeatCount").dimension("Lane")),
+            )
+            .object_class("Aircraft", ObjectClassBuilder::new("Glider"))
+            .interaction_class(
+                "HLAinteractionRoot",
+                InteractionClassBuilder::new("HLAcrash").transportation("Carrier pigeon"),
+            )
+            .object_class("HLAobjectRoot", ObjectClassBuilder::new("2CV"))
+            .build()
+            .unwrap_err();
+        let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            vec![
+                "/objectModel/dataTypes/simpleDataTypes/simpleData[Speed]: duplicate name 'Speed'",
+                "/objectModel/dataTypes/enumeratedDataTypes/enumeratedData[Color]/enumerator[Red]: duplicate name 'Red'",
+                "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Bus]/attribute[Speed]: duplicate name 'Speed'",
+                "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Bus]/attribute[Seats]/dataType: 'SeatCount' is not declared",
+                "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Bus]/attribute[Seats]/dimensions/dimension: 'Lane' is not declared",
+                "/objectModel/objects/objectClass[HLAobjectRoot]: 'Aircraft' is not declared",
+                "/objectModel/interactions/interactionClass[HLAinteractionRoot]/interactionClass[HLAcrash]: reserved name 'HLAcrash'",
+                "/objectModel/interactions/interactionClass[HLAinteractionRoot]/interactionClass[HLAcrash]/transportation: 'Carrier pigeon' is not declared",
+                "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[2CV]: invalid name '2CV'",
+            ],
+            errors
+        );
+    }
+}
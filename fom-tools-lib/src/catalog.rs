@@ -0,0 +1,370 @@
+//! The HLA standard MIM and the standard FOM modules bundled with fom-tools,
+//! compiled into the library so that they are available without access to the
+//! files they were read from. Enabled by the `catalog` feature.
+
+use crate::*;
+use std::sync::OnceLock;
+
+/// The family of standard object models that a catalog entry belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Family {
+    /// The HLA standard MIM.
+    Mim,
+    /// The SISO Real-time Platform Reference FOM.
+    Rpr,
+    /// The NATO Education and Training Network FOM.
+    Netn,
+}
+
+/// An object model document of the catalog.
+#[derive(Debug)]
+pub struct CatalogEntry {
+    pub family: Family,
+    /// The short name of the object model, e.g. `RPR-Base` or `NETN-ORG`.
+    pub name: &'static str,
+    /// The release of the family that the object model belongs to, e.g.
+    /// `v2.0` for the RPR FOM or `3.0` for the NETN FOM.
+    pub version: &'static str,
+    /// The path of the document within the fom-tools-lib crate.
+    pub path: &'static str,
+    /// The text of the document.
+    pub source: &'static str,
+    object_model: OnceLock<Result<ObjectModelType, Error>>,
+}
+
+impl CatalogEntry {
+    /// Return the object model of the document, which is read when it is
+    /// first needed, or the error that reading it gave.
+    pub fn object_model(&self) -> Result<&ObjectModelType, &Error> {
+        self.object_model
+            .get_or_init(|| parse_str(self.source))
+            .as_ref()
+    }
+
+    /// Return whether the entry is the object model that a reference in the
+    /// model identification of another object model identifies. The reference
+    /// may give the short name of the entry, the file name of its document
+    /// without the extension, its full name with or without the designation
+    /// of its standard, or an abbreviation given in parentheses in its name.
+    /// For example, the RPR Base module is identified by `RPR-Base`,
+    /// `RPR-Base_v2.0` and `Real-time Platform Reference Base FOM Module`,
+    /// and the MIM by `MIM`. An entry whose document cannot be read is only
+    /// identified by its short name or file name.
+    pub fn is_identified_by(&self, identification: &str) -> bool {
+        let identification = identification.trim();
+        let file_stem = self
+            .path
+            .rsplit('/')
+            .next()
+            .and_then(|file_name| file_name.strip_suffix(".xml"));
+        if identification == self.name || Some(identification) == file_stem {
+            return true;
+        }
+        let model_name = self
+            .object_model()
+            .ok()
+            .and_then(|fom| fom.model_identification.as_ref())
+            .and_then(|model_identification| model_identification.name.as_deref())
+            .unwrap_or("");
+        model_name == identification
+            || model_name.ends_with(&format!(" - {}", identification))
+            || model_name.contains(&format!("({})", identification))
+    }
+}
+
+macro_rules! entry {
+    ($family:expr, $name:expr, $version:expr, $path:expr) => {
+        CatalogEntry {
+            family: $family,
+            name: $name,
+            version: $version,
+            path: $path,
+            source: include_str!(concat!("../", $path)),
+            object_model: OnceLock::new(),
+        }
+    };
+}
+
+static ENTRIES: [CatalogEntry; 27] = [
+    entry!(
+        Family::Mim,
+        "HLAstandardMIM",
+        "2010",
+        "data/omt/HLAstandardMIM.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Aggregate",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Aggregate_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Base",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Base_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Communication",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Communication_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-DER",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-DER_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Enumerations",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Enumerations_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Foundation",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Foundation_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Logistics",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Logistics_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Minefield",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Minefield_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Physical",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Physical_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-SE",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-SE_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-SIMAN",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-SIMAN_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Switches",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Switches_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-UA",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-UA_v2.0.xml"
+    ),
+    entry!(
+        Family::Rpr,
+        "RPR-Warfare",
+        "v2.0",
+        "data/modules/RPR-FOM_v2.0/RPR-Warfare_v2.0.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-AIS",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-AIS.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-BASE",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-BASE.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-CBRN",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-CBRN.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-COM",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-COM.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-ETR",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-ETR.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-LOG",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-LOG.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-METOC",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-METOC.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-MRM",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-MRM.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-ORG",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-ORG.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-Physical",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-Physical.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-SE",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-SE.xml"
+    ),
+    entry!(
+        Family::Netn,
+        "NETN-TMR",
+        "3.0",
+        "data/modules/NETN-FOM-3.0/NETN-TMR.xml"
+    ),
+];
+
+/// Return every entry of the catalog: the MIM, then the RPR FOM modules and
+/// then the NETN FOM modules, each by name.
+pub fn entries() -> &'static [CatalogEntry] {
+    &ENTRIES
+}
+
+/// Return the entry with the provided short name and version, e.g.
+/// `get("RPR-Base", "v2.0")`.
+pub fn get(name: &str, version: &str) -> Option<&'static CatalogEntry> {
+    ENTRIES
+        .iter()
+        .find(|entry| entry.name == name && entry.version == version)
+}
+
+/// Return the HLA standard MIM.
+pub fn mim() -> &'static CatalogEntry {
+    &ENTRIES[0]
+}
+
+/// Return the RPR FOM module with the provided short name and version, e.g.
+/// `rpr("RPR-Physical", "v2.0")`.
+pub fn rpr(name: &str, version: &str) -> Option<&'static CatalogEntry> {
+    get(name, version).filter(|entry| entry.family == Family::Rpr)
+}
+
+/// Return the NETN FOM module with the provided short name and version, e.g.
+/// `netn("NETN-ORG", "3.0")`.
+pub fn netn(name: &str, version: &str) -> Option<&'static CatalogEntry> {
+    get(name, version).filter(|entry| entry.family == Family::Netn)
+}
+
+/// Return the entry that a dependency, or other reference in the model
+/// identification of an object model, identifies. See
+/// `CatalogEntry::is_identified_by`.
+pub fn resolve(identification: &str) -> Option<&'static CatalogEntry> {
+    ENTRIES
+        .iter()
+        .find(|entry| entry.is_identified_by(identification))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_entries() {
+        let base = get("RPR-Base", "v2.0").unwrap();
+        assert_eq!(Family::Rpr, base.family);
+        assert!(base
+            .object_model()
+            .unwrap()
+            .object_class("BaseEntity")
+            .is_some());
+        assert!(rpr("RPR-Base", "v2.0").is_some());
+        assert!(netn("RPR-Base", "v2.0").is_none());
+        assert!(get("RPR-Base", "v1.0").is_none());
+
+        let org = netn("NETN-ORG", "3.0").unwrap();
+        assert_eq!("data/modules/NETN-FOM-3.0/NETN-ORG.xml", org.path);
+        assert_eq!(Family::Mim, mim().family);
+    }
+
+    #[test]
+    fn test_resolve_dependencies() {
+        assert_eq!("HLAstandardMIM", resolve("MIM").unwrap().name);
+        assert_eq!(
+            "RPR-Base",
+            resolve("Real-time Platform Reference Base FOM Module")
+                .unwrap()
+                .name
+        );
+        assert_eq!("RPR-Physical", resolve("RPR-Physical_v2.0").unwrap().name);
+        assert_eq!("NETN-BASE", resolve("NETN-BASE").unwrap().name);
+        assert!(resolve("NETN-Unknown").is_none());
+
+        // Every dependency of a catalog entry is itself in the catalog.
+        for entry in entries() {
+            let references = entry
+                .object_model()
+                .unwrap()
+                .model_identification
+                .as_ref()
+                .and_then(|model_identification| model_identification.references.as_ref());
+            for reference in references.into_iter().flatten() {
+                if let (Some("Dependency"), Some(identification)) = (
+                    reference.reference_type.as_deref(),
+                    reference.identification.as_deref(),
+                ) {
+                    assert!(
+                        resolve(identification).is_some(),
+                        "{}: {}",
+                        entry.name,
+                        identification
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_entry_is_read() {
+        for entry in entries() {
+            if let Err(e) = entry.object_model() {
+                panic!("{}: {}", entry.path, e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_entries_are_consistent() {
+        let modules: Vec<&ObjectModelType> = entries()
+            .iter()
+            .map(|entry| entry.object_model().unwrap())
+            .collect();
+        let registry = DataTypeRegistry::from_modules(&modules);
+        assert!(registry.dangling_references().is_empty());
+    }
+}
@@ -0,0 +1,790 @@
+//! The configuration of `fom check` for a project, read from a
+//! `fom-check.toml` file, so that local runs and CI check the same files in
+//! the same way. For example:
+//!
+//! ```toml
+//! # The version of the OMT that the modules are written against.
+//! omt-version = "2010"
+//! # The highest level of checks to run, as with `--level`.
+//! level = 4
+//! # The lint packs to run along with the built-in lints.
+//! packs = ["netn"]
+//!
+//! [files]
+//! include = ["modules/**/*.xml"]
+//! exclude = ["*-draft.xml"]
+//!
+//! [lints]
+//! missing-semantics = "deny"
+//!
+//! # Names that the lints of the NETN pack accept.
+//! [exceptions]
+//! data-types = ["LegacyType"]
+//!
+//! # Settings for some of the modules, applied after those above.
+//! [[override]]
+//! files = ["modules/experimental/*.xml"]
+//! lints = { missing-semantics = "warn" }
+//! ```
+//!
+//! `fom check` uses the `fom-check.toml` file of the current directory or of
+//! the nearest of its ancestors that has one.
+
+use crate::lint::{Level, LintRegistry};
+use crate::netn::{NamingExceptions, NetnPack};
+use crate::toml::{self, Entry, Table, Value};
+use crate::SchemaVersion;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The name of the configuration file that `fom check` looks for.
+pub const CONFIG_FILE_NAME: &str = "fom-check.toml";
+
+/// The names of the lint packs that a configuration may run.
+const PACKS: &[&str] = &["netn"];
+
+/// The configuration of `fom check`. The default configuration is that of a
+/// project without a configuration file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CheckConfig {
+    /// The file the configuration was read from.
+    pub file: Option<PathBuf>,
+    /// The directory that the globs are relative to: that of the file, or
+    /// the current directory if it is empty.
+    pub root: PathBuf,
+    /// The version of the OMT that documents must be written against, if
+    /// they must be written against one. A document of another version is a
+    /// problem, but is still checked against the schema of its own version.
+    pub omt_version: Option<SchemaVersion>,
+    /// The highest level of checks to run, from 1 to 4.
+    pub level: Option<u8>,
+    /// Whether to check conformance against the FDD schema.
+    pub fdd: bool,
+    /// The names of the lint packs to run along with the built-in lints.
+    pub packs: Vec<String>,
+    /// The files to check when none are named.
+    pub include: Vec<Glob>,
+    /// The files never to check.
+    pub exclude: Vec<Glob>,
+    /// The levels of lints.
+    pub lints: Vec<LintSetting>,
+    /// The names that the lints of the NETN pack accept, along with the
+    /// legacy names that its conventions acknowledge.
+    pub exceptions: NamingExceptions,
+    /// The settings for some of the files, in the order they apply.
+    pub overrides: Vec<Override>,
+}
+
+/// The level of a lint, as set by a configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintSetting {
+    pub id: String,
+    pub level: Level,
+    /// The line of the configuration file that sets the level.
+    pub line: usize,
+}
+
+/// The settings of a configuration for the files that match its globs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Override {
+    pub files: Vec<Glob>,
+    pub lints: Vec<LintSetting>,
+    pub exceptions: NamingExceptions,
+}
+
+/// Why a configuration could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub file: Option<PathBuf>,
+    /// The line, from 1, of the problem, if it is in the text of the file.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ConfigError {
+    fn at(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            file: None,
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+        }
+        if self.file.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// A pattern for the paths of files, relative to the root of a
+/// configuration, with `/` between directories. `*` matches any characters
+/// within a file or directory name, `?` matches one, and `**` matches any
+/// number of directories. A pattern without a `/` matches the name of a file
+/// in any directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glob {
+    source: String,
+    segments: Vec<String>,
+}
+
+impl Glob {
+    pub fn new(source: &str) -> Self {
+        let source = source.trim_start_matches("./");
+        let segments = if source.contains('/') {
+            source.split('/').map(String::from).collect()
+        } else {
+            vec![String::from("**"), String::from(source)]
+        };
+        Self {
+            source: String::from(source),
+            segments,
+        }
+    }
+
+    /// The text of the pattern.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Return whether the pattern matches the relative path.
+    pub fn matches(&self, path: &str) -> bool {
+        let path: Vec<&str> = path.split('/').collect();
+        match_segments(&self.segments, &path, false)
+    }
+
+    /// Return whether the pattern may match the paths of files within the
+    /// directory at the relative path.
+    fn may_match_within(&self, directory: &str) -> bool {
+        let directory: Vec<&str> = directory.split('/').filter(|s| !s.is_empty()).collect();
+        match_segments(&self.segments, &directory, true)
+    }
+}
+
+/// Return whether the segments of the pattern match those of the path, or,
+/// if `prefix` is set, whether they may match a path that starts with it.
+fn match_segments(pattern: &[String], path: &[&str], prefix: bool) -> bool {
+    match_sequence(
+        pattern,
+        path,
+        |segment| segment == "**",
+        |segment, name| {
+            let segment: Vec<char> = segment.chars().collect();
+            let name: Vec<char> = name.chars().collect();
+            match_sequence(
+                &segment,
+                &name,
+                |c| *c == '*',
+                |c, n| *c == '?' || c == n,
+                false,
+            )
+        },
+        prefix,
+    )
+}
+
+/// Return whether the pattern matches the items, where the items that
+/// `is_any` accepts match any number of items and every other one matches a
+/// single item that `matches` accepts, or, if `prefix` is set, whether it may
+/// match items that start with them.
+///
+/// Rather than backtracking, which takes exponential time on patterns such as
+/// `**/**/**/a`, the set of positions in the pattern that the items seen so
+/// far may lead to is tracked, item by item.
+fn match_sequence<P, T>(
+    pattern: &[P],
+    items: &[T],
+    is_any: impl Fn(&P) -> bool,
+    matches: impl Fn(&P, &T) -> bool,
+    prefix: bool,
+) -> bool {
+    // Add the positions past each wildcard, which may match no items.
+    let skip_wildcards = |positions: &mut Vec<bool>| {
+        for i in 0..pattern.len() {
+            if positions[i] && is_any(&pattern[i]) {
+                positions[i + 1] = true;
+            }
+        }
+    };
+    let mut positions = vec![false; pattern.len() + 1];
+    positions[0] = true;
+    skip_wildcards(&mut positions);
+    for item in items {
+        let mut next = vec![false; pattern.len() + 1];
+        for (i, part) in pattern.iter().enumerate() {
+            if !positions[i] {
+                continue;
+            }
+            if is_any(part) {
+                next[i] = true;
+            } else if matches(part, item) {
+                next[i + 1] = true;
+            }
+        }
+        skip_wildcards(&mut next);
+        if !next.contains(&true) {
+            return false;
+        }
+        positions = next;
+    }
+    if prefix {
+        positions.contains(&true)
+    } else {
+        positions[pattern.len()]
+    }
+}
+
+impl Override {
+    /// Return whether the settings apply to the file at the relative path.
+    pub fn matches(&self, path: &str) -> bool {
+        self.files.iter().any(|glob| glob.matches(path))
+    }
+}
+
+impl CheckConfig {
+    /// Return the configuration file that applies to the directory: the
+    /// `fom-check.toml` file of the directory or of the nearest of its
+    /// ancestors that has one.
+    pub fn discover(directory: &Path) -> Option<PathBuf> {
+        directory
+            .ancestors()
+            .map(|directory| directory.join(CONFIG_FILE_NAME))
+            .find(|file| file.is_file())
+    }
+
+    /// Read the configuration file, whose directory is the root of the
+    /// configuration.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let with_file = |error: ConfigError| ConfigError {
+            file: Some(path.to_path_buf()),
+            ..error
+        };
+        let source = fs::read_to_string(path).map_err(|e| {
+            with_file(ConfigError {
+                file: None,
+                line: None,
+                message: e.to_string(),
+            })
+        })?;
+        let mut config = Self::parse_str(&source).map_err(with_file)?;
+        config.file = Some(path.to_path_buf());
+        config.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(config)
+    }
+
+    /// Read a configuration from the text of a configuration file, whose root
+    /// is the current directory.
+    pub fn parse_str(source: &str) -> Result<Self, ConfigError> {
+        let table = toml::parse(source).map_err(|e| ConfigError::at(e.line, e.message))?;
+        let mut config = CheckConfig::default();
+        for entry in &table.entries {
+            match entry.key.as_str() {
+                "omt-version" => {
+                    config.omt_version = match string(entry)? {
+                        "2010" => Some(SchemaVersion::Ieee1516_2010),
+                        "2025" => Some(SchemaVersion::Ieee1516_2025),
+                        other => {
+                            return Err(ConfigError::at(
+                                entry.line,
+                                format!("unknown OMT version '{}', expected 2010 or 2025", other),
+                            ))
+                        }
+                    }
+                }
+                "level" => match entry.value {
+                    Value::Integer(level @ 1..=4) => config.level = Some(level as u8),
+                    Value::Integer(level) => {
+                        return Err(ConfigError::at(
+                            entry.line,
+                            format!("unknown level {}, expected 1 to 4", level),
+                        ))
+                    }
+                    _ => return Err(mismatch(entry, "an integer")),
+                },
+                "fdd" => match entry.value {
+                    Value::Boolean(fdd) => config.fdd = fdd,
+                    _ => return Err(mismatch(entry, "a boolean")),
+                },
+                "packs" => {
+                    for pack in strings(entry)? {
+                        if !PACKS.contains(&pack.as_str()) {
+                            return Err(ConfigError::at(
+                                entry.line,
+                                format!("unknown lint pack '{}'", pack),
+                            ));
+                        }
+                        config.packs.push(pack);
+                    }
+                }
+                "files" => {
+                    for entry in &table_of(entry)?.entries {
+                        let globs = strings(entry)?.iter().map(|s| Glob::new(s)).collect();
+                        match entry.key.as_str() {
+                            "include" => config.include = globs,
+                            "exclude" => config.exclude = globs,
+                            _ => return Err(unknown(entry, "files.")),
+                        }
+                    }
+                }
+                "lints" => config.lints = lint_settings(table_of(entry)?)?,
+                "exceptions" => config.exceptions = exceptions(table_of(entry)?, "exceptions.")?,
+                "override" => {
+                    let Value::Tables(tables) = &entry.value else {
+                        return Err(mismatch(entry, "an array of tables"));
+                    };
+                    for table in tables {
+                        config.overrides.push(override_of(table, entry.line)?);
+                    }
+                }
+                _ => return Err(unknown(entry, "")),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Return the path of the file relative to the root, with `/` between
+    /// directories, or the path as given if the file is not within the root.
+    pub fn relative_path(&self, path: &Path) -> String {
+        let root = if self.root.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            &self.root
+        };
+        let relative = match (fs::canonicalize(root), fs::canonicalize(path)) {
+            (Ok(root), Ok(path)) => path
+                .strip_prefix(&root)
+                .map(Path::to_path_buf)
+                .unwrap_or(path),
+            _ => path.to_path_buf(),
+        };
+        let segments: Vec<String> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        segments.join("/")
+    }
+
+    /// Return whether the file is excluded from checks.
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let path = self.relative_path(path);
+        self.exclude.iter().any(|glob| glob.matches(&path))
+    }
+
+    /// Return the files within the root that are included, and not excluded,
+    /// in the order of their paths. Hidden directories are not searched.
+    pub fn included_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        if !self.include.is_empty() {
+            self.find_included(&self.root, "", &mut files);
+        }
+        files.sort();
+        files
+    }
+
+    fn find_included(&self, directory: &Path, relative: &str, files: &mut Vec<PathBuf>) {
+        let search = if directory.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            directory
+        };
+        let Ok(entries) = fs::read_dir(search) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let path = directory.join(&name);
+            let relative = if relative.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", relative, name)
+            };
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let searched = !name.starts_with('.')
+                    && self
+                        .include
+                        .iter()
+                        .any(|glob| glob.may_match_within(&relative));
+                if searched {
+                    self.find_included(&path, &relative, files);
+                }
+            } else if self.include.iter().any(|glob| glob.matches(&relative))
+                && !self.exclude.iter().any(|glob| glob.matches(&relative))
+            {
+                files.push(path);
+            }
+        }
+    }
+
+    /// Return the lints to run over the file, at the levels set for it.
+    pub fn registry(&self, path: &Path) -> LintRegistry {
+        let path = self.relative_path(path);
+        let overrides: Vec<&Override> = self
+            .overrides
+            .iter()
+            .filter(|settings| settings.matches(&path))
+            .collect();
+        let mut registry = LintRegistry::new();
+        if self.packs.iter().any(|pack| pack == "netn") {
+            let mut exceptions = NamingExceptions::legacy();
+            exceptions.extend(&self.exceptions);
+            for settings in &overrides {
+                exceptions.extend(&settings.exceptions);
+            }
+            registry.register_pack(&NetnPack::with_exceptions(exceptions));
+        }
+        let settings = self
+            .lints
+            .iter()
+            .chain(overrides.iter().flat_map(|settings| &settings.lints));
+        for setting in settings {
+            // The lints of packs that are not run have no level to set.
+            if registry.get(&setting.id).is_some() {
+                registry
+                    .set_level(&setting.id, setting.level)
+                    .expect("the lint is registered");
+            }
+        }
+        registry
+    }
+}
+
+/// Return every lint that a configuration may set the level of.
+fn known_lints() -> LintRegistry {
+    let mut registry = LintRegistry::new();
+    registry.register_pack(&NetnPack::new());
+    registry
+}
+
+fn mismatch(entry: &Entry, expected: &str) -> ConfigError {
+    ConfigError::at(
+        entry.line,
+        format!(
+            "'{}' is {}, expected {}",
+            entry.key,
+            entry.value.type_name(),
+            expected
+        ),
+    )
+}
+
+fn unknown(entry: &Entry, table: &str) -> ConfigError {
+    ConfigError::at(entry.line, format!("unknown key '{}{}'", table, entry.key))
+}
+
+fn string(entry: &Entry) -> Result<&str, ConfigError> {
+    match &entry.value {
+        Value::String(value) => Ok(value),
+        _ => Err(mismatch(entry, "a string")),
+    }
+}
+
+fn strings(entry: &Entry) -> Result<Vec<String>, ConfigError> {
+    let expected = "an array of strings";
+    let Value::Array(values) = &entry.value else {
+        return Err(mismatch(entry, expected));
+    };
+    values
+        .iter()
+        .map(|value| match value {
+            Value::String(value) => Ok(value.clone()),
+            _ => Err(mismatch(entry, expected)),
+        })
+        .collect()
+}
+
+fn table_of(entry: &Entry) -> Result<&Table, ConfigError> {
+    match &entry.value {
+        Value::Table(table) => Ok(table),
+        _ => Err(mismatch(entry, "a table")),
+    }
+}
+
+fn lint_settings(table: &Table) -> Result<Vec<LintSetting>, ConfigError> {
+    let known = known_lints();
+    let mut settings = Vec::new();
+    for entry in &table.entries {
+        if known.get(&entry.key).is_none() {
+            return Err(ConfigError::at(
+                entry.line,
+                format!("unknown lint '{}'", entry.key),
+            ));
+        }
+        let level = string(entry)?
+            .parse()
+            .map_err(|e| ConfigError::at(entry.line, format!("{}", e)))?;
+        settings.push(LintSetting {
+            id: entry.key.clone(),
+            level,
+            line: entry.line,
+        });
+    }
+    Ok(settings)
+}
+
+fn exceptions(table: &Table, prefix: &str) -> Result<NamingExceptions, ConfigError> {
+    let mut exceptions = NamingExceptions::default();
+    for entry in &table.entries {
+        let names = strings(entry)?.into_iter();
+        match entry.key.as_str() {
+            "classes" => exceptions.classes.extend(names),
+            "members" => exceptions.members.extend(names),
+            "data-types" => exceptions.data_types.extend(names),
+            _ => return Err(unknown(entry, prefix)),
+        }
+    }
+    Ok(exceptions)
+}
+
+fn override_of(table: &Table, line: usize) -> Result<Override, ConfigError> {
+    let mut settings = Override::default();
+    for entry in &table.entries {
+        match entry.key.as_str() {
+            "files" => settings.files = strings(entry)?.iter().map(|s| Glob::new(s)).collect(),
+            "lints" => settings.lints = lint_settings(table_of(entry)?)?,
+            "exceptions" => {
+                settings.exceptions = exceptions(table_of(entry)?, "override.exceptions.")?
+            }
+            _ => return Err(unknown(entry, "override.")),
+        }
+    }
+    if settings.files.is_empty() {
+        return Err(ConfigError::at(
+            line,
+            "an override has no files to apply to",
+        ));
+    }
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"omt-version = "2010"
+level = 3
+fdd = true
+packs = ["netn"]
+
+[files]
+include = ["modules/**/*.xml"]
+exclude = ["*-draft.xml"]
+
+[lints]
+missing-semantics = "deny"
+netn-enumerator-name = "warn"
+
+[exceptions]
+data-types = ["LegacyType"]
+
+[[override]]
+files = ["modules/experimental/*.xml"]
+lints = { missing-semantics = "allow" }
+exceptions = { classes = ["_Scratch"] }
+"#;
+
+    fn error(source: &str) -> String {
+        CheckConfig::parse_str(source).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse() {
+        let config = CheckConfig::parse_str(CONFIG).unwrap();
+        assert_eq!(Some(SchemaVersion::Ieee1516_2010), config.omt_version);
+        assert_eq!(Some(3), config.level);
+        assert!(config.fdd);
+        assert_eq!(vec![String::from("netn")], config.packs);
+        assert_eq!(vec![Glob::new("modules/**/*.xml")], config.include);
+        assert_eq!("*-draft.xml", config.exclude[0].as_str());
+        assert_eq!(
+            vec![
+                LintSetting {
+                    id: String::from("missing-semantics"),
+                    level: Level::Deny,
+                    line: 11,
+                },
+                LintSetting {
+                    id: String::from("netn-enumerator-name"),
+                    level: Level::Warn,
+                    line: 12,
+                },
+            ],
+            config.lints
+        );
+        assert!(config.exceptions.data_types.contains("LegacyType"));
+        assert_eq!(1, config.overrides.len());
+        assert!(config.overrides[0].matches("modules/experimental/New.xml"));
+        assert!(config.overrides[0].exceptions.classes.contains("_Scratch"));
+
+        assert_eq!(CheckConfig::default(), CheckConfig::parse_str("").unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!("1: unknown key 'levels'", error("levels = 4"));
+        assert_eq!("1: unknown level 5, expected 1 to 4", error("level = 5"));
+        assert_eq!(
+            "1: 'fdd' is a string, expected a boolean",
+            error("fdd = \"yes\"")
+        );
+        assert_eq!(
+            "1: unknown OMT version '2000', expected 2010 or 2025",
+            error("omt-version = \"2000\"")
+        );
+        assert_eq!("1: unknown lint pack 'dis'", error("packs = [\"dis\"]"));
+        assert_eq!(
+            "2: unknown lint 'missing-docs'",
+            error("[lints]\nmissing-docs = \"deny\"")
+        );
+        assert_eq!(
+            "2: unknown lint level 'forbid', expected 'allow', 'warn' or 'deny'",
+            error("[lints]\nmissing-semantics = \"forbid\"")
+        );
+        assert_eq!(
+            "2: unknown key 'files.includes'",
+            error("[files]\nincludes = [\"*.xml\"]")
+        );
+        assert_eq!(
+            "1: an override has no files to apply to",
+            error("[[override]]\nlints = { missing-semantics = \"allow\" }")
+        );
+        assert_eq!("1: unclosed string", error("level = \"4"));
+    }
+
+    #[test]
+    fn test_globs() {
+        let glob = Glob::new("modules/**/*.xml");
+        assert!(glob.matches("modules/a.xml"));
+        assert!(glob.matches("modules/RPR-FOM_v2.0/RPR-Base_v2.0.xml"));
+        assert!(!glob.matches("examples/a.xml"));
+        assert!(!glob.matches("modules/a.xsd"));
+        assert!(glob.may_match_within("modules/RPR-FOM_v2.0"));
+        assert!(!glob.may_match_within("examples"));
+
+        let glob = Glob::new("*-draft.xml");
+        assert!(glob.matches("new-draft.xml"));
+        assert!(glob.matches("modules/new-draft.xml"));
+        assert!(!glob.matches("modules/new.xml"));
+
+        let glob = Glob::new("./examples/Restaurant?OMmodule.xml");
+        assert!(glob.matches("examples/RestaurantFOMmodule.xml"));
+        assert!(glob.matches("examples/RestaurantSOMmodule.xml"));
+        assert!(!glob.may_match_within("modules"));
+
+        let glob = Glob::new("modules/**/**/*.xml");
+        assert!(glob.matches("modules/a.xml"));
+        assert!(glob.matches("modules/b/c/a.xml"));
+        assert!(glob.may_match_within("modules/b"));
+        assert!(!glob.may_match_within("examples/b"));
+    }
+
+    #[test]
+    fn test_globs_do_not_backtrack() {
+        // Each of these would take exponential time to reject by trying
+        // every way of matching the wildcards.
+        let glob = Glob::new(&format!("{}a", "**/".repeat(40)));
+        let path = "b/".repeat(40) + "b";
+        assert!(!glob.matches(&path));
+        assert!(glob.matches(&(path.clone() + "/a")));
+        assert!(glob.may_match_within(&path));
+
+        let glob = Glob::new(&format!("{}b", "*a".repeat(40)));
+        let name = "a".repeat(80);
+        assert!(!glob.matches(&name));
+        assert!(glob.matches(&(name + "b")));
+    }
+
+    #[test]
+    fn test_registry() {
+        let config = CheckConfig::parse_str(CONFIG).unwrap();
+        let registry = config.registry(Path::new("modules/Base.xml"));
+        assert_eq!(Some(Level::Deny), registry.level("missing-semantics"));
+        assert_eq!(Some(Level::Warn), registry.level("netn-enumerator-name"));
+        let registry = config.registry(Path::new("modules/experimental/New.xml"));
+        assert_eq!(Some(Level::Allow), registry.level("missing-semantics"));
+
+        let registry = CheckConfig::default().registry(Path::new("Base.xml"));
+        assert_eq!(Some(Level::Warn), registry.level("missing-semantics"));
+        assert_eq!(None, registry.level("netn-enumerator-name"));
+    }
+
+    #[test]
+    fn test_registry_applies_exceptions() {
+        let config = CheckConfig::parse_str(CONFIG).unwrap();
+        let fom = crate::parse_str(
+            r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+    <dataTypes>
+        <fixedRecordDataTypes>
+            <fixedRecordData>
+                <name>LegacyType</name>
+                <encoding>HLAfixedRecord</encoding>
+            </fixedRecordData>
+            <fixedRecordData>
+                <name>OtherType</name>
+                <encoding>HLAfixedRecord</encoding>
+            </fixedRecordData>
+        </fixedRecordDataTypes>
+    </dataTypes>
+</objectModel>"#,
+        )
+        .unwrap();
+        let names: Vec<String> = config
+            .registry(Path::new("modules/Base.xml"))
+            .run(&fom)
+            .iter()
+            .filter(|diagnostic| diagnostic.lint == "netn-fixed-record-datatype-name")
+            .map(|diagnostic| diagnostic.path.clone())
+            .collect();
+        assert_eq!(
+            vec!["/objectModel/dataTypes/fixedRecordDataTypes/fixedRecordData[OtherType]"],
+            names
+        );
+    }
+
+    #[test]
+    fn test_discover_and_include_files() {
+        let directory = std::env::temp_dir().join(format!("fom-check-{}", std::process::id()));
+        fs::create_dir_all(directory.join("modules/experimental")).unwrap();
+        fs::write(directory.join(CONFIG_FILE_NAME), CONFIG).unwrap();
+        let discovered = CheckConfig::discover(&directory.join("modules/experimental"));
+        assert_eq!(Some(directory.join(CONFIG_FILE_NAME)), discovered);
+        let config = CheckConfig::read(discovered.unwrap()).unwrap();
+        assert_eq!(directory, config.root);
+        fs::remove_dir_all(&directory).unwrap();
+
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+
+        let config = CheckConfig {
+            root: root.clone(),
+            include: vec![Glob::new("modules/RPR-FOM_v2.0/*.xml")],
+            exclude: vec![Glob::new("RPR-Enumerations_v2.0.xml")],
+            ..CheckConfig::default()
+        };
+        let files = config.included_files();
+        assert_eq!(13, files.len());
+        assert_eq!(
+            root.join("modules/RPR-FOM_v2.0/RPR-Aggregate_v2.0.xml"),
+            files[0]
+        );
+        assert!(config.is_excluded(&root.join("modules/RPR-FOM_v2.0/RPR-Enumerations_v2.0.xml")));
+        assert_eq!(
+            "modules/RPR-FOM_v2.0/RPR-Base_v2.0.xml",
+            config.relative_path(&root.join("modules/RPR-FOM_v2.0/RPR-Base_v2.0.xml"))
+        );
+    }
+}
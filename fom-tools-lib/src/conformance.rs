@@ -0,0 +1,967 @@
+//! Conformance of object models with IEEE 1516.2-2010, level 3 of `fom check`.
+//!
+//! The modules of an object model are checked together, along with the HLA
+//! standard MIM that every FOM includes. Each module is validated against the
+//! OMT conformance schema, or the FDD schema. As those schemas describe
+//! complete object models, a reference to a transportation, dimension or
+//! datatype is resolved against all of the modules and the MIM, and a module
+//! may leave out what another module provides, such as the sharing of a
+//! scaffolding class. The modules are then checked against the rules of the
+//! standard that a schema cannot express.
+//!
+//! Each finding cites the clause of IEEE 1516.2-2010 that it breaks, and is
+//! graded as an error or a warning. Modules that are not IEEE 1516.2-2010
+//! documents are left out.
+
+use crate::datatypes::STANDARD_MIM;
+use crate::dom::{Element, Position};
+use crate::path_segment;
+use crate::schema::{Schema, SchemaViolation, ViolationKind};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::OnceLock;
+
+/// The schema the modules are validated against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Profile {
+    /// The OMT conformance schema, for FOMs and SOMs.
+    #[default]
+    Omt,
+    /// The FDD schema, which requires only what an RTI needs, for the FDD of a
+    /// federation execution.
+    Fdd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The object model conforms, but likely not as intended.
+    Warning,
+    /// The object model does not conform.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A rule of IEEE 1516.2-2010 that an object model can break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// A module does not conform to the schema of the profile.
+    Schema,
+    /// A transportation type, dimension or datatype is referred to, but no
+    /// module nor the MIM defines it.
+    UndefinedReference,
+    /// The root of the object class hierarchy is not `HLAobjectRoot`.
+    ObjectClassRoot,
+    /// The root of the interaction class hierarchy is not
+    /// `HLAinteractionRoot`.
+    InteractionClassRoot,
+    /// Two modules, or a module and the MIM, define a datatype of the same
+    /// name differently.
+    ConflictingDataType,
+    /// The discriminant of a variant record datatype is not an enumerated
+    /// datatype, or an alternative names an enumerator it does not have.
+    VariantDiscriminant,
+    /// An enumerated datatype has more than one enumerator of the same name.
+    DuplicateEnumerator,
+    /// A note is never referred to.
+    UnusedNote,
+}
+
+impl Rule {
+    /// The name of the rule, as shown with its findings.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::Schema => "schema",
+            Rule::UndefinedReference => "undefined-reference",
+            Rule::ObjectClassRoot => "object-class-root",
+            Rule::InteractionClassRoot => "interaction-class-root",
+            Rule::ConflictingDataType => "conflicting-datatype",
+            Rule::VariantDiscriminant => "variant-discriminant",
+            Rule::DuplicateEnumerator => "duplicate-enumerator",
+            Rule::UnusedNote => "unused-note",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Rule::UnusedNote => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// A location in one of the modules checked, or in the MIM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    /// The index of the module, or None for the HLA standard MIM.
+    pub document: Option<usize>,
+    pub path: String,
+    pub position: Position,
+}
+
+/// A way in which the modules do not conform to IEEE 1516.2-2010.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub rule: Rule,
+    pub severity: Severity,
+    /// The clause of IEEE 1516.2-2010 that the finding cites, e.g. `4.4` for
+    /// the attribute table.
+    pub clause: &'static str,
+    /// The index of the module the finding is in.
+    pub document: usize,
+    /// The location of the offending element within the module, in the form of
+    /// the path of a `FomError`.
+    pub path: String,
+    pub message: String,
+    /// Where the offending element starts in the module.
+    pub position: Position,
+    /// Another location the finding involves, such as a conflicting definition.
+    pub related: Option<Location>,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}: {} (IEEE 1516.2-2010 {})",
+            self.position, self.severity, self.path, self.message, self.clause
+        )
+    }
+}
+
+/// Return the root element of the HLA standard MIM.
+fn standard_mim_document() -> &'static Element {
+    static MIM: OnceLock<Element> = OnceLock::new();
+    MIM.get_or_init(|| {
+        Element::parse(STANDARD_MIM.as_bytes()).expect("the standard MIM is well-formed")
+    })
+}
+
+/// Check the modules of an object model, each given by the root element of
+/// its document, for conformance with IEEE 1516.2-2010. Returns the findings
+/// of each module in turn, in document order. Documents in a namespace that
+/// the schema does not accept are left out, as they cannot be checked.
+pub fn check_conformance(documents: &[&Element], profile: Profile) -> Vec<Finding> {
+    let schema = match profile {
+        Profile::Omt => Schema::omt(),
+        Profile::Fdd => Schema::fdd(),
+    };
+    let mut modules = vec![Module::new(None, standard_mim_document())];
+    modules.extend(
+        documents
+            .iter()
+            .enumerate()
+            .filter(|(_, root)| schema.accepts_namespace(root.namespace.as_deref()))
+            .map(|(i, root)| Module::new(Some(i), root)),
+    );
+    let mut checker = Checker {
+        modules,
+        findings: Vec::new(),
+    };
+    checker.schema(schema);
+    checker.root_classes();
+    checker.conflicting_data_types();
+    checker.variant_discriminants();
+    checker.duplicate_enumerators();
+    checker.unused_notes();
+    checker
+        .findings
+        .sort_by_key(|finding| (finding.document, finding.position));
+    checker.findings
+}
+
+/// A module being checked, or the MIM.
+struct Module<'a> {
+    document: Option<usize>,
+    root: &'a Element,
+    /// The names of the child elements of each element, by its path.
+    children: HashMap<String, HashSet<&'a str>>,
+}
+
+impl<'a> Module<'a> {
+    fn new(document: Option<usize>, root: &'a Element) -> Self {
+        let mut children = HashMap::new();
+        let mut elements = vec![(format!("/{}", path_segment(root)), root)];
+        while let Some((path, e)) = elements.pop() {
+            let names = e
+                .child_elements()
+                .map(|child| child.name.as_str())
+                .collect();
+            for child in e.child_elements() {
+                elements.push((format!("{}/{}", path, path_segment(child)), child));
+            }
+            children.insert(path, names);
+        }
+        Self {
+            document,
+            root,
+            children,
+        }
+    }
+
+    /// Return the rows of the datatype tables, along with the paths of the
+    /// rows and the names of their tables.
+    fn data_types(&self) -> impl Iterator<Item = (String, &'a str, &'a Element)> {
+        let root = self.root;
+        root.get_child("dataTypes")
+            .into_iter()
+            .flat_map(move |data_types| {
+                data_types.child_elements().flat_map(move |table| {
+                    table.child_elements().map(move |row| {
+                        let path = path_of(&[root, data_types, table, row]);
+                        (path, table.name.as_str(), row)
+                    })
+                })
+            })
+    }
+}
+
+/// Return the trimmed text of the named child of the element.
+fn child_text(e: &Element, name: &str) -> Option<String> {
+    e.get_child(name)
+        .and_then(Element::get_text)
+        .map(|text| String::from(text.trim()))
+}
+
+/// Return the path of the element at the end of a chain of elements, each the
+/// child of the one before, starting from the root.
+fn path_of(elements: &[&Element]) -> String {
+    let segments: Vec<String> = elements.iter().map(|e| path_segment(e)).collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Return the clause of IEEE 1516.2-2010 that specifies the part of an object
+/// model at the path, e.g. `4.4` for `/objectModel/objects/objectClass[A]/attribute[B]`.
+fn clause_of_path(path: &str) -> &'static str {
+    let names: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.split('[').next().unwrap_or(segment))
+        .collect();
+    for name in names.iter().rev() {
+        match *name {
+            "attribute" => return "4.4",
+            "parameter" => return "4.5",
+            "objectClass" => return "4.2",
+            "interactionClass" => return "4.3",
+            _ => {}
+        }
+    }
+    match (names.get(1).copied(), names.get(2).copied()) {
+        (Some("modelIdentification"), _) => "4.1",
+        (Some("objects"), _) => "4.2",
+        (Some("interactions"), _) => "4.3",
+        (Some("dimensions"), _) => "4.6",
+        (Some("time"), _) => "4.7",
+        (Some("tags"), _) => "4.8",
+        (Some("synchronizations"), _) => "4.9",
+        (Some("transportations"), _) => "4.10",
+        (Some("updateRates"), _) => "4.11",
+        (Some("switches"), _) => "4.12",
+        (Some("dataTypes"), Some(table)) => clause_of_data_type_table(table),
+        (Some("dataTypes"), None) => "4.13",
+        (Some("notes"), _) => "4.14",
+        _ => "Annex E",
+    }
+}
+
+fn clause_of_data_type_table(table: &str) -> &'static str {
+    match table {
+        "basicDataRepresentations" | "basicData" => "4.13.1",
+        "simpleDataTypes" | "simpleData" => "4.13.2",
+        "enumeratedDataTypes" | "enumeratedData" => "4.13.3",
+        "arrayDataTypes" | "arrayData" => "4.13.4",
+        "fixedRecordDataTypes" | "fixedRecordData" => "4.13.5",
+        "variantRecordDataTypes" | "variantRecordData" => "4.13.6",
+        _ => "4.13",
+    }
+}
+
+/// Return the clause that specifies the table a `keyref` constraint of the
+/// IEEE 1516.2-2010 schemas refers to.
+fn clause_of_reference(constraint: &str) -> &'static str {
+    match constraint {
+        "transportationRef" => "4.10",
+        "dimensionRef" | "dimensionDatatypeRef" => "4.6",
+        "representationRef" => "4.13.1",
+        _ => "4.13",
+    }
+}
+
+/// Return whether the value at the path is "NA", which a tag has for its
+/// datatype when no user-supplied tag is used (4.8).
+fn not_applicable(path: &str, value: &str) -> bool {
+    value == "NA" && path.starts_with("/objectModel/tags/")
+}
+
+/// Return whether the two elements define the same thing, regardless of
+/// their namespaces, positions, whitespace, semantics and note references.
+fn equivalent(a: &Element, b: &Element) -> bool {
+    let attributes = |e: &Element| -> HashSet<(String, String)> {
+        e.attributes
+            .iter()
+            .filter(|attribute| attribute.name != "notes")
+            .map(|attribute| (attribute.name.clone(), attribute.value.trim().to_string()))
+            .collect()
+    };
+    fn children(e: &Element) -> Vec<&Element> {
+        e.child_elements()
+            .filter(|child| child.name != "semantics")
+            .collect()
+    }
+    let text = |e: &Element| -> String {
+        let text = e.get_text().unwrap_or_default();
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+    let (a_children, b_children) = (children(a), children(b));
+    a.name == b.name
+        && attributes(a) == attributes(b)
+        && a_children.len() == b_children.len()
+        && (!a_children.is_empty() || text(a) == text(b))
+        && a_children
+            .iter()
+            .zip(&b_children)
+            .all(|(a, b)| equivalent(a, b))
+}
+
+struct Checker<'a> {
+    /// The modules checked, after the MIM.
+    modules: Vec<Module<'a>>,
+    findings: Vec<Finding>,
+}
+
+impl<'a> Checker<'a> {
+    /// Return the modules to report findings on, i.e. all but the MIM.
+    fn checked(&self) -> impl Iterator<Item = (usize, &Module<'a>)> {
+        self.modules
+            .iter()
+            .filter_map(|module| module.document.map(|document| (document, module)))
+    }
+
+    fn report(
+        &mut self,
+        rule: Rule,
+        clause: &'static str,
+        document: usize,
+        (path, position): (String, Position),
+        message: String,
+    ) {
+        self.findings.push(Finding {
+            rule,
+            severity: rule.severity(),
+            clause,
+            document,
+            path,
+            message,
+            position,
+            related: None,
+        });
+    }
+
+    /// Validate each module against the schema, resolving references and
+    /// required elements against the other modules and the MIM.
+    fn schema(&mut self, schema: &Schema) {
+        let mut keys: HashMap<String, HashSet<String>> = HashMap::new();
+        for module in &self.modules {
+            for (constraint, values) in schema.key_values(module.root) {
+                keys.entry(constraint).or_default().extend(values);
+            }
+        }
+        let mut findings = Vec::new();
+        for (document, module) in self.checked() {
+            for violation in schema.validate(module.root) {
+                let value = violation.value.as_deref().unwrap_or_default();
+                let (rule, clause) = match &violation.kind {
+                    ViolationKind::UndefinedKey { refer, .. }
+                        if keys.get(refer).is_some_and(|values| values.contains(value))
+                            || not_applicable(&violation.path, value) =>
+                    {
+                        continue
+                    }
+                    ViolationKind::UndefinedKey { constraint, .. } => {
+                        (Rule::UndefinedReference, clause_of_reference(constraint))
+                    }
+                    ViolationKind::MissingElement(names)
+                        if self.provided_elsewhere(module, &violation.path, names) =>
+                    {
+                        continue
+                    }
+                    // Cite the clause of the missing element, which for a
+                    // missing table is not that of the object model.
+                    ViolationKind::MissingElement(names) => {
+                        let name = names.first().map_or("", String::as_str);
+                        let path = format!("{}/{}", violation.path, name);
+                        (Rule::Schema, clause_of_path(&path))
+                    }
+                    _ => (Rule::Schema, clause_of_path(&violation.path)),
+                };
+                findings.push((rule, clause, document, violation));
+            }
+        }
+        for (rule, clause, document, violation) in findings {
+            let message = violation.message();
+            let SchemaViolation { path, position, .. } = violation;
+            self.report(rule, clause, document, (path, position), message);
+        }
+    }
+
+    /// Return whether a module other than the provided one, or the MIM, has
+    /// one of the named elements as a child of the element at the path.
+    fn provided_elsewhere(&self, module: &Module, path: &str, names: &[String]) -> bool {
+        self.modules
+            .iter()
+            .filter(|other| other.document != module.document)
+            .filter_map(|other| other.children.get(path))
+            .any(|children| names.iter().any(|name| children.contains(name.as_str())))
+    }
+
+    /// The root object class must be `HLAobjectRoot` (4.2), and the root
+    /// interaction class `HLAinteractionRoot` (4.3).
+    fn root_classes(&mut self) {
+        let tables = [
+            (
+                "objects",
+                "objectClass",
+                "HLAobjectRoot",
+                Rule::ObjectClassRoot,
+                "4.2",
+            ),
+            (
+                "interactions",
+                "interactionClass",
+                "HLAinteractionRoot",
+                Rule::InteractionClassRoot,
+                "4.3",
+            ),
+        ];
+        let mut findings = Vec::new();
+        for (document, module) in self.checked() {
+            for (table_name, class_name, root_name, rule, clause) in tables {
+                let Some(table) = module.root.get_child(table_name) else {
+                    continue;
+                };
+                for class in table.child_elements().filter(|e| e.name == class_name) {
+                    let name = child_text(class, "name").unwrap_or_default();
+                    if name != root_name {
+                        let location = (path_of(&[module.root, table, class]), class.position);
+                        let message =
+                            format!("root {} '{}' is not '{}'", class_name, name, root_name);
+                        findings.push((rule, clause, document, location, message));
+                    }
+                }
+            }
+        }
+        for (rule, clause, document, location, message) in findings {
+            self.report(rule, clause, document, location, message);
+        }
+    }
+
+    /// Datatype names are unique across the datatype tables (4.13). Within a
+    /// module the schema checks this; across modules, a datatype may be
+    /// defined more than once, but only ever in the same way.
+    fn conflicting_data_types(&mut self) {
+        // The first definition of each datatype, in the MIM and then in the
+        // modules in turn.
+        let mut first: HashMap<String, (Option<usize>, String, &Element)> = HashMap::new();
+        let mut findings = Vec::new();
+        for module in &self.modules {
+            for (path, _, row) in module.data_types() {
+                let Some(name) = child_text(row, "name") else {
+                    continue;
+                };
+                match first.get(&name) {
+                    None => {
+                        first.insert(name, (module.document, path, row));
+                    }
+                    Some((other, other_path, other_row)) if *other != module.document => {
+                        if let (Some(document), false) =
+                            (module.document, equivalent(row, other_row))
+                        {
+                            let related = Location {
+                                document: *other,
+                                path: other_path.clone(),
+                                position: other_row.position,
+                            };
+                            let defined_in = match other {
+                                Some(_) => "another module",
+                                None => "the HLA standard MIM",
+                            };
+                            let message = format!(
+                                "datatype '{}' is defined differently in {}",
+                                name, defined_in
+                            );
+                            findings.push((document, path, row.position, message, related));
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        for (document, path, position, message, related) in findings {
+            let clause = clause_of_path(&path);
+            self.report(
+                Rule::ConflictingDataType,
+                clause,
+                document,
+                (path, position),
+                message,
+            );
+            if let Some(finding) = self.findings.last_mut() {
+                finding.related = Some(related);
+            }
+        }
+    }
+
+    /// Return the datatype of the provided name, preferring the definition of
+    /// the provided module, along with the name of its table.
+    fn data_type(&self, module: &Module<'a>, name: &str) -> Option<(&'a str, &'a Element)> {
+        let named = |m: &Module<'a>| {
+            m.data_types()
+                .find(|(_, _, row)| child_text(row, "name").as_deref() == Some(name))
+                .map(|(_, table, row)| (table, row))
+        };
+        named(module).or_else(|| self.modules.iter().find_map(named))
+    }
+
+    /// The discriminant of a variant record is an enumerated datatype, and
+    /// each alternative is for enumerators of it (4.13.6).
+    fn variant_discriminants(&mut self) {
+        let mut findings = Vec::new();
+        for (document, module) in self.checked() {
+            for (row_path, _, row) in module.data_types() {
+                if row.name != "variantRecordData" {
+                    continue;
+                }
+                let Some(discriminant) = row.get_child("dataType") else {
+                    continue;
+                };
+                let discriminant_name = child_text(row, "dataType").unwrap_or_default();
+                let enumerators: HashSet<String> = match self.data_type(module, &discriminant_name)
+                {
+                    // An undefined discriminant is reported as a schema
+                    // violation.
+                    None => continue,
+                    Some(("enumeratedDataTypes", enumerated)) => enumerated
+                        .child_elements()
+                        .filter(|e| e.name == "enumerator")
+                        .filter_map(|e| child_text(e, "name"))
+                        .collect(),
+                    Some(_) => {
+                        let message = format!(
+                            "discriminant datatype '{}' is not an enumerated datatype",
+                            discriminant_name
+                        );
+                        let location = (format!("{}/dataType", row_path), discriminant.position);
+                        findings.push((document, location, message));
+                        continue;
+                    }
+                };
+                for alternative in row.child_elements().filter(|e| e.name == "alternative") {
+                    let Some(enumerator) = alternative.get_child("enumerator") else {
+                        continue;
+                    };
+                    let text = enumerator.get_text().unwrap_or_default();
+                    let undefined = text
+                        .split(',')
+                        .flat_map(|item| {
+                            let item = item.trim();
+                            match item.strip_prefix('[').and_then(|i| i.strip_suffix(']')) {
+                                Some(range) => range.split("..").map(str::trim).collect(),
+                                None => vec![item],
+                            }
+                        })
+                        .filter(|name| *name != "HLAother" && !enumerators.contains(*name));
+                    for name in undefined {
+                        let message = format!(
+                            "'{}' is not an enumerator of discriminant datatype '{}'",
+                            name, discriminant_name
+                        );
+                        let path = format!("{}/{}/enumerator", row_path, path_segment(alternative));
+                        findings.push((document, (path, enumerator.position), message));
+                    }
+                }
+            }
+        }
+        for (document, location, message) in findings {
+            self.report(
+                Rule::VariantDiscriminant,
+                "4.13.6",
+                document,
+                location,
+                message,
+            );
+        }
+    }
+
+    /// The enumerators of an enumerated datatype have unique names (4.13.3).
+    fn duplicate_enumerators(&mut self) {
+        let mut findings = Vec::new();
+        for (document, module) in self.checked() {
+            for (row_path, _, row) in module.data_types() {
+                if row.name != "enumeratedData" {
+                    continue;
+                }
+                let mut names = HashSet::new();
+                for enumerator in row.child_elements().filter(|e| e.name == "enumerator") {
+                    let Some(name) = child_text(enumerator, "name") else {
+                        continue;
+                    };
+                    if !names.insert(name.clone()) {
+                        let path = format!("{}/{}", row_path, path_segment(enumerator));
+                        let message = format!("duplicate enumerator '{}'", name);
+                        findings.push((document, (path, enumerator.position), message));
+                    }
+                }
+            }
+        }
+        for (document, location, message) in findings {
+            self.report(
+                Rule::DuplicateEnumerator,
+                "4.13.3",
+                document,
+                location,
+                message,
+            );
+        }
+    }
+
+    /// Each note is for some part of the object model that refers to it
+    /// (4.14).
+    fn unused_notes(&mut self) {
+        let mut findings = Vec::new();
+        for (document, module) in self.checked() {
+            let Some(notes) = module.root.get_child("notes") else {
+                continue;
+            };
+            let mut referenced = HashSet::new();
+            let mut elements = vec![module.root];
+            while let Some(e) = elements.pop() {
+                if let Some(labels) = e.get_attribute("notes") {
+                    referenced.extend(labels.split_whitespace());
+                }
+                elements.extend(e.child_elements());
+            }
+            for note in notes.child_elements().filter(|e| e.name == "note") {
+                let label = child_text(note, "label").unwrap_or_default();
+                if !referenced.contains(label.as_str()) {
+                    let location = (path_of(&[module.root, notes, note]), note.position);
+                    let message = format!("note '{}' is not referred to", label);
+                    findings.push((document, location, message));
+                }
+            }
+        }
+        for (document, location, message) in findings {
+            self.report(Rule::UnusedNote, "4.14", document, location, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(documents: &[&str], profile: Profile) -> Vec<String> {
+        let roots: Vec<Element> = documents
+            .iter()
+            .map(|document| Element::parse(document.as_bytes()).unwrap())
+            .collect();
+        let roots: Vec<&Element> = roots.iter().collect();
+        check_conformance(&roots, profile)
+            .iter()
+            .map(|finding| format!("{}:{}", finding.document, finding))
+            .collect()
+    }
+
+    fn module(content: &str) -> String {
+        format!(
+            r#"<?xml version="1.0"?>
+<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+    <modelIdentification>
+        <name>Module</name>
+        <type>FOM</type>
+        <version>1.0</version>
+        <modificationDate>2010-08-31</modificationDate>
+        <securityClassification>Unclassified</securityClassification>
+        <purpose>Testing</purpose>
+        <applicationDomain>Testing</applicationDomain>
+        <description>A module</description>
+        <poc>
+            <pocType>Primary author</pocType>
+            <pocName>Author</pocName>
+            <pocOrg>Organization</pocOrg>
+        </poc>
+    </modelIdentification>
+{}
+</objectModel>"#,
+            content
+        )
+    }
+
+    const SWITCHES: &str = r#"
+    <switches>
+        <autoProvide isEnabled="false"/>
+        <conveyRegionDesignatorSets isEnabled="false"/>
+        <conveyProducingFederate isEnabled="false"/>
+        <attributeScopeAdvisory isEnabled="false"/>
+        <attributeRelevanceAdvisory isEnabled="false"/>
+        <objectClassRelevanceAdvisory isEnabled="false"/>
+        <interactionRelevanceAdvisory isEnabled="false"/>
+        <serviceReporting isEnabled="false"/>
+        <exceptionReporting isEnabled="false"/>
+        <delaySubscriptionEvaluation isEnabled="false"/>
+        <automaticResignAction resignAction="CancelThenDeleteThenDivest"/>
+    </switches>"#;
+
+    #[test]
+    fn test_complete_object_model_conforms() {
+        let document = module(SWITCHES);
+        assert_eq!(Vec::<String>::new(), check(&[&document], Profile::Omt));
+        assert_eq!(Vec::<String>::new(), check(&[&document], Profile::Fdd));
+    }
+
+    #[test]
+    fn test_missing_table_cites_its_clause() {
+        let document = module("");
+        assert_eq!(
+            vec!["0:2:1: error: /objectModel: missing required element 'switches' (IEEE 1516.2-2010 4.12)"],
+            check(&[&document], Profile::Omt)
+        );
+    }
+
+    #[test]
+    fn test_modules_provide_for_each_other() {
+        let vehicle = module(
+            r#"
+    <objects>
+        <objectClass>
+            <name>HLAobjectRoot</name>
+            <objectClass>
+                <name>Vehicle</name>
+                <sharing>PublishSubscribe</sharing>
+                <semantics>A vehicle</semantics>
+            </objectClass>
+        </objectClass>
+    </objects>
+    <dataTypes>
+        <simpleDataTypes>
+            <simpleData>
+                <name>SpeedFloat32</name>
+                <representation>HLAfloat32BE</representation>
+                <units>m/s</units>
+                <resolution>NA</resolution>
+                <accuracy>NA</accuracy>
+                <semantics>A speed</semantics>
+            </simpleData>
+        </simpleDataTypes>
+    </dataTypes>"#,
+        );
+        let car = module(&format!(
+            r#"
+    <objects>
+        <objectClass>
+            <name>HLAobjectRoot</name>
+            <objectClass>
+                <name>Vehicle</name>
+                <objectClass>
+                    <name>Car</name>
+                    <sharing>PublishSubscribe</sharing>
+                    <semantics>A car</semantics>
+                    <attribute>
+                        <name>Speed</name>
+                        <dataType>SpeedFloat32</dataType>
+                        <updateType>Conditional</updateType>
+                        <updateCondition>On change</updateCondition>
+                        <ownership>NoTransfer</ownership>
+                        <sharing>PublishSubscribe</sharing>
+                        <transportation>HLAbestEffort</transportation>
+                        <order>Receive</order>
+                        <semantics>The speed of the car</semantics>
+                    </attribute>
+                    <attribute>
+                        <name>Mass</name>
+                        <dataType>MassFloat32</dataType>
+                        <updateType>Static</updateType>
+                        <updateCondition>NA</updateCondition>
+                        <ownership>NoTransfer</ownership>
+                        <sharing>PublishSubscribe</sharing>
+                        <transportation>HLAreliable</transportation>
+                        <order>Receive</order>
+                        <semantics>The mass of the car</semantics>
+                    </attribute>
+                </objectClass>
+            </objectClass>
+        </objectClass>
+    </objects>{}"#,
+            SWITCHES
+        ));
+        assert_eq!(
+            vec![
+                "1:41:25: error: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Car]/attribute[Mass]/dataType: value 'MassFloat32' of 'dataTypeRef' matches no 'dataTypeKey' (IEEE 1516.2-2010 4.13)",
+            ],
+            check(&[&vehicle, &car], Profile::Omt)
+        );
+        assert_eq!(
+            vec![
+                "0:22:13: error: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]: missing required element 'sharing' (IEEE 1516.2-2010 4.2)",
+                "0:30:25: error: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Car]/attribute[Speed]/dataType: value 'SpeedFloat32' of 'dataTypeRef' matches no 'dataTypeKey' (IEEE 1516.2-2010 4.13)",
+                "0:41:25: error: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/objectClass[Car]/attribute[Mass]/dataType: value 'MassFloat32' of 'dataTypeRef' matches no 'dataTypeKey' (IEEE 1516.2-2010 4.13)",
+            ],
+            check(&[&car], Profile::Omt)
+        );
+    }
+
+    #[test]
+    fn test_class_roots() {
+        let document = module(&format!(
+            r#"
+    <objects>
+        <objectClass>
+            <name>ObjectRoot</name>
+            <sharing>Neither</sharing>
+        </objectClass>
+    </objects>
+    <interactions>
+        <interactionClass>
+            <name>InteractionRoot</name>
+            <sharing>Neither</sharing>
+            <transportation>HLAreliable</transportation>
+            <order>Receive</order>
+        </interactionClass>
+    </interactions>{}"#,
+            SWITCHES
+        ));
+        assert_eq!(
+            vec![
+                "0:20:9: error: /objectModel/objects/objectClass[ObjectRoot]: root objectClass 'ObjectRoot' is not 'HLAobjectRoot' (IEEE 1516.2-2010 4.2)",
+                "0:26:9: error: /objectModel/interactions/interactionClass[InteractionRoot]: root interactionClass 'InteractionRoot' is not 'HLAinteractionRoot' (IEEE 1516.2-2010 4.3)",
+            ],
+            check(&[&document], Profile::Omt)
+        );
+    }
+
+    #[test]
+    fn test_data_types() {
+        let document = module(&format!(
+            r#"{}
+    <dataTypes>
+        <enumeratedDataTypes>
+            <enumeratedData>
+                <name>HLAboolean</name>
+                <representation>HLAoctet</representation>
+                <semantics>A redefinition</semantics>
+                <enumerator>
+                    <name>HLAfalse</name>
+                    <value>0</value>
+                </enumerator>
+                <enumerator>
+                    <name>HLAtrue</name>
+                    <value>1</value>
+                </enumerator>
+            </enumeratedData>
+            <enumeratedData>
+                <name>ColorEnum8</name>
+                <representation>HLAoctet</representation>
+                <semantics>A color</semantics>
+                <enumerator>
+                    <name>Red</name>
+                    <value>1</value>
+                </enumerator>
+                <enumerator>
+                    <name>Red</name>
+                    <value>2</value>
+                </enumerator>
+            </enumeratedData>
+        </enumeratedDataTypes>
+        <arrayDataTypes>
+            <arrayData>
+                <name>HLAASCIIstring</name>
+                <dataType>HLAASCIIchar</dataType>
+                <cardinality>Dynamic</cardinality>
+                <encoding>HLAvariableArray</encoding>
+                <semantics>An identical redefinition</semantics>
+            </arrayData>
+        </arrayDataTypes>
+        <variantRecordDataTypes>
+            <variantRecordData>
+                <name>ByColorVariant</name>
+                <discriminant>Color</discriminant>
+                <dataType>ColorEnum8</dataType>
+                <alternative>
+                    <enumerator>Red, Blue</enumerator>
+                    <name>Value</name>
+                    <dataType>HLAASCIIstring</dataType>
+                </alternative>
+                <encoding>HLAvariantRecord</encoding>
+                <semantics>A variant</semantics>
+            </variantRecordData>
+            <variantRecordData>
+                <name>ByStringVariant</name>
+                <discriminant>String</discriminant>
+                <dataType>HLAASCIIstring</dataType>
+                <alternative>
+                    <enumerator>HLAother</enumerator>
+                    <name>Value</name>
+                    <dataType>HLAASCIIstring</dataType>
+                </alternative>
+                <encoding>HLAvariantRecord</encoding>
+                <semantics>A variant</semantics>
+            </variantRecordData>
+        </variantRecordDataTypes>
+    </dataTypes>"#,
+            SWITCHES
+        ));
+        assert_eq!(
+            vec![
+                "0:34:13: error: /objectModel/dataTypes/enumeratedDataTypes/enumeratedData[HLAboolean]: datatype 'HLAboolean' is defined differently in the HLA standard MIM (IEEE 1516.2-2010 4.13.3)",
+                "0:55:17: error: /objectModel/dataTypes/enumeratedDataTypes/enumeratedData[ColorEnum8]/enumerator[Red]: duplicate enumerator 'Red' (IEEE 1516.2-2010 4.13.3)",
+                "0:76:21: error: /objectModel/dataTypes/variantRecordDataTypes/variantRecordData[ByColorVariant]/alternative[Value]/enumerator: 'Blue' is not an enumerator of discriminant datatype 'ColorEnum8' (IEEE 1516.2-2010 4.13.6)",
+                "0:86:17: error: /objectModel/dataTypes/variantRecordDataTypes/variantRecordData[ByStringVariant]/dataType: discriminant datatype 'HLAASCIIstring' is not an enumerated datatype (IEEE 1516.2-2010 4.13.6)",
+            ],
+            check(&[&document], Profile::Omt)
+        );
+
+        let root = Element::parse(document.as_bytes()).unwrap();
+        let findings = check_conformance(&[&root], Profile::Omt);
+        assert_eq!(Rule::ConflictingDataType, findings[0].rule);
+        let related = findings[0].related.as_ref().unwrap();
+        assert_eq!(None, related.document);
+        assert_eq!(
+            "/objectModel/dataTypes/enumeratedDataTypes/enumeratedData[HLAboolean]",
+            related.path
+        );
+    }
+
+    #[test]
+    fn test_unused_notes() {
+        let document = module(&format!(
+            r#"
+    <objects>
+        <objectClass notes="Used">
+            <name>HLAobjectRoot</name>
+        </objectClass>
+    </objects>{}
+    <notes>
+        <note>
+            <label>Used</label>
+            <semantics>A note</semantics>
+        </note>
+        <note>
+            <label>Unused</label>
+            <semantics>Another note</semantics>
+        </note>
+    </notes>"#,
+            SWITCHES
+        ));
+        assert_eq!(
+            vec!["0:42:9: warning: /objectModel/notes/note[Unused]: note 'Unused' is not referred to (IEEE 1516.2-2010 4.14)"],
+            check(&[&document], Profile::Omt)
+        );
+    }
+}
@@ -0,0 +1,577 @@
+//! Resolution of the datatype references of object models, e.g. those of
+//! attributes, parameters and other datatypes, to the datatypes they name.
+
+use crate::*;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// The HLA standard MIM, as published with IEEE 1516.2-2010.
+pub(crate) const STANDARD_MIM: &str = include_str!("../data/omt/HLAstandardMIM.xml");
+
+/// Return the HLA standard MIM, which declares the predefined datatypes such
+/// as `HLAinteger32BE`, `HLAASCIIstring` and `HLAopaqueData`.
+pub fn standard_mim() -> &'static ObjectModelType {
+    static MIM: OnceLock<ObjectModelType> = OnceLock::new();
+    MIM.get_or_init(|| parse_str(STANDARD_MIM).expect("the standard MIM is a valid object model"))
+}
+
+/// The name used in place of a datatype by parts of an object model that have
+/// none, such as `HLAprivilegeToDeleteObject` in some object models.
+pub(crate) const NOT_APPLICABLE: &str = "NA";
+
+/// A datatype of an object model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataTypeRef<'a> {
+    Basic(&'a BasicDataType),
+    Simple(&'a SimpleDataType),
+    Enumerated(&'a EnumeratedDataType),
+    Array(&'a ArrayDataType),
+    FixedRecord(&'a FixedRecordDataType),
+    VariantRecord(&'a VariantRecordDataType),
+}
+
+impl<'a> DataTypeRef<'a> {
+    pub fn name(&self) -> &'a str {
+        match self {
+            DataTypeRef::Basic(data_type) => &data_type.name,
+            DataTypeRef::Simple(data_type) => &data_type.name,
+            DataTypeRef::Enumerated(data_type) => &data_type.name,
+            DataTypeRef::Array(data_type) => &data_type.name,
+            DataTypeRef::FixedRecord(data_type) => &data_type.name,
+            DataTypeRef::VariantRecord(data_type) => &data_type.name,
+        }
+    }
+
+    pub fn span(&self) -> &'a SourceSpan {
+        match self {
+            DataTypeRef::Basic(data_type) => &data_type.span,
+            DataTypeRef::Simple(data_type) => &data_type.span,
+            DataTypeRef::Enumerated(data_type) => &data_type.span,
+            DataTypeRef::Array(data_type) => &data_type.span,
+            DataTypeRef::FixedRecord(data_type) => &data_type.span,
+            DataTypeRef::VariantRecord(data_type) => &data_type.span,
+        }
+    }
+
+    /// Return the location of the datatype within its object model, in the
+    /// form used by `FomError`, e.g.
+    /// `/objectModel/dataTypes/arrayDataTypes/arrayData[HLAASCIIstring]`.
+    pub fn path(&self) -> String {
+        let (table, row) = match self {
+            DataTypeRef::Basic(_) => ("basicDataRepresentations", "basicData"),
+            DataTypeRef::Simple(_) => ("simpleDataTypes", "simpleData"),
+            DataTypeRef::Enumerated(_) => ("enumeratedDataTypes", "enumeratedData"),
+            DataTypeRef::Array(_) => ("arrayDataTypes", "arrayData"),
+            DataTypeRef::FixedRecord(_) => ("fixedRecordDataTypes", "fixedRecordData"),
+            DataTypeRef::VariantRecord(_) => ("variantRecordDataTypes", "variantRecordData"),
+        };
+        format!("/objectModel/dataTypes/{}/{}[{}]", table, row, self.name())
+    }
+
+    /// Return the semantics of the datatype, or the interpretation of a basic
+    /// data representation, if it has any.
+    pub fn semantics(&self) -> Option<&'a str> {
+        match self {
+            DataTypeRef::Basic(data_type) => data_type.interpretation.as_deref(),
+            DataTypeRef::Simple(data_type) => data_type.semantics.as_deref(),
+            DataTypeRef::Enumerated(data_type) => data_type.semantics.as_deref(),
+            DataTypeRef::Array(data_type) => data_type.semantics.as_deref(),
+            DataTypeRef::FixedRecord(data_type) => data_type.semantics.as_deref(),
+            DataTypeRef::VariantRecord(data_type) => data_type.semantics.as_deref(),
+        }
+    }
+
+    /// Return the names of the datatypes this datatype refers to directly, in
+    /// document order.
+    pub fn referenced_names(&self) -> Vec<&'a str> {
+        let references: Vec<Option<&'a ReferenceType>> = match self {
+            DataTypeRef::Basic(_) => Vec::new(),
+            DataTypeRef::Simple(data_type) => vec![data_type.representation.as_ref()],
+            DataTypeRef::Enumerated(data_type) => vec![data_type.representation.as_ref()],
+            DataTypeRef::Array(data_type) => vec![data_type.data_type.as_ref()],
+            DataTypeRef::FixedRecord(data_type) => data_type
+                .fields
+                .iter()
+                .flatten()
+                .map(|field| field.data_type.as_ref())
+                .collect(),
+            DataTypeRef::VariantRecord(data_type) => std::iter::once(data_type.data_type.as_ref())
+                .chain(
+                    data_type
+                        .alternatives
+                        .iter()
+                        .flatten()
+                        .map(|alternative| alternative.data_type.as_ref()),
+                )
+                .collect(),
+        };
+        references
+            .into_iter()
+            .flatten()
+            .map(|reference| reference.value.as_str())
+            .filter(|name| *name != NOT_APPLICABLE)
+            .collect()
+    }
+}
+
+impl ObjectModelType {
+    /// Return the datatypes declared by the object model itself, table by
+    /// table in the order of the OMT, and in document order within each
+    /// table.
+    pub fn declared_data_types(&self) -> Vec<DataTypeRef<'_>> {
+        let data_types = match &self.data_types {
+            Some(data_types) => data_types,
+            None => return Vec::new(),
+        };
+        data_types
+            .basic_data_representations
+            .iter()
+            .flat_map(|d| d.basic_datas.iter().flatten().map(DataTypeRef::Basic))
+            .chain(
+                data_types
+                    .simple_data_types
+                    .iter()
+                    .flat_map(|d| d.simple_datas.iter().flatten().map(DataTypeRef::Simple)),
+            )
+            .chain(data_types.enumerated_data_types.iter().flat_map(|d| {
+                d.enumerated_datas
+                    .iter()
+                    .flatten()
+                    .map(DataTypeRef::Enumerated)
+            }))
+            .chain(
+                data_types
+                    .array_data_types
+                    .iter()
+                    .flat_map(|d| d.array_datas.iter().flatten().map(DataTypeRef::Array)),
+            )
+            .chain(data_types.fixed_record_data_types.iter().flat_map(|d| {
+                d.fixed_record_datas
+                    .iter()
+                    .flatten()
+                    .map(DataTypeRef::FixedRecord)
+            }))
+            .chain(data_types.variand_record_data_types.iter().flat_map(|d| {
+                d.variant_record_datas
+                    .iter()
+                    .flatten()
+                    .map(DataTypeRef::VariantRecord)
+            }))
+            .collect()
+    }
+}
+
+/// A reference to a datatype by name from a part of an object model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataTypeReference<'a> {
+    /// The location of the referring element, in the form used by `FomError`,
+    /// e.g. `/objectModel/dataTypes/arrayDataTypes/arrayData[HLAASCIIstring]/dataType`.
+    pub path: String,
+    /// The name of the referenced datatype.
+    pub name: &'a str,
+    /// The span of the part of the object model that holds the reference.
+    pub span: &'a SourceSpan,
+}
+
+/// The datatypes of one or more object models and of the HLA standard MIM,
+/// by name, along with every reference to them.
+#[derive(Debug, Clone)]
+pub struct DataTypeRegistry<'a> {
+    data_types: HashMap<&'a str, DataTypeRef<'a>>,
+    /// The names of the datatypes, in the order they were declared.
+    names: Vec<&'a str>,
+    references: Vec<DataTypeReference<'a>>,
+}
+
+impl<'a> DataTypeRegistry<'a> {
+    /// Create a registry of the datatypes of the object model and of the HLA
+    /// standard MIM.
+    pub fn new(fom: &'a ObjectModelType) -> Self {
+        Self::from_modules(&[fom])
+    }
+
+    /// Create a registry of the datatypes of the object models, such as the
+    /// modules of a FOM, and of the HLA standard MIM. When more than one
+    /// declares a datatype of the same name, the first declaration is used, and
+    /// those of the MIM come last.
+    pub fn from_modules(modules: &[&'a ObjectModelType]) -> Self {
+        let mut registry = Self {
+            data_types: HashMap::new(),
+            names: Vec::new(),
+            references: Vec::new(),
+        };
+        for fom in modules
+            .iter()
+            .copied()
+            .chain(std::iter::once(standard_mim()))
+        {
+            registry.add(fom);
+        }
+        registry
+    }
+
+    fn add(&mut self, fom: &'a ObjectModelType) {
+        for data_type in fom.declared_data_types() {
+            if !self.data_types.contains_key(data_type.name()) {
+                self.data_types.insert(data_type.name(), data_type);
+                self.names.push(data_type.name());
+            }
+        }
+        self.add_references(fom);
+    }
+
+    fn add_reference(
+        &mut self,
+        path: String,
+        reference: Option<&'a ReferenceType>,
+        span: &'a SourceSpan,
+    ) {
+        if let Some(reference) = reference {
+            if reference.value != NOT_APPLICABLE {
+                self.references.push(DataTypeReference {
+                    path,
+                    name: &reference.value,
+                    span,
+                });
+            }
+        }
+    }
+
+    /// Record the datatype references of the object model, in document order.
+    fn add_references(&mut self, fom: &'a ObjectModelType) {
+        for node in fom.object_classes() {
+            let class_path: Vec<String> = node
+                .classes()
+                .iter()
+                .map(|class| format!("objectClass[{}]", class.name))
+                .collect();
+            for attribute in node.class().attributes.iter().flatten() {
+                let path = format!(
+                    "/objectModel/objects/{}/attribute[{}]/dataType",
+                    class_path.join("/"),
+                    attribute.name
+                );
+                self.add_reference(path, attribute.data_type.as_ref(), &attribute.span);
+            }
+        }
+        for node in fom.interaction_classes() {
+            let class_path: Vec<String> = node
+                .classes()
+                .iter()
+                .map(|class| format!("interactionClass[{}]", class.name))
+                .collect();
+            for parameter in node.class().parameters.iter().flatten() {
+                let path = format!(
+                    "/objectModel/interactions/{}/parameter[{}]/dataType",
+                    class_path.join("/"),
+                    parameter.name
+                );
+                self.add_reference(path, parameter.data_type.as_ref(), &parameter.span);
+            }
+        }
+        let dimensions = fom
+            .dimensions
+            .iter()
+            .flat_map(|d| d.dimensions.iter().flatten());
+        for dimension in dimensions {
+            let path = format!(
+                "/objectModel/dimensions/dimension[{}]/dataType",
+                dimension.name
+            );
+            self.add_reference(path, dimension.data_type.as_ref(), &dimension.span);
+        }
+
+        let data_types = match &fom.data_types {
+            Some(data_types) => data_types,
+            None => return,
+        };
+        let simple_datas = data_types
+            .simple_data_types
+            .iter()
+            .flat_map(|d| d.simple_datas.iter().flatten());
+        for simple_data in simple_datas {
+            let path = format!(
+                "/objectModel/dataTypes/simpleDataTypes/simpleData[{}]/representation",
+                simple_data.name
+            );
+            self.add_reference(path, simple_data.representation.as_ref(), &simple_data.span);
+        }
+        let enumerated_datas = data_types
+            .enumerated_data_types
+            .iter()
+            .flat_map(|d| d.enumerated_datas.iter().flatten());
+        for enumerated_data in enumerated_datas {
+            let path = format!(
+                "/objectModel/dataTypes/enumeratedDataTypes/enumeratedData[{}]/representation",
+                enumerated_data.name
+            );
+            self.add_reference(
+                path,
+                enumerated_data.representation.as_ref(),
+                &enumerated_data.span,
+            );
+        }
+        let array_datas = data_types
+            .array_data_types
+            .iter()
+            .flat_map(|d| d.array_datas.iter().flatten());
+        for array_data in array_datas {
+            let path = format!(
+                "/objectModel/dataTypes/arrayDataTypes/arrayData[{}]/dataType",
+                array_data.name
+            );
+            self.add_reference(path, array_data.data_type.as_ref(), &array_data.span);
+        }
+        let fixed_record_datas = data_types
+            .fixed_record_data_types
+            .iter()
+            .flat_map(|d| d.fixed_record_datas.iter().flatten());
+        for fixed_record_data in fixed_record_datas {
+            for field in fixed_record_data.fields.iter().flatten() {
+                let path = format!(
+                    "/objectModel/dataTypes/fixedRecordDataTypes/fixedRecordData[{}]/field[{}]/dataType",
+                    fixed_record_data.name, field.name
+                );
+                self.add_reference(path, field.data_type.as_ref(), &field.span);
+            }
+        }
+        let variant_record_datas = data_types
+            .variand_record_data_types
+            .iter()
+            .flat_map(|d| d.variant_record_datas.iter().flatten());
+        for variant_record_data in variant_record_datas {
+            let record_path = format!(
+                "/objectModel/dataTypes/variantRecordDataTypes/variantRecordData[{}]",
+                variant_record_data.name
+            );
+            self.add_reference(
+                format!("{}/dataType", record_path),
+                variant_record_data.data_type.as_ref(),
+                &variant_record_data.span,
+            );
+            for alternative in variant_record_data.alternatives.iter().flatten() {
+                let segment = match &alternative.name {
+                    Some(name) => format!("alternative[{}]", name),
+                    None => String::from("alternative"),
+                };
+                self.add_reference(
+                    format!("{}/{}/dataType", record_path, segment),
+                    alternative.data_type.as_ref(),
+                    &alternative.span,
+                );
+            }
+        }
+    }
+
+    /// Return the datatype with the provided name.
+    pub fn get(&self, name: &str) -> Option<DataTypeRef<'a>> {
+        self.data_types.get(name).copied()
+    }
+
+    /// Return the datatype that the reference names.
+    pub fn resolve(&self, reference: &ReferenceType) -> Option<DataTypeRef<'a>> {
+        self.get(&reference.value)
+    }
+
+    /// Return every datatype, in the order they were declared.
+    pub fn data_types(&self) -> impl Iterator<Item = DataTypeRef<'a>> + '_ {
+        self.names.iter().map(move |name| self.data_types[name])
+    }
+
+    /// Return every datatype reference of the object models and the MIM, in
+    /// document order.
+    pub fn references(&self) -> &[DataTypeReference<'a>] {
+        &self.references
+    }
+
+    /// Return the references to datatypes that none of the object models or
+    /// the MIM declare.
+    pub fn dangling_references(&self) -> Vec<&DataTypeReference<'a>> {
+        self.references
+            .iter()
+            .filter(|reference| !self.data_types.contains_key(reference.name))
+            .collect()
+    }
+
+    /// Return the groups of datatypes that refer to each other, directly or
+    /// indirectly, so that the size of their values has no bound. Where a
+    /// cycle passes only through fixed records and fixed arrays, no value is
+    /// finite and none can be encoded; through an `HLAvariableArray` or a
+    /// variant record, values can be encoded, but not with a fixed size. The
+    /// datatypes of each group, and the groups themselves, are in the order
+    /// they were declared.
+    pub fn cycles(&self) -> Vec<Vec<&'a str>> {
+        // Tarjan's algorithm for strongly connected components.
+        struct Search<'r, 'a> {
+            registry: &'r DataTypeRegistry<'a>,
+            index: HashMap<&'a str, usize>,
+            low_link: HashMap<&'a str, usize>,
+            stack: Vec<&'a str>,
+            components: Vec<Vec<&'a str>>,
+        }
+
+        impl<'r, 'a> Search<'r, 'a> {
+            fn visit(&mut self, name: &'a str) {
+                let index = self.index.len();
+                self.index.insert(name, index);
+                self.low_link.insert(name, index);
+                self.stack.push(name);
+
+                let data_type = self.registry.data_types[name];
+                for referenced in data_type.referenced_names() {
+                    let referenced = match self.registry.get(referenced) {
+                        Some(referenced) => referenced.name(),
+                        None => continue,
+                    };
+                    if !self.index.contains_key(referenced) {
+                        self.visit(referenced);
+                        let low_link = self.low_link[name].min(self.low_link[referenced]);
+                        self.low_link.insert(name, low_link);
+                    } else if self.stack.contains(&referenced) {
+                        let low_link = self.low_link[name].min(self.index[referenced]);
+                        self.low_link.insert(name, low_link);
+                    }
+                }
+
+                if self.low_link[name] == self.index[name] {
+                    let position = self.stack.iter().rposition(|n| *n == name).unwrap();
+                    let component = self.stack.split_off(position);
+                    let is_cycle =
+                        component.len() > 1 || data_type.referenced_names().contains(&name);
+                    if is_cycle {
+                        self.components.push(component);
+                    }
+                }
+            }
+        }
+
+        let mut search = Search {
+            registry: self,
+            index: HashMap::new(),
+            low_link: HashMap::new(),
+            stack: Vec::new(),
+            components: Vec::new(),
+        };
+        for name in &self.names {
+            if !search.index.contains_key(name) {
+                search.visit(name);
+            }
+        }
+
+        let order: HashMap<&str, usize> = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (*n, i))
+            .collect();
+        let mut cycles = search.components;
+        for cycle in &mut cycles {
+            cycle.sort_by_key(|name| order[name]);
+        }
+        cycles.sort_by_key(|cycle| order[cycle[0]]);
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_mim_is_consistent() {
+        let registry = DataTypeRegistry::from_modules(&[]);
+        assert!(registry.dangling_references().is_empty());
+        assert!(registry.cycles().is_empty());
+        assert!(matches!(
+            registry.get("HLAinteger32BE"),
+            Some(DataTypeRef::Basic(_))
+        ));
+        assert!(matches!(
+            registry.get("HLAASCIIstring"),
+            Some(DataTypeRef::Array(_))
+        ));
+        assert!(matches!(
+            registry.get("HLAopaqueData"),
+            Some(DataTypeRef::Array(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_references() {
+        let fom = parse_str(
+            r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <objects>
+    <objectClass>
+      <name>HLAobjectRoot</name>
+      <objectClass>
+        <name>Car</name>
+        <attribute>
+          <name>Name</name>
+          <dataType>HLAunicodeString</dataType>
+        </attribute>
+        <attribute>
+          <name>Position</name>
+          <dataType>PositionStruct</dataType>
+        </attribute>
+      </objectClass>
+    </objectClass>
+  </objects>
+  <dataTypes>
+    <arrayDataTypes>
+      <arrayData>
+        <name>Route</name>
+        <dataType>Waypoint</dataType>
+        <cardinality>Dynamic</cardinality>
+        <encoding>HLAvariableArray</encoding>
+      </arrayData>
+    </arrayDataTypes>
+    <fixedRecordDataTypes>
+      <fixedRecordData>
+        <name>Waypoint</name>
+        <field>
+          <name>Next</name>
+          <dataType>Route</dataType>
+        </field>
+        <field>
+          <name>Speed</name>
+          <dataType>SpeedFloat</dataType>
+        </field>
+      </fixedRecordData>
+    </fixedRecordDataTypes>
+  </dataTypes>
+</objectModel>"#,
+        )
+        .unwrap();
+        let registry = DataTypeRegistry::new(&fom);
+
+        let car = fom.object_class("HLAobjectRoot.Car").unwrap();
+        let name = car.attributes.as_ref().unwrap()[0]
+            .data_type
+            .as_ref()
+            .unwrap();
+        match registry.resolve(name) {
+            Some(DataTypeRef::Array(array)) => assert_eq!("HLAunicodeString", array.name),
+            other => panic!("expected an array datatype, got {:?}", other),
+        }
+
+        let dangling: Vec<(&str, &str)> = registry
+            .dangling_references()
+            .iter()
+            .map(|reference| (reference.path.as_str(), reference.name))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Car]/attribute[Position]/dataType",
+                    "PositionStruct"
+                ),
+                (
+                    "/objectModel/dataTypes/fixedRecordDataTypes/fixedRecordData[Waypoint]/field[Speed]/dataType",
+                    "SpeedFloat"
+                ),
+            ],
+            dangling
+        );
+        assert_eq!(vec![vec!["Route", "Waypoint"]], registry.cycles());
+    }
+}
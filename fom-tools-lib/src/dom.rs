@@ -0,0 +1,482 @@
+//! A minimal XML document object model that, unlike `xmltree`, remembers where
+//! in the source document each element started. The model builders in this
+//! crate use these positions when reporting problems with a document.
+
+use std::fmt;
+use std::io::{Read, Write};
+use xml::attribute::OwnedAttribute;
+use xml::common::Position as _;
+use xml::name::{Name, OwnedName};
+use xml::namespace::Namespace;
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
+use xml::writer::{EmitterConfig, EventWriter};
+
+pub use xml::reader::Error as ParseError;
+pub use xml::writer::Error as WriteError;
+
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// A line and column within an XML document. Both are one-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Position {
+    pub line: u64,
+    pub column: u64,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// An attribute of an element, with the namespace of its name if it has one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub name: String,
+    pub namespace: Option<String>,
+    pub value: String,
+}
+
+/// A child of an element. Comments and processing instructions are not kept.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Node {
+    pub fn as_element(&self) -> Option<&Element> {
+        match self {
+            Node::Element(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    /// The local name of the element, without any namespace prefix.
+    pub name: String,
+    pub namespace: Option<String>,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Node>,
+    /// Where the start tag of the element begins in the source document.
+    pub position: Position,
+    /// Where the element ends in the source document, i.e. the position just
+    /// past its end tag, or past its start tag if it is an empty element.
+    pub end: Position,
+}
+
+impl Element {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            namespace: None,
+            attributes: Vec::new(),
+            children: Vec::new(),
+            position: Position::default(),
+            end: Position::default(),
+        }
+    }
+
+    /// Parse the root element of the XML document provided by the reader.
+    pub fn parse<R: Read>(r: R) -> Result<Element, ParseError> {
+        let mut events = Events::new(r);
+        let root = events.read_root_start()?;
+        let root = events.read_element(root)?;
+        events.read_to_end()?;
+        Ok(root)
+    }
+
+    /// Return the first child element with the provided local name.
+    pub fn get_child(&self, name: &str) -> Option<&Element> {
+        self.child_elements().find(|e| e.name == name)
+    }
+
+    /// Return all child elements, in document order.
+    pub fn child_elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(Node::as_element)
+    }
+
+    /// Return the concatenated text content of this element, if it has any.
+    pub fn get_text(&self) -> Option<String> {
+        let text: Vec<&str> = self
+            .children
+            .iter()
+            .filter_map(|node| match node {
+                Node::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        if text.is_empty() {
+            None
+        } else {
+            Some(text.concat())
+        }
+    }
+
+    /// Return the value of the attribute with the provided local name. Only
+    /// attributes without a namespace are considered.
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.namespace.is_none() && a.name == name)
+            .map(|a| a.value.as_str())
+    }
+}
+
+/// The events of an XML document, read one ahead so that the end of an element
+/// is known when its end tag is read. Elements can be read whole, or, for a
+/// caller that converts a document as it is read, start tag by start tag.
+pub(crate) struct Events<R: Read> {
+    reader: EventReader<R>,
+    next: Option<(XmlEvent, Position)>,
+}
+
+impl<R: Read> Events<R> {
+    pub(crate) fn new(r: R) -> Self {
+        // Comments are reported so that the end of an element followed by a
+        // comment is not taken to be past the comment.
+        Self {
+            reader: EventReader::new_with_config(r, ParserConfig::new().ignore_comments(false)),
+            next: None,
+        }
+    }
+
+    /// Return the next event and the position in the document it starts at.
+    pub(crate) fn next(&mut self) -> Result<(XmlEvent, Position), ParseError> {
+        match self.next.take() {
+            Some(next) => Ok(next),
+            None => self.read(),
+        }
+    }
+
+    fn read(&mut self) -> Result<(XmlEvent, Position), ParseError> {
+        let event = self.reader.next()?;
+        let position = self.reader.position();
+        let position = Position {
+            line: position.row + 1,
+            column: position.column + 1,
+        };
+        Ok((event, position))
+    }
+
+    /// Return the position of the next event, which is where an element ends
+    /// when its end tag was the last event read.
+    fn peek_position(&mut self) -> Result<Position, ParseError> {
+        if self.next.is_none() {
+            self.next = Some(self.read()?);
+        }
+        Ok(self
+            .next
+            .as_ref()
+            .map(|(_, position)| *position)
+            .unwrap_or_default())
+    }
+
+    /// Read up to and including the start tag of the root element, and return
+    /// the root element without any children.
+    pub(crate) fn read_root_start(&mut self) -> Result<Element, ParseError> {
+        self.read_root_start_with_namespace().map(|(root, _)| root)
+    }
+
+    /// Read up to and including the start tag of the root element, and return
+    /// the root element without any children, along with the namespace
+    /// prefixes in scope at its start tag.
+    pub(crate) fn read_root_start_with_namespace(
+        &mut self,
+    ) -> Result<(Element, Namespace), ParseError> {
+        loop {
+            if let (
+                XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    namespace,
+                },
+                position,
+            ) = self.next()?
+            {
+                return Ok((start_element(name, attributes, position), namespace));
+            }
+            // The XML reader reports a document without a root element as an
+            // error before its end.
+        }
+    }
+
+    /// Read the children and end tag of the element whose start tag was the
+    /// last event read.
+    pub(crate) fn read_element(&mut self, e: Element) -> Result<Element, ParseError> {
+        self.read_children(e, |_, _, child| Ok(Some(child)))
+    }
+
+    /// Read the children and end tag of the element whose start tag was the
+    /// last event read. The provided function is given the element as read so
+    /// far and the start tag of each child element, and either reads the rest
+    /// of the child itself and returns None, or returns the child to have it
+    /// read whole and added to the element.
+    pub(crate) fn read_children<F>(&mut self, e: Element, mut f: F) -> Result<Element, ParseError>
+    where
+        F: FnMut(&mut Self, &Element, Element) -> Result<Option<Element>, ParseError>,
+    {
+        // Elements that have been started but not yet ended. The last entry is
+        // the element currently being populated.
+        let mut open = vec![e];
+        loop {
+            match self.next()? {
+                (
+                    XmlEvent::StartElement {
+                        name, attributes, ..
+                    },
+                    position,
+                ) => {
+                    let child = start_element(name, attributes, position);
+                    if open.len() > 1 {
+                        open.push(child);
+                    } else if let Some(child) = f(self, &open[0], child)? {
+                        open.push(child);
+                    }
+                }
+                (XmlEvent::EndElement { .. }, _) => {
+                    // The XML reader guarantees start and end tags are balanced.
+                    let mut element = open.pop().expect("end tag without start tag");
+                    element.end = self.peek_position()?;
+                    match open.last_mut() {
+                        Some(parent) => parent.children.push(Node::Element(element)),
+                        None => return Ok(element),
+                    }
+                }
+                (XmlEvent::Characters(text), _) | (XmlEvent::CData(text), _) => {
+                    if let Some(parent) = open.last_mut() {
+                        parent.children.push(Node::Text(text));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Read the rest of the document after the end of the root element.
+    pub(crate) fn read_to_end(&mut self) -> Result<(), ParseError> {
+        loop {
+            if let (XmlEvent::EndDocument, _) = self.next()? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn start_element(name: OwnedName, attributes: Vec<OwnedAttribute>, position: Position) -> Element {
+    Element {
+        name: name.local_name,
+        namespace: name.namespace,
+        attributes: attributes
+            .into_iter()
+            .map(|a| Attribute {
+                name: a.name.local_name,
+                namespace: a.name.namespace,
+                value: a.value,
+            })
+            .collect(),
+        children: Vec::new(),
+        position,
+        end: position,
+    }
+}
+
+/// How an element is written as an XML document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteOptions {
+    /// The string written for each level of nesting, or None to write the
+    /// document without line breaks or indentation.
+    pub indent: Option<String>,
+    /// Whether to begin the document with an XML declaration.
+    pub xml_declaration: bool,
+}
+
+impl Default for WriteOptions {
+    /// Indent with four spaces, as the SISO published FOM modules are.
+    fn default() -> Self {
+        Self {
+            indent: Some(String::from("    ")),
+            xml_declaration: true,
+        }
+    }
+}
+
+impl Element {
+    /// Write this element as the root element of an XML document. The
+    /// namespace of this element is declared as the default namespace, and
+    /// any other namespace used within it is declared on it with a prefix;
+    /// `xsi` for XML Schema instance attributes such as `xsi:schemaLocation`.
+    /// When indenting, text that is only whitespace is not written, as the
+    /// indentation replaces it.
+    pub fn write<W: Write>(&self, w: W, options: &WriteOptions) -> Result<(), WriteError> {
+        let mut writer = EmitterConfig::new()
+            .perform_indent(options.indent.is_some())
+            .indent_string(options.indent.clone().unwrap_or_default())
+            .write_document_declaration(options.xml_declaration)
+            .pad_self_closing(false)
+            .create_writer(w);
+
+        let mut namespaces = Vec::new();
+        self.collect_namespaces(&mut namespaces);
+        let mut prefixes: Vec<(String, String)> = Vec::new();
+        let mut generated = 0;
+        for namespace in namespaces {
+            if Some(&namespace) == self.namespace.as_ref() {
+                continue;
+            }
+            let prefix = if namespace == XSI_NAMESPACE {
+                String::from("xsi")
+            } else {
+                generated += 1;
+                format!("ns{}", generated)
+            };
+            prefixes.push((namespace, prefix));
+        }
+
+        self.write_element(&mut writer, options, &prefixes, true)
+    }
+
+    /// Collect the namespaces of this element, its attributes and its
+    /// descendants, in document order and without duplicates.
+    fn collect_namespaces(&self, namespaces: &mut Vec<String>) {
+        let attribute_namespaces = self.attributes.iter().map(|a| &a.namespace);
+        for namespace in std::iter::once(&self.namespace)
+            .chain(attribute_namespaces)
+            .flatten()
+        {
+            if !namespaces.contains(namespace) {
+                namespaces.push(namespace.clone());
+            }
+        }
+        for child in self.child_elements() {
+            child.collect_namespaces(namespaces);
+        }
+    }
+
+    fn write_element<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        options: &WriteOptions,
+        prefixes: &[(String, String)],
+        is_root: bool,
+    ) -> Result<(), WriteError> {
+        let prefix_of = |namespace: &Option<String>| {
+            prefixes
+                .iter()
+                .find(|(n, _)| Some(n) == namespace.as_ref())
+                .map(|(_, prefix)| prefix.as_str())
+        };
+        let name = match prefix_of(&self.namespace) {
+            Some(prefix) => Name::prefixed(&self.name, prefix),
+            None => Name::local(&self.name),
+        };
+
+        let mut start = xml::writer::XmlEvent::start_element(name);
+        if is_root {
+            if let Some(namespace) = &self.namespace {
+                start = start.default_ns(namespace.as_str());
+            }
+            for (namespace, prefix) in prefixes {
+                start = start.ns(prefix.as_str(), namespace.as_str());
+            }
+        }
+        for a in &self.attributes {
+            let name = match prefix_of(&a.namespace) {
+                Some(prefix) => Name::prefixed(&a.name, prefix),
+                None => Name::local(&a.name),
+            };
+            start = start.attr(name, &a.value);
+        }
+        writer.write(start)?;
+
+        for child in &self.children {
+            match child {
+                Node::Element(e) => e.write_element(writer, options, prefixes, false)?,
+                Node::Text(text) if options.indent.is_some() && text.trim().is_empty() => {}
+                Node::Text(text) => writer.write(xml::writer::XmlEvent::characters(text))?,
+            }
+        }
+        writer.write(xml::writer::XmlEvent::end_element())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_records_positions() {
+        let xml = "<root>\n  <child a=\"1\">text</child><!-- comment -->\n  <empty/>\n</root>";
+        let root = Element::parse(xml.as_bytes()).unwrap();
+        assert_eq!(root.position, Position { line: 1, column: 1 });
+        assert_eq!(root.end, Position { line: 4, column: 8 });
+
+        let child = root.get_child("child").unwrap();
+        assert_eq!(child.position, Position { line: 2, column: 3 });
+        assert_eq!(
+            child.end,
+            Position {
+                line: 2,
+                column: 28
+            }
+        );
+
+        let empty = root.get_child("empty").unwrap();
+        assert_eq!(empty.position, Position { line: 3, column: 3 });
+        assert_eq!(
+            empty.end,
+            Position {
+                line: 3,
+                column: 11
+            }
+        );
+        assert_eq!(child.get_attribute("a"), Some("1"));
+        assert_eq!(child.get_text(), Some(String::from("text")));
+    }
+
+    #[test]
+    fn test_write_declares_namespaces_and_indents() {
+        let xml = r#"<root xmlns="urn:a" xmlns:b="urn:b" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="urn:a a.xsd">
+  <child a="1 &amp; 2">text &lt; more</child>
+  <b:other/>
+</root>"#;
+        let root = Element::parse(xml.as_bytes()).unwrap();
+
+        let mut written = Vec::new();
+        root.write(&mut written, &WriteOptions::default()).unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<root xmlns="urn:a" xmlns:ns1="urn:b" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="urn:a a.xsd">
+    <child a="1 &amp; 2">text &lt; more</child>
+    <ns1:other/>
+</root>"#,
+            written
+        );
+
+        let reparsed = Element::parse(written.as_bytes()).unwrap();
+        let other = reparsed.child_elements().nth(1).unwrap();
+        assert_eq!(Some(String::from("urn:b")), other.namespace);
+
+        let options = WriteOptions {
+            indent: None,
+            xml_declaration: false,
+        };
+        let mut written = Vec::new();
+        root.get_child("child")
+            .unwrap()
+            .write(&mut written, &options)
+            .unwrap();
+        assert_eq!(
+            r#"<child xmlns="urn:a" a="1 &amp; 2">text &lt; more</child>"#,
+            String::from_utf8(written).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_xml() {
+        assert!(Element::parse("<root><child></root>".as_bytes()).is_err());
+    }
+}
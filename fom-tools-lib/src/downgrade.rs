@@ -0,0 +1,872 @@
+//! Writing of object models in the formats that preceded IEEE 1516.2-2010, for
+//! RTIs that only accept those: IEEE 1516.2-2000 FDD documents and HLA 1.3 FED
+//! files. These are the reverse of the readers of the `legacy` and `fed`
+//! modules.
+//!
+//! The older formats cannot express everything an object model can, so each
+//! writer also reports the information that was lost: the parts of the object
+//! model that were left out, listed by `SourceFormat::absent_fields`, and
+//! those that were written in a less precise form.
+
+use crate::dom::{Attribute, Element, Node, WriteOptions};
+use crate::*;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::io::Write;
+
+/// How a part of the object model was lost when writing it in an older format.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LossKind {
+    /// The format has no counterpart for the part, so it was left out.
+    Dropped,
+    /// The part was written as the provided value, which the format can
+    /// express, e.g. a transportation other than `HLAreliable` and
+    /// `HLAbestEffort` as `reliable` in a FED file.
+    Approximated(String),
+}
+
+/// A part of the object model that could not be written faithfully.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loss {
+    pub kind: LossKind,
+    /// The location of the part within the 2010 document of the object model,
+    /// in the form used by `FomError`.
+    pub path: String,
+    /// The value of the part, if it has one.
+    pub value: Option<String>,
+}
+
+impl fmt::Display for Loss {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.path)?;
+        match (&self.kind, &self.value) {
+            (LossKind::Dropped, Some(value)) => write!(f, "dropped '{}'", value),
+            (LossKind::Dropped, None) => write!(f, "dropped"),
+            (LossKind::Approximated(written), Some(value)) => {
+                write!(f, "'{}' written as '{}'", value, written)
+            }
+            (LossKind::Approximated(written), None) => write!(f, "written as '{}'", written),
+        }
+    }
+}
+
+/// An object model written in an older format, along with the information
+/// that was lost in doing so.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Downgraded<T> {
+    pub document: T,
+    pub losses: Vec<Loss>,
+}
+
+fn dropped(path: String, value: Option<String>) -> Loss {
+    Loss {
+        kind: LossKind::Dropped,
+        path,
+        value,
+    }
+}
+
+fn approximated(path: String, value: Option<String>, written: &str) -> Loss {
+    Loss {
+        kind: LossKind::Approximated(String::from(written)),
+        path,
+        value,
+    }
+}
+
+/// Return the path of the provided element of the 2010 OMT with the names of
+/// its ancestors, relative to `objectModel`, in the form used by
+/// `SourceFormat::absent_fields`. Nested classes are shown as one, e.g.
+/// `objects/objectClass/attribute`.
+fn format_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        String::from(name)
+    } else if parent.ends_with(&format!("/{}", name)) && name.ends_with("Class") {
+        String::from(parent)
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+/// Record a loss for each element of the provided 2010 document that the
+/// format has no counterpart for, and for each `idtag`, which none of the
+/// older formats have. The FED format also has no notes.
+fn collect_dropped(
+    e: &Element,
+    format: SourceFormat,
+    parent_path: &str,
+    relative_path: &str,
+    losses: &mut Vec<Loss>,
+) {
+    let path = format!("{}/{}", parent_path, path_segment(e));
+    for attribute in &e.attributes {
+        let is_dropped = match attribute.name.as_str() {
+            "idtag" => true,
+            "notes" => format == SourceFormat::Hla13Fed,
+            _ => false,
+        };
+        if attribute.namespace.is_none() && is_dropped {
+            losses.push(dropped(
+                format!("{}/@{}", path, attribute.name),
+                Some(attribute.value.clone()),
+            ));
+        }
+    }
+    for child in e.child_elements() {
+        let child_relative_path = format_path(relative_path, &child.name);
+        if format.can_express(&child_relative_path) {
+            collect_dropped(child, format, &path, &child_relative_path, losses);
+        } else {
+            let value = match child.child_elements().next() {
+                None => child.get_text(),
+                Some(_) => None,
+            };
+            losses.push(dropped(format!("{}/{}", path, path_segment(child)), value));
+        }
+    }
+}
+
+/// Return the text of the named child element, if it is present.
+fn child_text(e: &Element, name: &str) -> Option<String> {
+    e.get_child(name).map(get_element_text)
+}
+
+fn set_attribute(e: &mut Element, name: &str, value: &str) {
+    e.attributes.push(Attribute {
+        name: String::from(name),
+        namespace: None,
+        value: String::from(value),
+    });
+}
+
+/// The elements of the 2010 OMT that are tables, whose child elements are all
+/// elements of the 2000 OMT too, rather than fields.
+const TABLES: &[&str] = &[
+    "objectModel",
+    "objects",
+    "interactions",
+    "dimensions",
+    "time",
+    "tags",
+    "synchronizations",
+    "transportations",
+    "dataTypes",
+    "basicDataRepresentations",
+    "simpleDataTypes",
+    "enumeratedDataTypes",
+    "arrayDataTypes",
+    "fixedRecordDataTypes",
+    "variantRecordDataTypes",
+    "notes",
+];
+
+/// The rows of the 2010 tables, as parent and child element names, that hold
+/// other rows rather than fields; e.g. `enumerator` is a row of
+/// `enumeratedData`, but a field of `alternative`.
+const NESTED_ROWS: &[(&str, &str)] = &[
+    ("objectClass", "objectClass"),
+    ("objectClass", "attribute"),
+    ("interactionClass", "interactionClass"),
+    ("interactionClass", "parameter"),
+    ("enumeratedData", "enumerator"),
+    ("fixedRecordData", "field"),
+    ("variantRecordData", "alternative"),
+];
+
+/// Convert an object model into the root `objectModel` element of an IEEE
+/// 1516.2-2000 FDD document. Fields are moved from child elements into
+/// attributes, along with the notes that apply to them, which are held by
+/// attributes suffixed with `Notes`. The 2000 OMT only has notes on fields, so
+/// the notes that apply to a row of a table are moved onto its name.
+pub fn downgrade_to_ieee1516_2000(fom: &ObjectModelType) -> Downgraded<Element> {
+    let format = SourceFormat::Ieee1516_2000;
+    let e = serialize_object_model(fom);
+    let mut losses = Vec::new();
+    collect_dropped(&e, format, "", "", &mut losses);
+
+    let mut root = Element::new("objectModel");
+    set_attribute(&mut root, "DTDversion", "1516.2");
+    let path = format!("/{}", path_segment(&e));
+    if let Some(model_identification) = e.get_child("modelIdentification") {
+        downgrade_model_identification(&mut root, model_identification, &path, &mut losses);
+    }
+    for child in e.child_elements() {
+        let relative_path = format_path("", &child.name);
+        if child.name != "modelIdentification" && format.can_express(&relative_path) {
+            let downgraded = downgrade_element(child, &path, &relative_path, &mut losses);
+            root.children.push(Node::Element(downgraded));
+        }
+    }
+    Downgraded {
+        document: root,
+        losses,
+    }
+}
+
+/// Move the fields of `modelIdentification` onto the root element, as 2000
+/// attributes. The 2000 OMT has a single point of contact, along with a
+/// sponsor, and holds its references as text.
+fn downgrade_model_identification(
+    root: &mut Element,
+    e: &Element,
+    parent_path: &str,
+    losses: &mut Vec<Loss>,
+) {
+    let path = format!("{}/{}", parent_path, path_segment(e));
+    let mut has_sponsor = false;
+    let mut has_poc = false;
+    let mut references = Vec::new();
+    for child in e.child_elements() {
+        let child_path = format!("{}/{}", path, path_segment(child));
+        let name = match child.name.as_str() {
+            "name" | "type" | "version" | "purpose" | "other" => child.name.as_str(),
+            "modificationDate" => "date",
+            "applicationDomain" => "appDomain",
+            "poc" => {
+                let is_sponsor = child_text(child, "pocType").as_deref() == Some("Sponsor");
+                if is_sponsor && !has_sponsor {
+                    has_sponsor = true;
+                    let sponsor =
+                        child_text(child, "pocOrg").or_else(|| child_text(child, "pocName"));
+                    if let Some(sponsor) = sponsor {
+                        set_attribute(root, "sponsor", &sponsor);
+                    }
+                } else if !is_sponsor && !has_poc {
+                    has_poc = true;
+                    downgrade_poc(root, child, &child_path, losses);
+                } else {
+                    losses.push(dropped(child_path, child_text(child, "pocName")));
+                }
+                continue;
+            }
+            "reference" => {
+                let identification = child_text(child, "identification").unwrap_or_default();
+                let reference = match child_text(child, "type") {
+                    Some(reference_type) => format!("{}: {}", reference_type, identification),
+                    None => identification,
+                };
+                references.push((child_path, reference));
+                continue;
+            }
+            // Recorded by collect_dropped.
+            _ => continue,
+        };
+        set_attribute(root, name, &get_element_text(child));
+        if let Some(notes) = child.get_attribute("notes") {
+            set_attribute(root, &format!("{}Notes", name), notes);
+        }
+    }
+    if !references.is_empty() {
+        let text: Vec<&str> = references.iter().map(|(_, r)| r.as_str()).collect();
+        let text = text.join("; ");
+        for (path, reference) in &references {
+            if references.len() > 1 || reference != &text {
+                losses.push(approximated(path.clone(), Some(reference.clone()), &text));
+            }
+        }
+        set_attribute(root, "references", &text);
+    }
+}
+
+fn downgrade_poc(root: &mut Element, e: &Element, path: &str, losses: &mut Vec<Loss>) {
+    let mut telephones = 0;
+    let mut emails = 0;
+    for child in e.child_elements() {
+        let (name, count) = match child.name.as_str() {
+            "pocName" => ("pocName", None),
+            "pocOrg" => ("pocOrg", None),
+            "pocTelephone" => ("pocPhone", Some(&mut telephones)),
+            "pocEmail" => ("pocEmail", Some(&mut emails)),
+            _ => continue,
+        };
+        if let Some(count) = count {
+            *count += 1;
+            if *count > 1 {
+                let child_path = format!("{}/{}", path, path_segment(child));
+                losses.push(dropped(child_path, child.get_text()));
+                continue;
+            }
+        }
+        set_attribute(root, name, &get_element_text(child));
+    }
+}
+
+fn downgrade_element(
+    e: &Element,
+    parent_path: &str,
+    relative_path: &str,
+    losses: &mut Vec<Loss>,
+) -> Element {
+    let format = SourceFormat::Ieee1516_2000;
+    let path = format!("{}/{}", parent_path, path_segment(e));
+    let name = match e.name.as_str() {
+        "synchronizationPoint" => "synchronization",
+        name => name,
+    };
+    let mut downgraded = Element::new(name);
+    let is_table = TABLES.contains(&e.name.as_str());
+
+    // Values of a field that the 2000 OMT holds in a single attribute.
+    let mut values = Vec::new();
+    let mut values_notes = Vec::new();
+    for child in e.child_elements() {
+        let child_relative_path = format_path(relative_path, &child.name);
+        if !format.can_express(&child_relative_path) {
+            continue;
+        }
+        if is_table || NESTED_ROWS.contains(&(e.name.as_str(), child.name.as_str())) {
+            let row = downgrade_element(child, &path, &child_relative_path, losses);
+            downgraded.children.push(Node::Element(row));
+            continue;
+        }
+        let text = get_element_text(child);
+        let (name, text) = match (e.name.as_str(), child.name.as_str()) {
+            ("switches", _) => {
+                let enabled = child
+                    .get_attribute("isEnabled")
+                    .is_some_and(|v| matches!(v.trim(), "true" | "1"));
+                let value = if enabled { "Enabled" } else { "Disabled" };
+                set_attribute(&mut downgraded, &child.name, value);
+                continue;
+            }
+            (_, "dimensions") => {
+                let dimensions: Vec<String> =
+                    child.child_elements().map(get_element_text).collect();
+                let text = if dimensions.is_empty() {
+                    String::from("NA")
+                } else {
+                    dimensions.join(" ")
+                };
+                ("dimensions", text)
+            }
+            ("enumerator", "value") => {
+                values.push(text);
+                values_notes.extend(child.get_attribute("notes"));
+                continue;
+            }
+            ("note", "label") => ("name", text),
+            ("transportation", "semantics") => ("description", text),
+            (_, name) => (name, text),
+        };
+        set_attribute(&mut downgraded, name, &text);
+        if let Some(notes) = child.get_attribute("notes") {
+            set_attribute(&mut downgraded, &format!("{}Notes", name), notes);
+        }
+    }
+    if !values.is_empty() {
+        set_attribute(&mut downgraded, "values", &values.join(", "));
+        if !values_notes.is_empty() {
+            set_attribute(&mut downgraded, "valuesNotes", &values_notes.join(" "));
+        }
+    }
+
+    if let Some(notes) = e.get_attribute("notes") {
+        let name_attribute = ["name", "label"]
+            .iter()
+            .find(|name| e.get_child(name).is_some())
+            .map(|name| if *name == "label" { "name" } else { name });
+        match name_attribute {
+            Some(name) => {
+                let notes_attribute = format!("{}Notes", name);
+                let merged = match downgraded.get_attribute(&notes_attribute) {
+                    Some(name_notes) => format!("{} {}", name_notes, notes),
+                    None => String::from(notes),
+                };
+                downgraded.attributes.retain(|a| a.name != notes_attribute);
+                set_attribute(&mut downgraded, &notes_attribute, &merged);
+                losses.push(approximated(
+                    format!("{}/@notes", path),
+                    Some(String::from(notes)),
+                    &format!("{}={}", notes_attribute, merged),
+                ));
+            }
+            None => losses.push(dropped(
+                format!("{}/@notes", path),
+                Some(String::from(notes)),
+            )),
+        }
+    }
+    downgraded
+}
+
+/// Write an object model as an IEEE 1516.2-2000 FDD document to the provided
+/// writer, as directed by the provided options, and return the information
+/// that was lost.
+pub fn write_ieee1516_2000<W: Write>(
+    fom: &ObjectModelType,
+    w: W,
+    options: &WriteOptions,
+) -> Result<Vec<Loss>, Error> {
+    let downgraded = downgrade_to_ieee1516_2000(fom);
+    downgraded.document.write(w, options)?;
+    Ok(downgraded.losses)
+}
+
+/// Return the provided name as an atom of a FED file, quoting it if it holds
+/// characters that would end an atom.
+fn fed_atom(name: &str) -> String {
+    let needs_quotes = name.is_empty()
+        || name
+            .chars()
+            .any(|c| c.is_whitespace() || "();\"".contains(c));
+    if needs_quotes {
+        format!("\"{}\"", name.replace('"', ""))
+    } else {
+        String::from(name)
+    }
+}
+
+/// The routing spaces of a FED file. Each distinct set of dimensions that an
+/// attribute or interaction class is given becomes a space.
+struct Spaces {
+    /// The spaces in the order they are written, with the names of their
+    /// dimensions within the space.
+    spaces: Vec<(String, Vec<String>)>,
+    /// The name of the space for each set of dimensions, as sorted names.
+    by_dimensions: HashMap<Vec<String>, String>,
+}
+
+impl Spaces {
+    /// Return the spaces for the dimensions of the classes of the provided
+    /// object model. The dimensions of a FED file read by `fed::parse_str`
+    /// are named `<space>.<dimension>`, and become that space again when the
+    /// set is all of the dimensions with that prefix. Otherwise the space is
+    /// named after its dimensions.
+    fn new(fom: &ObjectModelType) -> Self {
+        let mut sets = BTreeSet::new();
+        if let Some(root) = fom
+            .objects
+            .as_ref()
+            .and_then(|o| o.root_object_class.as_ref())
+        {
+            collect_object_class_dimensions(root, &mut sets);
+        }
+        if let Some(interactions) = &fom.interactions {
+            collect_interaction_class_dimensions(&interactions.interactions, &mut sets);
+        }
+        let all_dimensions: Vec<String> = fom
+            .dimensions
+            .iter()
+            .flat_map(|d| d.dimensions.iter().flatten())
+            .map(|d| d.name.clone())
+            .collect();
+
+        let mut spaces = Spaces {
+            spaces: Vec::new(),
+            by_dimensions: HashMap::new(),
+        };
+        for set in sets {
+            let prefix = set[0].split_once('.').map(|(space, _)| space);
+            let space = prefix.filter(|space| {
+                let prefix = format!("{}.", space);
+                let with_prefix: BTreeSet<&String> = all_dimensions
+                    .iter()
+                    .chain(set.iter())
+                    .filter(|d| d.starts_with(&prefix))
+                    .collect();
+                with_prefix.len() == set.len()
+            });
+            let (name, dimensions) = match space {
+                Some(space) => (
+                    String::from(space),
+                    set.iter()
+                        .map(|d| String::from(&d[space.len() + 1..]))
+                        .collect(),
+                ),
+                None => (set.join("_"), set.clone()),
+            };
+            spaces.spaces.push((name.clone(), dimensions));
+            spaces.by_dimensions.insert(set, name);
+        }
+        spaces
+    }
+
+    fn space_of(&self, dimensions: Option<&Vec<ReferenceType>>) -> Option<&str> {
+        let mut set: Vec<String> = dimensions?.iter().map(|d| d.value.clone()).collect();
+        set.sort();
+        set.dedup();
+        self.by_dimensions.get(&set).map(String::as_str)
+    }
+}
+
+fn dimension_set(dimensions: Option<&Vec<ReferenceType>>) -> Option<Vec<String>> {
+    let mut set: Vec<String> = dimensions?.iter().map(|d| d.value.clone()).collect();
+    set.sort();
+    set.dedup();
+    if set.is_empty() {
+        None
+    } else {
+        Some(set)
+    }
+}
+
+fn collect_object_class_dimensions(class: &ObjectClassType, sets: &mut BTreeSet<Vec<String>>) {
+    for attribute in class.attributes.iter().flatten() {
+        sets.extend(dimension_set(attribute.dimensions.as_ref()));
+    }
+    for subclass in class.object_classes.iter().flatten() {
+        collect_object_class_dimensions(subclass, sets);
+    }
+}
+
+fn collect_interaction_class_dimensions(
+    class: &InteractionClassType,
+    sets: &mut BTreeSet<Vec<String>>,
+) {
+    sets.extend(dimension_set(class.dimensions.as_ref()));
+    for subclass in class.interaction_classes.iter().flatten() {
+        collect_interaction_class_dimensions(subclass, sets);
+    }
+}
+
+/// Writes the clauses of a FED file, recording the information lost.
+struct FedWriter<'a> {
+    fom: &'a ObjectModelType,
+    spaces: Spaces,
+    text: String,
+    losses: Vec<Loss>,
+}
+
+impl FedWriter<'_> {
+    fn line(&mut self, depth: usize, text: &str) {
+        self.text.push('\n');
+        self.text.push_str(&"  ".repeat(depth));
+        self.text.push_str(text);
+    }
+
+    /// Return the transport, order and space of an attribute or interaction
+    /// class, as they follow its name. Those that are absent are left out
+    /// from the end; a transport or order followed by one that is present is
+    /// written as `reliable` or `receive`.
+    fn delivery(
+        &mut self,
+        path: &str,
+        transportation: Option<&ReferenceType>,
+        order: Option<&OrderType>,
+        dimensions: Option<&Vec<ReferenceType>>,
+    ) -> String {
+        let space = self.spaces.space_of(dimensions).map(fed_atom);
+        let order = match order {
+            Some(OrderType::Receive) => Some(String::from("receive")),
+            Some(OrderType::TimeStamp) => Some(String::from("timestamp")),
+            None if space.is_some() => {
+                self.losses
+                    .push(approximated(format!("{}/order", path), None, "receive"));
+                Some(String::from("receive"))
+            }
+            None => None,
+        };
+        let transport = match transportation.map(|t| t.value.as_str()) {
+            Some("HLAreliable") => Some(String::from("reliable")),
+            Some("HLAbestEffort") => Some(String::from("best_effort")),
+            Some(name) => {
+                let is_best_effort = self
+                    .fom
+                    .transportations
+                    .iter()
+                    .flat_map(|t| t.transportations.iter().flatten())
+                    .any(|t| t.name == name && t.reliable == Some(ReliableType::No));
+                let transport = if is_best_effort {
+                    "best_effort"
+                } else {
+                    "reliable"
+                };
+                self.losses.push(approximated(
+                    format!("{}/transportation", path),
+                    Some(String::from(name)),
+                    transport,
+                ));
+                Some(String::from(transport))
+            }
+            None if order.is_some() => {
+                self.losses.push(approximated(
+                    format!("{}/transportation", path),
+                    None,
+                    "reliable",
+                ));
+                Some(String::from("reliable"))
+            }
+            None => None,
+        };
+        let atoms: Vec<String> = vec![transport, order, space]
+            .into_iter()
+            .flatten()
+            .collect();
+        atoms.iter().map(|a| format!(" {}", a)).collect()
+    }
+
+    fn object_class(&mut self, class: &ObjectClassType, parent_path: &str, depth: usize) {
+        let path = format!("{}/objectClass[{}]", parent_path, class.name);
+        let is_root = depth == 2;
+        let name = match class.name.as_str() {
+            "HLAobjectRoot" if is_root => "ObjectRoot",
+            name => name,
+        };
+        self.line(depth, &format!("(class {}", fed_atom(name)));
+        for attribute in class.attributes.iter().flatten() {
+            let attribute_path = format!("{}/attribute[{}]", path, attribute.name);
+            let name = match attribute.name.as_str() {
+                "HLAprivilegeToDeleteObject" if is_root => "privilegeToDelete",
+                name => name,
+            };
+            let delivery = self.delivery(
+                &attribute_path,
+                attribute.transportation.as_ref(),
+                attribute.order.as_ref(),
+                attribute.dimensions.as_ref(),
+            );
+            self.line(
+                depth + 1,
+                &format!("(attribute {}{})", fed_atom(name), delivery),
+            );
+        }
+        for subclass in class.object_classes.iter().flatten() {
+            self.object_class(subclass, &path, depth + 1);
+        }
+        self.text.push(')');
+    }
+
+    fn interaction_class(&mut self, class: &InteractionClassType, parent_path: &str, depth: usize) {
+        let path = format!("{}/interactionClass[{}]", parent_path, class.name);
+        let name = match class.name.as_str() {
+            "HLAinteractionRoot" if depth == 2 => "InteractionRoot",
+            name => name,
+        };
+        let delivery = self.delivery(
+            &path,
+            class.transportation.as_ref(),
+            class.order.as_ref(),
+            class.dimensions.as_ref(),
+        );
+        self.line(depth, &format!("(class {}{}", fed_atom(name), delivery));
+        for parameter in class.parameters.iter().flatten() {
+            self.line(
+                depth + 1,
+                &format!("(parameter {})", fed_atom(&parameter.name)),
+            );
+        }
+        for subclass in class.interaction_classes.iter().flatten() {
+            self.interaction_class(subclass, &path, depth + 1);
+        }
+        self.text.push(')');
+    }
+}
+
+/// Convert an object model into the text of an HLA 1.3 FED file. The names
+/// of the class tree roots and the privilege to delete attribute are those of
+/// HLA 1.3; see the `fed` module for how the other parts of the object model
+/// correspond to a FED file. Dimensions become routing spaces, so their
+/// datatypes, upper bounds and normalization functions are lost.
+pub fn downgrade_to_fed(fom: &ObjectModelType) -> Downgraded<String> {
+    let mut losses = Vec::new();
+    collect_dropped(
+        &serialize_object_model(fom),
+        SourceFormat::Hla13Fed,
+        "",
+        "",
+        &mut losses,
+    );
+    let mut writer = FedWriter {
+        fom,
+        spaces: Spaces::new(fom),
+        text: String::from("(FED"),
+        losses,
+    };
+
+    let name = fom
+        .model_identification
+        .as_ref()
+        .and_then(|m| m.name.as_deref());
+    if let Some(name) = name {
+        writer.line(1, &format!("(Federation {})", fed_atom(name)));
+    }
+    writer.line(1, "(FEDversion v1.3)");
+    if !writer.spaces.spaces.is_empty() {
+        writer.line(1, "(spaces");
+        for (space, dimensions) in writer.spaces.spaces.clone() {
+            writer.line(2, &format!("(space {}", fed_atom(&space)));
+            for dimension in dimensions {
+                writer.line(3, &format!("(dimension {})", fed_atom(&dimension)));
+            }
+            writer.text.push(')');
+        }
+        writer.text.push(')');
+    }
+    let root = fom
+        .objects
+        .as_ref()
+        .and_then(|o| o.root_object_class.as_ref());
+    if let Some(root) = root {
+        writer.line(1, "(objects");
+        writer.object_class(root, "/objectModel/objects", 2);
+        writer.text.push(')');
+    }
+    if let Some(interactions) = &fom.interactions {
+        writer.line(1, "(interactions");
+        writer.interaction_class(&interactions.interactions, "/objectModel/interactions", 2);
+        writer.text.push(')');
+    }
+    writer.text.push_str(")\n");
+    Downgraded {
+        document: writer.text,
+        losses: writer.losses,
+    }
+}
+
+/// Write an object model as an HLA 1.3 FED file to the provided writer, and
+/// return the information that was lost.
+pub fn write_fed<W: Write>(fom: &ObjectModelType, mut w: W) -> Result<Vec<Loss>, Error> {
+    let downgraded = downgrade_to_fed(fom);
+    w.write_all(downgraded.document.as_bytes())?;
+    Ok(downgraded.losses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restaurant() -> ObjectModelType {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("examples")
+            .join("RestaurantFOMmodule.xml");
+        parse_file(path).unwrap()
+    }
+
+    #[test]
+    fn test_downgrade_to_ieee1516_2000_reads_back() {
+        let fom = restaurant();
+        let downgraded = downgrade_to_ieee1516_2000(&fom);
+        let xml = {
+            let mut xml = Vec::new();
+            downgraded
+                .document
+                .write(&mut xml, &WriteOptions::default())
+                .unwrap();
+            String::from_utf8(xml).unwrap()
+        };
+        let parsed = parse_reader_with_options(xml.as_bytes(), &ParseOptions::default()).unwrap();
+        assert_eq!(SourceFormat::Ieee1516_2000, parsed.format);
+        let read_back = parsed.object_model;
+
+        // Everything the 2000 OMT can express survives.
+        let mut kept = fom.clone();
+        kept.objects = read_back.objects.clone();
+        kept.interactions = read_back.interactions.clone();
+        kept.data_types = read_back.data_types.clone();
+        kept.notes = read_back.notes.clone();
+        assert!(fom.same_model(&kept));
+        assert_eq!(
+            fom.model_identification.as_ref().unwrap().name,
+            read_back.model_identification.as_ref().unwrap().name
+        );
+
+        // And what it cannot is reported.
+        let dropped: Vec<&str> = downgraded
+            .losses
+            .iter()
+            .filter(|l| l.kind == LossKind::Dropped)
+            .map(|l| l.path.as_str())
+            .collect();
+        assert!(dropped.contains(
+            &"/objectModel/modelIdentification[Restaurant FOM Module Example]/description"
+        ));
+        assert!(dropped.contains(&"/objectModel/updateRates"));
+        assert!(downgraded
+            .losses
+            .iter()
+            .all(|l| !l.path.starts_with("/objectModel/objects")));
+    }
+
+    #[test]
+    fn test_downgrade_to_fed_reads_back() {
+        let fom = restaurant();
+        let downgraded = downgrade_to_fed(&fom);
+        let read_back = parse_fed_str(&downgraded.document).unwrap();
+
+        let object_classes: Vec<String> =
+            fom.object_classes().map(|c| c.qualified_name()).collect();
+        let read_back_classes: Vec<String> = read_back
+            .object_classes()
+            .map(|c| c.qualified_name())
+            .collect();
+        assert_eq!(object_classes, read_back_classes);
+        let interaction_classes: Vec<String> = fom
+            .interaction_classes()
+            .map(|c| c.qualified_name())
+            .collect();
+        let read_back_classes: Vec<String> = read_back
+            .interaction_classes()
+            .map(|c| c.qualified_name())
+            .collect();
+        assert_eq!(interaction_classes, read_back_classes);
+
+        let dropped: Vec<String> = downgraded
+            .losses
+            .iter()
+            .filter(|l| l.kind == LossKind::Dropped)
+            .map(|l| l.path.clone())
+            .collect();
+        assert!(dropped.contains(&String::from("/objectModel/dataTypes")));
+        assert!(dropped
+            .iter()
+            .any(|p| p.starts_with("/objectModel/objects/") && p.ends_with("/dataType")));
+    }
+
+    #[test]
+    fn test_downgrade_to_fed_collapses_dimensions_and_transportations() {
+        let fed = r#"(FED
+  (Federation Test)
+  (FEDversion v1.3)
+  (spaces
+    (space Geo
+      (dimension X)
+      (dimension Y)))
+  (objects
+    (class ObjectRoot
+      (attribute privilegeToDelete reliable timestamp)
+      (class Vehicle
+        (attribute Position best_effort receive Geo))))
+  (interactions
+    (class InteractionRoot reliable receive
+      (class Fire reliable timestamp Geo
+        (parameter Target)))))
+"#;
+        let mut fom = parse_fed_str(fed).unwrap();
+        assert_eq!(
+            fed,
+            downgrade_to_fed(&fom).document,
+            "a FED file is written as it was read"
+        );
+
+        let vehicle = fom
+            .objects
+            .as_mut()
+            .unwrap()
+            .root_object_class
+            .as_mut()
+            .unwrap()
+            .object_classes
+            .as_mut()
+            .unwrap()
+            .get_mut(0)
+            .unwrap();
+        vehicle.attributes.as_mut().unwrap()[0].transportation = Some(ReferenceType {
+            value: String::from("Multicast"),
+        });
+        let downgraded = downgrade_to_fed(&fom);
+        assert!(downgraded
+            .document
+            .contains("(attribute Position reliable receive Geo)"));
+        assert_eq!(
+            vec![Loss {
+                kind: LossKind::Approximated(String::from("reliable")),
+                path: String::from(
+                    "/objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/attribute[Position]/transportation"
+                ),
+                value: Some(String::from("Multicast")),
+            }],
+            downgraded.losses
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::dom::{self, Position};
+use crate::fed;
+use crate::SchemaVersion;
+use std::fmt;
+use std::io;
+
+/// The reason an element of an object model document could not be converted
+/// into the object model.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FomErrorKind {
+    /// A child element that the OMT requires is absent.
+    MissingElement(String),
+    /// The value is not one of those allowed by the OMT, e.g. an unknown
+    /// sharing or order type.
+    UnknownValue,
+    /// The value could not be interpreted as the expected type, e.g. a boolean.
+    InvalidValue,
+    /// The value is outside of the range of the named representation, e.g. an
+    /// enumerator value too large for its enumerated datatype.
+    OutOfRange(String),
+    /// The value differs from the named one, which the OMT fixes it to, e.g.
+    /// the section of a service. The object model holds the fixed value, so
+    /// it replaces the value when the object model is written.
+    FixedValue(String),
+    /// The value was added to the OMT by the named version, later than that
+    /// of the document.
+    RequiresSchemaVersion(SchemaVersion),
+    /// The document is not in one of the OMT namespaces.
+    UnknownNamespace,
+    /// The document does not declare a namespace.
+    MissingNamespace,
+}
+
+/// A problem with a single element, or attribute, of an object model document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FomError {
+    pub kind: FomErrorKind,
+    /// The location of the offending element within the document, e.g.
+    /// `/objectModel/objects/objectClass[HLAobjectRoot]/attribute[Name]/sharing`.
+    /// Attributes are denoted with a leading `@`.
+    pub path: String,
+    /// The offending value, if there was one.
+    pub value: Option<String>,
+    /// Where the offending element starts in the source document.
+    pub position: Position,
+}
+
+impl FomError {
+    /// Return the description of the error, without its position and path.
+    pub fn message(&self) -> String {
+        struct Message<'a>(&'a FomError);
+
+        impl fmt::Display for Message<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.write_message(f)
+            }
+        }
+
+        Message(self).to_string()
+    }
+
+    fn write_message(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.kind, &self.value) {
+            (FomErrorKind::MissingElement(name), _) => {
+                write!(f, "missing required element '{}'", name)
+            }
+            (FomErrorKind::UnknownValue, Some(value)) => write!(f, "unknown value '{}'", value),
+            (FomErrorKind::UnknownValue, None) => write!(f, "unknown value"),
+            (FomErrorKind::InvalidValue, Some(value)) => write!(f, "invalid value '{}'", value),
+            (FomErrorKind::InvalidValue, None) => write!(f, "invalid value"),
+            (FomErrorKind::OutOfRange(representation), Some(value)) => {
+                write!(f, "value '{}' out of range of '{}'", value, representation)
+            }
+            (FomErrorKind::OutOfRange(representation), None) => {
+                write!(f, "value out of range of '{}'", representation)
+            }
+            (FomErrorKind::FixedValue(fixed), Some(value)) => write!(
+                f,
+                "value '{}' is not the fixed value '{}', which replaces it",
+                value, fixed
+            ),
+            (FomErrorKind::FixedValue(fixed), None) => {
+                write!(f, "not the fixed value '{}', which replaces it", fixed)
+            }
+            (FomErrorKind::RequiresSchemaVersion(version), Some(value)) => {
+                write!(f, "'{}' requires {}", value, version)
+            }
+            (FomErrorKind::RequiresSchemaVersion(version), None) => {
+                write!(f, "requires {}", version)
+            }
+            (FomErrorKind::UnknownNamespace, Some(value)) => {
+                write!(f, "unknown namespace '{}'", value)
+            }
+            (FomErrorKind::UnknownNamespace, None) => write!(f, "unknown namespace"),
+            (FomErrorKind::MissingNamespace, _) => write!(f, "no namespace declared"),
+        }
+    }
+}
+
+impl fmt::Display for FomError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}: ", self.position, self.path)?;
+        self.write_message(f)
+    }
+}
+
+impl std::error::Error for FomError {}
+
+/// All of the problems found while converting an object model document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FomErrors(pub Vec<FomError>);
+
+impl FomErrors {
+    pub fn iter(&self) -> std::slice::Iter<'_, FomError> {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for FomErrors {
+    type Item = FomError;
+    type IntoIter = std::vec::IntoIter<FomError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl fmt::Display for FomErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FomErrors {}
+
+/// Any error that can occur while reading or writing an object model.
+#[derive(Debug)]
+pub enum Error {
+    /// The document could not be read.
+    Io(io::Error),
+    /// The document is not well-formed XML.
+    Xml(dom::ParseError),
+    /// The document is not a well-formed HLA 1.3 FED file.
+    Fed(fed::SyntaxError),
+    /// The document could not be written.
+    Write(dom::WriteError),
+    /// The document is well-formed XML, but does not describe a valid object
+    /// model.
+    Fom(FomErrors),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Xml(e) => write!(f, "{}", e),
+            Error::Fed(e) => write!(f, "{}", e),
+            Error::Write(e) => write!(f, "{}", e),
+            Error::Fom(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Xml(e) => Some(e),
+            Error::Fed(e) => Some(e),
+            Error::Write(e) => Some(e),
+            Error::Fom(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<dom::ParseError> for Error {
+    fn from(e: dom::ParseError) -> Self {
+        Error::Xml(e)
+    }
+}
+
+impl From<fed::SyntaxError> for Error {
+    fn from(e: fed::SyntaxError) -> Self {
+        Error::Fed(e)
+    }
+}
+
+impl From<dom::WriteError> for Error {
+    fn from(e: dom::WriteError) -> Self {
+        Error::Write(e)
+    }
+}
+
+impl From<FomErrors> for Error {
+    fn from(e: FomErrors) -> Self {
+        Error::Fom(e)
+    }
+}
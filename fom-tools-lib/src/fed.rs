@@ -0,0 +1,503 @@
+//! A reader for the federation execution details (FED) files of HLA 1.3,
+//! which are Lisp-like s-expressions, e.g.
+//!
+//! ```text
+//! (FED
+//!   (Federation Restaurant)
+//!   (FEDversion v1.3)
+//!   (spaces
+//!     (space Geo (dimension X) (dimension Y)))
+//!   (objects
+//!     (class ObjectRoot
+//!       (attribute privilegeToDelete reliable timestamp)
+//!       (class Employee
+//!         (attribute PayRate best_effort receive Geo))))
+//!   (interactions
+//!     (class InteractionRoot best_effort receive
+//!       (class Communication reliable timestamp
+//!         (parameter Message)))))
+//! ```
+//!
+//! A FED file is read into the IEEE 1516.2-2010 `objectModel` element that
+//! describes the same federation, so that it is converted into the object
+//! model like any other document. The elements are positioned at the
+//! s-expressions they were read from.
+//!
+//! FED files predate the HLA naming conventions of IEEE 1516, so the roots of
+//! the class trees, `ObjectRoot` and `InteractionRoot`, and the
+//! `privilegeToDelete` attribute are renamed to their IEEE 1516 names. The
+//! transports `reliable` and `best_effort` become `HLAreliable` and
+//! `HLAbestEffort`, and the orders `receive` and `timestamp` become `Receive`
+//! and `TimeStamp`. IEEE 1516 has no routing spaces; each dimension of a space
+//! becomes a dimension named `<space>.<dimension>`, and an attribute or
+//! interaction class in a space is given all of its dimensions. Keywords are
+//! matched without regard to case, as the RTIs that read FED files do.
+
+use crate::dom::{Element, Node, Position};
+use crate::IEEE1516_2010_NAMESPACE;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A FED file that is not made up of well-formed s-expressions, or whose
+/// s-expressions do not have the structure of a FED file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    /// Where the offending s-expression, or character, starts.
+    pub position: Position,
+    pub message: String,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+/// Read the provided FED file into the `objectModel` element of the
+/// equivalent IEEE 1516.2-2010 document.
+pub fn parse_str(s: &str) -> Result<Element, SyntaxError> {
+    let mut reader = Reader::new(s);
+    let fed = match reader.next()? {
+        Some(expr) => expr,
+        None => return Err(error(reader.position, "no FED s-expression")),
+    };
+    if let Some(extra) = reader.next()? {
+        return Err(error(extra.position, "unexpected s-expression after FED"));
+    }
+    convert_fed(&fed)
+}
+
+/// An s-expression, with where it starts and the position just past its end.
+#[derive(Debug)]
+struct Expr {
+    kind: ExprKind,
+    position: Position,
+    end: Position,
+}
+
+#[derive(Debug)]
+enum ExprKind {
+    Atom(String),
+    List(Vec<Expr>),
+}
+
+impl Expr {
+    fn atom(&self) -> Option<&str> {
+        match &self.kind {
+            ExprKind::Atom(text) => Some(text),
+            ExprKind::List(_) => None,
+        }
+    }
+
+    /// Return the items of this list if its first item is an atom, along
+    /// with that atom in lower case.
+    fn clause(&self) -> Option<(String, &[Expr])> {
+        match &self.kind {
+            ExprKind::List(items) => match items.split_first() {
+                Some((keyword, rest)) => keyword.atom().map(|k| (k.to_lowercase(), rest)),
+                None => None,
+            },
+            ExprKind::Atom(_) => None,
+        }
+    }
+}
+
+fn error(position: Position, message: &str) -> SyntaxError {
+    SyntaxError {
+        position,
+        message: String::from(message),
+    }
+}
+
+/// Reads s-expressions from the text of a FED file. Comments run from a `;`
+/// to the end of the line, and atoms may be quoted with `"`.
+struct Reader<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    position: Position,
+}
+
+impl<'a> Reader<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.chars().peekable(),
+            position: Position { line: 1, column: 1 },
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            if c == ';' {
+                while !matches!(self.chars.peek(), None | Some('\n')) {
+                    self.bump();
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Read the next s-expression, or None at the end of the text.
+    fn next(&mut self) -> Result<Option<Expr>, SyntaxError> {
+        self.skip_whitespace_and_comments();
+        let position = self.position;
+        let kind = match self.chars.peek() {
+            None => return Ok(None),
+            Some('(') => {
+                self.bump();
+                let mut items = Vec::new();
+                loop {
+                    self.skip_whitespace_and_comments();
+                    match self.chars.peek() {
+                        None => return Err(error(position, "unclosed '('")),
+                        Some(')') => {
+                            self.bump();
+                            break;
+                        }
+                        _ => items.extend(self.next()?),
+                    }
+                }
+                ExprKind::List(items)
+            }
+            Some(')') => return Err(error(position, "unexpected ')'")),
+            Some('"') => {
+                self.bump();
+                let mut text = String::new();
+                loop {
+                    match self.bump() {
+                        None => return Err(error(position, "unterminated string")),
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                    }
+                }
+                ExprKind::Atom(text)
+            }
+            Some(_) => {
+                let mut text = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_whitespace() || matches!(c, '(' | ')' | '"' | ';') {
+                        break;
+                    }
+                    text.push(c);
+                    self.bump();
+                }
+                ExprKind::Atom(text)
+            }
+        };
+        Ok(Some(Expr {
+            kind,
+            position,
+            end: self.position,
+        }))
+    }
+}
+
+/// Return an empty element of the 2010 OMT positioned at the provided
+/// s-expression.
+fn element(name: &str, expr: &Expr) -> Element {
+    let mut e = Element::new(name);
+    e.namespace = Some(String::from(IEEE1516_2010_NAMESPACE));
+    e.position = expr.position;
+    e.end = expr.end;
+    e
+}
+
+fn text_element(name: &str, text: &str, expr: &Expr) -> Element {
+    let mut e = element(name, expr);
+    e.children.push(Node::Text(String::from(text)));
+    e
+}
+
+fn push(parent: &mut Element, child: Element) {
+    parent.children.push(Node::Element(child));
+}
+
+/// Return the atom that names a clause, e.g. the class name of `(class A ...)`.
+fn name<'a>(keyword: &str, expr: &Expr, rest: &'a [Expr]) -> Result<&'a str, SyntaxError> {
+    rest.first()
+        .and_then(Expr::atom)
+        .ok_or_else(|| error(expr.position, &format!("{} without a name", keyword)))
+}
+
+/// The routing spaces of a FED file, by name, with the names of the
+/// dimensions they have been converted into.
+type Spaces = HashMap<String, Vec<String>>;
+
+fn convert_fed(fed: &Expr) -> Result<Element, SyntaxError> {
+    let items = match fed.clause() {
+        Some((keyword, items)) if keyword == "fed" => items,
+        _ => return Err(error(fed.position, "expected (FED ...)")),
+    };
+    let mut root = element("objectModel", fed);
+
+    // Spaces are referred to by attributes and interaction classes, so they
+    // are read first, wherever they appear.
+    let mut spaces = Spaces::new();
+    let mut dimensions = None;
+    for item in items {
+        if let Some((keyword, rest)) = item.clause() {
+            if keyword == "spaces" {
+                dimensions = Some(convert_spaces(item, rest, &mut spaces)?);
+            }
+        }
+    }
+
+    let mut objects = None;
+    let mut interactions = None;
+    for item in items {
+        let (keyword, rest) = item
+            .clause()
+            .ok_or_else(|| error(item.position, "expected a FED clause"))?;
+        match keyword.as_str() {
+            "federation" => {
+                let mut model_identification = element("modelIdentification", item);
+                push(
+                    &mut model_identification,
+                    text_element("name", name("Federation", item, rest)?, item),
+                );
+                push(&mut model_identification, text_element("type", "FOM", item));
+                push(&mut root, model_identification);
+            }
+            "fedversion" => {
+                name("FEDversion", item, rest)?;
+            }
+            "spaces" => {}
+            "objects" => {
+                let mut e = element("objects", item);
+                for class in rest {
+                    push(&mut e, convert_object_class(class, &spaces, true)?);
+                }
+                objects = Some(e);
+            }
+            "interactions" => {
+                let mut e = element("interactions", item);
+                for class in rest {
+                    push(&mut e, convert_interaction_class(class, &spaces, true)?);
+                }
+                interactions = Some(e);
+            }
+            _ => {
+                return Err(error(
+                    item.position,
+                    &format!("unknown FED clause '{}'", keyword),
+                ))
+            }
+        }
+    }
+    // In the order of the 2010 OMT.
+    for e in vec![objects, interactions, dimensions]
+        .into_iter()
+        .flatten()
+    {
+        push(&mut root, e);
+    }
+    Ok(root)
+}
+
+fn convert_spaces(expr: &Expr, rest: &[Expr], spaces: &mut Spaces) -> Result<Element, SyntaxError> {
+    let mut dimensions = element("dimensions", expr);
+    for space in rest {
+        let space_items = match space.clause() {
+            Some((keyword, space_items)) if keyword == "space" => space_items,
+            _ => return Err(error(space.position, "expected (space ...)")),
+        };
+        let space_name = name("space", space, space_items)?;
+        let mut names = Vec::new();
+        for dimension in &space_items[1..] {
+            let dimension_name = match dimension.clause() {
+                Some((keyword, items)) if keyword == "dimension" => {
+                    name("dimension", dimension, items)?
+                }
+                _ => return Err(error(dimension.position, "expected (dimension ...)")),
+            };
+            let qualified_name = format!("{}.{}", space_name, dimension_name);
+            let mut e = element("dimension", dimension);
+            push(&mut e, text_element("name", &qualified_name, dimension));
+            push(&mut dimensions, e);
+            names.push(qualified_name);
+        }
+        spaces.insert(String::from(space_name), names);
+    }
+    Ok(dimensions)
+}
+
+/// Add the elements for the transport, order and space that follow the name
+/// of an attribute or interaction class, each of which may be omitted from
+/// the end.
+fn convert_delivery(e: &mut Element, atoms: &[&Expr], spaces: &Spaces) -> Result<(), SyntaxError> {
+    let mut atoms = atoms.iter();
+    if let Some(space) = atoms.clone().nth(2) {
+        let space_name = space.atom().unwrap_or_default();
+        let names = spaces
+            .get(space_name)
+            .ok_or_else(|| error(space.position, &format!("unknown space '{}'", space_name)))?;
+        let mut dimensions = element("dimensions", space);
+        for name in names {
+            push(&mut dimensions, text_element("dimension", name, space));
+        }
+        push(e, dimensions);
+    }
+    if let Some(transport) = atoms.next() {
+        let text = transport.atom().unwrap_or_default();
+        let transportation = match text.to_lowercase().as_str() {
+            "reliable" => "HLAreliable",
+            "best_effort" => "HLAbestEffort",
+            _ => {
+                return Err(error(
+                    transport.position,
+                    &format!("unknown transport '{}'", text),
+                ))
+            }
+        };
+        push(e, text_element("transportation", transportation, transport));
+    }
+    if let Some(order) = atoms.next() {
+        let text = order.atom().unwrap_or_default();
+        let order_type = match text.to_lowercase().as_str() {
+            "receive" => "Receive",
+            "timestamp" => "TimeStamp",
+            _ => return Err(error(order.position, &format!("unknown order '{}'", text))),
+        };
+        push(e, text_element("order", order_type, order));
+    }
+    if let Some(extra) = atoms.nth(1) {
+        return Err(error(extra.position, "unexpected atom"));
+    }
+    Ok(())
+}
+
+/// Split the items of a clause after its name into the leading atoms and the
+/// nested clauses.
+fn split_atoms(rest: &[Expr]) -> (Vec<&Expr>, &[Expr]) {
+    let count = rest.iter().take_while(|expr| expr.atom().is_some()).count();
+    (rest[..count].iter().collect(), &rest[count..])
+}
+
+fn convert_object_class(
+    class: &Expr,
+    spaces: &Spaces,
+    is_root: bool,
+) -> Result<Element, SyntaxError> {
+    let rest = match class.clause() {
+        Some((keyword, rest)) if keyword == "class" => rest,
+        _ => return Err(error(class.position, "expected (class ...)")),
+    };
+    let class_name = name("class", class, rest)?;
+    let class_name = match class_name {
+        "ObjectRoot" if is_root => "HLAobjectRoot",
+        _ => class_name,
+    };
+    let (atoms, clauses) = split_atoms(&rest[1..]);
+    if let Some(atom) = atoms.first() {
+        return Err(error(atom.position, "unexpected atom"));
+    }
+    let mut e = element("objectClass", class);
+    push(&mut e, text_element("name", class_name, class));
+    for clause in clauses {
+        match clause.clause() {
+            Some((keyword, items)) if keyword == "attribute" => {
+                let attribute_name = name("attribute", clause, items)?;
+                let attribute_name = match attribute_name {
+                    "privilegeToDelete" if is_root => "HLAprivilegeToDeleteObject",
+                    _ => attribute_name,
+                };
+                let (atoms, clauses) = split_atoms(&items[1..]);
+                if let Some(nested) = clauses.first() {
+                    return Err(error(nested.position, "unexpected s-expression"));
+                }
+                let mut attribute = element("attribute", clause);
+                push(&mut attribute, text_element("name", attribute_name, clause));
+                convert_delivery(&mut attribute, &atoms, spaces)?;
+                push(&mut e, attribute);
+            }
+            Some((keyword, _)) if keyword == "class" => {
+                push(&mut e, convert_object_class(clause, spaces, false)?);
+            }
+            _ => {
+                return Err(error(
+                    clause.position,
+                    "expected (attribute ...) or (class ...)",
+                ))
+            }
+        }
+    }
+    Ok(e)
+}
+
+fn convert_interaction_class(
+    class: &Expr,
+    spaces: &Spaces,
+    is_root: bool,
+) -> Result<Element, SyntaxError> {
+    let rest = match class.clause() {
+        Some((keyword, rest)) if keyword == "class" => rest,
+        _ => return Err(error(class.position, "expected (class ...)")),
+    };
+    let class_name = name("class", class, rest)?;
+    let class_name = match class_name {
+        "InteractionRoot" if is_root => "HLAinteractionRoot",
+        _ => class_name,
+    };
+    let (atoms, clauses) = split_atoms(&rest[1..]);
+    let mut e = element("interactionClass", class);
+    push(&mut e, text_element("name", class_name, class));
+    convert_delivery(&mut e, &atoms, spaces)?;
+    for clause in clauses {
+        match clause.clause() {
+            Some((keyword, items)) if keyword == "parameter" => {
+                let parameter_name = name("parameter", clause, items)?;
+                if let Some(extra) = items.get(1) {
+                    return Err(error(extra.position, "unexpected s-expression"));
+                }
+                let mut parameter = element("parameter", clause);
+                push(&mut parameter, text_element("name", parameter_name, clause));
+                push(&mut e, parameter);
+            }
+            Some((keyword, _)) if keyword == "class" => {
+                push(&mut e, convert_interaction_class(clause, spaces, false)?);
+            }
+            _ => {
+                return Err(error(
+                    clause.position,
+                    "expected (parameter ...) or (class ...)",
+                ))
+            }
+        }
+    }
+    Ok(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_str_reports_syntax_errors() {
+        let error =
+            parse_str("(FED\n  (Federation Test)\n  (objects (class ObjectRoot)").unwrap_err();
+        assert_eq!(Position { line: 3, column: 3 }, error.position);
+        assert_eq!("3:3: unclosed '('", error.to_string());
+
+        let error = parse_str("(FED (objects (class ObjectRoot (attribute A fast))))").unwrap_err();
+        assert_eq!("1:46: unknown transport 'fast'", error.to_string());
+
+        let error = parse_str("(FED) (FED)").unwrap_err();
+        assert_eq!(Position { line: 1, column: 7 }, error.position);
+
+        assert!(parse_str("; nothing but a comment").is_err());
+    }
+}
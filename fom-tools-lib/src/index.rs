@@ -0,0 +1,631 @@
+//! An index of an object model, in which the classes, attributes, parameters,
+//! datatypes, dimensions and transportations are held in arenas and refer to
+//! each other by typed IDs rather than by name.
+//!
+//! IDs are assigned in document order, classes depth first from the root, so
+//! the same document is always indexed with the same IDs. Handle tables and
+//! generated code can rely on them for as long as the document is unchanged.
+
+use crate::*;
+use std::collections::HashMap;
+use std::fmt;
+
+macro_rules! id_types {
+    ($($(#[$attr:meta])* $name:ident;)*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name(u32);
+
+            impl $name {
+                fn new(index: usize) -> Self {
+                    Self(u32::try_from(index).expect("fewer than 2^32 parts of an object model"))
+                }
+
+                /// Return the position of the part within its arena, which is
+                /// the number of parts of its kind indexed before it.
+                pub fn index(self) -> usize {
+                    self.0 as usize
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write!(f, "{}", self.0)
+                }
+            }
+        )*
+    };
+}
+
+id_types! {
+    /// The ID of an object class within a `FomIndex`.
+    ObjectClassId;
+    /// The ID of an attribute of an object class within a `FomIndex`.
+    AttributeId;
+    /// The ID of an interaction class within a `FomIndex`.
+    InteractionClassId;
+    /// The ID of a parameter of an interaction class within a `FomIndex`.
+    ParameterId;
+    /// The ID of a datatype within a `FomIndex`.
+    DataTypeId;
+    /// The ID of a dimension within a `FomIndex`.
+    DimensionId;
+    /// The ID of a transportation type within a `FomIndex`.
+    TransportationId;
+}
+
+/// An object class of an indexed object model.
+#[derive(Debug, Clone)]
+pub struct ObjectClassEntry<'a> {
+    pub class: &'a ObjectClassType,
+    /// The names of the classes from the root down to this class, separated
+    /// by dots, e.g. `HLAobjectRoot.BaseEntity.PhysicalEntity`.
+    pub qualified_name: String,
+    pub parent: Option<ObjectClassId>,
+    pub children: Vec<ObjectClassId>,
+    /// The attributes the class declares itself, in document order.
+    pub attributes: Vec<AttributeId>,
+}
+
+/// An attribute of an indexed object model, with the parts of the object
+/// model it refers to. References to parts that are not declared are left out.
+#[derive(Debug, Clone)]
+pub struct AttributeEntry<'a> {
+    pub attribute: &'a AttributeType,
+    /// The class that declares the attribute.
+    pub class: ObjectClassId,
+    pub data_type: Option<DataTypeId>,
+    pub dimensions: Vec<DimensionId>,
+    pub transportation: Option<TransportationId>,
+}
+
+/// An interaction class of an indexed object model, with the parts of the
+/// object model it refers to. References to parts that are not declared are
+/// left out.
+#[derive(Debug, Clone)]
+pub struct InteractionClassEntry<'a> {
+    pub class: &'a InteractionClassType,
+    /// The names of the classes from the root down to this class, separated
+    /// by dots, e.g. `HLAinteractionRoot.WeaponFire`.
+    pub qualified_name: String,
+    pub parent: Option<InteractionClassId>,
+    pub children: Vec<InteractionClassId>,
+    /// The parameters the class declares itself, in document order.
+    pub parameters: Vec<ParameterId>,
+    pub dimensions: Vec<DimensionId>,
+    pub transportation: Option<TransportationId>,
+}
+
+/// A parameter of an indexed object model.
+#[derive(Debug, Clone)]
+pub struct ParameterEntry<'a> {
+    pub parameter: &'a ParameterType,
+    /// The class that declares the parameter.
+    pub class: InteractionClassId,
+    /// The datatype of the parameter, unless it is not declared.
+    pub data_type: Option<DataTypeId>,
+}
+
+/// An object model, indexed for lookup of its parts by ID in constant time.
+///
+/// As object models may refer to the datatypes, dimensions and transportations
+/// of the HLA standard MIM without declaring them, those of the MIM are indexed
+/// after those of the object model, except for any that the object model
+/// declares itself.
+#[derive(Debug, Clone)]
+pub struct FomIndex<'a> {
+    object_classes: Vec<ObjectClassEntry<'a>>,
+    attributes: Vec<AttributeEntry<'a>>,
+    interaction_classes: Vec<InteractionClassEntry<'a>>,
+    parameters: Vec<ParameterEntry<'a>>,
+    data_types: Vec<DataTypeRef<'a>>,
+    dimensions: Vec<&'a DimensionType>,
+    transportations: Vec<&'a TransportationType>,
+    object_class_ids: HashMap<String, ObjectClassId>,
+    interaction_class_ids: HashMap<String, InteractionClassId>,
+    data_type_ids: HashMap<&'a str, DataTypeId>,
+    dimension_ids: HashMap<&'a str, DimensionId>,
+    transportation_ids: HashMap<&'a str, TransportationId>,
+}
+
+impl<'a> FomIndex<'a> {
+    /// Index the object model.
+    pub fn new(fom: &'a ObjectModelType) -> Self {
+        let mut index = Self {
+            object_classes: Vec::new(),
+            attributes: Vec::new(),
+            interaction_classes: Vec::new(),
+            parameters: Vec::new(),
+            data_types: Vec::new(),
+            dimensions: Vec::new(),
+            transportations: Vec::new(),
+            object_class_ids: HashMap::new(),
+            interaction_class_ids: HashMap::new(),
+            data_type_ids: HashMap::new(),
+            dimension_ids: HashMap::new(),
+            transportation_ids: HashMap::new(),
+        };
+
+        // Everything the classes refer to is indexed before the classes.
+        for data_type in DataTypeRegistry::new(fom).data_types() {
+            let id = DataTypeId::new(index.data_types.len());
+            index.data_types.push(data_type);
+            index.data_type_ids.insert(data_type.name(), id);
+        }
+        for module in [fom, standard_mim()] {
+            let dimensions = module.dimensions.iter().flat_map(|d| d.dimensions.iter());
+            for dimension in dimensions.flatten() {
+                if !index.dimension_ids.contains_key(dimension.name.as_str()) {
+                    let id = DimensionId::new(index.dimensions.len());
+                    index.dimensions.push(dimension);
+                    index.dimension_ids.insert(&dimension.name, id);
+                }
+            }
+            let transportations = module
+                .transportations
+                .iter()
+                .flat_map(|t| t.transportations.iter());
+            for transportation in transportations.flatten() {
+                if !index
+                    .transportation_ids
+                    .contains_key(transportation.name.as_str())
+                {
+                    let id = TransportationId::new(index.transportations.len());
+                    index.transportations.push(transportation);
+                    index.transportation_ids.insert(&transportation.name, id);
+                }
+            }
+        }
+
+        for node in fom.object_classes() {
+            index.add_object_class(&node);
+        }
+        for node in fom.interaction_classes() {
+            index.add_interaction_class(&node);
+        }
+        index
+    }
+
+    // Classes are added depth first, so the superclass of a class is always
+    // added before it.
+    fn add_object_class(&mut self, node: &ClassNode<'a, ObjectClassType>) {
+        let id = ObjectClassId::new(self.object_classes.len());
+        let parent = node
+            .parent()
+            .map(|parent| self.object_class_ids[&parent.qualified_name()]);
+        if let Some(parent) = parent {
+            self.object_classes[parent.index()].children.push(id);
+        }
+        let class = node.class();
+        let mut attributes = Vec::new();
+        for attribute in class.attributes.iter().flatten() {
+            attributes.push(AttributeId::new(self.attributes.len()));
+            self.attributes.push(AttributeEntry {
+                attribute,
+                class: id,
+                data_type: self.resolve_data_type(attribute.data_type.as_ref()),
+                dimensions: self.resolve_dimensions(attribute.dimensions.as_ref()),
+                transportation: self.resolve_transportation(attribute.transportation.as_ref()),
+            });
+        }
+        let qualified_name = node.qualified_name();
+        self.object_class_ids.insert(qualified_name.clone(), id);
+        self.object_classes.push(ObjectClassEntry {
+            class,
+            qualified_name,
+            parent,
+            children: Vec::new(),
+            attributes,
+        });
+    }
+
+    fn add_interaction_class(&mut self, node: &ClassNode<'a, InteractionClassType>) {
+        let id = InteractionClassId::new(self.interaction_classes.len());
+        let parent = node
+            .parent()
+            .map(|parent| self.interaction_class_ids[&parent.qualified_name()]);
+        if let Some(parent) = parent {
+            self.interaction_classes[parent.index()].children.push(id);
+        }
+        let class = node.class();
+        let mut parameters = Vec::new();
+        for parameter in class.parameters.iter().flatten() {
+            parameters.push(ParameterId::new(self.parameters.len()));
+            self.parameters.push(ParameterEntry {
+                parameter,
+                class: id,
+                data_type: self.resolve_data_type(parameter.data_type.as_ref()),
+            });
+        }
+        let qualified_name = node.qualified_name();
+        self.interaction_class_ids
+            .insert(qualified_name.clone(), id);
+        self.interaction_classes.push(InteractionClassEntry {
+            class,
+            qualified_name,
+            parent,
+            children: Vec::new(),
+            parameters,
+            dimensions: self.resolve_dimensions(class.dimensions.as_ref()),
+            transportation: self.resolve_transportation(class.transportation.as_ref()),
+        });
+    }
+
+    fn resolve_data_type(&self, reference: Option<&ReferenceType>) -> Option<DataTypeId> {
+        self.data_type_id(&reference?.value)
+    }
+
+    fn resolve_dimensions(&self, references: Option<&Vec<ReferenceType>>) -> Vec<DimensionId> {
+        references
+            .into_iter()
+            .flatten()
+            .filter_map(|reference| self.dimension_id(&reference.value))
+            .collect()
+    }
+
+    fn resolve_transportation(
+        &self,
+        reference: Option<&ReferenceType>,
+    ) -> Option<TransportationId> {
+        self.transportation_id(&reference?.value)
+    }
+
+    pub fn object_class(&self, id: ObjectClassId) -> &ObjectClassEntry<'a> {
+        &self.object_classes[id.index()]
+    }
+
+    pub fn attribute(&self, id: AttributeId) -> &AttributeEntry<'a> {
+        &self.attributes[id.index()]
+    }
+
+    pub fn interaction_class(&self, id: InteractionClassId) -> &InteractionClassEntry<'a> {
+        &self.interaction_classes[id.index()]
+    }
+
+    pub fn parameter(&self, id: ParameterId) -> &ParameterEntry<'a> {
+        &self.parameters[id.index()]
+    }
+
+    pub fn data_type(&self, id: DataTypeId) -> DataTypeRef<'a> {
+        self.data_types[id.index()]
+    }
+
+    pub fn dimension(&self, id: DimensionId) -> &'a DimensionType {
+        self.dimensions[id.index()]
+    }
+
+    pub fn transportation(&self, id: TransportationId) -> &'a TransportationType {
+        self.transportations[id.index()]
+    }
+
+    /// Return the ID of the object class with the provided qualified name. The
+    /// name of the root class may be left out.
+    pub fn object_class_id(&self, qualified_name: &str) -> Option<ObjectClassId> {
+        let root = self.object_classes.first()?;
+        qualified_id(&self.object_class_ids, &root.class.name, qualified_name)
+    }
+
+    /// Return the ID of the interaction class with the provided qualified name.
+    /// The name of the root class may be left out.
+    pub fn interaction_class_id(&self, qualified_name: &str) -> Option<InteractionClassId> {
+        let root = self.interaction_classes.first()?;
+        qualified_id(
+            &self.interaction_class_ids,
+            &root.class.name,
+            qualified_name,
+        )
+    }
+
+    /// Return the ID of the named attribute of the object class, whether the
+    /// class declares it or inherits it.
+    pub fn attribute_id(&self, class: ObjectClassId, name: &str) -> Option<AttributeId> {
+        let mut class = Some(class);
+        while let Some(id) = class {
+            let entry = self.object_class(id);
+            let found = entry
+                .attributes
+                .iter()
+                .find(|attribute| self.attribute(**attribute).attribute.name == name);
+            if found.is_some() {
+                return found.copied();
+            }
+            class = entry.parent;
+        }
+        None
+    }
+
+    /// Return the ID of the named parameter of the interaction class, whether
+    /// the class declares it or inherits it.
+    pub fn parameter_id(&self, class: InteractionClassId, name: &str) -> Option<ParameterId> {
+        let mut class = Some(class);
+        while let Some(id) = class {
+            let entry = self.interaction_class(id);
+            let found = entry
+                .parameters
+                .iter()
+                .find(|parameter| self.parameter(**parameter).parameter.name == name);
+            if found.is_some() {
+                return found.copied();
+            }
+            class = entry.parent;
+        }
+        None
+    }
+
+    pub fn data_type_id(&self, name: &str) -> Option<DataTypeId> {
+        self.data_type_ids.get(name).copied()
+    }
+
+    pub fn dimension_id(&self, name: &str) -> Option<DimensionId> {
+        self.dimension_ids.get(name).copied()
+    }
+
+    pub fn transportation_id(&self, name: &str) -> Option<TransportationId> {
+        self.transportation_ids.get(name).copied()
+    }
+
+    /// Return the superclasses of the object class, nearest first.
+    pub fn object_class_ancestors(
+        &self,
+        id: ObjectClassId,
+    ) -> impl Iterator<Item = ObjectClassId> + '_ {
+        std::iter::successors(self.object_class(id).parent, move |id| {
+            self.object_class(*id).parent
+        })
+    }
+
+    /// Return the superclasses of the interaction class, nearest first.
+    pub fn interaction_class_ancestors(
+        &self,
+        id: InteractionClassId,
+    ) -> impl Iterator<Item = InteractionClassId> + '_ {
+        std::iter::successors(self.interaction_class(id).parent, move |id| {
+            self.interaction_class(*id).parent
+        })
+    }
+
+    /// Return the attributes of the object class, including those it inherits,
+    /// starting with those of the root class.
+    pub fn effective_attributes(&self, id: ObjectClassId) -> Vec<AttributeId> {
+        let mut classes: Vec<ObjectClassId> = self.object_class_ancestors(id).collect();
+        classes.reverse();
+        classes.push(id);
+        classes
+            .into_iter()
+            .flat_map(|class| self.object_class(class).attributes.iter().copied())
+            .collect()
+    }
+
+    /// Return the parameters of the interaction class, including those it
+    /// inherits, starting with those of the root class.
+    pub fn effective_parameters(&self, id: InteractionClassId) -> Vec<ParameterId> {
+        let mut classes: Vec<InteractionClassId> = self.interaction_class_ancestors(id).collect();
+        classes.reverse();
+        classes.push(id);
+        classes
+            .into_iter()
+            .flat_map(|class| self.interaction_class(class).parameters.iter().copied())
+            .collect()
+    }
+
+    /// Return every object class, in the order of their IDs.
+    pub fn object_classes(&self) -> impl Iterator<Item = (ObjectClassId, &ObjectClassEntry<'a>)> {
+        (0..).map(ObjectClassId::new).zip(&self.object_classes)
+    }
+
+    /// Return every attribute, in the order of their IDs.
+    pub fn attributes(&self) -> impl Iterator<Item = (AttributeId, &AttributeEntry<'a>)> {
+        (0..).map(AttributeId::new).zip(&self.attributes)
+    }
+
+    /// Return every interaction class, in the order of their IDs.
+    pub fn interaction_classes(
+        &self,
+    ) -> impl Iterator<Item = (InteractionClassId, &InteractionClassEntry<'a>)> {
+        (0..)
+            .map(InteractionClassId::new)
+            .zip(&self.interaction_classes)
+    }
+
+    /// Return every parameter, in the order of their IDs.
+    pub fn parameters(&self) -> impl Iterator<Item = (ParameterId, &ParameterEntry<'a>)> {
+        (0..).map(ParameterId::new).zip(&self.parameters)
+    }
+
+    /// Return every datatype, in the order of their IDs.
+    pub fn data_types(&self) -> impl Iterator<Item = (DataTypeId, DataTypeRef<'a>)> + '_ {
+        (0..)
+            .map(DataTypeId::new)
+            .zip(self.data_types.iter().copied())
+    }
+
+    /// Return every dimension, in the order of their IDs.
+    pub fn dimensions(&self) -> impl Iterator<Item = (DimensionId, &'a DimensionType)> + '_ {
+        (0..)
+            .map(DimensionId::new)
+            .zip(self.dimensions.iter().copied())
+    }
+
+    /// Return every transportation type, in the order of their IDs.
+    pub fn transportations(
+        &self,
+    ) -> impl Iterator<Item = (TransportationId, &'a TransportationType)> + '_ {
+        (0..)
+            .map(TransportationId::new)
+            .zip(self.transportations.iter().copied())
+    }
+}
+
+/// Return the ID of the class with the provided qualified name, which may
+/// leave out the name of the root class.
+fn qualified_id<T: Copy>(ids: &HashMap<String, T>, root: &str, qualified_name: &str) -> Option<T> {
+    ids.get(qualified_name)
+        .or_else(|| ids.get(&format!("{}.{}", root, qualified_name)))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object_model() -> ObjectModelType {
+        parse_str(
+            r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <objects>
+    <objectClass>
+      <name>HLAobjectRoot</name>
+      <objectClass>
+        <name>Vehicle</name>
+        <attribute>
+          <name>Position</name>
+          <dataType>Location</dataType>
+          <dimensions>
+            <dimension>Zone</dimension>
+            <dimension>Undeclared</dimension>
+          </dimensions>
+          <transportation>HLAbestEffort</transportation>
+        </attribute>
+        <objectClass>
+          <name>Car</name>
+          <attribute>
+            <name>Passengers</name>
+            <dataType>HLAinteger32BE</dataType>
+            <transportation>HLAreliable</transportation>
+          </attribute>
+        </objectClass>
+      </objectClass>
+      <objectClass>
+        <name>Person</name>
+      </objectClass>
+    </objectClass>
+  </objects>
+  <interactions>
+    <interactionClass>
+      <name>HLAinteractionRoot</name>
+      <interactionClass>
+        <name>Crash</name>
+        <dimensions>
+          <dimension>Zone</dimension>
+        </dimensions>
+        <transportation>HLAreliable</transportation>
+        <parameter>
+          <name>Where</name>
+          <dataType>Location</dataType>
+        </parameter>
+      </interactionClass>
+    </interactionClass>
+  </interactions>
+  <dimensions>
+    <dimension>
+      <name>Zone</name>
+      <upperBound>8</upperBound>
+    </dimension>
+  </dimensions>
+  <dataTypes>
+    <fixedRecordDataTypes>
+      <fixedRecordData>
+        <name>Location</name>
+        <encoding>HLAfixedRecord</encoding>
+      </fixedRecordData>
+    </fixedRecordDataTypes>
+  </dataTypes>
+</objectModel>"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_index_object_model() {
+        let fom = object_model();
+        let index = FomIndex::new(&fom);
+
+        let names: Vec<&str> = index
+            .object_classes()
+            .map(|(_, class)| class.qualified_name.as_str())
+            .collect();
+        assert_eq!(
+            vec![
+                "HLAobjectRoot",
+                "HLAobjectRoot.Vehicle",
+                "HLAobjectRoot.Vehicle.Car",
+                "HLAobjectRoot.Person"
+            ],
+            names
+        );
+
+        let vehicle = index.object_class_id("Vehicle").unwrap();
+        let car = index.object_class_id("HLAobjectRoot.Vehicle.Car").unwrap();
+        assert_eq!(Some(vehicle), index.object_class(car).parent);
+        assert_eq!(vec![car], index.object_class(vehicle).children);
+        assert_eq!(
+            vec![vehicle, ObjectClassId::new(0)],
+            index.object_class_ancestors(car).collect::<Vec<_>>()
+        );
+
+        // Inherited attributes are found from the subclass.
+        let position = index.attribute_id(car, "Position").unwrap();
+        let passengers = index.attribute_id(car, "Passengers").unwrap();
+        assert_eq!(None, index.attribute_id(vehicle, "Passengers"));
+        assert_eq!(vec![position, passengers], index.effective_attributes(car));
+
+        // References resolve to the object model and to the MIM, and
+        // references to parts that are not declared are left out.
+        let position = index.attribute(position);
+        assert_eq!(vehicle, position.class);
+        assert_eq!(
+            "Location",
+            index.data_type(position.data_type.unwrap()).name()
+        );
+        assert_eq!(
+            vec![index.dimension_id("Zone").unwrap()],
+            position.dimensions
+        );
+        let transportation = index.transportation(position.transportation.unwrap());
+        assert_eq!("HLAbestEffort", transportation.name);
+        let passengers = index.attribute(passengers);
+        let data_type = index.data_type(passengers.data_type.unwrap());
+        assert!(matches!(data_type, DataTypeRef::Basic(_)));
+        // The datatypes of the object model come before those of the MIM.
+        assert_eq!(DataTypeId::new(0), index.data_type_id("Location").unwrap());
+        assert_eq!(DimensionId::new(0), index.dimension_id("Zone").unwrap());
+        assert!(index.dimension_id("HLAfederate").is_some());
+
+        let crash = index.interaction_class_id("Crash").unwrap();
+        let crash_entry = index.interaction_class(crash);
+        assert_eq!(Some(InteractionClassId::new(0)), crash_entry.parent);
+        assert_eq!(1, crash_entry.dimensions.len());
+        assert!(crash_entry.transportation.is_some());
+        let location = index.parameter(index.parameter_id(crash, "Where").unwrap());
+        assert_eq!(index.data_type_id("Location"), location.data_type);
+        assert_eq!(crash_entry.parameters, index.effective_parameters(crash));
+    }
+
+    #[test]
+    fn test_ids_are_deterministic() {
+        let fom = standard_mim();
+        let first = FomIndex::new(fom);
+        let second = FomIndex::new(fom);
+        let ids = |index: &FomIndex| -> Vec<(String, usize)> {
+            index
+                .object_classes()
+                .map(|(id, class)| (class.qualified_name.clone(), id.index()))
+                .chain(
+                    index
+                        .interaction_classes()
+                        .map(|(id, class)| (class.qualified_name.clone(), id.index())),
+                )
+                .chain(
+                    index
+                        .data_types()
+                        .map(|(id, data_type)| (String::from(data_type.name()), id.index())),
+                )
+                .collect()
+        };
+        assert_eq!(ids(&first), ids(&second));
+        assert_eq!(
+            Some(ObjectClassId::new(0)),
+            first.object_class_id("HLAobjectRoot")
+        );
+    }
+}
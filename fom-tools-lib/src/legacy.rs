@@ -0,0 +1,547 @@
+//! Object models read from documents in the formats that preceded IEEE
+//! 1516.2-2010: IEEE 1516.2-2000 OMT documents, read here, and HLA 1.3 FED
+//! files, read by the `fed` module.
+//!
+//! Both are read into the 2010 `objectModel` element that describes the same
+//! object model, which is then converted like any other document. The parts of
+//! the 2010 OMT that a format has no counterpart for are listed by
+//! `SourceFormat::absent_fields`.
+
+use crate::dom::{Attribute, Element, Node};
+use crate::*;
+
+/// The format of the document that an object model was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    /// An IEEE 1516.2-2010 OMT document, including the SISO published form.
+    Ieee1516_2010,
+    /// An IEEE 1516.2-2025 (HLA 4) OMT document, including the SISO published
+    /// form.
+    Ieee1516_2025,
+    /// An IEEE 1516.2-2000 OMT document, whose elements hold their fields as
+    /// attributes and which has no namespace.
+    Ieee1516_2000,
+    /// An HLA 1.3 federation execution details (FED) file.
+    Hla13Fed,
+}
+
+/// The elements of the 2010 OMT, other than those of the MIM, that IEEE
+/// 1516.2-2000 documents cannot express.
+const IEEE1516_2000_ABSENT_FIELDS: &[&str] = &[
+    "modelIdentification/securityClassification",
+    "modelIdentification/releaseRestriction",
+    "modelIdentification/description",
+    "modelIdentification/useLimitation",
+    "modelIdentification/useHistory",
+    "modelIdentification/keyword",
+    "modelIdentification/glyph",
+    "serviceUtilization",
+    "transportations/transportation/reliable",
+    "switches/conveyProducingFederate",
+    "switches/exceptionReporting",
+    "switches/delaySubscriptionEvaluation",
+    "switches/automaticResignAction",
+    "updateRates",
+];
+
+/// The elements of the 2010 OMT that HLA 1.3 FED files cannot express.
+const HLA13_FED_ABSENT_FIELDS: &[&str] = &[
+    "modelIdentification/version",
+    "modelIdentification/modificationDate",
+    "modelIdentification/securityClassification",
+    "modelIdentification/releaseRestriction",
+    "modelIdentification/purpose",
+    "modelIdentification/applicationDomain",
+    "modelIdentification/description",
+    "modelIdentification/useLimitation",
+    "modelIdentification/useHistory",
+    "modelIdentification/keyword",
+    "modelIdentification/poc",
+    "modelIdentification/reference",
+    "modelIdentification/other",
+    "modelIdentification/glyph",
+    "serviceUtilization",
+    "objects/objectClass/sharing",
+    "objects/objectClass/semantics",
+    "objects/objectClass/attribute/dataType",
+    "objects/objectClass/attribute/updateType",
+    "objects/objectClass/attribute/updateCondition",
+    "objects/objectClass/attribute/ownership",
+    "objects/objectClass/attribute/sharing",
+    "objects/objectClass/attribute/semantics",
+    "interactions/interactionClass/sharing",
+    "interactions/interactionClass/semantics",
+    "interactions/interactionClass/parameter/dataType",
+    "interactions/interactionClass/parameter/semantics",
+    "dimensions/dimension/dataType",
+    "dimensions/dimension/upperBound",
+    "dimensions/dimension/normalization",
+    "dimensions/dimension/value",
+    "time",
+    "tags",
+    "synchronizations",
+    "transportations",
+    "switches",
+    "updateRates",
+    "dataTypes",
+    "notes",
+];
+
+impl SourceFormat {
+    /// Return the paths, relative to `objectModel`, of the elements of the
+    /// 2010 OMT that this format has no counterpart for, e.g.
+    /// `objects/objectClass/attribute/dataType`. The corresponding parts of an
+    /// object model read from a document in this format are always absent,
+    /// because the document could not have specified them, rather than
+    /// because it left them unspecified.
+    pub fn absent_fields(&self) -> &'static [&'static str] {
+        match self {
+            SourceFormat::Ieee1516_2010 | SourceFormat::Ieee1516_2025 => &[],
+            SourceFormat::Ieee1516_2000 => IEEE1516_2000_ABSENT_FIELDS,
+            SourceFormat::Hla13Fed => HLA13_FED_ABSENT_FIELDS,
+        }
+    }
+
+    /// Return whether this format has a counterpart for the element of the
+    /// 2010 OMT at the provided path, relative to `objectModel`. The elements
+    /// within an element without a counterpart have none either.
+    pub fn can_express(&self, path: &str) -> bool {
+        !self.absent_fields().iter().any(|absent| {
+            path == *absent
+                || path
+                    .strip_prefix(absent)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Return whether the provided root element is that of an IEEE 1516.2-2000
+/// document. Those have no namespace, and their `objectModel` element holds
+/// the name of the object model as an attribute, along with a `DTDversion`
+/// attribute when the DTD has been applied.
+pub(crate) fn is_ieee1516_2000(e: &Element) -> bool {
+    e.namespace.is_none()
+        && e.name == "objectModel"
+        && (e.get_attribute("DTDversion").is_some() || e.get_attribute("name").is_some())
+}
+
+/// Return an empty element of the 2010 OMT positioned at the provided element.
+fn element_at(name: &str, e: &Element) -> Element {
+    let mut upgraded = Element::new(name);
+    upgraded.namespace = Some(String::from(IEEE1516_2010_NAMESPACE));
+    upgraded.position = e.position;
+    upgraded.end = e.end;
+    upgraded
+}
+
+/// Return an element of the 2010 OMT that holds the provided text, along with
+/// the provided note labels.
+fn text_element_at(name: &str, text: &str, notes: Option<&str>, e: &Element) -> Element {
+    let mut upgraded = element_at(name, e);
+    upgraded.children.push(Node::Text(String::from(text)));
+    if let Some(notes) = notes {
+        upgraded.attributes.push(Attribute {
+            name: String::from("notes"),
+            namespace: None,
+            value: String::from(notes),
+        });
+    }
+    upgraded
+}
+
+fn push(parent: &mut Element, child: Element) {
+    parent.children.push(Node::Element(child));
+}
+
+/// Return the labels of the notes that apply to the named attribute of the
+/// provided element, which a 2000 document holds in the attribute of the same
+/// name suffixed with `Notes`.
+fn notes_of<'a>(e: &'a Element, attribute_name: &str) -> Option<&'a str> {
+    e.get_attribute(&format!("{}Notes", attribute_name))
+}
+
+/// Return the attributes of the provided element that hold its fields.
+fn fields(e: &Element) -> impl Iterator<Item = &Attribute> {
+    e.attributes
+        .iter()
+        .filter(|a| a.namespace.is_none() && !a.name.ends_with("Notes") && a.name != "DTDversion")
+}
+
+/// Read the root element of an IEEE 1516.2-2000 document into the `objectModel`
+/// element of the equivalent 2010 document. Fields are moved from attributes
+/// into child elements, and their `...Notes` attributes onto those elements
+/// as `notes`. The identification of the object model, held by attributes of
+/// the root element in 2000, is moved into `modelIdentification`.
+pub(crate) fn upgrade_ieee1516_2000(e: &Element) -> Element {
+    let mut root = element_at("objectModel", e);
+    push(&mut root, upgrade_model_identification(e));
+    for child in e.child_elements() {
+        push(&mut root, upgrade_element(child));
+    }
+    root
+}
+
+fn upgrade_model_identification(e: &Element) -> Element {
+    let mut model_identification = element_at("modelIdentification", e);
+    let mut poc = element_at("poc", e);
+    for field in fields(e) {
+        let notes = notes_of(e, &field.name);
+        let name = match field.name.as_str() {
+            "name" | "type" | "version" | "purpose" | "other" => field.name.as_str(),
+            "date" => "modificationDate",
+            "appDomain" => "applicationDomain",
+            "sponsor" => {
+                let mut sponsor = element_at("poc", e);
+                push(&mut sponsor, text_element_at("pocType", "Sponsor", None, e));
+                push(
+                    &mut sponsor,
+                    text_element_at("pocOrg", &field.value, notes, e),
+                );
+                push(&mut model_identification, sponsor);
+                continue;
+            }
+            "pocName" | "pocOrg" => {
+                push(
+                    &mut poc,
+                    text_element_at(&field.name, &field.value, notes, e),
+                );
+                continue;
+            }
+            "pocPhone" => {
+                push(
+                    &mut poc,
+                    text_element_at("pocTelephone", &field.value, notes, e),
+                );
+                continue;
+            }
+            "pocEmail" => {
+                push(
+                    &mut poc,
+                    text_element_at("pocEmail", &field.value, notes, e),
+                );
+                continue;
+            }
+            "references" => {
+                let mut reference = element_at("reference", e);
+                push(
+                    &mut reference,
+                    text_element_at("identification", &field.value, notes, e),
+                );
+                push(&mut model_identification, reference);
+                continue;
+            }
+            // Unknown attributes are left out, as unknown elements are.
+            _ => continue,
+        };
+        push(
+            &mut model_identification,
+            text_element_at(name, &field.value, notes, e),
+        );
+    }
+    if !poc.children.is_empty() {
+        push(&mut model_identification, poc);
+    }
+    model_identification
+}
+
+fn upgrade_element(e: &Element) -> Element {
+    let name = match e.name.as_str() {
+        "synchronization" => "synchronizationPoint",
+        name => name,
+    };
+    let mut upgraded = element_at(name, e);
+    for field in fields(e) {
+        let notes = notes_of(e, &field.name);
+        match (e.name.as_str(), field.name.as_str()) {
+            // Switches are enabled or disabled by an attribute of their own.
+            ("switches", _) => {
+                let mut switch = element_at(&field.name, e);
+                switch.attributes.push(Attribute {
+                    name: String::from("isEnabled"),
+                    namespace: None,
+                    value: String::from(if field.value == "Enabled" {
+                        "true"
+                    } else {
+                        "false"
+                    }),
+                });
+                push(&mut upgraded, switch);
+            }
+            ("attribute", "dimensions") | ("interactionClass", "dimensions") => {
+                let mut dimensions = element_at("dimensions", e);
+                for dimension in field.value.split(|c: char| c == ',' || c.is_whitespace()) {
+                    if !dimension.is_empty() && dimension != "NA" {
+                        push(
+                            &mut dimensions,
+                            text_element_at("dimension", dimension, None, e),
+                        );
+                    }
+                }
+                if let Some(notes) = notes {
+                    dimensions.attributes.push(Attribute {
+                        name: String::from("notes"),
+                        namespace: None,
+                        value: String::from(notes),
+                    });
+                }
+                push(&mut upgraded, dimensions);
+            }
+            ("enumerator", "values") => {
+                for value in field.value.split(',') {
+                    push(
+                        &mut upgraded,
+                        text_element_at("value", value.trim(), notes, e),
+                    );
+                }
+            }
+            ("transportation", "description") => {
+                push(
+                    &mut upgraded,
+                    text_element_at("semantics", &field.value, notes, e),
+                );
+            }
+            ("note", "name") => {
+                push(
+                    &mut upgraded,
+                    text_element_at("label", &field.value, notes, e),
+                );
+            }
+            (_, name) => push(&mut upgraded, text_element_at(name, &field.value, notes, e)),
+        }
+    }
+    // Notes may hold their semantics as text rather than as an attribute.
+    if e.name == "note" && e.get_attribute("semantics").is_none() {
+        if let Some(text) = e.get_text() {
+            if !text.trim().is_empty() {
+                push(
+                    &mut upgraded,
+                    text_element_at("semantics", text.trim(), None, e),
+                );
+            }
+        }
+    }
+    for child in e.child_elements() {
+        push(&mut upgraded, upgrade_element(child));
+    }
+    upgraded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IEEE1516_2000_DOCUMENT: &str = r#"<?xml version="1.0"?>
+<objectModel DTDversion="1516.2" name="Restaurant" type="FOM" version="1.0"
+    date="2000-01-01" sponsor="Caterers Inc" pocName="Jane Doe" pocEmail="jane@example.com">
+  <objects>
+    <objectClass name="HLAobjectRoot" sharing="Neither">
+      <attribute name="HLAprivilegeToDeleteObject" dataType="HLAtoken" updateType="Static"
+          ownership="DivestAcquire" sharing="PublishSubscribe" dimensions="NA"
+          transportation="HLAreliable" order="TimeStamp"/>
+      <objectClass name="Employee" sharing="PublishSubscribe" semantics="A worker"
+          semanticsNotes="Note1">
+        <attribute name="PayRate" dataType="DollarRate" updateType="Conditional"
+            updateCondition="On change" ownership="NoTransfer" sharing="PublishSubscribe"
+            dimensions="Wages" transportation="HLAreliable" order="Receive"/>
+      </objectClass>
+    </objectClass>
+  </objects>
+  <interactions>
+    <interactionClass name="HLAinteractionRoot" sharing="Neither" dimensions="NA"
+        transportation="HLAbestEffort" order="Receive">
+      <interactionClass name="Greeting" sharing="Publish" transportation="HLAreliable"
+          order="TimeStamp">
+        <parameter name="Message" dataType="HLAunicodeString"/>
+      </interactionClass>
+    </interactionClass>
+  </interactions>
+  <dimensions>
+    <dimension name="Wages" dataType="HLAinteger32BE" upperBound="100"/>
+  </dimensions>
+  <synchronizations>
+    <synchronization label="Ready" dataType="NA" capability="RegisterAchieve"/>
+  </synchronizations>
+  <switches autoProvide="Enabled" conveyRegionDesignatorSets="Disabled"/>
+  <dataTypes>
+    <enumeratedDataTypes>
+      <enumeratedData name="Course" representation="HLAinteger32BE">
+        <enumerator name="Soup" values="1"/>
+        <enumerator name="Main" values="2, 3"/>
+      </enumeratedData>
+    </enumeratedDataTypes>
+  </dataTypes>
+  <notes>
+    <note name="Note1" semantics="Employees are paid weekly"/>
+  </notes>
+</objectModel>"#;
+
+    #[test]
+    fn test_parse_ieee1516_2000_document() {
+        let parsed =
+            parse_reader_with_options(IEEE1516_2000_DOCUMENT.as_bytes(), &ParseOptions::default())
+                .unwrap();
+        assert_eq!(SourceFormat::Ieee1516_2000, parsed.format);
+        assert!(parsed.warnings.is_empty());
+        let fom = parsed.object_model;
+
+        let model_identification = fom.model_identification.as_ref().unwrap();
+        assert_eq!(Some(String::from("Restaurant")), model_identification.name);
+        assert_eq!(Some(ModelType::FOM), model_identification.model_type);
+        assert_eq!(
+            Some(String::from("2000-01-01")),
+            model_identification.modification_date
+        );
+        let poc = model_identification.poc.as_ref().unwrap();
+        assert_eq!(2, poc.len());
+        assert_eq!(Some(PocTypeType::Sponsor), poc[0].poc_type);
+        assert_eq!(Some(String::from("Jane Doe")), poc[1].poc_name);
+
+        let employee = fom.find_object_class("Employee").unwrap();
+        assert_eq!("HLAobjectRoot.Employee", employee.qualified_name());
+        assert_eq!(Some(String::from("A worker")), employee.class().semantics);
+        assert_eq!(
+            vec!["Note1"],
+            employee.class().note_references_at("semantics")
+        );
+        assert_eq!(
+            "Employees are paid weekly",
+            fom.resolve_notes_at(employee.class(), "semantics")[0]
+                .semantics
+                .as_deref()
+                .unwrap()
+        );
+        let pay_rate = &employee.class().attributes.as_ref().unwrap()[0];
+        assert_eq!(Some(OwnershipType::NoTransfer), pay_rate.onwership);
+        assert_eq!(Some(1), pay_rate.dimensions.as_ref().map(Vec::len));
+        let root = fom.object_class("HLAobjectRoot").unwrap();
+        // "NA" is the empty set of dimensions, as `<dimensions/>` is in 2010.
+        assert_eq!(
+            Some(vec![]),
+            root.attributes.as_ref().unwrap()[0].dimensions
+        );
+
+        let greeting = fom.interaction_class("Greeting").unwrap();
+        assert_eq!(Some(OrderType::TimeStamp), greeting.order);
+        assert_eq!(1, greeting.parameters.as_ref().unwrap().len());
+
+        let switches = fom.switches.as_ref().unwrap();
+        assert_eq!(Some(SwitchType { is_enabled: true }), switches.auto_provide);
+        assert_eq!(
+            Some(SwitchType { is_enabled: false }),
+            switches.convey_region_designator_sets
+        );
+        assert_eq!(None, switches.exception_reporting);
+        assert_eq!(
+            "Ready",
+            fom.synchronizations
+                .as_ref()
+                .unwrap()
+                .synchronization_points
+                .as_ref()
+                .unwrap()[0]
+                .label
+        );
+
+        let enumerated = &fom
+            .data_types
+            .as_ref()
+            .unwrap()
+            .enumerated_data_types
+            .as_ref()
+            .unwrap()
+            .enumerated_datas
+            .as_ref()
+            .unwrap()[0];
+        let main = &enumerated.enumerators.as_ref().unwrap()[1];
+        assert_eq!(
+            vec![Some(&2), Some(&3)],
+            main.value.iter().map(TypedValue::value).collect::<Vec<_>>()
+        );
+        assert_eq!("Note1", fom.note("Note1").unwrap().label);
+
+        // Positions refer to the 2000 document.
+        let span = employee.class().span().unwrap();
+        assert_eq!(9, span.start.line);
+    }
+
+    #[test]
+    fn test_parse_fed() {
+        let fed = r#";; The restaurant federation
+(FED
+  (Federation Restaurant)
+  (FEDversion v1.3)
+  (spaces
+    (space Geo
+      (dimension X)
+      (dimension Y)))
+  (objects
+    (class ObjectRoot
+      (attribute privilegeToDelete reliable timestamp)
+      (class RTIprivate)
+      (class Employee
+        (attribute PayRate reliable receive)
+        (attribute Location best_effort receive Geo))))
+  (interactions
+    (class InteractionRoot best_effort receive
+      (class RTIprivate best_effort receive)
+      (class "Greeting" reliable timestamp Geo
+        (parameter Message)))))
+"#;
+        let parsed =
+            parse_fed_reader_with_options(fed.as_bytes(), &ParseOptions::default()).unwrap();
+        assert_eq!(SourceFormat::Hla13Fed, parsed.format);
+        assert!(parsed.warnings.is_empty());
+        let fom = parsed.object_model;
+
+        let model_identification = fom.model_identification.as_ref().unwrap();
+        assert_eq!(Some(String::from("Restaurant")), model_identification.name);
+        assert_eq!(Some(ModelType::FOM), model_identification.model_type);
+
+        let root = fom.object_class("HLAobjectRoot").unwrap();
+        let privilege = &root.attributes.as_ref().unwrap()[0];
+        assert_eq!("HLAprivilegeToDeleteObject", privilege.name);
+        assert_eq!(Some(OrderType::TimeStamp), privilege.order);
+
+        let employee = fom.object_class("HLAobjectRoot.Employee").unwrap();
+        let attributes = employee.attributes.as_ref().unwrap();
+        assert_eq!(
+            Some(ReferenceType {
+                value: String::from("HLAreliable")
+            }),
+            attributes[0].transportation
+        );
+        assert_eq!(None, attributes[0].data_type);
+        let dimensions: Vec<&str> = attributes[1]
+            .dimensions
+            .iter()
+            .flatten()
+            .map(|d| d.value.as_str())
+            .collect();
+        assert_eq!(vec!["Geo.X", "Geo.Y"], dimensions);
+        assert_eq!(
+            2,
+            fom.dimensions
+                .as_ref()
+                .unwrap()
+                .dimensions
+                .as_ref()
+                .unwrap()
+                .len()
+        );
+
+        let greeting = fom
+            .interaction_class("HLAinteractionRoot.Greeting")
+            .unwrap();
+        assert_eq!(Some(OrderType::TimeStamp), greeting.order);
+        assert_eq!("Message", greeting.parameters.as_ref().unwrap()[0].name);
+        let span = greeting.span().unwrap();
+        assert_eq!(19, span.start.line);
+        assert_eq!(7, span.start.column);
+
+        assert!(!SourceFormat::Hla13Fed.can_express("dataTypes/simpleDataTypes"));
+        assert!(!SourceFormat::Hla13Fed.can_express("objects/objectClass/attribute/dataType"));
+        assert!(SourceFormat::Hla13Fed.can_express("objects/objectClass/attribute/order"));
+        assert!(SourceFormat::Ieee1516_2000.can_express("dataTypes"));
+        assert!(!SourceFormat::Ieee1516_2000.can_express("updateRates"));
+    }
+}
@@ -1,90 +1,471 @@
+use std::convert::TryFrom;
+use std::fs::File;
 use std::io::Read;
-use xmltree::{Element, ParseError, XMLNode};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Invoke the provided macro with every type of the object model that is
+/// converted from an element of its own, i.e. that holds annotations and a
+/// source span.
+macro_rules! for_each_model_node {
+    ($m:ident) => {
+        $m!(
+            ObjectModelType,
+            ModelIdentificationType,
+            KeywordType,
+            PocType,
+            IdReferenceType,
+            ServiceUtiliizationType,
+            ObjectsType,
+            ObjectClassType,
+            AttributeType,
+            InteractionsType,
+            InteractionClassType,
+            ParameterType,
+            DimensionsType,
+            DimensionType,
+            TimeType,
+            TimeTypeType,
+            TagsType,
+            TagType,
+            SynchronizationsType,
+            SynchronizationPointType,
+            TransportationsType,
+            TransportationType,
+            SwitchesType,
+            UpdateRatesType,
+            UpdateRateType,
+            DataTypesType,
+            BasicDataRepresentationsType,
+            BasicDataType,
+            SimpleDataTypesType,
+            SimpleDataType,
+            EnumeratedDataTypesType,
+            EnumeratedDataType,
+            EnumeratorType,
+            ArrayDataTypesType,
+            ArrayDataType,
+            FixedRecordDataTypesType,
+            FixedRecordDataType,
+            FieldType,
+            VariantRecordDataTypesType,
+            VariantRecordDataType,
+            AlternativeType,
+            NotesType,
+            NoteType,
+        );
+    };
+}
+
+/// Invoke the provided macro with every type of the object model that is
+/// converted from an element of its own, along with the fields that hold the
+/// types converted from its child elements, in document order.
+macro_rules! for_each_model_node_with_children {
+    ($m:ident) => {
+        $m!(
+            ObjectModelType {
+                model_identification,
+                service_utilization,
+                objects,
+                interactions,
+                dimensions,
+                time,
+                tags,
+                synchronizations,
+                transportations,
+                switches,
+                update_rates,
+                data_types,
+                notes,
+            },
+            ModelIdentificationType {
+                keywords,
+                poc,
+                references,
+            },
+            KeywordType {},
+            PocType {},
+            IdReferenceType {},
+            ServiceUtiliizationType {},
+            ObjectsType { root_object_class },
+            ObjectClassType {
+                attributes,
+                object_classes,
+            },
+            AttributeType {},
+            InteractionsType { interactions },
+            InteractionClassType {
+                parameters,
+                interaction_classes,
+            },
+            ParameterType {},
+            DimensionsType { dimensions },
+            DimensionType {},
+            TimeType {
+                time_stamp,
+                lookahead,
+            },
+            TimeTypeType {},
+            TagsType {
+                update_reflect_tag,
+                send_receive_tag,
+                delete_remove_tag,
+                divestiture_request_tag,
+                divestiture_completion_tag,
+                acquisition_request_tag,
+                request_update_tag,
+            },
+            TagType {},
+            SynchronizationsType {
+                synchronization_points,
+            },
+            SynchronizationPointType {},
+            TransportationsType { transportations },
+            TransportationType {},
+            SwitchesType {},
+            UpdateRatesType { update_rates },
+            UpdateRateType {},
+            DataTypesType {
+                basic_data_representations,
+                simple_data_types,
+                enumerated_data_types,
+                array_data_types,
+                fixed_record_data_types,
+                variand_record_data_types,
+            },
+            BasicDataRepresentationsType { basic_datas },
+            BasicDataType {},
+            SimpleDataTypesType { simple_datas },
+            SimpleDataType {},
+            EnumeratedDataTypesType { enumerated_datas },
+            EnumeratedDataType { enumerators },
+            EnumeratorType {},
+            ArrayDataTypesType { array_datas },
+            ArrayDataType {},
+            FixedRecordDataTypesType { fixed_record_datas },
+            FixedRecordDataType { fields },
+            FieldType {},
+            VariantRecordDataTypesType {
+                variant_record_datas,
+            },
+            VariantRecordDataType { alternatives },
+            AlternativeType {},
+            NotesType { notes },
+            NoteType {},
+        );
+    };
+}
+
+pub mod builder;
+#[cfg(feature = "catalog")]
+pub mod catalog;
+pub mod config;
+pub mod conformance;
+mod datatypes;
+pub mod dom;
+mod downgrade;
+mod error;
+pub mod fed;
+mod index;
+mod legacy;
+pub mod lint;
+mod navigate;
+pub mod netn;
+mod notes;
+mod pattern;
+pub mod schema;
+mod serialize;
+mod span;
+mod stream;
+mod toml;
+mod version;
+
+pub use datatypes::{standard_mim, DataTypeRef, DataTypeReference, DataTypeRegistry};
+pub use dom::WriteOptions;
+use dom::{Element, Position};
+pub use downgrade::{
+    downgrade_to_fed, downgrade_to_ieee1516_2000, write_fed, write_ieee1516_2000, Downgraded, Loss,
+    LossKind,
+};
+pub use error::{Error, FomError, FomErrorKind, FomErrors};
+pub use index::{
+    AttributeEntry, AttributeId, DataTypeId, DimensionId, FomIndex, InteractionClassEntry,
+    InteractionClassId, ObjectClassEntry, ObjectClassId, ParameterEntry, ParameterId,
+    TransportationId,
+};
+pub use legacy::SourceFormat;
+pub use navigate::{
+    BreadthFirst, ClassMember, ClassNode, ClassTree, DepthFirst, EffectiveAttribute,
+    EffectiveMember, EffectiveParameter, LookupError,
+};
+pub use notes::{Annotated, NoteReference};
+pub use serialize::{
+    serialize_object_model, to_xml_string, write_file, write_object_model,
+    write_object_model_with_options,
+};
+pub use span::{SourceSpan, Spanned};
+pub use version::{upgrade_to_2025, SchemaVersion};
+
+/// Tracks where in the document the conversion currently is, and the problems
+/// found so far, so that every problem in a document can be reported rather
+/// than just the first.
+struct Context {
+    path: Vec<String>,
+    errors: Vec<FomError>,
+    warnings: Vec<FomError>,
+    /// The file the document was read from, if known.
+    file: Option<Arc<Path>>,
+}
+
+impl Context {
+    fn new() -> Self {
+        Self {
+            path: Vec::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            file: None,
+        }
+    }
+
+    /// Return the span of the provided element in the source document, which
+    /// is unknown if the element was not read from a document.
+    fn span(&self, e: &Element) -> SourceSpan {
+        if e.position == Position::default() {
+            return SourceSpan::default();
+        }
+        SourceSpan {
+            file: self.file.clone(),
+            start: e.position,
+            end: e.end,
+        }
+    }
+
+    /// Descend into the provided element.
+    fn enter(&mut self, e: &Element) {
+        self.path.push(path_segment(e));
+    }
+
+    fn leave(&mut self) {
+        self.path.pop();
+    }
+
+    fn path(&self) -> String {
+        format!("/{}", self.path.join("/"))
+    }
+
+    /// Record a problem with the element currently being converted.
+    fn error(&mut self, kind: FomErrorKind, e: &Element, value: Option<String>) {
+        let path = self.path();
+        self.errors.push(FomError {
+            kind,
+            path,
+            value,
+            position: e.position,
+        });
+    }
+
+    /// Record a problem with the named attribute of the element currently
+    /// being converted.
+    fn attribute_error(
+        &mut self,
+        kind: FomErrorKind,
+        e: &Element,
+        attribute_name: &str,
+        value: &str,
+    ) {
+        let error = self.attribute_problem(kind, e, attribute_name, value);
+        self.errors.push(error);
+    }
+
+    /// Record a problem with the element currently being converted that does
+    /// not prevent it from being converted.
+    fn warning(&mut self, kind: FomErrorKind, e: &Element, value: Option<String>) {
+        let path = self.path();
+        self.warnings.push(FomError {
+            kind,
+            path,
+            value,
+            position: e.position,
+        });
+    }
+
+    /// Record a problem with the named attribute of the element currently
+    /// being converted that does not prevent it from being converted.
+    fn attribute_warning(
+        &mut self,
+        kind: FomErrorKind,
+        e: &Element,
+        attribute_name: &str,
+        value: &str,
+    ) {
+        let warning = self.attribute_problem(kind, e, attribute_name, value);
+        self.warnings.push(warning);
+    }
+
+    fn attribute_problem(
+        &self,
+        kind: FomErrorKind,
+        e: &Element,
+        attribute_name: &str,
+        value: &str,
+    ) -> FomError {
+        FomError {
+            kind,
+            path: format!("{}/@{}", self.path(), attribute_name),
+            value: Some(String::from(value)),
+            position: e.position,
+        }
+    }
+}
+
+/// Return the segment of a path, such as that of a `FomError`, that denotes
+/// the provided element. Elements that are identified by a name or label are
+/// shown with it, e.g. `objectClass[Platform]`.
+fn path_segment(e: &Element) -> String {
+    match get_text_of_child_element(e, "name").or_else(|| get_text_of_child_element(e, "label")) {
+        Some(name) if !name.is_empty() => format!("{}[{}]", e.name, name),
+        _ => e.name.clone(),
+    }
+}
+
+/// Conversion of an XML element into a part of the object model. Problems with
+/// the element are recorded in the context, and None is returned if the
+/// element could not be converted at all.
+trait FromElement: Sized {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self>;
+}
+
+/// Conversion of an XML attribute value into a part of the object model.
+trait FromAttribute: Sized {
+    fn from_attribute(value: &str) -> Result<Self, FomErrorKind>;
+}
+
+/// Convert the provided element within the context, keeping track of the path
+/// to it.
+fn convert_element<T: FromElement>(cx: &mut Context, e: &Element) -> Option<T> {
+    cx.enter(e);
+    let converted = T::from_element(e, cx);
+    cx.leave();
+    converted
+}
 
 /// Return the trimmed text content of the provided element, or the empty string
 /// if the current element has no text content.
 fn get_element_text(e: &Element) -> String {
     if let Some(text) = e.get_text() {
-        text.into_owned().trim().to_string()
+        text.trim().to_string()
     } else {
         String::from("")
     }
 }
 
+/// Return the named child elements of the provided root element. Only child
+/// elements in the same namespace as the root element are OMT elements;
+/// elements from other namespaces are extensions, which the OMT schema permits
+/// within almost every element, and are ignored.
+fn get_child_elements<'a>(
+    root: &'a Element,
+    child_element_name: &'a str,
+) -> impl Iterator<Item = &'a Element> + 'a {
+    root.child_elements()
+        .filter(move |e| e.name == child_element_name && e.namespace == root.namespace)
+}
+
+/// Return the first named child element of the provided root element. See
+/// `get_child_elements` for how namespaces are handled.
+fn get_child_element<'a>(root: &'a Element, child_element_name: &'a str) -> Option<&'a Element> {
+    get_child_elements(root, child_element_name).next()
+}
+
 /// Return the trimmed text content of the named child element of the provided
 /// root element. Return None if the named child element does not exist.
 fn get_text_of_child_element(root: &Element, child_element_name: &str) -> Option<String> {
-    root.get_child(child_element_name)
-        .map(|e| get_element_text(e))
+    get_child_element(root, child_element_name).map(get_element_text)
 }
 
 /// Return a copy of the attribute value associated with the attribute name for the
 /// supplied element. None if the attribute name does not exist.
 fn get_text_of_attribute(element: &Element, attribute_name: &str) -> Option<String> {
-    element.attributes.get(attribute_name).cloned()
+    element.get_attribute(attribute_name).map(String::from)
 }
 
 /// Return the trimmed text content of the named child element of the provided
-/// root element. Panic, with the provided panic message, if the name child
-/// element does not exist.
-fn get_text_of_child_element_or_panic(
+/// root element. If the named child element does not exist an error is
+/// recorded and the empty string returned; a document with errors never
+/// produces a model, so the empty string is not observable.
+fn get_required_text_of_child_element(
+    cx: &mut Context,
     root: &Element,
     child_element_name: &str,
-    panic_message: &'static str,
 ) -> String {
     match get_text_of_child_element(root, child_element_name) {
         Some(text) => text,
-        None => panic!("{}", panic_message),
+        None => {
+            cx.error(
+                FomErrorKind::MissingElement(String::from(child_element_name)),
+                root,
+                None,
+            );
+            String::from("")
+        }
     }
 }
 
 /// Return an instance of the generic type created from the named child
 /// element of the provided root element. Return None if the named child
-/// element does not exist.
-fn get_child_element_as_type<'a, T: From<&'a Element>>(
-    root: &'a Element,
-    child_element_name: &'a str,
+/// element does not exist, or could not be converted.
+fn get_child_element_as_type<T: FromElement>(
+    cx: &mut Context,
+    root: &Element,
+    child_element_name: &str,
 ) -> Option<T> {
-    root.get_child(child_element_name).map(|e| T::from(e))
+    get_child_element(root, child_element_name).and_then(|e| convert_element(cx, e))
 }
 
 /// Return an instance of the generic type created from the named attribute
 /// of the provided element. Return None if the named attribute does not
-/// exist
-fn get_attribute_as_type<'a, T: From<&'a String>>(
-    element: &'a Element,
-    attribute_name: &'a str,
+/// exist, or could not be converted.
+fn get_attribute_as_type<T: FromAttribute>(
+    cx: &mut Context,
+    element: &Element,
+    attribute_name: &str,
 ) -> Option<T> {
-    element
-        .attributes
-        .get(attribute_name)
-        .map(|attribute_value| T::from(attribute_value))
+    let value = element.get_attribute(attribute_name)?;
+    match T::from_attribute(value) {
+        Ok(converted) => Some(converted),
+        Err(kind) => {
+            cx.attribute_error(kind, element, attribute_name, value);
+            None
+        }
+    }
 }
 
 /// Return an instance of the generic type created from the named child
-/// element of the provided root element. Panic, with the provided panic
-/// message, if the named child element does not exist.
-fn get_child_element_as_type_or_panic<'a, T: From<&'a Element>>(
-    root: &'a Element,
-    child_element_name: &'a str,
-    panic_message: &'static str,
-) -> T {
-    match get_child_element_as_type(root, child_element_name) {
-        Some(val) => val,
-        None => panic!("{}", panic_message),
+/// element of the provided root element. If the named child element does not
+/// exist an error is recorded and None returned.
+fn get_required_child_element_as_type<T: FromElement>(
+    cx: &mut Context,
+    root: &Element,
+    child_element_name: &str,
+) -> Option<T> {
+    match get_child_element(root, child_element_name) {
+        Some(e) => convert_element(cx, e),
+        None => {
+            cx.error(
+                FomErrorKind::MissingElement(String::from(child_element_name)),
+                root,
+                None,
+            );
+            None
+        }
     }
 }
 
 /// Return the trimmed text of all named child elements of the provided root element.
 /// The returned vector will be empty if no such child elements exist.
 fn get_text_of_child_elements(root: &Element, child_element_name: &str) -> Vec<String> {
-    root.children
-        .iter()
-        .filter(|xml_node| match xml_node {
-            XMLNode::Element(e) => e.name == child_element_name,
-            _ => false,
-        })
-        .map(|xml_node| get_element_text(xml_node.as_element().unwrap()))
+    get_child_elements(root, child_element_name)
+        .map(get_element_text)
         .collect()
 }
 
@@ -104,30 +485,232 @@ fn get_text_of_child_elements_as_option(
 }
 
 /// Return instances of the generic type created from each of the named child
-/// elements of the provided root element. The returned vector will be empty
-/// if no such child elements exist.
-fn get_child_elements_as_type<'a, T: From<&'a Element>>(
-    root: &'a Element,
+/// elements of the provided root element. Child elements that could not be
+/// converted are left out. The returned vector will be empty if no such child
+/// elements exist.
+fn get_child_elements_as_type<T: FromElement>(
+    cx: &mut Context,
+    root: &Element,
     child_element_name: &str,
 ) -> Vec<T> {
-    root.children
-        .iter()
-        .filter(|xml_node| match xml_node {
-            XMLNode::Element(e) => e.name == child_element_name,
-            _ => false,
-        })
-        .map(|xml_node| T::from(xml_node.as_element().unwrap()))
+    get_child_elements(root, child_element_name)
+        .filter_map(|e| convert_element(cx, e))
         .collect()
 }
 
-fn get_child_elements_as_type_as_option<'a, T: From<&'a Element>>(
-    root: &'a Element,
+fn get_child_elements_as_type_as_option<T: FromElement>(
+    cx: &mut Context,
+    root: &Element,
     child_element_name: &str,
 ) -> Option<Vec<T>> {
-    map_vec_to_option(get_child_elements_as_type(root, child_element_name))
+    map_vec_to_option(get_child_elements_as_type(cx, root, child_element_name))
+}
+
+/// The `notes` and `idtag` attributes, which the OMT permits on every element,
+/// of an element within a part of the object model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationType {
+    /// The path to the annotated element from the element of the part of the
+    /// object model holding the annotation. The empty path is that element
+    /// itself; otherwise it is made up of element names separated by `/`,
+    /// with a one-based index when the element is not the first of its name,
+    /// e.g. `semantics`, `value[2]` or `dimensions/dimension[3]`. An
+    /// annotation whose path selects no element, such as one with the index
+    /// `[0]`, is not written.
+    pub path: String,
+    /// The labels of the notes that apply to the annotated element.
+    pub notes: Option<Vec<String>>,
+    pub idtag: Option<String>,
+}
+
+/// Return the annotations of the provided element and of its descendants.
+/// The descendants below the named child elements are left out, as those
+/// child elements are converted into parts of the object model that hold
+/// their own annotations.
+fn get_annotations(e: &Element, annotated_child_elements: &[&str]) -> Option<Vec<AnnotationType>> {
+    let mut annotations = Vec::new();
+    collect_annotations(e, String::new(), annotated_child_elements, &mut annotations);
+    map_vec_to_option(annotations)
+}
+
+fn collect_annotations(
+    e: &Element,
+    path: String,
+    annotated_child_elements: &[&str],
+    annotations: &mut Vec<AnnotationType>,
+) {
+    let notes = get_text_of_attribute(e, "notes")
+        .map(|notes| notes.split_whitespace().map(String::from).collect());
+    let idtag = get_text_of_attribute(e, "idtag");
+
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    let children: Vec<(String, &Element)> = e
+        .child_elements()
+        .filter(|child| child.namespace == e.namespace)
+        .filter_map(|child| {
+            let index = match counts.iter_mut().find(|(name, _)| *name == child.name) {
+                Some((_, count)) => {
+                    *count += 1;
+                    *count
+                }
+                None => {
+                    counts.push((&child.name, 1));
+                    1
+                }
+            };
+            if annotated_child_elements.contains(&child.name.as_str()) {
+                return None;
+            }
+            let segment = if index == 1 {
+                child.name.clone()
+            } else {
+                format!("{}[{}]", child.name, index)
+            };
+            if path.is_empty() {
+                Some((segment, child))
+            } else {
+                Some((format!("{}/{}", path, segment), child))
+            }
+        })
+        .collect();
+
+    if notes.is_some() || idtag.is_some() {
+        annotations.push(AnnotationType { path, notes, idtag });
+    }
+    for (child_path, child) in children {
+        collect_annotations(child, child_path, &[], annotations);
+    }
+}
+
+/// The text of an element of an object model document, along with the value
+/// of the type that the OMT expects it to hold. The text is kept so that the
+/// document can be written back as it was read, e.g. `60.0` rather than `60`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedValue<T> {
+    Valid {
+        value: T,
+        text: String,
+    },
+    /// Text that does not hold a value of the expected type. It is reported as
+    /// a warning when the document is read, so that the rest of the object
+    /// model can still be used.
+    Invalid(String),
 }
 
+impl<T> TypedValue<T> {
+    /// Return the value, unless the text did not hold one.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            TypedValue::Valid { value, .. } => Some(value),
+            TypedValue::Invalid(_) => None,
+        }
+    }
+
+    /// Return the text of the element the value was read from.
+    pub fn text(&self) -> &str {
+        match self {
+            TypedValue::Valid { text, .. } | TypedValue::Invalid(text) => text,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, TypedValue::Valid { .. })
+    }
+}
+
+impl<T: std::fmt::Display> TypedValue<T> {
+    /// Create a valid value, whose text is the canonical form of the value.
+    pub fn new(value: T) -> Self {
+        let text = value.to_string();
+        TypedValue::Valid { value, text }
+    }
+}
+
+impl<T: std::fmt::Display> From<T> for TypedValue<T> {
+    fn from(value: T) -> Self {
+        TypedValue::new(value)
+    }
+}
+
+/// Interpretation of the text of an element as a value of the type that the
+/// OMT expects it to hold.
+trait FromText: Sized {
+    fn from_text(text: &str) -> Result<Self, FomErrorKind>;
+}
+
+impl<T: FromText> FromElement for TypedValue<T> {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        let text = get_element_text(e);
+        match T::from_text(&text) {
+            Ok(value) => Some(TypedValue::Valid { value, text }),
+            Err(kind) => {
+                cx.warning(kind, e, Some(text.clone()));
+                Some(TypedValue::Invalid(text))
+            }
+        }
+    }
+}
+
+impl FromText for f64 {
+    /// Accept the lexical form of xs:decimal, i.e. no exponent, infinity or NaN.
+    fn from_text(text: &str) -> Result<Self, FomErrorKind> {
+        let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let is_decimal = !(integer.is_empty() && fraction.is_empty())
+            && integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit());
+        match text.parse() {
+            Ok(value) if is_decimal => Ok(value),
+            _ => Err(FomErrorKind::InvalidValue),
+        }
+    }
+}
+
+impl FromText for u32 {
+    fn from_text(text: &str) -> Result<Self, FomErrorKind> {
+        text.parse().map_err(|_| FomErrorKind::InvalidValue)
+    }
+}
+
+impl FromText for i128 {
+    fn from_text(text: &str) -> Result<Self, FomErrorKind> {
+        text.parse().map_err(|_| FomErrorKind::InvalidValue)
+    }
+}
+
+/// The namespace of IEEE 1516.2-2010 object model documents.
+pub const IEEE1516_2010_NAMESPACE: &str = "http://standards.ieee.org/IEEE1516-2010";
+
+/// The namespace of IEEE 1516.2-2010 object model documents as published by
+/// SISO, and used by the HLA standard MIM.
+pub const SISO_IEEE1516_2010_NAMESPACE: &str = "http://www.sisostds.org/schemas/IEEE1516-2010";
+
+/// The namespace of IEEE 1516.2-2025 (HLA 4) object model documents.
+pub const IEEE1516_2025_NAMESPACE: &str = "http://standards.ieee.org/IEEE1516-2025";
+
+/// The namespace of IEEE 1516.2-2025 object model documents as published by
+/// SISO.
+pub const SISO_IEEE1516_2025_NAMESPACE: &str = "http://www.sisostds.org/schemas/IEEE1516-2025";
+
+/// The namespaces in which object model documents are understood. See
+/// `SchemaVersion` for the version of the OMT each one identifies.
+pub const OMT_NAMESPACES: [&str; 4] = [
+    IEEE1516_2010_NAMESPACE,
+    SISO_IEEE1516_2010_NAMESPACE,
+    IEEE1516_2025_NAMESPACE,
+    SISO_IEEE1516_2025_NAMESPACE,
+];
+
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectModelType {
+    /// The namespace of the root element of the document, if it had one.
+    pub namespace: Option<String>,
+    /// The `xsi:schemaLocation` of the document, as namespace and location pairs.
+    pub schema_location: Option<Vec<SchemaLocationType>>,
     pub model_identification: Option<ModelIdentificationType>,
     pub service_utilization: Option<ServiceUtiliizationType>,
     pub objects: Option<ObjectsType>,
@@ -141,28 +724,88 @@ pub struct ObjectModelType {
     pub update_rates: Option<UpdateRatesType>,
     pub data_types: Option<DataTypesType>,
     pub notes: Option<NotesType>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
+}
+
+impl FromElement for ObjectModelType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            namespace: e.namespace.clone(),
+            schema_location: get_schema_location(cx, e),
+            model_identification: get_child_element_as_type(cx, e, "modelIdentification"),
+            service_utilization: get_child_element_as_type(cx, e, "serviceUtilization"),
+            objects: get_child_element_as_type(cx, e, "objects"),
+            interactions: get_child_element_as_type(cx, e, "interactions"),
+            dimensions: get_child_element_as_type(cx, e, "dimensions"),
+            time: get_child_element_as_type(cx, e, "time"),
+            tags: get_child_element_as_type(cx, e, "tags"),
+            synchronizations: get_child_element_as_type(cx, e, "synchronizations"),
+            transportations: get_child_element_as_type(cx, e, "transportations"),
+            switches: get_child_element_as_type(cx, e, "switches"),
+            update_rates: get_child_element_as_type(cx, e, "updateRates"),
+            data_types: get_child_element_as_type(cx, e, "dataTypes"),
+            notes: get_child_element_as_type(cx, e, "notes"),
+            annotations: get_annotations(
+                e,
+                &[
+                    "modelIdentification",
+                    "serviceUtilization",
+                    "objects",
+                    "interactions",
+                    "dimensions",
+                    "time",
+                    "tags",
+                    "synchronizations",
+                    "transportations",
+                    "switches",
+                    "updateRates",
+                    "dataTypes",
+                    "notes",
+                ],
+            ),
+            span: cx.span(e),
+        })
+    }
 }
 
-impl From<&Element> for ObjectModelType {
-    fn from(e: &Element) -> Self {
-        Self {
-            model_identification: get_child_element_as_type(e, "modelIdentification"),
-            service_utilization: get_child_element_as_type(e, "serviceUtilization"),
-            objects: get_child_element_as_type(e, "objects"),
-            interactions: get_child_element_as_type(e, "interactions"),
-            dimensions: get_child_element_as_type(e, "dimensions"),
-            time: get_child_element_as_type(e, "time"),
-            tags: get_child_element_as_type(e, "tags"),
-            synchronizations: get_child_element_as_type(e, "synchronizations"),
-            transportations: get_child_element_as_type(e, "transportations"),
-            switches: get_child_element_as_type(e, "switches"),
-            update_rates: get_child_element_as_type(e, "updateRates"),
-            data_types: get_child_element_as_type(e, "dataTypes"),
-            notes: get_child_element_as_type(e, "notes"),
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaLocationType {
+    pub namespace: String,
+    pub location: String,
+}
+
+/// Return the namespace and location pairs of the `xsi:schemaLocation`
+/// attribute of the provided element. A warning is recorded if the attribute
+/// is not made up of pairs, or has no location for the namespace of the
+/// element.
+fn get_schema_location(cx: &mut Context, e: &Element) -> Option<Vec<SchemaLocationType>> {
+    let value = e
+        .attributes
+        .iter()
+        .find(|a| a.name == "schemaLocation" && a.namespace.as_deref() == Some(XSI_NAMESPACE))
+        .map(|a| a.value.as_str())?;
+
+    let tokens: Vec<&str> = value.split_whitespace().collect();
+    if !tokens.len().is_multiple_of(2) {
+        cx.attribute_warning(FomErrorKind::InvalidValue, e, "schemaLocation", value);
+    }
+    let schema_location: Vec<SchemaLocationType> = tokens
+        .chunks_exact(2)
+        .map(|pair| SchemaLocationType {
+            namespace: String::from(pair[0]),
+            location: String::from(pair[1]),
+        })
+        .collect();
+    if let Some(namespace) = &e.namespace {
+        if !schema_location.iter().any(|l| &l.namespace == namespace) {
+            cx.attribute_warning(FomErrorKind::InvalidValue, e, "schemaLocation", value);
         }
     }
+    Some(schema_location)
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ModelIdentificationType {
     pub name: Option<String>,
     pub model_type: Option<ModelType>,
@@ -180,48 +823,54 @@ pub struct ModelIdentificationType {
     pub references: Option<Vec<IdReferenceType>>,
     pub other: Option<String>,
     pub glyph: Option<GlyphType>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for ModelIdentificationType {
-    fn from(e: &Element) -> Self {
-        Self {
+impl FromElement for ModelIdentificationType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
             name: get_text_of_child_element(e, "name"),
-            model_type: get_child_element_as_type(e, "type"),
+            model_type: get_child_element_as_type(cx, e, "type"),
             version: get_text_of_child_element(e, "version"),
             modification_date: get_text_of_child_element(e, "modificationDate"),
-            security_classification: get_child_element_as_type(e, "securityClassification"),
+            security_classification: get_child_element_as_type(cx, e, "securityClassification"),
             release_restriction: get_text_of_child_elements_as_option(e, "releaseRestriction"),
             purpose: get_text_of_child_element(e, "purpose"),
-            application_domain: get_child_element_as_type(e, "applicationDomain"),
+            application_domain: get_child_element_as_type(cx, e, "applicationDomain"),
             description: get_text_of_child_element(e, "description"),
             use_limitation: get_text_of_child_element(e, "useLimitation"),
             use_history: get_text_of_child_elements_as_option(e, "useHistory"),
-            keywords: get_child_elements_as_type_as_option(e, "keyword"),
-            poc: get_child_elements_as_type_as_option(e, "poc"),
-            references: get_child_elements_as_type_as_option(e, "reference"),
+            keywords: get_child_elements_as_type_as_option(cx, e, "keyword"),
+            poc: get_child_elements_as_type_as_option(cx, e, "poc"),
+            references: get_child_elements_as_type_as_option(cx, e, "reference"),
             other: get_text_of_child_element(e, "other"),
-            glyph: get_child_element_as_type(e, "glyph"),
-        }
+            glyph: get_child_element_as_type(cx, e, "glyph"),
+            annotations: get_annotations(e, &["keyword", "poc", "reference"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum ModelType {
     FOM,
     SOM,
     Other(String),
 }
 
-impl From<&Element> for ModelType {
-    fn from(e: &Element) -> Self {
+impl FromElement for ModelType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "FOM" => ModelType::FOM,
-            "SOM" => ModelType::SOM,
-            _ => ModelType::Other(text),
+            "FOM" => Some(ModelType::FOM),
+            "SOM" => Some(ModelType::SOM),
+            _ => Some(ModelType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum SecurityClassificationType {
     Unclassified,
     Confidential,
@@ -230,19 +879,20 @@ pub enum SecurityClassificationType {
     Other(String),
 }
 
-impl From<&Element> for SecurityClassificationType {
-    fn from(e: &Element) -> Self {
+impl FromElement for SecurityClassificationType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Unclassified" => SecurityClassificationType::Unclassified,
-            "Confidential" => SecurityClassificationType::Confidential,
-            "Secret" => SecurityClassificationType::Secret,
-            "Top Secret" => SecurityClassificationType::TopSecret,
-            _ => SecurityClassificationType::Other(text),
+            "Unclassified" => Some(SecurityClassificationType::Unclassified),
+            "Confidential" => Some(SecurityClassificationType::Confidential),
+            "Secret" => Some(SecurityClassificationType::Secret),
+            "Top Secret" => Some(SecurityClassificationType::TopSecret),
+            _ => Some(SecurityClassificationType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum ApplicationDomainType {
     Analysis,
     Training,
@@ -252,54 +902,65 @@ pub enum ApplicationDomainType {
     Other(String),
 }
 
-impl From<&Element> for ApplicationDomainType {
-    fn from(e: &Element) -> Self {
+impl FromElement for ApplicationDomainType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Analysis" => ApplicationDomainType::Analysis,
-            "Training" => ApplicationDomainType::Training,
-            "Test and Evaluation" => ApplicationDomainType::TestAndEvaluation,
-            "Engineering" => ApplicationDomainType::Engineering,
-            "Acquisition" => ApplicationDomainType::Acquisition,
-            _ => ApplicationDomainType::Other(text),
+            "Analysis" => Some(ApplicationDomainType::Analysis),
+            "Training" => Some(ApplicationDomainType::Training),
+            "Test and Evaluation" => Some(ApplicationDomainType::TestAndEvaluation),
+            "Engineering" => Some(ApplicationDomainType::Engineering),
+            "Acquisition" => Some(ApplicationDomainType::Acquisition),
+            _ => Some(ApplicationDomainType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct KeywordType {
     pub taxonomy: Option<String>,
     pub keyword_value: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for KeywordType {
-    fn from(e: &Element) -> Self {
-        Self {
+impl FromElement for KeywordType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
             taxonomy: get_text_of_child_element(e, "taxonomy"),
             keyword_value: get_text_of_child_element(e, "keywordValue"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct PocType {
     pub poc_type: Option<PocTypeType>,
     pub poc_name: Option<String>,
     pub poc_org: Option<String>,
     pub poc_telephones: Option<Vec<String>>,
     pub poc_emails: Option<Vec<String>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for PocType {
-    fn from(e: &Element) -> Self {
-        Self {
-            poc_type: get_child_element_as_type(e, "pocType"),
+impl FromElement for PocType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            poc_type: get_child_element_as_type(cx, e, "pocType"),
             poc_name: get_text_of_child_element(e, "pocName"),
             poc_org: get_text_of_child_element(e, "pocOrg"),
             poc_telephones: get_text_of_child_elements_as_option(e, "pocTelephone"),
             poc_emails: get_text_of_child_elements_as_option(e, "pocEmail"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum PocTypeType {
     PrimaryAuthor,
     Contributor,
@@ -310,36 +971,45 @@ pub enum PocTypeType {
     Other(String),
 }
 
-impl From<&Element> for PocTypeType {
-    fn from(e: &Element) -> Self {
+impl FromElement for PocTypeType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Primary author" => PocTypeType::PrimaryAuthor,
-            "Contributor" => PocTypeType::Contributor,
-            "Proponent" => PocTypeType::Proponent,
-            "Sponsor" => PocTypeType::Sponsor,
-            "Release authority" => PocTypeType::ReleaseAuthority,
-            "Technical POC" => PocTypeType::TechnicalPoc,
-            _ => PocTypeType::Other(text),
+            "Primary author" => Some(PocTypeType::PrimaryAuthor),
+            "Contributor" => Some(PocTypeType::Contributor),
+            "Proponent" => Some(PocTypeType::Proponent),
+            "Sponsor" => Some(PocTypeType::Sponsor),
+            "Release authority" => Some(PocTypeType::ReleaseAuthority),
+            "Technical POC" => Some(PocTypeType::TechnicalPoc),
+            _ => Some(PocTypeType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct IdReferenceType {
     pub reference_type: Option<String>,
     pub identification: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for IdReferenceType {
-    fn from(e: &Element) -> Self {
-        Self {
+impl FromElement for IdReferenceType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
             reference_type: get_text_of_child_element(e, "type"),
             identification: get_text_of_child_element(e, "identification"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct GlyphType {
+    /// The base64 encoded image, if it is included in the document rather
+    /// than referenced by `href`.
+    pub image: Option<String>,
     pub href: Option<String>,
     pub glyph_type: Option<GlyphTypeType>,
     pub height: Option<String>,
@@ -347,18 +1017,20 @@ pub struct GlyphType {
     pub alt: Option<String>,
 }
 
-impl From<&Element> for GlyphType {
-    fn from(e: &Element) -> Self {
-        Self {
+impl FromElement for GlyphType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            image: Some(get_element_text(e)).filter(|image| !image.is_empty()),
             href: get_text_of_attribute(e, "href"),
-            glyph_type: get_attribute_as_type(e, "type"),
+            glyph_type: get_attribute_as_type(cx, e, "type"),
             height: get_text_of_attribute(e, "height"),
             width: get_text_of_attribute(e, "width"),
             alt: get_text_of_attribute(e, "alt"),
-        }
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum GlyphTypeType {
     Bitmap,
     Jpg,
@@ -368,86 +1040,437 @@ pub enum GlyphTypeType {
     Other(String),
 }
 
-impl From<&String> for GlyphTypeType {
-    fn from(attribute: &String) -> Self {
-        match attribute.to_uppercase().as_str() {
+/// The OMT allows any image type in addition to those enumerated, so types
+/// are matched exactly; `png` is `Other("png")` rather than `Png`, keeping the
+/// document's spelling.
+impl FromAttribute for GlyphTypeType {
+    fn from_attribute(value: &str) -> Result<Self, FomErrorKind> {
+        Ok(match value {
             "BITMAP" => GlyphTypeType::Bitmap,
             "JPG" => GlyphTypeType::Jpg,
             "GIF" => GlyphTypeType::Gif,
             "PNG" => GlyphTypeType::Png,
             "TIFF" => GlyphTypeType::Tiff,
-            _ => GlyphTypeType::Other(attribute.clone()),
-        }
+            _ => GlyphTypeType::Other(String::from(value)),
+        })
     }
 }
 
-pub struct ServiceUtiliizationType {
-    pub connect: Option<ServiceInfoType>,
-    pub disconnect: Option<ServiceInfoType>,
-    // ... and the rest
-}
+/// Define the HLA services from the table that follows it, which gives for each
+/// service its field of `ServiceUtiliizationType`, the name of its OMT element,
+/// the section of IEEE 1516.1-2010 that specifies it and whether it is a
+/// callback, in the order of IEEE 1516.1-2010.
+macro_rules! services {
+    (
+        $(
+            $field:ident: $element:literal, $clause:literal, $subclause:literal, $is_callback:literal;
+        )*
+    ) => {
+        /// The HLA services, by the name of their OMT element, with the section
+        /// of IEEE 1516.1-2010 that specifies each and whether it is a callback.
+        /// The OMT fixes the `section` and `isCallback` attributes of each
+        /// service to these.
+        const SERVICES: &[(&str, u32, u32, bool)] = &[
+            $(($element, $clause, $subclause, $is_callback),)*
+        ];
+
+        /// The services of the HLA interface specification that a federate, or
+        /// federation, uses.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct ServiceUtiliizationType {
+            $(pub $field: Option<ServiceInfoType>,)*
+            pub annotations: Option<Vec<AnnotationType>>,
+            pub span: SourceSpan,
+        }
 
-impl From<&Element> for ServiceUtiliizationType {
-    fn from(e: &Element) -> Self {
-        Self {
-            connect: get_child_element_as_type(e, "connect"),
-            disconnect: get_child_element_as_type(e, "disconnect"),
+        impl FromElement for ServiceUtiliizationType {
+            fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+                Some(Self {
+                    $($field: get_child_element_as_type(cx, e, $element),)*
+                    annotations: get_annotations(e, &[]),
+                    span: cx.span(e),
+                })
+            }
         }
+
+        impl ServiceUtiliizationType {
+            /// Return every service, by the name of its OMT element and in the
+            /// order of IEEE 1516.1-2010, along with its information if the
+            /// object model has any.
+            fn all_services(&self) -> Vec<(&'static str, &Option<ServiceInfoType>)> {
+                vec![$(($element, &self.$field),)*]
+            }
+        }
+    };
+}
+
+services! {
+    connect: "connect", 4, 2, false;
+    disconnect: "disconnect", 4, 3, false;
+    connection_lost: "connectionLost", 4, 4, true;
+    create_federation_execution: "createFederationExecution", 4, 5, false;
+    destroy_federation_execution: "destroyFederationExecution", 4, 6, false;
+    list_federation_executions: "listFederationExecutions", 4, 7, false;
+    report_federation_executions: "reportFederationExecutions", 4, 8, true;
+    join_federation_execution: "joinFederationExecution", 4, 9, false;
+    resign_federation_execution: "resignFederationExecution", 4, 10, false;
+    register_federation_synchronization_point:
+        "registerFederationSynchronizationPoint", 4, 11, false;
+    confirm_synchronization_point_registration:
+        "confirmSynchronizationPointRegistration", 4, 12, true;
+    announce_synchronization_point: "announceSynchronizationPoint", 4, 13, true;
+    synchronization_point_achieved: "synchronizationPointAchieved", 4, 14, false;
+    federation_synchronized: "federationSynchronized", 4, 15, true;
+    request_federation_save: "requestFederationSave", 4, 16, false;
+    initiate_federate_save: "initiateFederateSave", 4, 17, true;
+    federate_save_begun: "federateSaveBegun", 4, 18, false;
+    federate_save_complete: "federateSaveComplete", 4, 19, false;
+    federation_saved: "federationSaved", 4, 20, true;
+    abort_federation_save: "abortFederationSave", 4, 21, false;
+    query_federation_save_status: "queryFederationSaveStatus", 4, 22, false;
+    federation_save_status_response: "federationSaveStatusResponse", 4, 23, true;
+    request_federation_restore: "requestFederationRestore", 4, 24, false;
+    confirm_federation_restoration_request: "confirmFederationRestorationRequest", 4, 25, true;
+    federation_restore_begun: "federationRestoreBegun", 4, 26, true;
+    initiate_federate_restore: "initiateFederateRestore", 4, 27, true;
+    federate_restore_complete: "federateRestoreComplete", 4, 28, false;
+    federation_restored: "federationRestored", 4, 29, true;
+    abort_federation_restore: "abortFederationRestore", 4, 30, false;
+    query_federation_restore_status: "queryFederationRestoreStatus", 4, 31, false;
+    federation_restore_status_response: "federationRestoreStatusResponse", 4, 32, true;
+    publish_object_class_attributes: "publishObjectClassAttributes", 5, 2, false;
+    unpublish_object_class_attributes: "unpublishObjectClassAttributes", 5, 3, false;
+    publish_interaction_class: "publishInteractionClass", 5, 4, false;
+    unpublish_interaction_class: "unpublishInteractionClass", 5, 5, false;
+    subscribe_object_class_attributes: "subscribeObjectClassAttributes", 5, 6, false;
+    unsubscribe_object_class_attributes: "unsubscribeObjectClassAttributes", 5, 7, false;
+    subscribe_interaction_class: "subscribeInteractionClass", 5, 8, false;
+    unsubscribe_interaction_class: "unsubscribeInteractionClass", 5, 9, false;
+    start_registration_for_object_class: "startRegistrationForObjectClass", 5, 10, true;
+    stop_registration_for_object_class: "stopRegistrationForObjectClass", 5, 11, true;
+    turn_interactions_on: "turnInteractionsOn", 5, 12, true;
+    turn_interactions_off: "turnInteractionsOff", 5, 13, true;
+    reserve_object_instance_name: "reserveObjectInstanceName", 6, 2, false;
+    object_instance_name_reserved: "objectInstanceNameReserved", 6, 3, true;
+    release_object_instance_name: "releaseObjectInstanceName", 6, 4, false;
+    reserve_multiple_object_instance_names: "reserveMultipleObjectInstanceNames", 6, 5, false;
+    multiple_object_instance_names_reserved: "multipleObjectInstanceNamesReserved", 6, 6, true;
+    release_multiple_object_instance_names: "releaseMultipleObjectInstanceNames", 6, 7, false;
+    register_object_instance: "registerObjectInstance", 6, 8, false;
+    discover_object_instance: "discoverObjectInstance", 6, 9, true;
+    update_attribute_values: "updateAttributeValues", 6, 10, false;
+    reflect_attribute_values: "reflectAttributeValues", 6, 11, true;
+    send_interaction: "sendInteraction", 6, 12, false;
+    receive_interaction: "receiveInteraction", 6, 13, true;
+    delete_object_instance: "deleteObjectInstance", 6, 14, false;
+    remove_object_instance: "removeobjectinstance", 6, 15, true;
+    local_delete_object_instance: "localDeleteObjectInstance", 6, 16, false;
+    attributes_in_scope: "attributesInScope", 6, 17, true;
+    attributes_out_of_scope: "attributesOutOfScope", 6, 18, true;
+    request_attribute_value_update: "requestAttributeValueUpdate", 6, 19, false;
+    provide_attribute_value_update: "provideAttributeValueUpdate", 6, 20, true;
+    turn_updates_on_for_object_instance: "turnUpdatesOnForObjectInstance", 6, 21, true;
+    turn_updates_off_for_object_instance: "turnUpdatesOffForObjectInstance", 6, 22, true;
+    request_attribute_transportation_type_change:
+        "requestAttributeTransportationTypeChange", 6, 23, false;
+    confirm_attribute_transportation_type_change:
+        "confirmAttributeTransportationTypeChange", 6, 24, true;
+    query_attribute_transportation_type: "queryAttributeTransportationType", 6, 25, false;
+    report_attribute_transportation_type: "reportAttributeTransportationType", 6, 26, true;
+    request_interaction_transportation_type_change:
+        "requestInteractionTransportationTypeChange", 6, 27, false;
+    confirm_interaction_transportation_type_change:
+        "confirmInteractionTransportationTypeChange", 6, 28, true;
+    query_interaction_transportation_type: "queryInteractionTransportationType", 6, 29, false;
+    report_interaction_transportation_type: "reportInteractionTransportationType", 6, 30, true;
+    unconditional_attribute_ownership_divestiture:
+        "unconditionalAttributeOwnershipDivestiture", 7, 2, false;
+    negotiated_attribute_ownership_divestiture:
+        "negotiatedAttributeOwnershipDivestiture", 7, 3, false;
+    request_attribute_ownership_assumption: "requestAttributeOwnershipAssumption", 7, 4, true;
+    request_divestiture_confirmation: "requestDivestitureConfirmation", 7, 5, true;
+    confirm_divestiture: "confirmDivestiture", 7, 6, false;
+    attribute_ownership_acquisition_notification:
+        "attributeOwnershipAcquisitionNotification", 7, 7, true;
+    attribute_ownership_acquisition: "attributeOwnershipAcquisition", 7, 8, false;
+    attribute_ownership_acquisition_if_available:
+        "attributeOwnershipAcquisitionIfAvailable", 7, 9, false;
+    attribute_ownership_unavailable: "attributeOwnershipUnavailable", 7, 10, true;
+    request_attribute_ownership_release: "requestAttributeOwnershipRelease", 7, 11, true;
+    attribute_ownership_release_denied: "attributeOwnershipReleaseDenied", 7, 12, false;
+    attribute_ownership_divestiture_if_wanted:
+        "attributeOwnershipDivestitureIfWanted", 7, 13, false;
+    cancel_negotiated_attribute_ownership_divestiture:
+        "cancelNegotiatedAttributeOwnershipDivestiture", 7, 14, false;
+    cancel_attribute_ownership_acquisition: "cancelAttributeOwnershipAcquisition", 7, 15, false;
+    confirm_attribute_ownership_acquisition_cancellation:
+        "confirmAttributeOwnershipAcquisitionCancellation", 7, 16, true;
+    query_attribute_ownership: "queryAttributeOwnership", 7, 17, false;
+    inform_attribute_ownership: "informAttributeOwnership", 7, 18, true;
+    is_attribute_owned_by_federate: "isAttributeOwnedByFederate", 7, 19, false;
+    enable_time_regulation: "enableTimeRegulation", 8, 2, false;
+    time_regulation_enabled: "timeRegulationEnabled", 8, 3, true;
+    disable_time_regulation: "disableTimeRegulation", 8, 4, false;
+    enable_time_constrained: "enableTimeConstrained", 8, 5, false;
+    time_constrained_enabled: "timeConstrainedEnabled", 8, 6, true;
+    disable_time_constrained: "disableTimeConstrained", 8, 7, false;
+    time_advance_request: "timeAdvanceRequest", 8, 8, false;
+    time_advance_request_available: "timeAdvanceRequestAvailable", 8, 9, false;
+    next_message_request: "nextMessageRequest", 8, 10, false;
+    next_message_request_available: "nextMessageRequestAvailable", 8, 11, false;
+    flush_queue_request: "flushQueueRequest", 8, 12, false;
+    time_advance_grant: "timeAdvanceGrant", 8, 13, true;
+    enable_asynchronous_delivery: "enableAsynchronousDelivery", 8, 14, false;
+    disable_asynchronous_delivery: "disableAsynchronousDelivery", 8, 15, false;
+    query_galt: "queryGALT", 8, 16, false;
+    query_logical_time: "queryLogicalTime", 8, 17, false;
+    query_lits: "queryLITS", 8, 18, false;
+    modify_lookahead: "modifyLookahead", 8, 19, false;
+    query_lookahead: "queryLookahead", 8, 20, false;
+    retract: "retract", 8, 21, false;
+    request_retraction: "requestRetraction", 8, 22, true;
+    change_attribute_order_type: "changeAttributeOrderType", 8, 23, false;
+    change_interaction_order_type: "changeInteractionOrderType", 8, 24, false;
+    create_region: "createRegion", 9, 2, false;
+    commit_region_modifications: "commitRegionModifications", 9, 3, false;
+    delete_region: "deleteRegion", 9, 4, false;
+    register_object_instance_with_regions: "registerObjectInstanceWithRegions", 9, 5, false;
+    associate_regions_for_updates: "associateRegionsForUpdates", 9, 6, false;
+    unassociate_regions_for_updates: "unassociateRegionsForUpdates", 9, 7, false;
+    subscribe_object_class_attributes_with_regions:
+        "subscribeObjectClassAttributesWithRegions", 9, 8, false;
+    unsubscribe_object_class_attributes_with_regions:
+        "unsubscribeObjectClassAttributesWithRegions", 9, 9, false;
+    subscribe_interaction_class_with_regions: "subscribeInteractionClassWithRegions", 9, 10, false;
+    unsubscribe_interaction_class_with_regions:
+        "unsubscribeInteractionClassWithRegions", 9, 11, false;
+    send_interaction_with_regions: "sendInteractionWithRegions", 9, 12, false;
+    request_attribute_value_update_with_regions:
+        "requestAttributeValueUpdateWithRegions", 9, 13, false;
+    get_automatic_resign_directive: "getAutomaticResignDirective", 10, 2, false;
+    set_automatic_resign_directive: "setAutomaticResignDirective", 10, 3, false;
+    get_federate_handle: "getFederateHandle", 10, 4, false;
+    get_federate_name: "getFederateName", 10, 5, false;
+    get_object_class_handle: "getObjectClassHandle", 10, 6, false;
+    get_object_class_name: "getObjectClassName", 10, 7, false;
+    get_known_object_class_handle: "getKnownObjectClassHandle", 10, 8, false;
+    get_object_instance_handle: "getObjectInstanceHandle", 10, 9, false;
+    get_object_instance_name: "getObjectInstanceName", 10, 10, false;
+    get_attribute_handle: "getAttributeHandle", 10, 11, false;
+    get_attribute_name: "getAttributeName", 10, 12, false;
+    get_update_rate_value: "getUpdateRateValue", 10, 13, false;
+    get_update_rate_value_for_attribute: "getUpdateRateValueForAttribute", 10, 14, false;
+    get_interaction_class_handle: "getInteractionClassHandle", 10, 15, false;
+    get_interaction_class_name: "getInteractionClassName", 10, 16, false;
+    get_parameter_handle: "getParameterHandle", 10, 17, false;
+    get_parameter_name: "getParameterName", 10, 18, false;
+    get_order_type: "getOrderType", 10, 19, false;
+    get_order_name: "getOrderName", 10, 20, false;
+    get_transportation_type_handle: "getTransportationTypeHandle", 10, 21, false;
+    get_transportation_type_name: "getTransportationTypeName", 10, 22, false;
+    get_available_dimensions_for_class_attribute:
+        "getAvailableDimensionsForClassAttribute", 10, 23, false;
+    get_available_dimensions_for_interaction_class:
+        "getAvailableDimensionsForInteractionClass", 10, 24, false;
+    get_dimension_handle: "getDimensionHandle", 10, 25, false;
+    get_dimension_name: "getDimensionName", 10, 26, false;
+    get_dimension_upper_bound: "getDimensionUpperBound", 10, 27, false;
+    get_dimension_handle_set: "getDimensionHandleSet", 10, 28, false;
+    get_range_bounds: "getRangeBounds", 10, 29, false;
+    set_range_bounds: "setRangeBounds", 10, 30, false;
+    normalize_federate_handle: "normalizeFederateHandle", 10, 31, false;
+    normalize_service_group: "normalizeServiceGroup", 10, 32, false;
+    enable_object_class_relevance_advisory_switch:
+        "enableObjectClassRelevanceAdvisorySwitch", 10, 33, false;
+    disable_object_class_relevance_advisory_switch:
+        "disableObjectClassRelevanceAdvisorySwitch", 10, 34, false;
+    enable_attribute_relevance_advisory_switch:
+        "enableAttributeRelevanceAdvisorySwitch", 10, 35, false;
+    disable_attribute_relevance_advisory_switch:
+        "disableAttributeRelevanceAdvisorySwitch", 10, 36, false;
+    enable_attribute_scope_advisory_switch: "enableAttributeScopeAdvisorySwitch", 10, 37, false;
+    disable_attribute_scope_advisory_switch: "disableAttributeScopeAdvisorySwitch", 10, 38, false;
+    enable_interaction_relevance_advisory_switch:
+        "enableInteractionRelevanceAdvisorySwitch", 10, 39, false;
+    disable_interaction_relevance_advisory_switch:
+        "disableInteractionRelevanceAdvisorySwitch", 10, 40, false;
+    evoke_callback: "evokeCallback", 10, 41, false;
+    evoke_multiple_callbacks: "evokeMultipleCallbacks", 10, 42, false;
+    enable_callbacks: "enableCallbacks", 10, 43, false;
+    disable_callbacks: "disableCallbacks", 10, 44, false;
+}
+
+impl ServiceUtiliizationType {
+    /// Return the services that the object model has information about, by
+    /// the name of their OMT element and in the order of IEEE 1516.1-2010.
+    pub fn services(&self) -> impl Iterator<Item = (&'static str, &ServiceInfoType)> + '_ {
+        self.all_services()
+            .into_iter()
+            .filter_map(|(name, info)| info.as_ref().map(|info| (name, info)))
+    }
+
+    /// Return the services that the object model claims to use.
+    pub fn used_services(&self) -> impl Iterator<Item = (&'static str, &ServiceInfoType)> + '_ {
+        self.services().filter(|(_, info)| info.is_used)
+    }
+
+    /// Return the services that the object model has information about,
+    /// grouped by the service group they belong to. Groups without any such
+    /// services are left out.
+    pub fn services_by_group(&self) -> Vec<(ServiceGroup, Vec<(&'static str, &ServiceInfoType)>)> {
+        let mut groups: Vec<(ServiceGroup, Vec<(&'static str, &ServiceInfoType)>)> = Vec::new();
+        for (name, info) in self.services() {
+            let group = match info.section.group() {
+                Some(group) => group,
+                None => continue,
+            };
+            match groups.iter_mut().find(|(g, _)| *g == group) {
+                Some((_, services)) => services.push((name, info)),
+                None => groups.push((group, vec![(name, info)])),
+            }
+        }
+        groups
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServiceInfoType {
-    pub section: Option<String>,
-    pub is_callback: Option<String>,
-    pub is_used: Option<String>,
+    pub section: ServiceSectionType,
+    pub is_callback: bool,
+    pub is_used: bool,
+}
+
+impl FromElement for ServiceInfoType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        let &(_, clause, subclause, is_callback) = SERVICES.iter().find(|s| s.0 == e.name)?;
+        let section = ServiceSectionType { clause, subclause };
+
+        // The section and callback attributes are fixed for each service, so
+        // a document that disagrees is wrong, but not in a way that matters.
+        // The fixed values are kept, and written in place of the document's.
+        if let Some(value) = e.get_attribute("section") {
+            if value.trim() != section.to_string() {
+                let kind = FomErrorKind::FixedValue(section.to_string());
+                cx.attribute_warning(kind, e, "section", value);
+            }
+        }
+        if let Some(value) = get_attribute_as_type::<bool>(cx, e, "isCallback") {
+            if value != is_callback {
+                let text = e.get_attribute("isCallback").unwrap_or_default();
+                let kind = FomErrorKind::FixedValue(is_callback.to_string());
+                cx.attribute_warning(kind, e, "isCallback", text);
+            }
+        }
+
+        Some(Self {
+            section,
+            is_callback,
+            is_used: get_attribute_as_type(cx, e, "isUsed").unwrap_or(false),
+        })
+    }
 }
 
-impl From<&Element> for ServiceInfoType {
-    fn from(e: &Element) -> Self {
-        Self {
-            section: get_text_of_attribute(e, "section"),
-            is_callback: get_text_of_attribute(e, "isCallback"),
-            is_used: get_text_of_attribute(e, "isUsed"),
+/// The clause and subclause of IEEE 1516.1-2010 that specifies a service,
+/// e.g. `4.2` for Connect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ServiceSectionType {
+    pub clause: u32,
+    pub subclause: u32,
+}
+
+impl ServiceSectionType {
+    /// Return the service group of the services specified in the section.
+    pub fn group(&self) -> Option<ServiceGroup> {
+        match self.clause {
+            4 => Some(ServiceGroup::FederationManagement),
+            5 => Some(ServiceGroup::DeclarationManagement),
+            6 => Some(ServiceGroup::ObjectManagement),
+            7 => Some(ServiceGroup::OwnershipManagement),
+            8 => Some(ServiceGroup::TimeManagement),
+            9 => Some(ServiceGroup::DataDistributionManagement),
+            10 => Some(ServiceGroup::SupportServices),
+            _ => None,
         }
     }
 }
 
+impl std::fmt::Display for ServiceSectionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}.{}", self.clause, self.subclause)
+    }
+}
+
+/// The groups of services of the HLA interface specification, each specified
+/// in its own clause of IEEE 1516.1-2010.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ServiceGroup {
+    FederationManagement,
+    DeclarationManagement,
+    ObjectManagement,
+    OwnershipManagement,
+    TimeManagement,
+    DataDistributionManagement,
+    SupportServices,
+}
+
+impl std::fmt::Display for ServiceGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            ServiceGroup::FederationManagement => "Federation Management",
+            ServiceGroup::DeclarationManagement => "Declaration Management",
+            ServiceGroup::ObjectManagement => "Object Management",
+            ServiceGroup::OwnershipManagement => "Ownership Management",
+            ServiceGroup::TimeManagement => "Time Management",
+            ServiceGroup::DataDistributionManagement => "Data Distribution Management",
+            ServiceGroup::SupportServices => "Support Services",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectsType {
     pub root_object_class: Option<ObjectClassType>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for ObjectsType {
-    fn from(e: &Element) -> Self {
-        Self {
-            root_object_class: get_child_element_as_type(e, "objectClass"),
-        }
+impl FromElement for ObjectsType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            root_object_class: get_child_element_as_type(cx, e, "objectClass"),
+            annotations: get_annotations(e, &["objectClass"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectClassType {
     pub name: String,
-    pub sharing: SharingType,
+    pub sharing: Option<SharingType>,
     pub semantics: Option<String>,
     pub attributes: Option<Vec<AttributeType>>,
     pub object_classes: Option<Vec<ObjectClassType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for ObjectClassType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'objectModel -> objects -> objectClass -> name' found",
-            ),
-            sharing: get_child_element_as_type(e, "sharing").unwrap_or(SharingType::Neither),
+impl FromElement for ObjectClassType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            sharing: get_child_element_as_type(cx, e, "sharing"),
             semantics: get_text_of_child_element(e, "semantics"),
-            attributes: get_child_elements_as_type_as_option(e, "attribute"),
-            object_classes: get_child_elements_as_type_as_option(e, "objectClasses"),
-        }
+            attributes: get_child_elements_as_type_as_option(cx, e, "attribute"),
+            object_classes: get_child_elements_as_type_as_option(cx, e, "objectClass"),
+            annotations: get_annotations(e, &["attribute", "objectClass"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum SharingType {
     Publish,
     Subscribe,
@@ -455,19 +1478,23 @@ pub enum SharingType {
     Neither,
 }
 
-impl From<&Element> for SharingType {
-    fn from(e: &Element) -> Self {
+impl FromElement for SharingType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Publish" => SharingType::Publish,
-            "Subscribe" => SharingType::Subscribe,
-            "PublishSubscribe" => SharingType::PublishSubscribe,
-            "Neither" => SharingType::Neither,
-            _ => panic!("Unexpected sharing type: {}", text),
+            "Publish" => Some(SharingType::Publish),
+            "Subscribe" => Some(SharingType::Subscribe),
+            "PublishSubscribe" => Some(SharingType::PublishSubscribe),
+            "Neither" => Some(SharingType::Neither),
+            _ => {
+                cx.error(FomErrorKind::UnknownValue, e, Some(text));
+                None
+            }
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct AttributeType {
     pub name: String,
     pub data_type: Option<ReferenceType>,
@@ -479,43 +1506,44 @@ pub struct AttributeType {
     pub transportation: Option<ReferenceType>,
     pub order: Option<OrderType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for AttributeType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'objectModel -> objects -> objectClass -> attribute -> name' found",
-            ),
-            data_type: get_child_element_as_type(e, "dataType"),
-            update_type: get_child_element_as_type(e, "updateType"),
+impl FromElement for AttributeType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            data_type: get_child_element_as_type(cx, e, "dataType"),
+            update_type: get_child_element_as_type(cx, e, "updateType"),
             update_condition: get_text_of_child_element(e, "updateCondition"),
-            onwership: get_child_element_as_type(e, "ownership"),
-            sharing: get_child_element_as_type(e, "sharing"),
-            dimensions: e
-                .get_child("dimensions")
-                .map(|e| get_child_elements_as_type(e, "dimension")),
-            transportation: get_child_element_as_type(e, "transportation"),
-            order: get_child_element_as_type(e, "order"),
+            onwership: get_child_element_as_type(cx, e, "ownership"),
+            sharing: get_child_element_as_type(cx, e, "sharing"),
+            dimensions: get_child_element(e, "dimensions")
+                .map(|e| get_child_elements_as_type(cx, e, "dimension")),
+            transportation: get_child_element_as_type(cx, e, "transportation"),
+            order: get_child_element_as_type(cx, e, "order"),
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ReferenceType {
     pub value: String,
 }
 
-impl From<&Element> for ReferenceType {
-    fn from(e: &Element) -> Self {
-        Self {
+impl FromElement for ReferenceType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
+        Some(Self {
             value: get_element_text(e),
-        }
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum UpdateType {
     Static,
     Periodic,
@@ -524,19 +1552,20 @@ pub enum UpdateType {
     Other(String),
 }
 
-impl From<&Element> for UpdateType {
-    fn from(e: &Element) -> Self {
+impl FromElement for UpdateType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Static" => UpdateType::Static,
-            "Periodic" => UpdateType::Periodic,
-            "Conditional" => UpdateType::Conditional,
-            "NA" => UpdateType::Na,
-            _ => UpdateType::Other(text),
+            "Static" => Some(UpdateType::Static),
+            "Periodic" => Some(UpdateType::Periodic),
+            "Conditional" => Some(UpdateType::Conditional),
+            "NA" => Some(UpdateType::Na),
+            _ => Some(UpdateType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum OwnershipType {
     Divest,
     Acquire,
@@ -545,51 +1574,57 @@ pub enum OwnershipType {
     Other(String),
 }
 
-impl From<&Element> for OwnershipType {
-    fn from(e: &Element) -> Self {
+impl FromElement for OwnershipType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Divest" => OwnershipType::Divest,
-            "Acquire" => OwnershipType::Acquire,
-            "DivestAcquire" => OwnershipType::DivestAcquire,
-            "NoTransfer" => OwnershipType::NoTransfer,
-            _ => OwnershipType::Other(text),
+            "Divest" => Some(OwnershipType::Divest),
+            "Acquire" => Some(OwnershipType::Acquire),
+            "DivestAcquire" => Some(OwnershipType::DivestAcquire),
+            "NoTransfer" => Some(OwnershipType::NoTransfer),
+            _ => Some(OwnershipType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum OrderType {
     Receive,
     TimeStamp,
 }
 
-impl From<&Element> for OrderType {
-    fn from(e: &Element) -> Self {
+impl FromElement for OrderType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Receive" => OrderType::Receive,
-            "TimeStamp" => OrderType::TimeStamp,
-            _ => panic!("Unknown OrderType: {}", text),
+            "Receive" => Some(OrderType::Receive),
+            "TimeStamp" => Some(OrderType::TimeStamp),
+            _ => {
+                cx.error(FomErrorKind::UnknownValue, e, Some(text));
+                None
+            }
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct InteractionsType {
     pub interactions: InteractionClassType,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for InteractionsType {
-    fn from(e: &Element) -> Self {
-        Self {
-            interactions: get_child_element_as_type_or_panic(
-                e,
-                "interactionClass",
-                "No 'objectModel -> interactions -> interactionClass' found",
-            ),
-        }
+impl FromElement for InteractionsType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            interactions: get_required_child_element_as_type(cx, e, "interactionClass")?,
+            annotations: get_annotations(e, &["interactionClass"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct InteractionClassType {
     pub name: String,
     pub sharing: Option<SharingType>,
@@ -599,86 +1634,410 @@ pub struct InteractionClassType {
     pub semantics: Option<String>,
     pub parameters: Option<Vec<ParameterType>>,
     pub interaction_classes: Option<Vec<InteractionClassType>>,
-}
-
-impl From<&Element> for InteractionClassType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'objectModel -> interactions -> interactionClass -> name' found",
-            ),
-            sharing: get_child_element_as_type(e, "sharing"),
-            dimensions: get_child_elements_as_type_as_option(e, "dimension"),
-            transportation: get_child_element_as_type(e, "transportation"),
-            order: get_child_element_as_type(e, "order"),
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
+}
+
+impl FromElement for InteractionClassType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            sharing: get_child_element_as_type(cx, e, "sharing"),
+            dimensions: get_child_element(e, "dimensions")
+                .map(|e| get_child_elements_as_type(cx, e, "dimension")),
+            transportation: get_child_element_as_type(cx, e, "transportation"),
+            order: get_child_element_as_type(cx, e, "order"),
             semantics: get_text_of_child_element(e, "semantics"),
-            parameters: get_child_elements_as_type_as_option(e, "parameter"),
-            interaction_classes: get_child_elements_as_type_as_option(e, "interactionClass"),
-        }
+            parameters: get_child_elements_as_type_as_option(cx, e, "parameter"),
+            interaction_classes: get_child_elements_as_type_as_option(cx, e, "interactionClass"),
+            annotations: get_annotations(e, &["parameter", "interactionClass"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ParameterType {
     pub name: String,
     pub data_type: Option<ReferenceType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for ParameterType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'interactions -> interactionClass -> parameter -> name' found",
-            ),
-            data_type: get_child_element_as_type(e, "dataType"),
+impl FromElement for ParameterType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            data_type: get_child_element_as_type(cx, e, "dataType"),
             semantics: get_text_of_child_element(e, "semantics"),
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionsType {
+    pub dimensions: Option<Vec<DimensionType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
+}
+
+impl FromElement for DimensionsType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            dimensions: get_child_elements_as_type_as_option(cx, e, "dimension"),
+            annotations: get_annotations(e, &["dimension"]),
+            span: cx.span(e),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionType {
+    pub name: String,
+    pub data_type: Option<ReferenceType>,
+    pub upper_bound: Option<TypedValue<u64>>,
+    pub normalization: Option<TypedValue<NormalizationType>>,
+    pub value: Option<DimensionValueType>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
+}
+
+impl FromElement for DimensionType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            data_type: get_child_element_as_type(cx, e, "dataType"),
+            upper_bound: get_child_element_as_type(cx, e, "upperBound"),
+            normalization: get_child_element_as_type(cx, e, "normalization"),
+            value: get_child_element_as_type(cx, e, "value"),
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
+    }
+}
+
+impl FromText for u64 {
+    fn from_text(text: &str) -> Result<Self, FomErrorKind> {
+        text.parse().map_err(|_| FomErrorKind::InvalidValue)
+    }
+}
+
+/// The function that maps values of the domain of a dimension onto the
+/// normalized range `[0, upperBound)`, as written in the `normalization`
+/// element. The OMT defines the forms of the linear, linear enumerated,
+/// enumerated and logarithmic functions; anything else, such as the
+/// descriptions of the MIM dimensions, is kept as free text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NormalizationType {
+    /// `linear (domain, lower, upper)`
+    Linear {
+        domain: String,
+        lower: f64,
+        upper: f64,
+    },
+    /// `linearEnumerated (domain, [enumerator, ...])`
+    LinearEnumerated {
+        domain: String,
+        enumerators: Vec<String>,
+    },
+    /// `enumerated (domain, [enumerator -> value, ...])`
+    Enumerated {
+        domain: String,
+        mappings: Vec<(String, u64)>,
+    },
+    /// `logarithmic (domain, lower, upper)`
+    Logarithmic {
+        domain: String,
+        lower: f64,
+        upper: f64,
+    },
+    Other(String),
+}
+
+impl FromText for NormalizationType {
+    /// Fail for a function the OMT defines, with arguments that do not fit it.
+    fn from_text(text: &str) -> Result<Self, FomErrorKind> {
+        parse_normalization(text).map_err(|_| FomErrorKind::InvalidValue)
+    }
+}
+
+/// Parse the text of a `normalization` element. An error is returned if the
+/// text names one of the functions the OMT defines but its arguments are not
+/// of the form the function requires.
+fn parse_normalization(text: &str) -> Result<NormalizationType, ()> {
+    let (function, arguments) = match (text.find('('), text.rfind(')')) {
+        (Some(open), Some(close)) if open < close && text[close + 1..].trim().is_empty() => {
+            (text[..open].trim(), &text[open + 1..close])
+        }
+        _ => return Ok(NormalizationType::Other(String::from(text))),
+    };
+    let arguments = split_normalization_arguments(arguments);
+    let bound = |argument: &str| argument.parse::<f64>().map_err(|_| ());
+    let set = |argument: &str| -> Result<Vec<String>, ()> {
+        let argument = argument.strip_prefix('[').ok_or(())?;
+        let argument = argument.strip_suffix(']').ok_or(())?;
+        Ok(split_normalization_arguments(argument)
+            .into_iter()
+            .filter(|item| !item.is_empty())
+            .map(String::from)
+            .collect())
+    };
+
+    match (function, arguments.as_slice()) {
+        ("linear", [domain, lower, upper]) => Ok(NormalizationType::Linear {
+            domain: String::from(*domain),
+            lower: bound(lower)?,
+            upper: bound(upper)?,
+        }),
+        ("logarithmic", [domain, lower, upper]) => Ok(NormalizationType::Logarithmic {
+            domain: String::from(*domain),
+            lower: bound(lower)?,
+            upper: bound(upper)?,
+        }),
+        ("linearEnumerated", [domain, enumerators]) => Ok(NormalizationType::LinearEnumerated {
+            domain: String::from(*domain),
+            enumerators: set(enumerators)?,
+        }),
+        ("enumerated", [domain, mappings]) => {
+            let mappings = set(mappings)?
+                .iter()
+                .map(|mapping| {
+                    let (enumerator, value) = mapping
+                        .split_once("->")
+                        .or_else(|| mapping.split_once('\u{2192}'))
+                        .ok_or(())?;
+                    let value = value.trim().parse().map_err(|_| ())?;
+                    Ok((String::from(enumerator.trim()), value))
+                })
+                .collect::<Result<_, ()>>()?;
+            Ok(NormalizationType::Enumerated {
+                domain: String::from(*domain),
+                mappings,
+            })
+        }
+        ("linear", _) | ("logarithmic", _) | ("linearEnumerated", _) | ("enumerated", _) => Err(()),
+        _ => Ok(NormalizationType::Other(String::from(text))),
+    }
+}
+
+/// Split normalization function arguments at the commas that are not within
+/// brackets, trimming each argument.
+fn split_normalization_arguments(arguments: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(arguments[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
         }
     }
+    split.push(arguments[start..].trim());
+    split
 }
 
-pub struct DimensionsType {}
-impl From<&Element> for DimensionsType {
-    fn from(_e: &Element) -> Self {
-        Self {}
+impl NormalizationType {
+    /// Return the name of the domain that the function normalizes, unless the
+    /// function is free text.
+    pub fn domain(&self) -> Option<&str> {
+        match self {
+            NormalizationType::Linear { domain, .. }
+            | NormalizationType::LinearEnumerated { domain, .. }
+            | NormalizationType::Enumerated { domain, .. }
+            | NormalizationType::Logarithmic { domain, .. } => Some(domain),
+            NormalizationType::Other(_) => None,
+        }
+    }
+
+    /// Return the normalized value of a numeric domain value, for a dimension
+    /// with the provided upper bound. Domain values outside the bounds of the
+    /// function are clamped to them. None is returned for functions of
+    /// enumerated domains and free text.
+    pub fn normalize(&self, domain_value: f64, upper_bound: u64) -> Option<u64> {
+        let fraction = match self {
+            NormalizationType::Linear { lower, upper, .. } => {
+                (domain_value.max(*lower).min(*upper) - lower) / (upper - lower)
+            }
+            NormalizationType::Logarithmic { lower, upper, .. } => {
+                (domain_value.max(*lower).min(*upper) / lower).ln() / (upper / lower).ln()
+            }
+            _ => return None,
+        };
+        Some(scale_to_upper_bound(fraction, upper_bound))
+    }
+
+    /// Return the normalized value of an enumerated domain value, for a
+    /// dimension with the provided upper bound. None is returned if the
+    /// enumerator is not mapped by the function, or the function is not of an
+    /// enumerated domain.
+    pub fn normalize_enumerator(&self, enumerator: &str, upper_bound: u64) -> Option<u64> {
+        match self {
+            NormalizationType::LinearEnumerated { enumerators, .. } => {
+                let index = enumerators.iter().position(|e| e == enumerator)?;
+                if enumerators.len() == 1 {
+                    return Some(0);
+                }
+                let fraction = index as f64 / (enumerators.len() - 1) as f64;
+                Some(scale_to_upper_bound(fraction, upper_bound))
+            }
+            NormalizationType::Enumerated { mappings, .. } => mappings
+                .iter()
+                .find(|(e, _)| e == enumerator)
+                .map(|(_, value)| *value),
+            _ => None,
+        }
     }
 }
 
+/// Scale a fraction of the domain onto the normalized range `[0, upperBound)`.
+fn scale_to_upper_bound(fraction: f64, upper_bound: u64) -> u64 {
+    if !fraction.is_finite() {
+        return 0;
+    }
+    (fraction * upper_bound.saturating_sub(1) as f64).floor() as u64
+}
+
+impl std::fmt::Display for NormalizationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NormalizationType::Linear {
+                domain,
+                lower,
+                upper,
+            } => write!(f, "linear ({}, {}, {})", domain, lower, upper),
+            NormalizationType::LinearEnumerated {
+                domain,
+                enumerators,
+            } => write!(
+                f,
+                "linearEnumerated ({}, [{}])",
+                domain,
+                enumerators.join(", ")
+            ),
+            NormalizationType::Enumerated { domain, mappings } => {
+                let mappings: Vec<String> = mappings
+                    .iter()
+                    .map(|(enumerator, value)| format!("{} -> {}", enumerator, value))
+                    .collect();
+                write!(f, "enumerated ({}, [{}])", domain, mappings.join(", "))
+            }
+            NormalizationType::Logarithmic {
+                domain,
+                lower,
+                upper,
+            } => write!(f, "logarithmic ({}, {}, {})", domain, lower, upper),
+            NormalizationType::Other(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// The default range of a dimension, used when a region does not specify
+/// one, or `Excluded` if the dimension is excluded from regions by default.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DimensionValueType {
+    /// `n`
+    Value(u64),
+    /// `[lower..upper)`, or `[lower)` when there is no upper bound.
+    Range {
+        lower: u64,
+        upper: Option<u64>,
+    },
+    Excluded,
+}
+
+impl FromElement for DimensionValueType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        let text = get_element_text(e);
+        let parsed = if text == "Excluded" {
+            Some(DimensionValueType::Excluded)
+        } else if let Some(range) = text.strip_prefix('[').and_then(|t| t.strip_suffix(')')) {
+            match range.split_once("..") {
+                Some((lower, upper)) => {
+                    lower
+                        .parse()
+                        .ok()
+                        .zip(upper.parse().ok())
+                        .map(|(lower, upper)| DimensionValueType::Range {
+                            lower,
+                            upper: Some(upper),
+                        })
+                }
+                None => range
+                    .parse()
+                    .ok()
+                    .map(|lower| DimensionValueType::Range { lower, upper: None }),
+            }
+        } else {
+            text.parse().ok().map(DimensionValueType::Value)
+        };
+        if parsed.is_none() {
+            cx.error(FomErrorKind::InvalidValue, e, Some(text));
+        }
+        parsed
+    }
+}
+
+impl std::fmt::Display for DimensionValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DimensionValueType::Value(value) => write!(f, "{}", value),
+            DimensionValueType::Range {
+                lower,
+                upper: Some(upper),
+            } => write!(f, "[{}..{})", lower, upper),
+            DimensionValueType::Range { lower, upper: None } => write!(f, "[{})", lower),
+            DimensionValueType::Excluded => write!(f, "Excluded"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct TimeType {
     pub time_stamp: Option<TimeTypeType>,
     pub lookahead: Option<TimeTypeType>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for TimeType {
-    fn from(e: &Element) -> Self {
-        Self {
-            time_stamp: get_child_element_as_type(e, "timeStamp"),
-            lookahead: get_child_element_as_type(e, "lookahead"),
-        }
+impl FromElement for TimeType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            time_stamp: get_child_element_as_type(cx, e, "timeStamp"),
+            lookahead: get_child_element_as_type(cx, e, "lookahead"),
+            annotations: get_annotations(e, &["timeStamp", "lookahead"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct TimeTypeType {
     pub data_type: ReferenceType,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for TimeTypeType {
-    fn from(e: &Element) -> Self {
-        Self {
-            data_type: get_child_element_as_type_or_panic(
-                e,
-                "dataType",
-                "No 'time type -> dataType' found",
-            ),
+impl FromElement for TimeTypeType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            data_type: get_required_child_element_as_type(cx, e, "dataType")?,
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct TagsType {
     pub update_reflect_tag: Option<TagType>,
     pub send_receive_tag: Option<TagType>,
@@ -687,70 +2046,105 @@ pub struct TagsType {
     pub divestiture_completion_tag: Option<TagType>,
     pub acquisition_request_tag: Option<TagType>,
     pub request_update_tag: Option<TagType>,
-}
-
-impl From<&Element> for TagsType {
-    fn from(e: &Element) -> Self {
-        Self {
-            update_reflect_tag: get_child_element_as_type(e, "update_reflect_tag"),
-            send_receive_tag: get_child_element_as_type(e, "send_receive_tag"),
-            delete_remove_tag: get_child_element_as_type(e, "delete_remove_tag"),
-            divestiture_request_tag: get_child_element_as_type(e, "divestiture_request_tag"),
-            divestiture_completion_tag: get_child_element_as_type(e, "divestiture_completion_tag"),
-            acquisition_request_tag: get_child_element_as_type(e, "acquisition_request_tag"),
-            request_update_tag: get_child_element_as_type(e, "request_update_tag"),
-        }
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
+}
+
+impl FromElement for TagsType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            update_reflect_tag: get_child_element_as_type(cx, e, "updateReflectTag"),
+            send_receive_tag: get_child_element_as_type(cx, e, "sendReceiveTag"),
+            delete_remove_tag: get_child_element_as_type(cx, e, "deleteRemoveTag"),
+            divestiture_request_tag: get_child_element_as_type(cx, e, "divestitureRequestTag"),
+            divestiture_completion_tag: get_child_element_as_type(
+                cx,
+                e,
+                "divestitureCompletionTag",
+            ),
+            acquisition_request_tag: get_child_element_as_type(cx, e, "acquisitionRequestTag"),
+            request_update_tag: get_child_element_as_type(cx, e, "requestUpdateTag"),
+            annotations: get_annotations(
+                e,
+                &[
+                    "updateReflectTag",
+                    "sendReceiveTag",
+                    "deleteRemoveTag",
+                    "divestitureRequestTag",
+                    "divestitureCompletionTag",
+                    "acquisitionRequestTag",
+                    "requestUpdateTag",
+                ],
+            ),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct TagType {
     pub data_type: Option<ReferenceType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for TagType {
-    fn from(e: &Element) -> Self {
-        Self {
-            data_type: get_child_element_as_type(e, "dataType"),
+impl FromElement for TagType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            data_type: get_child_element_as_type(cx, e, "dataType"),
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SynchronizationsType {
     pub synchronization_points: Option<Vec<SynchronizationPointType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for SynchronizationsType {
-    fn from(e: &Element) -> Self {
-        Self {
-            synchronization_points: get_child_elements_as_type_as_option(e, "synchronizationPoint"),
-        }
+impl FromElement for SynchronizationsType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            synchronization_points: get_child_elements_as_type_as_option(
+                cx,
+                e,
+                "synchronizationPoint",
+            ),
+            annotations: get_annotations(e, &["synchronizationPoint"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SynchronizationPointType {
     pub label: String,
     pub data_type: Option<ReferenceType>,
     pub capability: Option<CapabilityType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for SynchronizationPointType {
-    fn from(e: &Element) -> Self {
-        Self {
-            label: get_text_of_child_element_or_panic(
-                e,
-                "label",
-                "No 'synchronizationPoint -> label' found",
-            ),
-            data_type: get_child_element_as_type(e, "dataType"),
-            capability: get_child_element_as_type(e, "capability"),
+impl FromElement for SynchronizationPointType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            label: get_required_text_of_child_element(cx, e, "label"),
+            data_type: get_child_element_as_type(cx, e, "dataType"),
+            capability: get_child_element_as_type(cx, e, "capability"),
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum CapabilityType {
     Register,
     Achieve,
@@ -759,68 +2153,82 @@ pub enum CapabilityType {
     Na,
 }
 
-impl From<&Element> for CapabilityType {
-    fn from(e: &Element) -> Self {
+impl FromElement for CapabilityType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Register" => CapabilityType::Register,
-            "Achieve" => CapabilityType::Achieve,
-            "RegisterAchieve" => CapabilityType::RegisterAchieve,
-            "NoSynch" => CapabilityType::NoSynch,
-            "NA" => CapabilityType::Na,
-            _ => panic!("Unknown capability: {}", text),
+            "Register" => Some(CapabilityType::Register),
+            "Achieve" => Some(CapabilityType::Achieve),
+            "RegisterAchieve" => Some(CapabilityType::RegisterAchieve),
+            "NoSynch" => Some(CapabilityType::NoSynch),
+            "NA" => Some(CapabilityType::Na),
+            _ => {
+                cx.error(FomErrorKind::UnknownValue, e, Some(text));
+                None
+            }
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct TransportationsType {
     pub transportations: Option<Vec<TransportationType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for TransportationsType {
-    fn from(e: &Element) -> Self {
-        Self {
-            transportations: get_child_elements_as_type_as_option(e, "transportation"),
-        }
+impl FromElement for TransportationsType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            transportations: get_child_elements_as_type_as_option(cx, e, "transportation"),
+            annotations: get_annotations(e, &["transportation"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct TransportationType {
     pub name: String,
     pub reliable: Option<ReliableType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for TransportationType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'transportation -> name' found",
-            ),
-            reliable: get_child_element_as_type(e, "reliable"),
+impl FromElement for TransportationType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            reliable: get_child_element_as_type(cx, e, "reliable"),
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum ReliableType {
     Yes,
     No,
 }
 
-impl From<&Element> for ReliableType {
-    fn from(e: &Element) -> Self {
+impl FromElement for ReliableType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Yes" => ReliableType::Yes,
-            "No" => ReliableType::No,
-            _ => panic!("Unexpected reliable type: {}", text),
+            "Yes" => Some(ReliableType::Yes),
+            "No" => Some(ReliableType::No),
+            _ => {
+                cx.error(FomErrorKind::UnknownValue, e, Some(text));
+                None
+            }
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SwitchesType {
     pub auto_provide: Option<SwitchType>,
     pub convey_region_designator_sets: Option<SwitchType>,
@@ -833,50 +2241,80 @@ pub struct SwitchesType {
     pub exception_reporting: Option<SwitchType>,
     pub delay_subscription_evaluation: Option<SwitchType>,
     pub automatic_resign_action: Option<ResignSwitchType>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for SwitchesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            auto_provide: get_attribute_as_type(e, "auto_provide"),
-            convey_region_designator_sets: get_attribute_as_type(
+impl FromElement for SwitchesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            auto_provide: get_child_element_as_type(cx, e, "autoProvide"),
+            convey_region_designator_sets: get_child_element_as_type(
+                cx,
                 e,
-                "convey_region_designator_sets",
+                "conveyRegionDesignatorSets",
             ),
-            convey_producing_federate: get_attribute_as_type(e, "convey_producing_federate"),
-            attribute_scope_advisory: get_attribute_as_type(e, "attribute_scope_advisory"),
-            attribute_relevance_advisory: get_attribute_as_type(e, "attribute_relevance_advisory"),
-            object_class_relevance_advisory: get_attribute_as_type(
+            convey_producing_federate: get_child_element_as_type(cx, e, "conveyProducingFederate"),
+            attribute_scope_advisory: get_child_element_as_type(cx, e, "attributeScopeAdvisory"),
+            attribute_relevance_advisory: get_child_element_as_type(
+                cx,
                 e,
-                "object_class_relevance_advisory",
+                "attributeRelevanceAdvisory",
             ),
-            interaction_relevance_advisory: get_attribute_as_type(
+            object_class_relevance_advisory: get_child_element_as_type(
+                cx,
                 e,
-                "interaction_relevance_advisory",
+                "objectClassRelevanceAdvisory",
             ),
-            service_reporting: get_attribute_as_type(e, "service_reporting"),
-            exception_reporting: get_attribute_as_type(e, "exception_reporting"),
-            delay_subscription_evaluation: get_attribute_as_type(
+            interaction_relevance_advisory: get_child_element_as_type(
+                cx,
                 e,
-                "delay_subscription_evaluation",
+                "interactionRelevanceAdvisory",
             ),
-            automatic_resign_action: get_attribute_as_type(e, "automatic_resign_action"),
-        }
+            service_reporting: get_child_element_as_type(cx, e, "serviceReporting"),
+            exception_reporting: get_child_element_as_type(cx, e, "exceptionReporting"),
+            delay_subscription_evaluation: get_child_element_as_type(
+                cx,
+                e,
+                "delaySubscriptionEvaluation",
+            ),
+            automatic_resign_action: get_child_element_as_type(cx, e, "automaticResignAction"),
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+/// The setting of an RTI switch. A switch element without an `isEnabled`
+/// attribute is disabled.
+#[derive(Debug, Clone, PartialEq)]
 pub struct SwitchType {
     pub is_enabled: bool,
 }
 
-impl From<&String> for SwitchType {
-    fn from(attribute: &String) -> Self {
-        Self {
-            is_enabled: attribute.parse().unwrap(),
+impl FromElement for SwitchType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            is_enabled: get_attribute_as_type(cx, e, "isEnabled").unwrap_or(false),
+        })
+    }
+}
+
+impl FromAttribute for bool {
+    fn from_attribute(value: &str) -> Result<Self, FomErrorKind> {
+        // The lexical forms of xs:boolean.
+        match value.trim() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(FomErrorKind::InvalidValue),
         }
     }
 }
 
+/// The action the RTI takes on behalf of a federate that resigns. An
+/// `automaticResignAction` element without a `resignAction` attribute means
+/// `NoAction`.
+#[derive(Debug, Clone, PartialEq)]
 pub enum ResignSwitchType {
     UnconditionallyDivestAttributes,
     DeleteObjects,
@@ -886,62 +2324,86 @@ pub enum ResignSwitchType {
     NoAction,
 }
 
-impl From<&String> for ResignSwitchType {
-    fn from(attribute: &String) -> Self {
-        match attribute.as_str() {
-            "UnconditionallyDivestAttributes" => ResignSwitchType::UnconditionallyDivestAttributes,
-            "DeleteObjects" => ResignSwitchType::DeleteObjects,
+impl FromElement for ResignSwitchType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        match e.get_attribute("resignAction") {
+            Some(_) => get_attribute_as_type(cx, e, "resignAction"),
+            None => Some(ResignSwitchType::NoAction),
+        }
+    }
+}
+
+impl FromAttribute for ResignSwitchType {
+    fn from_attribute(value: &str) -> Result<Self, FomErrorKind> {
+        match value {
+            "UnconditionallyDivestAttributes" => {
+                Ok(ResignSwitchType::UnconditionallyDivestAttributes)
+            }
+            "DeleteObjects" => Ok(ResignSwitchType::DeleteObjects),
             "CancelPendingOwnershipAcquisitions" => {
-                ResignSwitchType::CancelPendingOwnershipAcquisitions
+                Ok(ResignSwitchType::CancelPendingOwnershipAcquisitions)
             }
-            "DeleteObjectsThenDivest" => ResignSwitchType::DeleteObjectsThenDivest,
-            "CancelThenDeleteThenDivest" => ResignSwitchType::CancelThenDeleteThenDivest,
-            "NoAction" => ResignSwitchType::NoAction,
-            _ => panic!("Unknown resign switch type: {}", attribute),
+            "DeleteObjectsThenDivest" => Ok(ResignSwitchType::DeleteObjectsThenDivest),
+            "CancelThenDeleteThenDivest" => Ok(ResignSwitchType::CancelThenDeleteThenDivest),
+            "NoAction" => Ok(ResignSwitchType::NoAction),
+            _ => Err(FomErrorKind::UnknownValue),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct UpdateRatesType {
     pub update_rates: Option<Vec<UpdateRateType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for UpdateRatesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            update_rates: get_child_elements_as_type_as_option(e, "updateRate"),
-        }
+impl FromElement for UpdateRatesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            update_rates: get_child_elements_as_type_as_option(cx, e, "updateRate"),
+            annotations: get_annotations(e, &["updateRate"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct UpdateRateType {
     pub name: String,
     pub rate: Option<RateType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for UpdateRateType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(e, "name", "No 'updateRate -> name' found"),
-            rate: get_child_element_as_type(e, "rate"),
+impl FromElement for UpdateRateType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            rate: get_child_element_as_type(cx, e, "rate"),
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct RateType {
-    pub value: String,
+    /// The rate in Hz.
+    pub value: TypedValue<f64>,
 }
 
-impl From<&Element> for RateType {
-    fn from(e: &Element) -> Self {
-        Self {
-            value: get_element_text(e),
-        }
+impl FromElement for RateType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            value: TypedValue::from_element(e, cx)?,
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct DataTypesType {
     pub basic_data_representations: Option<BasicDataRepresentationsType>,
     pub simple_data_types: Option<SimpleDataTypesType>,
@@ -949,297 +2411,644 @@ pub struct DataTypesType {
     pub array_data_types: Option<ArrayDataTypesType>,
     pub fixed_record_data_types: Option<FixedRecordDataTypesType>,
     pub variand_record_data_types: Option<VariantRecordDataTypesType>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for DataTypesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            basic_data_representations: get_child_element_as_type(e, "basicDataRepresentations"),
-            simple_data_types: get_child_element_as_type(e, "simpleDataTypes"),
-            enumerated_data_types: get_child_element_as_type(e, "enumeratedDataTypes"),
-            array_data_types: get_child_element_as_type(e, "arrayDataTypes"),
-            fixed_record_data_types: get_child_element_as_type(e, "fixedRecordDataTypes"),
-            variand_record_data_types: get_child_element_as_type(e, "variantRecordDataTypes"),
-        }
+impl FromElement for DataTypesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            basic_data_representations: get_child_element_as_type(
+                cx,
+                e,
+                "basicDataRepresentations",
+            ),
+            simple_data_types: get_child_element_as_type(cx, e, "simpleDataTypes"),
+            enumerated_data_types: get_child_element_as_type(cx, e, "enumeratedDataTypes"),
+            array_data_types: get_child_element_as_type(cx, e, "arrayDataTypes"),
+            fixed_record_data_types: get_child_element_as_type(cx, e, "fixedRecordDataTypes"),
+            variand_record_data_types: get_child_element_as_type(cx, e, "variantRecordDataTypes"),
+            annotations: get_annotations(
+                e,
+                &[
+                    "basicDataRepresentations",
+                    "simpleDataTypes",
+                    "enumeratedDataTypes",
+                    "arrayDataTypes",
+                    "fixedRecordDataTypes",
+                    "variantRecordDataTypes",
+                ],
+            ),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct BasicDataRepresentationsType {
     pub basic_datas: Option<Vec<BasicDataType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for BasicDataRepresentationsType {
-    fn from(e: &Element) -> Self {
-        Self {
-            basic_datas: get_child_elements_as_type_as_option(e, "basicData"),
-        }
+impl FromElement for BasicDataRepresentationsType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            basic_datas: get_child_elements_as_type_as_option(cx, e, "basicData"),
+            annotations: get_annotations(e, &["basicData"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct BasicDataType {
     pub name: String,
     pub size: Option<SizeType>,
     pub interpretation: Option<String>,
     pub endian: Option<EndianType>,
     pub encoding: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for BasicDataType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(e, "name", "No 'basicData -> name' found"),
-            size: get_child_element_as_type(e, "size"),
+impl FromElement for BasicDataType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            size: get_child_element_as_type(cx, e, "size"),
             interpretation: get_text_of_child_element(e, "interpretation"),
-            endian: get_child_element_as_type(e, "endian"),
+            endian: get_child_element_as_type(cx, e, "endian"),
             encoding: get_text_of_child_element(e, "encoding"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SizeType {
-    pub size: Option<String>,
+    /// The size in bits.
+    pub size: TypedValue<u32>,
 }
 
-impl From<&Element> for SizeType {
-    fn from(e: &Element) -> Self {
-        Self {
-            size: Some(get_element_text(e)),
+impl FromElement for SizeType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            size: TypedValue::from_element(e, cx)?,
+        })
+    }
+}
+
+/// The integer representations of the HLA standard MIM, with their sizes in
+/// bits and whether they are signed.
+const HLA_INTEGER_REPRESENTATIONS: [(&str, u32, bool); 9] = [
+    ("HLAinteger16BE", 16, true),
+    ("HLAinteger32BE", 32, true),
+    ("HLAinteger64BE", 64, true),
+    ("HLAoctetPairBE", 16, false),
+    ("HLAinteger16LE", 16, true),
+    ("HLAinteger32LE", 32, true),
+    ("HLAinteger64LE", 64, true),
+    ("HLAoctetPairLE", 16, false),
+    ("HLAoctet", 8, false),
+];
+
+/// Return the smallest and largest integers of the provided size in bits.
+fn integer_range(bits: u32, signed: bool) -> (i128, i128) {
+    if signed {
+        (-(1 << (bits - 1)), (1 << (bits - 1)) - 1)
+    } else {
+        (0, (1 << bits) - 1)
+    }
+}
+
+impl BasicDataType {
+    /// Return the smallest and largest integers that the representation can
+    /// hold, if it is an integer representation of at most 64 bits. Whether it
+    /// is signed is judged from its name and interpretation, as in
+    /// `HLAinteger32BE` or `Integer in the range [0, 2^16-1]`; representations
+    /// described as floating point are not integer representations.
+    pub fn integer_range(&self) -> Option<(i128, i128)> {
+        let bits = *self.size.as_ref()?.size.value()?;
+        if bits == 0 || bits > 64 {
+            return None;
         }
+        let description = format!(
+            "{} {}",
+            self.name,
+            self.interpretation.as_deref().unwrap_or("")
+        )
+        .to_lowercase();
+        if description.contains("float") {
+            return None;
+        }
+        let signed = description.contains("integer")
+            && !description.contains("unsigned")
+            && !description.contains("[0,");
+        Some(integer_range(bits, signed))
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum EndianType {
     Big,
     Little,
 }
 
-impl From<&Element> for EndianType {
-    fn from(e: &Element) -> Self {
+impl FromElement for EndianType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "Big" => EndianType::Big,
-            "Little" => EndianType::Little,
-            _ => panic!("Unknown endian type: {}", text),
+            "Big" => Some(EndianType::Big),
+            "Little" => Some(EndianType::Little),
+            _ => {
+                cx.error(FomErrorKind::UnknownValue, e, Some(text));
+                None
+            }
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SimpleDataTypesType {
     pub simple_datas: Option<Vec<SimpleDataType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for SimpleDataTypesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            simple_datas: get_child_elements_as_type_as_option(e, "simpleData"),
-        }
+impl FromElement for SimpleDataTypesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            simple_datas: get_child_elements_as_type_as_option(cx, e, "simpleData"),
+            annotations: get_annotations(e, &["simpleData"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SimpleDataType {
     pub name: String,
     pub representation: Option<ReferenceType>,
     pub units: Option<String>,
-    pub resolution: Option<String>,
-    pub accuracy: Option<String>,
+    pub resolution: Option<TypedValue<MeasureType>>,
+    pub accuracy: Option<TypedValue<MeasureType>>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for SimpleDataType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(e, "name", "No 'simpleData -> name' found"),
-            representation: get_child_element_as_type(e, "representation"),
+impl FromElement for SimpleDataType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            representation: get_child_element_as_type(cx, e, "representation"),
             units: get_text_of_child_element(e, "units"),
-            resolution: get_text_of_child_element(e, "resolution"),
-            accuracy: get_text_of_child_element(e, "accuracy"),
+            resolution: get_child_element_as_type(cx, e, "resolution"),
+            accuracy: get_child_element_as_type(cx, e, "accuracy"),
             semantics: get_text_of_child_element(e, "semantics"),
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
+    }
+}
+
+/// The resolution or accuracy of a simple datatype, in its units. The OMT
+/// permits any text, so text that is not a number is kept as `Other`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeasureType {
+    Value(f64),
+    /// `NA`.
+    NotApplicable,
+    /// `perfect`, in any case, for datatypes that are represented exactly.
+    Perfect,
+    Other(String),
+}
+
+impl FromText for MeasureType {
+    fn from_text(text: &str) -> Result<Self, FomErrorKind> {
+        Ok(if text == "NA" {
+            MeasureType::NotApplicable
+        } else if text.eq_ignore_ascii_case("perfect") {
+            MeasureType::Perfect
+        } else {
+            match text.parse() {
+                Ok(value) if text.chars().any(|c| c.is_ascii_digit()) => MeasureType::Value(value),
+                _ => MeasureType::Other(String::from(text)),
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for MeasureType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MeasureType::Value(value) => write!(f, "{}", value),
+            MeasureType::NotApplicable => write!(f, "NA"),
+            MeasureType::Perfect => write!(f, "perfect"),
+            MeasureType::Other(text) => write!(f, "{}", text),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnumeratedDataTypesType {
     pub enumerated_datas: Option<Vec<EnumeratedDataType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for EnumeratedDataTypesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            enumerated_datas: get_child_elements_as_type_as_option(e, "enumeratedData"),
-        }
+impl FromElement for EnumeratedDataTypesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            enumerated_datas: get_child_elements_as_type_as_option(cx, e, "enumeratedData"),
+            annotations: get_annotations(e, &["enumeratedData"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnumeratedDataType {
     pub name: String,
     pub representation: Option<ReferenceType>,
     pub semantics: Option<String>,
     pub enumerators: Option<Vec<EnumeratorType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for EnumeratedDataType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'enumeratedData -> name' found",
-            ),
-            representation: get_child_element_as_type(e, "representation"),
+impl FromElement for EnumeratedDataType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            representation: get_child_element_as_type(cx, e, "representation"),
             semantics: get_text_of_child_element(e, "semantics"),
-            enumerators: get_child_elements_as_type_as_option(e, "enumerator"),
-        }
+            enumerators: get_child_elements_as_type_as_option(cx, e, "enumerator"),
+            annotations: get_annotations(e, &["enumerator"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnumeratorType {
     pub name: String,
-    pub value: Vec<String>,
+    pub value: Vec<TypedValue<i128>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
+}
+
+impl FromElement for EnumeratorType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            value: get_child_elements_as_type(cx, e, "value"),
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
+    }
 }
 
-impl From<&Element> for EnumeratorType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'enumeratedData -> enumerator -> name' found",
-            ),
-            value: get_text_of_child_elements(e, "value"),
+/// Return the range of integers that the named representation can hold, if it
+/// is an integer representation among the basic data representations of the
+/// object model or those of the HLA standard MIM.
+fn representation_integer_range(fom: &ObjectModelType, name: &str) -> Option<(i128, i128)> {
+    let basic_data = fom
+        .data_types
+        .as_ref()
+        .and_then(|data_types| data_types.basic_data_representations.as_ref())
+        .and_then(|representations| representations.basic_datas.as_ref())
+        .and_then(|basic_datas| {
+            basic_datas
+                .iter()
+                .find(|basic_data| basic_data.name == name)
+        });
+    match basic_data {
+        Some(basic_data) => basic_data.integer_range(),
+        None => HLA_INTEGER_REPRESENTATIONS
+            .iter()
+            .find(|(representation, _, _)| *representation == name)
+            .map(|(_, bits, signed)| integer_range(*bits, *signed)),
+    }
+}
+
+/// An enumerator value, and where it was found, to be checked against the
+/// range of the representation of its enumerated datatype once every
+/// datatype of the object model is known.
+struct EnumeratorValue {
+    representation: String,
+    text: String,
+    path: String,
+    position: Position,
+}
+
+/// Return the enumerator values of the enumerated datatypes of the provided
+/// document, for `check_enumerator_values`.
+fn get_enumerator_values(cx: &mut Context, e: &Element) -> Vec<EnumeratorValue> {
+    let mut values = Vec::new();
+    for data_types in get_child_elements(e, "dataTypes") {
+        cx.enter(data_types);
+        for enumerated_data_types in get_child_elements(data_types, "enumeratedDataTypes") {
+            cx.enter(enumerated_data_types);
+            for enumerated_data in get_child_elements(enumerated_data_types, "enumeratedData") {
+                let representation = get_text_of_child_element(enumerated_data, "representation");
+                if let Some(representation) = representation {
+                    cx.enter(enumerated_data);
+                    for enumerator in get_child_elements(enumerated_data, "enumerator") {
+                        collect_enumerator_values(cx, &representation, enumerator, &mut values);
+                    }
+                    cx.leave();
+                }
+            }
+            cx.leave();
+        }
+        cx.leave();
+    }
+    values
+}
+
+/// Add the values of the provided `enumerator` element, which is within the
+/// `enumeratedData` element the context is in, to the provided values.
+fn collect_enumerator_values(
+    cx: &mut Context,
+    representation: &str,
+    enumerator: &Element,
+    values: &mut Vec<EnumeratorValue>,
+) {
+    cx.enter(enumerator);
+    for value in get_child_elements(enumerator, "value") {
+        cx.enter(value);
+        values.push(EnumeratorValue {
+            representation: String::from(representation),
+            text: get_element_text(value),
+            path: cx.path(),
+            position: value.position,
+        });
+        cx.leave();
+    }
+    cx.leave();
+}
+
+/// Warn about enumerator values that the representation of their enumerated
+/// datatype cannot hold. Values of representations that are not known to be
+/// integer representations are not checked.
+fn check_enumerator_values(cx: &mut Context, values: Vec<EnumeratorValue>, fom: &ObjectModelType) {
+    for value in values {
+        let range = representation_integer_range(fom, &value.representation);
+        match (range, i128::from_text(&value.text)) {
+            (Some((lower, upper)), Ok(n)) if n < lower || n > upper => cx.warnings.push(FomError {
+                kind: FomErrorKind::OutOfRange(value.representation),
+                path: value.path,
+                value: Some(value.text),
+                position: value.position,
+            }),
+            _ => {}
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ArrayDataTypesType {
     pub array_datas: Option<Vec<ArrayDataType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for ArrayDataTypesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            array_datas: get_child_elements_as_type_as_option(e, "arrayData"),
-        }
+impl FromElement for ArrayDataTypesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            array_datas: get_child_elements_as_type_as_option(cx, e, "arrayData"),
+            annotations: get_annotations(e, &["arrayData"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ArrayDataType {
     pub name: String,
     pub data_type: Option<ReferenceType>,
-    pub cardinality: Option<String>, // needs to match a pattern
+    pub cardinality: Option<TypedValue<Cardinality>>,
     pub encoding: Option<ArrayDataTypeEncodingType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for ArrayDataType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(e, "name", "No 'arrayData -> name' found"),
-            data_type: get_child_element_as_type(e, "representation"),
-            cardinality: get_text_of_child_element(e, "cardinality"), // needs to match a pattern
-            encoding: get_child_element_as_type(e, "encoding"),
+impl FromElement for ArrayDataType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            data_type: get_child_element_as_type(cx, e, "dataType"),
+            cardinality: get_child_element_as_type(cx, e, "cardinality"),
+            encoding: get_child_element_as_type(cx, e, "encoding"),
             semantics: get_text_of_child_element(e, "semantics"),
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
+    }
+}
+
+/// The number of elements of an array datatype.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cardinality {
+    Fixed(u64),
+    /// A number of elements between the bounds, inclusive, written as
+    /// `[lower..upper]`.
+    Range(u64, u64),
+    Dynamic,
+    /// The cardinalities of each dimension of a multi-dimensional array,
+    /// written separated by commas, e.g. `3,Dynamic`.
+    MultiDimensional(Vec<Cardinality>),
+}
+
+impl Cardinality {
+    /// Return the cardinality of each dimension of the array.
+    pub fn dimensions(&self) -> &[Cardinality] {
+        match self {
+            Cardinality::MultiDimensional(dimensions) => dimensions,
+            _ => std::slice::from_ref(self),
+        }
+    }
+
+    fn parse_dimension(text: &str) -> Result<Self, FomErrorKind> {
+        if text == "Dynamic" {
+            return Ok(Cardinality::Dynamic);
+        }
+        let number = |text: &str| -> Result<u64, FomErrorKind> {
+            if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+                return Err(FomErrorKind::InvalidValue);
+            }
+            text.parse().map_err(|_| FomErrorKind::InvalidValue)
+        };
+        match text
+            .strip_prefix('[')
+            .and_then(|text| text.strip_suffix(']'))
+        {
+            Some(range) => {
+                let (lower, upper) = range.split_once("..").ok_or(FomErrorKind::InvalidValue)?;
+                let (lower, upper) = (number(lower)?, number(upper)?);
+                if lower > upper {
+                    return Err(FomErrorKind::InvalidValue);
+                }
+                Ok(Cardinality::Range(lower, upper))
+            }
+            None => Ok(Cardinality::Fixed(number(text)?)),
+        }
+    }
+}
+
+impl FromText for Cardinality {
+    fn from_text(text: &str) -> Result<Self, FomErrorKind> {
+        let dimensions = text
+            .split(',')
+            .map(Cardinality::parse_dimension)
+            .collect::<Result<Vec<_>, _>>()?;
+        if dimensions.len() == 1 {
+            Ok(dimensions.into_iter().next().unwrap())
+        } else {
+            Ok(Cardinality::MultiDimensional(dimensions))
         }
     }
 }
 
+impl std::fmt::Display for Cardinality {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Cardinality::Fixed(n) => write!(f, "{}", n),
+            Cardinality::Range(lower, upper) => write!(f, "[{}..{}]", lower, upper),
+            Cardinality::Dynamic => write!(f, "Dynamic"),
+            Cardinality::MultiDimensional(dimensions) => {
+                for (i, dimension) in dimensions.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", dimension)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum ArrayDataTypeEncodingType {
     HlaFixedArray,
     HlaVariableArray,
     Other(String),
 }
 
-impl From<&Element> for ArrayDataTypeEncodingType {
-    fn from(e: &Element) -> Self {
+impl FromElement for ArrayDataTypeEncodingType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "HLAfixedArray" => ArrayDataTypeEncodingType::HlaFixedArray,
-            "HLAvariableArray" => ArrayDataTypeEncodingType::HlaVariableArray,
-            _ => ArrayDataTypeEncodingType::Other(text),
+            "HLAfixedArray" => Some(ArrayDataTypeEncodingType::HlaFixedArray),
+            "HLAvariableArray" => Some(ArrayDataTypeEncodingType::HlaVariableArray),
+            _ => Some(ArrayDataTypeEncodingType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct FixedRecordDataTypesType {
     pub fixed_record_datas: Option<Vec<FixedRecordDataType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for FixedRecordDataTypesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            fixed_record_datas: get_child_elements_as_type_as_option(e, "fixedRecordData"),
-        }
+impl FromElement for FixedRecordDataTypesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            fixed_record_datas: get_child_elements_as_type_as_option(cx, e, "fixedRecordData"),
+            annotations: get_annotations(e, &["fixedRecordData"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct FixedRecordDataType {
     pub name: String,
     pub encoding: Option<FixedRecordEncodingType>,
     pub semantics: Option<String>,
     pub fields: Option<Vec<FieldType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for FixedRecordDataType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'fixedRecordData -> name' found",
-            ),
-            encoding: get_child_element_as_type(e, "encoding"),
+impl FromElement for FixedRecordDataType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            encoding: get_child_element_as_type(cx, e, "encoding"),
             semantics: get_text_of_child_element(e, "semantics"),
-            fields: get_child_elements_as_type_as_option(e, "field"),
-        }
+            fields: get_child_elements_as_type_as_option(cx, e, "field"),
+            annotations: get_annotations(e, &["field"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum FixedRecordEncodingType {
     HlaFixedRecord,
+    /// Added by IEEE 1516.2-2025.
+    HlaExtendableFixedRecord,
     Other(String),
 }
 
-impl From<&Element> for FixedRecordEncodingType {
-    fn from(e: &Element) -> Self {
+impl FromElement for FixedRecordEncodingType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "HLAfixedRecord" => FixedRecordEncodingType::HlaFixedRecord,
-            _ => FixedRecordEncodingType::Other(text),
+            "HLAfixedRecord" => Some(FixedRecordEncodingType::HlaFixedRecord),
+            "HLAextendableFixedRecord" => Some(FixedRecordEncodingType::HlaExtendableFixedRecord),
+            _ => Some(FixedRecordEncodingType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct FieldType {
     pub name: String,
     pub data_type: Option<ReferenceType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for FieldType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'fixedRecrodData -> field -> name' found",
-            ),
-            data_type: get_child_element_as_type(e, "dataType"),
+impl FromElement for FieldType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
+            data_type: get_child_element_as_type(cx, e, "dataType"),
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct VariantRecordDataTypesType {
     pub variant_record_datas: Option<Vec<VariantRecordDataType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for VariantRecordDataTypesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            variant_record_datas: get_child_elements_as_type_as_option(e, "variantRecordData"),
-        }
+impl FromElement for VariantRecordDataTypesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            variant_record_datas: get_child_elements_as_type_as_option(cx, e, "variantRecordData"),
+            annotations: get_annotations(e, &["variantRecordData"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct VariantRecordDataType {
     pub name: String,
     pub discriminant: Option<String>,
@@ -1247,101 +3056,341 @@ pub struct VariantRecordDataType {
     pub alternatives: Option<Vec<AlternativeType>>,
     pub encoding: Option<VariantRecordEncodingType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for VariantRecordDataType {
-    fn from(e: &Element) -> Self {
-        Self {
-            name: get_text_of_child_element_or_panic(
-                e,
-                "name",
-                "No 'variantRecordData -> name' found",
-            ),
+impl FromElement for VariantRecordDataType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            name: get_required_text_of_child_element(cx, e, "name"),
             discriminant: get_text_of_child_element(e, "discriminant"),
-            data_type: get_child_element_as_type(e, "dataType"),
-            alternatives: get_child_elements_as_type_as_option(e, "alternative"),
-            encoding: get_child_element_as_type(e, "encoding"),
+            data_type: get_child_element_as_type(cx, e, "dataType"),
+            alternatives: get_child_elements_as_type_as_option(cx, e, "alternative"),
+            encoding: get_child_element_as_type(cx, e, "encoding"),
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &["alternative"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct AlternativeType {
     pub enumerator: Option<String>,
     pub name: Option<String>,
     pub data_type: Option<ReferenceType>,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for AlternativeType {
-    fn from(e: &Element) -> Self {
-        Self {
+impl FromElement for AlternativeType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
             enumerator: get_text_of_child_element(e, "enumerator"),
             name: get_text_of_child_element(e, "name"),
-            data_type: get_child_element_as_type(e, "dataType"),
+            data_type: get_child_element_as_type(cx, e, "dataType"),
             semantics: get_text_of_child_element(e, "semantics"),
-        }
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum VariantRecordEncodingType {
     HlaVariantRecord,
+    /// Added by IEEE 1516.2-2025.
+    HlaExtendableVariantRecord,
     Other(String),
 }
 
-impl From<&Element> for VariantRecordEncodingType {
-    fn from(e: &Element) -> Self {
+impl FromElement for VariantRecordEncodingType {
+    fn from_element(e: &Element, _cx: &mut Context) -> Option<Self> {
         let text = get_element_text(e);
         match text.as_str() {
-            "HLAvariantRecord" => VariantRecordEncodingType::HlaVariantRecord,
-            _ => VariantRecordEncodingType::Other(text),
+            "HLAvariantRecord" => Some(VariantRecordEncodingType::HlaVariantRecord),
+            "HLAextendableVariantRecord" => {
+                Some(VariantRecordEncodingType::HlaExtendableVariantRecord)
+            }
+            _ => Some(VariantRecordEncodingType::Other(text)),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct NotesType {
     pub notes: Option<Vec<NoteType>>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for NotesType {
-    fn from(e: &Element) -> Self {
-        Self {
-            notes: get_child_elements_as_type_as_option(e, "note"),
-        }
+impl FromElement for NotesType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            notes: get_child_elements_as_type_as_option(cx, e, "note"),
+            annotations: get_annotations(e, &["note"]),
+            span: cx.span(e),
+        })
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct NoteType {
     pub label: String,
     pub semantics: Option<String>,
+    pub annotations: Option<Vec<AnnotationType>>,
+    pub span: SourceSpan,
 }
 
-impl From<&Element> for NoteType {
-    fn from(e: &Element) -> Self {
-        Self {
-            label: get_text_of_child_element_or_panic(e, "label", "No 'note -> label' found"),
+impl FromElement for NoteType {
+    fn from_element(e: &Element, cx: &mut Context) -> Option<Self> {
+        Some(Self {
+            label: get_required_text_of_child_element(cx, e, "label"),
             semantics: get_text_of_child_element(e, "semantics"),
+            annotations: get_annotations(e, &[]),
+            span: cx.span(e),
+        })
+    }
+}
+
+/// How to treat a document whose root element is not in one of the
+/// `OMT_NAMESPACES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NamespacePolicy {
+    /// Fail with an `UnknownNamespace` error.
+    #[default]
+    Reject,
+    /// Convert the document anyway, with an `UnknownNamespace` warning.
+    Warn,
+}
+
+/// How a document is read into an object model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseBackend {
+    /// Read the whole document into a DOM, then convert the DOM.
+    #[default]
+    Dom,
+    /// Convert the document as it is read, without keeping the rows of the
+    /// datatype and note tables in memory once converted. For very large
+    /// documents, such as those of enumeration modules. The object model
+    /// and the problems found are those of the `Dom` backend, though the
+    /// problems may be in a different order.
+    Streaming,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseOptions {
+    pub unknown_namespace: NamespacePolicy,
+    /// How XML documents are read. FED files are always read whole.
+    pub backend: ParseBackend,
+    /// The file the document was read from, recorded in the source spans of
+    /// the object model. Set by `parse_file_with_options` when absent.
+    pub source_file: Option<PathBuf>,
+}
+
+/// An object model, along with the problems found in its document that did not
+/// prevent it from being converted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parsed {
+    pub object_model: ObjectModelType,
+    pub warnings: Vec<FomError>,
+    /// The format of the document. See `SourceFormat::absent_fields` for the
+    /// parts of the object model that documents in older formats cannot
+    /// specify.
+    pub format: SourceFormat,
+}
+
+/// Check that the root element of a document is in one of the OMT namespaces.
+fn check_namespace(cx: &mut Context, e: &Element, options: &ParseOptions) {
+    match &e.namespace {
+        None => cx.warning(FomErrorKind::MissingNamespace, e, None),
+        Some(namespace) if !OMT_NAMESPACES.contains(&namespace.as_str()) => {
+            match options.unknown_namespace {
+                NamespacePolicy::Reject => {
+                    cx.error(FomErrorKind::UnknownNamespace, e, Some(namespace.clone()))
+                }
+                NamespacePolicy::Warn => {
+                    cx.warning(FomErrorKind::UnknownNamespace, e, Some(namespace.clone()))
+                }
+            }
+        }
+        Some(_) => {}
+    }
+}
+
+/// Convert the root `objectModel` element of an OMT document into an
+/// ObjectModelType, as directed by the provided options. Conversion does not
+/// stop at the first problem; every problem found in the document is returned.
+/// IEEE 1516.2-2000 documents are recognized by their root element and read
+/// as the equivalent 2010 document.
+pub fn parse_object_model_with_options(
+    e: &Element,
+    options: &ParseOptions,
+) -> Result<Parsed, FomErrors> {
+    if legacy::is_ieee1516_2000(e) {
+        let upgraded = legacy::upgrade_ieee1516_2000(e);
+        return convert_object_model(&upgraded, options, SourceFormat::Ieee1516_2000);
+    }
+    convert_object_model(e, options, omt_format(e))
+}
+
+/// Return the format of a document whose root element is that of a 2010 or
+/// later OMT document.
+fn omt_format(e: &Element) -> SourceFormat {
+    let version = e
+        .namespace
+        .as_deref()
+        .and_then(SchemaVersion::from_namespace);
+    match version {
+        Some(SchemaVersion::Ieee1516_2025) => SourceFormat::Ieee1516_2025,
+        _ => SourceFormat::Ieee1516_2010,
+    }
+}
+
+/// Convert the root `objectModel` element of a 2010 OMT document, read from a
+/// document in the provided format, into an ObjectModelType.
+fn convert_object_model(
+    e: &Element,
+    options: &ParseOptions,
+    format: SourceFormat,
+) -> Result<Parsed, FomErrors> {
+    let mut cx = Context::new();
+    cx.file = options.source_file.as_deref().map(Arc::from);
+    cx.enter(e);
+    check_namespace(&mut cx, e, options);
+    let fom = ObjectModelType::from_element(e, &mut cx);
+    if let Some(fom) = &fom {
+        let values = get_enumerator_values(&mut cx, e);
+        check_enumerator_values(&mut cx, values, fom);
+        if let Some(version) = fom.schema_version() {
+            version::check_schema_version(&mut cx, e, version);
         }
     }
+    cx.leave();
+    into_parsed(cx, fom, format)
+}
+
+/// Return the provided object model with the warnings recorded in the
+/// context, or the errors recorded in it if there are any.
+fn into_parsed(
+    cx: Context,
+    fom: Option<ObjectModelType>,
+    format: SourceFormat,
+) -> Result<Parsed, FomErrors> {
+    match fom {
+        Some(object_model) if cx.errors.is_empty() => Ok(Parsed {
+            object_model,
+            warnings: cx.warnings,
+            format,
+        }),
+        _ => Err(FomErrors(cx.errors)),
+    }
+}
+
+/// Convert the root `objectModel` element of an OMT document into an
+/// ObjectModelType. Conversion does not stop at the first problem; every
+/// problem found in the document is returned. Documents that are not in one of
+/// the `OMT_NAMESPACES` are rejected.
+pub fn parse_object_model(e: &Element) -> Result<ObjectModelType, FomErrors> {
+    parse_object_model_with_options(e, &ParseOptions::default()).map(|parsed| parsed.object_model)
+}
+
+impl TryFrom<&Element> for ObjectModelType {
+    type Error = FomErrors;
+
+    fn try_from(e: &Element) -> Result<Self, Self::Error> {
+        parse_object_model(e)
+    }
+}
+
+/// Read an object model from the provided reader, as directed by the provided
+/// options.
+pub fn parse_reader_with_options<R: Read>(r: R, options: &ParseOptions) -> Result<Parsed, Error> {
+    if options.backend == ParseBackend::Streaming {
+        return stream::parse_reader(r, options);
+    }
+    let fom_as_xml = Element::parse(r)?;
+    Ok(parse_object_model_with_options(&fom_as_xml, options)?)
 }
 
-pub fn parse<R: Read>(r: R) -> Result<(), ParseError> {
+/// Read an object model from the provided reader.
+pub fn parse_reader<R: Read>(r: R) -> Result<ObjectModelType, Error> {
     let fom_as_xml = Element::parse(r)?;
-    let fom = ObjectModelType::from(&fom_as_xml);
+    Ok(ObjectModelType::try_from(&fom_as_xml)?)
+}
+
+/// Read an object model from the file at the provided path, as directed by the
+/// provided options. The source spans of the object model name the file. Files
+/// with the `.fed` extension are read as HLA 1.3 FED files.
+pub fn parse_file_with_options<P: AsRef<Path>>(
+    path: P,
+    options: &ParseOptions,
+) -> Result<Parsed, Error> {
+    let path = path.as_ref();
+    let mut options = options.clone();
+    if options.source_file.is_none() {
+        options.source_file = Some(path.to_path_buf());
+    }
+    let is_fed = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("fed"));
+    if is_fed {
+        parse_fed_reader_with_options(File::open(path)?, &options)
+    } else {
+        parse_reader_with_options(File::open(path)?, &options)
+    }
+}
+
+/// Read an object model from the file at the provided path.
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<ObjectModelType, Error> {
+    parse_file_with_options(path, &ParseOptions::default()).map(|parsed| parsed.object_model)
+}
+
+/// Read an object model from the provided string.
+pub fn parse_str(s: &str) -> Result<ObjectModelType, Error> {
+    parse_reader(s.as_bytes())
+}
 
-    println!("{:?}", fom.model_identification.unwrap().name);
+/// Read an object model from the HLA 1.3 FED file provided by the reader, as
+/// directed by the provided options. See the `fed` module for how FED files
+/// correspond to object models.
+pub fn parse_fed_reader_with_options<R: Read>(
+    mut r: R,
+    options: &ParseOptions,
+) -> Result<Parsed, Error> {
+    let mut text = String::new();
+    r.read_to_string(&mut text)?;
+    let fom_as_xml = fed::parse_str(&text)?;
+    Ok(convert_object_model(
+        &fom_as_xml,
+        options,
+        SourceFormat::Hla13Fed,
+    )?)
+}
 
-    Ok(())
+/// Read an object model from the provided HLA 1.3 FED file.
+pub fn parse_fed_str(s: &str) -> Result<ObjectModelType, Error> {
+    parse_fed_reader_with_options(s.as_bytes(), &ParseOptions::default())
+        .map(|parsed| parsed.object_model)
+}
+
+/// Read an object model from the provided reader. Equivalent to `parse_reader`.
+pub fn parse<R: Read>(r: R) -> Result<ObjectModelType, Error> {
+    parse_reader(r)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use dom::Node;
+
     #[test]
     fn test_get_element_text() {
         let mut el = Element::new("root");
         let expected_text = String::from("text");
-        el.children.push(XMLNode::Text(expected_text.clone()));
+        el.children.push(Node::Text(expected_text.clone()));
         let extracted_text = get_element_text(&el);
         assert_eq!(extracted_text, expected_text);
     }
@@ -1351,8 +3400,8 @@ mod tests {
         let mut root = Element::new("root");
         let mut child = Element::new("child");
         let expected_text = String::from("text");
-        child.children.push(XMLNode::Text(expected_text.clone()));
-        root.children.push(XMLNode::Element(child));
+        child.children.push(Node::Text(expected_text.clone()));
+        root.children.push(Node::Element(child));
 
         let extracted_text = get_text_of_child_element(&root, "child");
         assert_eq!(Some(expected_text), extracted_text);
@@ -1360,14 +3409,569 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn test_get_text_of_child_element_or_panic() {
+    fn test_get_required_text_of_child_element() {
         let mut root = Element::new("root");
         let mut child = Element::new("child");
         let expected_text = String::from("text");
-        child.children.push(XMLNode::Text(expected_text.clone()));
-        root.children.push(XMLNode::Element(child));
+        child.children.push(Node::Text(expected_text.clone()));
+        root.children.push(Node::Element(child));
+
+        let mut cx = Context::new();
+        let extracted_text = get_required_text_of_child_element(&mut cx, &root, "child");
+        assert_eq!(expected_text, extracted_text);
+        assert!(cx.errors.is_empty());
 
-        let _ = get_text_of_child_element_or_panic(&root, "non-child", "panic message");
+        let _ = get_required_text_of_child_element(&mut cx, &root, "non-child");
+        assert_eq!(1, cx.errors.len());
+        assert_eq!(
+            FomErrorKind::MissingElement(String::from("non-child")),
+            cx.errors[0].kind
+        );
+    }
+
+    #[test]
+    fn test_parse_object_model_collects_all_errors() {
+        let xml = r#"<objectModel>
+  <objects>
+    <objectClass>
+      <name>HLAobjectRoot</name>
+      <sharing>Publsh</sharing>
+      <attribute>
+        <name>HLAprivilegeToDeleteObject</name>
+        <order>Recieve</order>
+      </attribute>
+    </objectClass>
+  </objects>
+  <transportations>
+    <transportation>
+      <reliable>Yes</reliable>
+    </transportation>
+  </transportations>
+</objectModel>"#;
+        let root = Element::parse(xml.as_bytes()).unwrap();
+        let errors: Vec<FomError> = parse_object_model(&root).unwrap_err().into_iter().collect();
+
+        assert_eq!(3, errors.len());
+        assert_eq!(FomErrorKind::UnknownValue, errors[0].kind);
+        assert_eq!(
+            "/objectModel/objects/objectClass[HLAobjectRoot]/sharing",
+            errors[0].path
+        );
+        assert_eq!(Some(String::from("Publsh")), errors[0].value);
+        assert_eq!(dom::Position { line: 5, column: 7 }, errors[0].position);
+
+        assert_eq!(
+            "/objectModel/objects/objectClass[HLAobjectRoot]/attribute[HLAprivilegeToDeleteObject]/order",
+            errors[1].path
+        );
+        assert_eq!(Some(String::from("Recieve")), errors[1].value);
+
+        assert_eq!(
+            FomErrorKind::MissingElement(String::from("name")),
+            errors[2].kind
+        );
+        assert_eq!(
+            "/objectModel/transportations/transportation",
+            errors[2].path
+        );
+        assert_eq!(
+            dom::Position {
+                line: 13,
+                column: 5
+            },
+            errors[2].position
+        );
+    }
+
+    #[test]
+    fn test_parse_str_returns_model() {
+        let xml = r#"<objectModel>
+  <modelIdentification>
+    <name>Test</name>
+    <type>FOM</type>
+  </modelIdentification>
+  <transportations>
+    <transportation>
+      <name>HLAreliable</name>
+      <reliable>Yes</reliable>
+    </transportation>
+  </transportations>
+</objectModel>"#;
+        let fom = parse_str(xml).unwrap();
+
+        let model_identification = fom.model_identification.as_ref().unwrap();
+        assert_eq!(Some(String::from("Test")), model_identification.name);
+        assert_eq!(Some(ModelType::FOM), model_identification.model_type);
+
+        let transportations = fom.transportations.as_ref().unwrap();
+        assert_eq!(
+            Some(vec![TransportationType {
+                name: String::from("HLAreliable"),
+                reliable: Some(ReliableType::Yes),
+                semantics: None,
+                annotations: None,
+                span: SourceSpan {
+                    file: None,
+                    start: Position { line: 7, column: 5 },
+                    end: Position {
+                        line: 10,
+                        column: 22
+                    },
+                },
+            }]),
+            transportations.transportations
+        );
+        assert_eq!(fom, fom.clone());
+    }
+
+    #[test]
+    fn test_parse_service_utilization() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <serviceUtilization>
+    <connect section="4.2" isCallback="false" isUsed="true"/>
+    <connectionLost section="4.4" isCallback="true" isUsed="false"/>
+    <publishObjectClassAttributes section="5.3" isCallback="false" isUsed="true"/>
+    <timeAdvanceGrant isUsed="true"/>
+  </serviceUtilization>
+</objectModel>"#;
+        let root = Element::parse(xml.as_bytes()).unwrap();
+        let parsed = parse_object_model_with_options(&root, &ParseOptions::default()).unwrap();
+        assert_eq!(1, parsed.warnings.len());
+        assert_eq!(
+            "/objectModel/serviceUtilization/publishObjectClassAttributes/@section",
+            parsed.warnings[0].path
+        );
+        assert_eq!(
+            "value '5.3' is not the fixed value '5.2', which replaces it",
+            parsed.warnings[0].message()
+        );
+        let e = serialize_object_model(&parsed.object_model);
+        let publish = e
+            .get_child("serviceUtilization")
+            .and_then(|e| e.get_child("publishObjectClassAttributes"))
+            .unwrap();
+        assert_eq!(Some("5.2"), publish.get_attribute("section"));
+
+        let service_utilization = parsed.object_model.service_utilization.unwrap();
+        assert_eq!(
+            Some(ServiceInfoType {
+                section: ServiceSectionType {
+                    clause: 8,
+                    subclause: 13
+                },
+                is_callback: true,
+                is_used: true,
+            }),
+            service_utilization.time_advance_grant
+        );
+
+        let used: Vec<&str> = service_utilization
+            .used_services()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            vec![
+                "connect",
+                "publishObjectClassAttributes",
+                "timeAdvanceGrant"
+            ],
+            used
+        );
+
+        let groups: Vec<(ServiceGroup, usize)> = service_utilization
+            .services_by_group()
+            .into_iter()
+            .map(|(group, services)| (group, services.len()))
+            .collect();
+        assert_eq!(
+            vec![
+                (ServiceGroup::FederationManagement, 2),
+                (ServiceGroup::DeclarationManagement, 1),
+                (ServiceGroup::TimeManagement, 1),
+            ],
+            groups
+        );
+    }
+
+    #[test]
+    fn test_parse_dimensions() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <dimensions>
+    <dimension>
+      <name>SodaFlavor</name>
+      <dataType>FlavorType</dataType>
+      <upperBound>3</upperBound>
+      <normalization>linearEnumerated (Flavor, [Cola, Orange, RootBeer])</normalization>
+      <value>[0..3)</value>
+    </dimension>
+    <dimension>
+      <name>BarQuantity</name>
+      <upperBound>25</upperBound>
+      <normalization>linear (NumberCups, 1, 25)</normalization>
+      <value>[0)</value>
+    </dimension>
+    <dimension>
+      <name>HLAfederate</name>
+      <normalization>Normalize Federate Handle service</normalization>
+      <value>Excluded</value>
+    </dimension>
+    <dimension>
+      <name>Altitude</name>
+      <upperBound>-1</upperBound>
+      <normalization>logarithmic (Altitude, ten, 1000)</normalization>
+    </dimension>
+    <dimension>
+      <name>Cups</name>
+      <upperBound>25</upperBound>
+      <normalization>linear(Cups,1.0,2.50E1)</normalization>
+    </dimension>
+  </dimensions>
+</objectModel>"#;
+        let root = Element::parse(xml.as_bytes()).unwrap();
+        let parsed = parse_object_model_with_options(&root, &ParseOptions::default()).unwrap();
+        let warnings: Vec<&str> = parsed.warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(
+            vec![
+                "/objectModel/dimensions/dimension[Altitude]/upperBound",
+                "/objectModel/dimensions/dimension[Altitude]/normalization",
+            ],
+            warnings
+        );
+
+        let fom = parsed.object_model;
+        let dimensions = fom
+            .dimensions
+            .as_ref()
+            .unwrap()
+            .dimensions
+            .as_ref()
+            .unwrap();
+        assert_eq!(5, dimensions.len());
+
+        let soda_flavor = &dimensions[0];
+        assert_eq!(Some(&3), soda_flavor.upper_bound.as_ref().unwrap().value());
+        assert_eq!(
+            Some(DimensionValueType::Range {
+                lower: 0,
+                upper: Some(3)
+            }),
+            soda_flavor.value
+        );
+        let normalization = soda_flavor.normalization.as_ref().unwrap().value().unwrap();
+        assert_eq!(Some("Flavor"), normalization.domain());
+        assert_eq!(Some(1), normalization.normalize_enumerator("Orange", 3));
+        assert_eq!(None, normalization.normalize_enumerator("Lemon", 3));
+
+        let bar_quantity = dimensions[1]
+            .normalization
+            .as_ref()
+            .unwrap()
+            .value()
+            .unwrap();
+        assert_eq!(
+            &NormalizationType::Linear {
+                domain: String::from("NumberCups"),
+                lower: 1.0,
+                upper: 25.0,
+            },
+            bar_quantity
+        );
+        assert_eq!("linear (NumberCups, 1, 25)", bar_quantity.to_string());
+        assert_eq!(Some(0), bar_quantity.normalize(1.0, 25));
+        assert_eq!(Some(12), bar_quantity.normalize(13.0, 25));
+        assert_eq!(Some(24), bar_quantity.normalize(100.0, 25));
+
+        assert_eq!(
+            Some(&NormalizationType::Other(String::from(
+                "Normalize Federate Handle service"
+            ))),
+            dimensions[2].normalization.as_ref().unwrap().value()
+        );
+        assert_eq!(Some(DimensionValueType::Excluded), dimensions[2].value);
+
+        // Values that do not fit are kept as text, and everything is written
+        // back as it was read.
+        let altitude = &dimensions[3];
+        assert_eq!(
+            Some(&TypedValue::Invalid(String::from("-1"))),
+            altitude.upper_bound.as_ref()
+        );
+        assert!(!altitude.normalization.as_ref().unwrap().is_valid());
+        let cups = dimensions[4].normalization.as_ref().unwrap();
+        assert_eq!(Some(24), cups.value().unwrap().normalize(25.0, 25));
+        let e = serialize_object_model(&fom);
+        let normalizations: Vec<String> = e
+            .get_child("dimensions")
+            .unwrap()
+            .child_elements()
+            .filter_map(|dimension| get_text_of_child_element(dimension, "normalization"))
+            .collect();
+        assert_eq!("logarithmic (Altitude, ten, 1000)", normalizations[3]);
+        assert_eq!("linear(Cups,1.0,2.50E1)", normalizations[4]);
+        assert!(fom.same_model(&parse_object_model(&e).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_enumerated_normalization() {
+        let normalization =
+            parse_normalization("enumerated (Flavor, [Cola -> 2, Orange -> 0])").unwrap();
+        assert_eq!(Some(2), normalization.normalize_enumerator("Cola", 3));
+        assert_eq!(Some(0), normalization.normalize_enumerator("Orange", 3));
+        assert_eq!(
+            "enumerated (Flavor, [Cola -> 2, Orange -> 0])",
+            normalization.to_string()
+        );
+        assert!(parse_normalization("enumerated (Flavor, [Cola])").is_err());
+    }
+
+    #[test]
+    fn test_parse_cardinality() {
+        assert_eq!(Ok(Cardinality::Fixed(16)), Cardinality::from_text("16"));
+        assert_eq!(Ok(Cardinality::Dynamic), Cardinality::from_text("Dynamic"));
+        assert_eq!(
+            Ok(Cardinality::Range(1, 2147483647)),
+            Cardinality::from_text("[1..2147483647]")
+        );
+        let cardinality = Cardinality::from_text("3,[0..8],Dynamic").unwrap();
+        assert_eq!(
+            &[
+                Cardinality::Fixed(3),
+                Cardinality::Range(0, 8),
+                Cardinality::Dynamic
+            ],
+            cardinality.dimensions()
+        );
+        assert_eq!("3,[0..8],Dynamic", cardinality.to_string());
+        assert_eq!(&[Cardinality::Fixed(3)], Cardinality::Fixed(3).dimensions());
+        for text in ["", "dynamic", "-1", "[8..0]", "[0..]", "3,"] {
+            assert_eq!(
+                Err(FomErrorKind::InvalidValue),
+                Cardinality::from_text(text),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_typed_data_type_values() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <updateRates>
+    <updateRate>
+      <name>Fast</name>
+      <rate>60.0</rate>
+    </updateRate>
+    <updateRate>
+      <name>Unknown</name>
+      <rate>fast</rate>
+    </updateRate>
+  </updateRates>
+  <dataTypes>
+    <basicDataRepresentations>
+      <basicData>
+        <name>UnsignedShort</name>
+        <size>16</size>
+        <interpretation>Integer in the range [0, 2^16-1]</interpretation>
+      </basicData>
+    </basicDataRepresentations>
+    <simpleDataTypes>
+      <simpleData>
+        <name>Speed</name>
+        <representation>HLAfloat32BE</representation>
+        <resolution>0.01</resolution>
+        <accuracy>Perfect</accuracy>
+      </simpleData>
+    </simpleDataTypes>
+    <enumeratedDataTypes>
+      <enumeratedData>
+        <name>Color</name>
+        <representation>HLAoctet</representation>
+        <enumerator>
+          <name>Red</name>
+          <value>1</value>
+          <value>256</value>
+        </enumerator>
+      </enumeratedData>
+      <enumeratedData>
+        <name>Size</name>
+        <representation>UnsignedShort</representation>
+        <enumerator>
+          <name>Large</name>
+          <value>-1</value>
+        </enumerator>
+        <enumerator>
+          <name>Small</name>
+          <value>one</value>
+        </enumerator>
+      </enumeratedData>
+    </enumeratedDataTypes>
+    <arrayDataTypes>
+      <arrayData>
+        <name>Matrix</name>
+        <dataType>HLAfloat32BE</dataType>
+        <cardinality>3,3</cardinality>
+        <encoding>HLAfixedArray</encoding>
+      </arrayData>
+    </arrayDataTypes>
+  </dataTypes>
+</objectModel>"#;
+        let root = Element::parse(xml.as_bytes()).unwrap();
+        let parsed = parse_object_model_with_options(&root, &ParseOptions::default()).unwrap();
+        let warnings: Vec<(String, FomErrorKind)> = parsed
+            .warnings
+            .iter()
+            .map(|w| (w.path.clone(), w.kind.clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    String::from("/objectModel/updateRates/updateRate[Unknown]/rate"),
+                    FomErrorKind::InvalidValue
+                ),
+                (
+                    String::from("/objectModel/dataTypes/enumeratedDataTypes/enumeratedData[Size]/enumerator[Small]/value"),
+                    FomErrorKind::InvalidValue
+                ),
+                (
+                    String::from("/objectModel/dataTypes/enumeratedDataTypes/enumeratedData[Color]/enumerator[Red]/value"),
+                    FomErrorKind::OutOfRange(String::from("HLAoctet"))
+                ),
+                (
+                    String::from("/objectModel/dataTypes/enumeratedDataTypes/enumeratedData[Size]/enumerator[Large]/value"),
+                    FomErrorKind::OutOfRange(String::from("UnsignedShort"))
+                ),
+            ],
+            warnings
+        );
+
+        let fom = parsed.object_model;
+        let update_rates = fom
+            .update_rates
+            .as_ref()
+            .unwrap()
+            .update_rates
+            .as_ref()
+            .unwrap();
+        let rate = &update_rates[0].rate.as_ref().unwrap().value;
+        assert_eq!(Some(&60.0), rate.value());
+        assert_eq!("60.0", rate.text());
+        assert_eq!(
+            TypedValue::Invalid(String::from("fast")),
+            update_rates[1].rate.as_ref().unwrap().value
+        );
+
+        let data_types = fom.data_types.as_ref().unwrap();
+        let basic_data = &data_types
+            .basic_data_representations
+            .as_ref()
+            .unwrap()
+            .basic_datas
+            .as_ref()
+            .unwrap()[0];
+        assert_eq!(Some(&16), basic_data.size.as_ref().unwrap().size.value());
+        assert_eq!(Some((0, 65535)), basic_data.integer_range());
+
+        let simple_data = &data_types
+            .simple_data_types
+            .as_ref()
+            .unwrap()
+            .simple_datas
+            .as_ref()
+            .unwrap()[0];
+        assert_eq!(
+            Some(&MeasureType::Value(0.01)),
+            simple_data.resolution.as_ref().unwrap().value()
+        );
+        assert_eq!(
+            Some(&MeasureType::Perfect),
+            simple_data.accuracy.as_ref().unwrap().value()
+        );
+
+        let color = &data_types
+            .enumerated_data_types
+            .as_ref()
+            .unwrap()
+            .enumerated_datas
+            .as_ref()
+            .unwrap()[0];
+        let red = &color.enumerators.as_ref().unwrap()[0];
+        assert_eq!(vec![TypedValue::from(1), TypedValue::from(256)], red.value);
+
+        let matrix = &data_types
+            .array_data_types
+            .as_ref()
+            .unwrap()
+            .array_datas
+            .as_ref()
+            .unwrap()[0];
+        assert_eq!(
+            Some(&Cardinality::MultiDimensional(vec![
+                Cardinality::Fixed(3),
+                Cardinality::Fixed(3)
+            ])),
+            matrix.cardinality.as_ref().unwrap().value()
+        );
+    }
+
+    #[test]
+    fn test_parse_file_reports_missing_file() {
+        match parse_file("no-such-file.xml") {
+            Err(Error::Io(_)) => {}
+            other => panic!("expected an IO error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_ignores_foreign_namespace_elements() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xmlns:ext="http://example.com/extension"
+    xsi:schemaLocation="http://standards.ieee.org/IEEE1516-2010 IEEE1516-DIF-2010.xsd">
+  <objects>
+    <objectClass>
+      <ext:name>Vendor</ext:name>
+      <name>HLAobjectRoot</name>
+      <ext:sharing>NotASharingType</ext:sharing>
+      <sharing>Neither</sharing>
+    </objectClass>
+  </objects>
+</objectModel>"#;
+        let root = Element::parse(xml.as_bytes()).unwrap();
+        let parsed = parse_object_model_with_options(&root, &ParseOptions::default()).unwrap();
+        assert!(parsed.warnings.is_empty());
+
+        let fom = parsed.object_model;
+        assert_eq!(Some(String::from(IEEE1516_2010_NAMESPACE)), fom.namespace);
+        assert_eq!(
+            Some(vec![SchemaLocationType {
+                namespace: String::from(IEEE1516_2010_NAMESPACE),
+                location: String::from("IEEE1516-DIF-2010.xsd"),
+            }]),
+            fom.schema_location
+        );
+        let root_object_class = fom.objects.unwrap().root_object_class.unwrap();
+        assert_eq!("HLAobjectRoot", root_object_class.name);
+        assert_eq!(Some(SharingType::Neither), root_object_class.sharing);
+    }
+
+    #[test]
+    fn test_parse_unknown_namespace() {
+        let xml = r#"<objectModel xmlns="http://example.com/not-an-omt"/>"#;
+        let root = Element::parse(xml.as_bytes()).unwrap();
+
+        let errors = parse_object_model(&root).unwrap_err();
+        assert_eq!(FomErrorKind::UnknownNamespace, errors.0[0].kind);
+
+        let options = ParseOptions {
+            unknown_namespace: NamespacePolicy::Warn,
+            ..ParseOptions::default()
+        };
+        let parsed = parse_object_model_with_options(&root, &options).unwrap();
+        assert_eq!(1, parsed.warnings.len());
+        assert_eq!(FomErrorKind::UnknownNamespace, parsed.warnings[0].kind);
+        assert_eq!(
+            Some(String::from("http://example.com/not-an-omt")),
+            parsed.warnings[0].value
+        );
     }
 }
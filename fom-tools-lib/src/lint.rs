@@ -0,0 +1,962 @@
+//! Lints: checks of object models against style conventions and best
+//! practices, level 4 of `fom check`, in the manner of rust-clippy.
+//!
+//! Each lint has a stable ID, such as `missing-semantics`, a category, and a
+//! level that says whether its findings are ignored, warned about, or denied.
+//! Lints are run by a `LintRegistry`, which holds the built-in lints, and to
+//! which crates can add packs of their own:
+//!
+//! ```
+//! use fom_tools_lib::lint::{Category, Level, Lint, LintContext, LintRegistry};
+//! use fom_tools_lib::ObjectModelType;
+//!
+//! struct ModelName;
+//!
+//! impl Lint for ModelName {
+//!     fn id(&self) -> &'static str {
+//!         "example-model-name"
+//!     }
+//!
+//!     fn category(&self) -> Category {
+//!         Category::Naming
+//!     }
+//!
+//!     fn default_level(&self) -> Level {
+//!         Level::Warn
+//!     }
+//!
+//!     fn description(&self) -> &'static str {
+//!         "object models are named"
+//!     }
+//!
+//!     fn explanation(&self) -> &'static str {
+//!         "Checks that the model identification of an object model names it."
+//!     }
+//!
+//!     fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+//!         let identification = fom.model_identification.as_ref();
+//!         if identification.and_then(|i| i.name.as_ref()).is_none() {
+//!             let path = "/objectModel/modelIdentification";
+//!             cx.report(path, &fom.span, "the object model has no name");
+//!         }
+//!     }
+//! }
+//!
+//! let mut registry = LintRegistry::new();
+//! registry.register(Box::new(ModelName));
+//! let fom = fom_tools_lib::parse_str(
+//!     r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010"/>"#,
+//! )
+//! .unwrap();
+//! let diagnostics = registry.run(&fom);
+//! assert_eq!("example-model-name", diagnostics[0].lint);
+//! ```
+
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// What to do with the findings of a lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// The lint is not run.
+    Allow,
+    /// Its findings are reported as warnings.
+    Warn,
+    /// Its findings are reported as errors, which fail `fom check`.
+    Deny,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Level::Allow => write!(f, "allow"),
+            Level::Warn => write!(f, "warn"),
+            Level::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = UnknownLevel;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Level::Allow),
+            "warn" => Ok(Level::Warn),
+            "deny" => Ok(Level::Deny),
+            _ => Err(UnknownLevel(String::from(s))),
+        }
+    }
+}
+
+/// The named level is not one of `allow`, `warn` and `deny`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLevel(pub String);
+
+impl fmt::Display for UnknownLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown lint level '{}', expected 'allow', 'warn' or 'deny'",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownLevel {}
+
+/// What a lint is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// The names of classes, attributes, parameters and datatypes.
+    Naming,
+    /// The descriptions of the parts of an object model.
+    Semantics,
+    /// The datatype tables.
+    DataTypes,
+    /// The shape of the class hierarchies and tables.
+    Structure,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Category::Naming => write!(f, "naming"),
+            Category::Semantics => write!(f, "semantics"),
+            Category::DataTypes => write!(f, "datatypes"),
+            Category::Structure => write!(f, "structure"),
+        }
+    }
+}
+
+/// A check of an object model against a style convention.
+pub trait Lint: Send + Sync {
+    /// The ID of the lint, in kebab case, e.g. `missing-semantics`. The IDs of
+    /// lints are stable, as configurations name lints by them, and are unique
+    /// within a registry, so lints outside of this crate should start theirs
+    /// with the name of their pack, e.g. `netn-`.
+    fn id(&self) -> &'static str;
+
+    fn category(&self) -> Category;
+
+    /// The level of the lint unless configured otherwise.
+    fn default_level(&self) -> Level;
+
+    /// What the lint checks for, in a few words, e.g. `parts of the object
+    /// model have semantics`.
+    fn description(&self) -> &'static str;
+
+    /// A longer explanation of what the lint checks for and why, shown by
+    /// `fom check --explain`.
+    fn explanation(&self) -> &'static str;
+
+    /// Check the object model, and report each finding to the context.
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext);
+}
+
+/// A package of lints, such as those for the conventions of an organisation,
+/// that can be added to a registry as a whole.
+pub trait LintPack {
+    /// The name of the pack, e.g. `netn`.
+    fn name(&self) -> &'static str;
+
+    fn lints(&self) -> Vec<Box<dyn Lint>>;
+}
+
+/// A finding of a lint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// The ID of the lint.
+    pub lint: &'static str,
+    /// The level of the lint when it was run, either `Warn` or `Deny`.
+    pub level: Level,
+    /// The location of the offending part within the object model, in the
+    /// form of the path of a `FomError`.
+    pub path: String,
+    pub message: String,
+    /// Where the offending part is in its source document.
+    pub span: SourceSpan,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let severity = match self.level {
+            Level::Deny => "error",
+            _ => "warning",
+        };
+        write!(
+            f,
+            "{}: {}: {}: {} [{}]",
+            self.span.start, severity, self.path, self.message, self.lint
+        )
+    }
+}
+
+/// Where a lint reports its findings.
+pub struct LintContext {
+    lint: &'static str,
+    level: Level,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl LintContext {
+    /// Report a finding about the part of the object model at the path.
+    pub fn report(
+        &mut self,
+        path: impl Into<String>,
+        span: &SourceSpan,
+        message: impl Into<String>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            lint: self.lint,
+            level: self.level,
+            path: path.into(),
+            message: message.into(),
+            span: span.clone(),
+        });
+    }
+}
+
+/// The named lint is not in the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLint(pub String);
+
+impl fmt::Display for UnknownLint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown lint '{}'", self.0)
+    }
+}
+
+impl std::error::Error for UnknownLint {}
+
+/// The lints to run over object models, and the level of each.
+pub struct LintRegistry {
+    lints: Vec<Box<dyn Lint>>,
+    levels: HashMap<&'static str, Level>,
+}
+
+impl LintRegistry {
+    /// Create a registry of the built-in lints, each at its default level.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register_pack(&Builtin);
+        registry
+    }
+
+    /// Create a registry without any lints.
+    pub fn empty() -> Self {
+        Self {
+            lints: Vec::new(),
+            levels: HashMap::new(),
+        }
+    }
+
+    /// Add the lint, at its default level.
+    ///
+    /// # Panics
+    ///
+    /// If the registry already has a lint with the same ID.
+    pub fn register(&mut self, lint: Box<dyn Lint>) {
+        let id = lint.id();
+        assert!(
+            !self.levels.contains_key(id),
+            "the lint '{}' is already registered",
+            id
+        );
+        self.levels.insert(id, lint.default_level());
+        self.lints.push(lint);
+    }
+
+    /// Add every lint of the pack, at their default levels.
+    ///
+    /// # Panics
+    ///
+    /// If the registry already has a lint with the same ID as one of the pack.
+    pub fn register_pack(&mut self, pack: &dyn LintPack) {
+        for lint in pack.lints() {
+            self.register(lint);
+        }
+    }
+
+    /// Return the lints, in the order they were added.
+    pub fn lints(&self) -> impl Iterator<Item = &dyn Lint> {
+        self.lints.iter().map(|lint| lint.as_ref())
+    }
+
+    /// Return the lint with the provided ID.
+    pub fn get(&self, id: &str) -> Option<&dyn Lint> {
+        self.lints().find(|lint| lint.id() == id)
+    }
+
+    /// Return the level of the lint with the provided ID.
+    pub fn level(&self, id: &str) -> Option<Level> {
+        self.levels.get(id).copied()
+    }
+
+    /// Set the level of the lint with the provided ID.
+    pub fn set_level(&mut self, id: &str, level: Level) -> Result<(), UnknownLint> {
+        match self.levels.get_mut(id) {
+            Some(current) => {
+                *current = level;
+                Ok(())
+            }
+            None => Err(UnknownLint(String::from(id))),
+        }
+    }
+
+    /// Run every lint that is not allowed over the object model, and return
+    /// their findings in document order.
+    pub fn run(&self, fom: &ObjectModelType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for lint in &self.lints {
+            let level = self.levels[lint.id()];
+            if level == Level::Allow {
+                continue;
+            }
+            let mut cx = LintContext {
+                lint: lint.id(),
+                level,
+                diagnostics: Vec::new(),
+            };
+            lint.check(fom, &mut cx);
+            diagnostics.append(&mut cx.diagnostics);
+        }
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+        diagnostics
+    }
+}
+
+impl Default for LintRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Return the location of the object class within its object model, in the
+/// form of the path of a `FomError`.
+pub fn object_class_path(node: &ClassNode<'_, ObjectClassType>) -> String {
+    class_path("objects", "objectClass", node)
+}
+
+/// Return the location of the interaction class within its object model, in
+/// the form of the path of a `FomError`.
+pub fn interaction_class_path(node: &ClassNode<'_, InteractionClassType>) -> String {
+    class_path("interactions", "interactionClass", node)
+}
+
+fn class_path<T: ClassTree>(table: &str, element: &str, node: &ClassNode<'_, T>) -> String {
+    let classes: Vec<String> = node
+        .classes()
+        .iter()
+        .map(|class| format!("{}[{}]", element, class.name()))
+        .collect();
+    format!("/objectModel/{}/{}", table, classes.join("/"))
+}
+
+/// The lints of this crate.
+struct Builtin;
+
+impl LintPack for Builtin {
+    fn name(&self) -> &'static str {
+        "builtin"
+    }
+
+    fn lints(&self) -> Vec<Box<dyn Lint>> {
+        vec![
+            Box::new(ReservedName),
+            Box::new(MissingSemantics),
+            Box::new(DataTypeCycle),
+            Box::new(UnusedDataType),
+            Box::new(EmptyObjectClass),
+        ]
+    }
+}
+
+/// Return the names of the parts of the HLA standard MIM.
+fn mim_names() -> &'static HashSet<String> {
+    static NAMES: OnceLock<HashSet<String>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        let mim = standard_mim();
+        let mut names = HashSet::new();
+        for node in mim.object_classes() {
+            names.insert(node.class().name.clone());
+            names.extend(node.class().members().iter().map(|a| a.name.clone()));
+        }
+        for node in mim.interaction_classes() {
+            names.insert(node.class().name.clone());
+            names.extend(node.class().members().iter().map(|p| p.name.clone()));
+        }
+        names.extend(
+            mim.declared_data_types()
+                .iter()
+                .map(|data_type| String::from(data_type.name())),
+        );
+        let transportations = mim
+            .transportations
+            .iter()
+            .flat_map(|t| t.transportations.iter().flatten());
+        names.extend(transportations.map(|t| t.name.clone()));
+        names
+    })
+}
+
+struct ReservedName;
+
+impl Lint for ReservedName {
+    fn id(&self) -> &'static str {
+        "reserved-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "names that start with 'HLA' are those of the MIM"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks for classes, attributes, parameters and datatypes whose names \
+start with 'HLA', in any case, but that the HLA standard MIM does not define.
+
+IEEE 1516.2-2010 reserves such names for the MIM and for later versions of \
+the standard, so an object model that uses one may clash with a future \
+version. Rename the part, e.g. 'HLAposition' to 'Position'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        let reserved = |name: &str| {
+            name.get(..3)
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case("hla"))
+                && !mim_names().contains(name)
+        };
+        let message = |kind: &str, name: &str| {
+            format!(
+                "{} '{}' starts with 'HLA', which is reserved for the MIM",
+                kind, name
+            )
+        };
+        for node in fom.object_classes() {
+            let class = node.class();
+            let path = object_class_path(&node);
+            if reserved(&class.name) {
+                cx.report(
+                    path.as_str(),
+                    &class.span,
+                    message("object class", &class.name),
+                );
+            }
+            for attribute in class.members() {
+                if reserved(&attribute.name) {
+                    let path = format!("{}/attribute[{}]", path, attribute.name);
+                    cx.report(path, &attribute.span, message("attribute", &attribute.name));
+                }
+            }
+        }
+        for node in fom.interaction_classes() {
+            let class = node.class();
+            let path = interaction_class_path(&node);
+            if reserved(&class.name) {
+                cx.report(
+                    path.as_str(),
+                    &class.span,
+                    message("interaction class", &class.name),
+                );
+            }
+            for parameter in class.members() {
+                if reserved(&parameter.name) {
+                    let path = format!("{}/parameter[{}]", path, parameter.name);
+                    cx.report(path, &parameter.span, message("parameter", &parameter.name));
+                }
+            }
+        }
+        for data_type in fom.declared_data_types() {
+            if reserved(data_type.name()) {
+                cx.report(
+                    data_type.path(),
+                    data_type.span(),
+                    message("datatype", data_type.name()),
+                );
+            }
+        }
+    }
+}
+
+struct MissingSemantics;
+
+impl Lint for MissingSemantics {
+    fn id(&self) -> &'static str {
+        "missing-semantics"
+    }
+
+    fn category(&self) -> Category {
+        Category::Semantics
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "classes, attributes, parameters and datatypes have semantics"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks for object classes, interaction classes, attributes, parameters \
+and datatypes that have no semantics, or whose semantics are empty or 'NA'. \
+Classes restated as the superclasses of the classes an object model declares \
+are left out, as they are described where they are declared.
+
+The semantics are the only description of what a part of an object model \
+means, so federates that do not share them may use it differently."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        let missing = |semantics: Option<&str>| {
+            semantics.is_none_or(|semantics| matches!(semantics.trim(), "" | "NA"))
+        };
+        for node in fom.object_classes() {
+            let class = node.class();
+            let path = object_class_path(&node);
+            // Only the classes an object model declares have a sharing; the
+            // others are restated as the superclasses of those.
+            if class.sharing.is_some() && missing(class.semantics.as_deref()) {
+                let message = format!("object class '{}' has no semantics", class.name);
+                cx.report(path.as_str(), &class.span, message);
+            }
+            for attribute in class.members() {
+                if missing(attribute.semantics.as_deref()) {
+                    let path = format!("{}/attribute[{}]", path, attribute.name);
+                    let message = format!("attribute '{}' has no semantics", attribute.name);
+                    cx.report(path, &attribute.span, message);
+                }
+            }
+        }
+        for node in fom.interaction_classes() {
+            let class = node.class();
+            let path = interaction_class_path(&node);
+            if class.sharing.is_some() && missing(class.semantics.as_deref()) {
+                let message = format!("interaction class '{}' has no semantics", class.name);
+                cx.report(path.as_str(), &class.span, message);
+            }
+            for parameter in class.members() {
+                if missing(parameter.semantics.as_deref()) {
+                    let path = format!("{}/parameter[{}]", path, parameter.name);
+                    let message = format!("parameter '{}' has no semantics", parameter.name);
+                    cx.report(path, &parameter.span, message);
+                }
+            }
+        }
+        for data_type in fom.declared_data_types() {
+            if missing(data_type.semantics()) {
+                let message = format!("datatype '{}' has no semantics", data_type.name());
+                cx.report(data_type.path(), data_type.span(), message);
+            }
+        }
+    }
+}
+
+struct DataTypeCycle;
+
+impl Lint for DataTypeCycle {
+    fn id(&self) -> &'static str {
+        "datatype-cycle"
+    }
+
+    fn category(&self) -> Category {
+        Category::DataTypes
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Deny
+    }
+
+    fn description(&self) -> &'static str {
+        "datatypes do not contain themselves"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks for datatypes that contain themselves, directly or through other \
+datatypes, such as a fixed record with a field of its own type.
+
+The size of a value of such a datatype has no bound. If the cycle passes only \
+through fixed records and fixed arrays, a value would be infinitely large and \
+cannot be encoded at all; through a variable array or a variant record it can \
+be encoded, but code generators that lay values out with a fixed size fail on \
+it. Break the cycle, e.g. by referring to the contained value by an \
+identifier instead."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        let registry = DataTypeRegistry::new(fom);
+        for cycle in registry.cycles() {
+            let Some(data_type) = registry.get(cycle[0]) else {
+                continue;
+            };
+            let message = if cycle.len() == 1 {
+                format!("datatype '{}' contains itself", cycle[0])
+            } else {
+                let names: Vec<String> = cycle
+                    .iter()
+                    .chain(std::iter::once(&cycle[0]))
+                    .map(|name| format!("'{}'", name))
+                    .collect();
+                format!(
+                    "datatype '{}' contains itself through {}",
+                    cycle[0],
+                    names.join(" -> ")
+                )
+            };
+            cx.report(data_type.path(), data_type.span(), message);
+        }
+    }
+}
+
+struct UnusedDataType;
+
+impl Lint for UnusedDataType {
+    fn id(&self) -> &'static str {
+        "unused-datatype"
+    }
+
+    fn category(&self) -> Category {
+        Category::DataTypes
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Allow
+    }
+
+    fn description(&self) -> &'static str {
+        "datatypes are used by the object model that declares them"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks for datatypes that no attribute, parameter, dimension or other \
+datatype of the same object model refers to.
+
+An unused datatype may be left over from a part of the object model that was \
+removed. The lint is allowed by default, as modules that only declare \
+datatypes for other modules, such as RPR-Base, use none of them."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        let registry = DataTypeRegistry::new(fom);
+        let used: HashSet<&str> = registry
+            .references()
+            .iter()
+            .map(|reference| reference.name)
+            .collect();
+        for data_type in fom.declared_data_types() {
+            if !used.contains(data_type.name()) && !mim_names().contains(data_type.name()) {
+                let message = format!("datatype '{}' is never used", data_type.name());
+                cx.report(data_type.path(), data_type.span(), message);
+            }
+        }
+    }
+}
+
+struct EmptyObjectClass;
+
+impl Lint for EmptyObjectClass {
+    fn id(&self) -> &'static str {
+        "empty-object-class"
+    }
+
+    fn category(&self) -> Category {
+        Category::Structure
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Allow
+    }
+
+    fn description(&self) -> &'static str {
+        "object classes have attributes or subclasses"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks for object classes that an object model declares with neither \
+attributes of their own nor subclasses.
+
+The instances of such a class differ from those of its superclass only in \
+their class, which is better conveyed by an attribute, such as an entity type. \
+The lint is allowed by default, as the RPR FOM declares such classes for the \
+kinds of platform, e.g. 'Aircraft'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        for node in fom.object_classes() {
+            let class = node.class();
+            if node.depth() > 0
+                && class.sharing.is_some()
+                && class.members().is_empty()
+                && class.subclasses().is_empty()
+            {
+                let message = format!(
+                    "object class '{}' has neither attributes nor subclasses",
+                    class.name
+                );
+                cx.report(object_class_path(&node), &class.span, message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+    <objects>
+        <objectClass>
+            <name>HLAobjectRoot</name>
+            <objectClass>
+                <name>HLAvehicle</name>
+                <sharing>PublishSubscribe</sharing>
+                <semantics>A vehicle</semantics>
+                <attribute>
+                    <name>Speed</name>
+                    <dataType>SpeedFloat32</dataType>
+                    <semantics>NA</semantics>
+                </attribute>
+                <objectClass>
+                    <name>Car</name>
+                    <sharing>PublishSubscribe</sharing>
+                </objectClass>
+            </objectClass>
+        </objectClass>
+    </objects>
+    <interactions>
+        <interactionClass>
+            <name>HLAinteractionRoot</name>
+            <interactionClass>
+                <name>Collision</name>
+                <sharing>PublishSubscribe</sharing>
+                <semantics>A collision</semantics>
+                <parameter>
+                    <name>HLAlocation</name>
+                    <dataType>LocationStruct</dataType>
+                    <semantics>Where the collision happened</semantics>
+                </parameter>
+            </interactionClass>
+        </interactionClass>
+    </interactions>
+    <dataTypes>
+        <simpleDataTypes>
+            <simpleData>
+                <name>SpeedFloat32</name>
+                <representation>HLAfloat32BE</representation>
+                <semantics>A speed</semantics>
+            </simpleData>
+            <simpleData>
+                <name>UnusedFloat32</name>
+                <representation>HLAfloat32BE</representation>
+            </simpleData>
+        </simpleDataTypes>
+        <fixedRecordDataTypes>
+            <fixedRecordData>
+                <name>LocationStruct</name>
+                <field>
+                    <name>Next</name>
+                    <dataType>LocationStruct</dataType>
+                </field>
+                <encoding>HLAfixedRecord</encoding>
+                <semantics>A location</semantics>
+            </fixedRecordData>
+        </fixedRecordDataTypes>
+    </dataTypes>
+</objectModel>"#;
+
+    /// Run only the lint with the provided ID over the document.
+    fn run(id: &str, document: &str) -> Vec<String> {
+        let mut registry = LintRegistry::new();
+        let ids: Vec<&'static str> = registry.lints().map(|lint| lint.id()).collect();
+        for other in ids {
+            let level = if other == id {
+                Level::Warn
+            } else {
+                Level::Allow
+            };
+            registry.set_level(other, level).unwrap();
+        }
+        let fom = parse_str(document).unwrap();
+        registry.run(&fom).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_registry_has_builtin_lints() {
+        let registry = LintRegistry::new();
+        let lints: Vec<(&str, Category, Level)> = registry
+            .lints()
+            .map(|lint| (lint.id(), lint.category(), lint.default_level()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("reserved-name", Category::Naming, Level::Warn),
+                ("missing-semantics", Category::Semantics, Level::Warn),
+                ("datatype-cycle", Category::DataTypes, Level::Deny),
+                ("unused-datatype", Category::DataTypes, Level::Allow),
+                ("empty-object-class", Category::Structure, Level::Allow),
+            ],
+            lints
+        );
+        assert_eq!(
+            "missing-semantics",
+            registry.get("missing-semantics").unwrap().id()
+        );
+        assert!(registry.get("missing-docs").is_none());
+        assert!(LintRegistry::empty().lints().next().is_none());
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut registry = LintRegistry::new();
+        assert_eq!(Some(Level::Warn), registry.level("reserved-name"));
+        registry.set_level("reserved-name", Level::Deny).unwrap();
+        assert_eq!(Some(Level::Deny), registry.level("reserved-name"));
+        assert_eq!(
+            Err(UnknownLint(String::from("missing-docs"))),
+            registry.set_level("missing-docs", Level::Deny)
+        );
+        assert_eq!(None, registry.level("missing-docs"));
+
+        assert_eq!(Ok(Level::Allow), "allow".parse());
+        assert_eq!(Ok(Level::Warn), "warn".parse());
+        assert_eq!(Ok(Level::Deny), "deny".parse());
+        assert_eq!(
+            Err(UnknownLevel(String::from("forbid"))),
+            "forbid".parse::<Level>()
+        );
+    }
+
+    #[test]
+    fn test_run_reports_at_the_level_of_each_lint() {
+        let mut registry = LintRegistry::new();
+        registry
+            .set_level("missing-semantics", Level::Allow)
+            .unwrap();
+        registry.set_level("reserved-name", Level::Deny).unwrap();
+        let fom = parse_str(DOCUMENT).unwrap();
+        let diagnostics: Vec<(&str, Level)> = registry
+            .run(&fom)
+            .iter()
+            .map(|diagnostic| (diagnostic.lint, diagnostic.level))
+            .collect();
+        assert_eq!(
+            vec![
+                ("reserved-name", Level::Deny),
+                ("reserved-name", Level::Deny),
+                ("datatype-cycle", Level::Deny),
+            ],
+            diagnostics
+        );
+    }
+
+    struct Pack;
+
+    impl LintPack for Pack {
+        fn name(&self) -> &'static str {
+            "pack"
+        }
+
+        fn lints(&self) -> Vec<Box<dyn Lint>> {
+            vec![Box::new(MissingSemantics)]
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the lint 'missing-semantics' is already registered")]
+    fn test_register_rejects_duplicate_ids() {
+        LintRegistry::new().register_pack(&Pack);
+    }
+
+    #[test]
+    fn test_reserved_name() {
+        assert_eq!(
+            vec![
+                "5:13: warning: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[HLAvehicle]: object class 'HLAvehicle' starts with 'HLA', which is reserved for the MIM [reserved-name]",
+                "28:17: warning: /objectModel/interactions/interactionClass[HLAinteractionRoot]/interactionClass[Collision]/parameter[HLAlocation]: parameter 'HLAlocation' starts with 'HLA', which is reserved for the MIM [reserved-name]",
+            ],
+            run("reserved-name", DOCUMENT)
+        );
+    }
+
+    #[test]
+    fn test_missing_semantics() {
+        assert_eq!(
+            vec![
+                "9:17: warning: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[HLAvehicle]/attribute[Speed]: attribute 'Speed' has no semantics [missing-semantics]",
+                "14:17: warning: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[HLAvehicle]/objectClass[Car]: object class 'Car' has no semantics [missing-semantics]",
+                "43:13: warning: /objectModel/dataTypes/simpleDataTypes/simpleData[UnusedFloat32]: datatype 'UnusedFloat32' has no semantics [missing-semantics]",
+            ],
+            run("missing-semantics", DOCUMENT)
+        );
+    }
+
+    #[test]
+    fn test_datatype_cycle() {
+        assert_eq!(
+            vec![
+                "49:13: warning: /objectModel/dataTypes/fixedRecordDataTypes/fixedRecordData[LocationStruct]: datatype 'LocationStruct' contains itself [datatype-cycle]",
+            ],
+            run("datatype-cycle", DOCUMENT)
+        );
+
+        let document = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+    <dataTypes>
+        <arrayDataTypes>
+            <arrayData>
+                <name>PathArray</name>
+                <dataType>StepStruct</dataType>
+                <cardinality>Dynamic</cardinality>
+                <encoding>HLAvariableArray</encoding>
+            </arrayData>
+        </arrayDataTypes>
+        <fixedRecordDataTypes>
+            <fixedRecordData>
+                <name>StepStruct</name>
+                <field>
+                    <name>Rest</name>
+                    <dataType>PathArray</dataType>
+                </field>
+                <encoding>HLAfixedRecord</encoding>
+            </fixedRecordData>
+        </fixedRecordDataTypes>
+    </dataTypes>
+</objectModel>"#;
+        assert_eq!(
+            vec![
+                "4:13: warning: /objectModel/dataTypes/arrayDataTypes/arrayData[PathArray]: datatype 'PathArray' contains itself through 'PathArray' -> 'StepStruct' -> 'PathArray' [datatype-cycle]",
+            ],
+            run("datatype-cycle", document)
+        );
+    }
+
+    #[test]
+    fn test_unused_datatype() {
+        assert_eq!(
+            vec![
+                "43:13: warning: /objectModel/dataTypes/simpleDataTypes/simpleData[UnusedFloat32]: datatype 'UnusedFloat32' is never used [unused-datatype]",
+            ],
+            run("unused-datatype", DOCUMENT)
+        );
+    }
+
+    #[test]
+    fn test_empty_object_class() {
+        assert_eq!(
+            vec![
+                "14:17: warning: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[HLAvehicle]/objectClass[Car]: object class 'Car' has neither attributes nor subclasses [empty-object-class]",
+            ],
+            run("empty-object-class", DOCUMENT)
+        );
+    }
+}
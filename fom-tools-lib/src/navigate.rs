@@ -0,0 +1,685 @@
+//! Lookup of object and interaction classes by name, navigation of the class
+//! trees of an object model, and the attributes and parameters that classes
+//! inherit.
+
+use crate::*;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// A class of an object or interaction class tree.
+pub trait ClassTree: Sized {
+    /// The attributes of object classes, or the parameters of interaction
+    /// classes.
+    type Member: ClassMember;
+
+    fn name(&self) -> &str;
+
+    /// Return the direct subclasses of this class.
+    fn subclasses(&self) -> &[Self];
+
+    /// Return the members declared by this class itself.
+    fn members(&self) -> &[Self::Member];
+}
+
+/// An attribute or parameter of a class.
+pub trait ClassMember {
+    fn name(&self) -> &str;
+}
+
+impl ClassTree for ObjectClassType {
+    type Member = AttributeType;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn subclasses(&self) -> &[Self] {
+        self.object_classes.as_deref().unwrap_or(&[])
+    }
+
+    fn members(&self) -> &[AttributeType] {
+        self.attributes.as_deref().unwrap_or(&[])
+    }
+}
+
+impl ClassTree for InteractionClassType {
+    type Member = ParameterType;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn subclasses(&self) -> &[Self] {
+        self.interaction_classes.as_deref().unwrap_or(&[])
+    }
+
+    fn members(&self) -> &[ParameterType] {
+        self.parameters.as_deref().unwrap_or(&[])
+    }
+}
+
+impl ClassMember for AttributeType {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl ClassMember for ParameterType {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A class within a class tree, along with the classes above it. As the
+/// classes of the object model do not refer to their superclasses, this is
+/// what navigation up the tree starts from.
+#[derive(Debug, PartialEq)]
+pub struct ClassNode<'a, T> {
+    /// The classes from the root class down to and including this class.
+    path: Vec<&'a T>,
+}
+
+// Implemented by hand, as deriving would require the classes to be Clone.
+impl<'a, T> Clone for ClassNode<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<'a, T: ClassTree> ClassNode<'a, T> {
+    /// Create a node for the root class of a tree.
+    pub fn root(class: &'a T) -> Self {
+        Self { path: vec![class] }
+    }
+
+    pub fn class(&self) -> &'a T {
+        self.path[self.path.len() - 1]
+    }
+
+    /// Return the node of the superclass of this class, unless it is the root.
+    pub fn parent(&self) -> Option<ClassNode<'a, T>> {
+        if self.path.len() > 1 {
+            Some(Self {
+                path: self.path[..self.path.len() - 1].to_vec(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Return the classes from the root class down to and including this
+    /// class.
+    pub fn classes(&self) -> &[&'a T] {
+        &self.path
+    }
+
+    /// Return the superclasses of this class, nearest first.
+    pub fn ancestors(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.path[..self.path.len() - 1].iter().rev().copied()
+    }
+
+    /// Return the number of superclasses of this class, i.e. zero for the
+    /// root class.
+    pub fn depth(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    /// Return the names of the classes from the root down to this class,
+    /// separated by dots, e.g. `HLAobjectRoot.BaseEntity.PhysicalEntity`.
+    pub fn qualified_name(&self) -> String {
+        let names: Vec<&str> = self.path.iter().map(|class| class.name()).collect();
+        names.join(".")
+    }
+
+    /// Return the nodes of the direct subclasses of this class.
+    pub fn children(&self) -> impl Iterator<Item = ClassNode<'a, T>> + '_ {
+        self.class()
+            .subclasses()
+            .iter()
+            .map(move |class| self.child(class))
+    }
+
+    /// Return the nodes of every class below this class, depth first.
+    pub fn descendants(&self) -> DepthFirst<'a, T> {
+        let mut walk = DepthFirst { stack: Vec::new() };
+        walk.push_children(self);
+        walk
+    }
+
+    /// Return the nodes of this class and of every class below it, depth first.
+    pub fn depth_first(&self) -> DepthFirst<'a, T> {
+        DepthFirst {
+            stack: vec![self.clone()],
+        }
+    }
+
+    /// Return the nodes of this class and of every class below it, breadth
+    /// first.
+    pub fn breadth_first(&self) -> BreadthFirst<'a, T> {
+        BreadthFirst {
+            queue: VecDeque::from(vec![self.clone()]),
+        }
+    }
+
+    /// Return the node of the class with the provided name relative to this
+    /// class, e.g. `PhysicalEntity.Platform` relative to `BaseEntity`.
+    pub fn lookup(&self, relative_name: &str) -> Option<ClassNode<'a, T>> {
+        let mut node = self.clone();
+        for name in relative_name.split('.') {
+            let class = node
+                .class()
+                .subclasses()
+                .iter()
+                .find(|c| c.name() == name)?;
+            node = node.child(class);
+        }
+        Some(node)
+    }
+
+    /// Return every member of this class, whether declared by the class itself
+    /// or inherited from a superclass, starting with those of the root class.
+    pub fn effective_members(&self) -> Vec<EffectiveMember<'a, T>> {
+        let mut members = Vec::new();
+        for depth in 0..self.path.len() {
+            let declared_by = Self {
+                path: self.path[..=depth].to_vec(),
+            };
+            for member in declared_by.class().members() {
+                let shadowed_at = self.path[depth + 1..].iter().position(|class| {
+                    class
+                        .members()
+                        .iter()
+                        .any(|other| other.name() == member.name())
+                });
+                members.push(EffectiveMember {
+                    member,
+                    declared_by: declared_by.clone(),
+                    shadowed_by: shadowed_at.map(|i| Self {
+                        path: self.path[..=depth + 1 + i].to_vec(),
+                    }),
+                });
+            }
+        }
+        members
+    }
+
+    fn child(&self, class: &'a T) -> ClassNode<'a, T> {
+        let mut path = self.path.clone();
+        path.push(class);
+        Self { path }
+    }
+}
+
+/// An attribute or parameter of a class, along with the class that declares
+/// it.
+#[derive(Debug)]
+pub struct EffectiveMember<'a, T: ClassTree> {
+    pub member: &'a T::Member,
+    pub declared_by: ClassNode<'a, T>,
+    /// The nearest class below the declaring class that declares a member of
+    /// the same name, which the OMT does not permit.
+    pub shadowed_by: Option<ClassNode<'a, T>>,
+}
+
+// Implemented by hand, as deriving would require the classes to be Clone.
+impl<'a, T: ClassTree> Clone for EffectiveMember<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            member: self.member,
+            declared_by: self.declared_by.clone(),
+            shadowed_by: self.shadowed_by.clone(),
+        }
+    }
+}
+
+/// An attribute of an object class, along with the class that declares it.
+pub type EffectiveAttribute<'a> = EffectiveMember<'a, ObjectClassType>;
+
+/// A parameter of an interaction class, along with the class that declares it.
+pub type EffectiveParameter<'a> = EffectiveMember<'a, InteractionClassType>;
+
+/// A depth-first, pre-order walk of a class tree.
+pub struct DepthFirst<'a, T> {
+    stack: Vec<ClassNode<'a, T>>,
+}
+
+impl<'a, T: ClassTree> DepthFirst<'a, T> {
+    fn push_children(&mut self, node: &ClassNode<'a, T>) {
+        // Pushed in reverse so that subclasses are visited in document order.
+        for class in node.class().subclasses().iter().rev() {
+            self.stack.push(node.child(class));
+        }
+    }
+}
+
+impl<'a, T: ClassTree> Iterator for DepthFirst<'a, T> {
+    type Item = ClassNode<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_children(&node);
+        Some(node)
+    }
+}
+
+/// A breadth-first walk of a class tree.
+pub struct BreadthFirst<'a, T> {
+    queue: VecDeque<ClassNode<'a, T>>,
+}
+
+impl<'a, T: ClassTree> Iterator for BreadthFirst<'a, T> {
+    type Item = ClassNode<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(node.children());
+        Some(node)
+    }
+}
+
+/// The reason a class could not be found by its unqualified name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LookupError {
+    NotFound(String),
+    /// More than one class has the name. The qualified names of the classes
+    /// are provided, in depth-first order.
+    Ambiguous(String, Vec<String>),
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LookupError::NotFound(name) => write!(f, "no class named '{}'", name),
+            LookupError::Ambiguous(name, qualified_names) => write!(
+                f,
+                "more than one class named '{}': {}",
+                name,
+                qualified_names.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LookupError {}
+
+/// Return the node of the class with the provided qualified name. The name of
+/// the root class may be left out, as in `BaseEntity.PhysicalEntity`.
+fn lookup<'a, T: ClassTree>(root: Option<&'a T>, qualified_name: &str) -> Option<ClassNode<'a, T>> {
+    let root = ClassNode::root(root?);
+    match qualified_name.split_once('.') {
+        _ if qualified_name == root.class().name() => Some(root),
+        Some((first, rest)) if first == root.class().name() => root.lookup(rest),
+        _ => root.lookup(qualified_name),
+    }
+}
+
+/// Return the node of the single class with the provided unqualified name.
+fn find_unique<'a, T: ClassTree>(
+    root: Option<&'a T>,
+    name: &str,
+) -> Result<ClassNode<'a, T>, LookupError> {
+    let mut found: Vec<ClassNode<'a, T>> = root
+        .map(ClassNode::root)
+        .into_iter()
+        .flat_map(|root| root.depth_first())
+        .filter(|node| node.class().name() == name)
+        .collect();
+    match found.len() {
+        0 => Err(LookupError::NotFound(String::from(name))),
+        1 => Ok(found.remove(0)),
+        _ => Err(LookupError::Ambiguous(
+            String::from(name),
+            found.iter().map(ClassNode::qualified_name).collect(),
+        )),
+    }
+}
+
+impl ObjectModelType {
+    fn root_object_class(&self) -> Option<&ObjectClassType> {
+        self.objects.as_ref()?.root_object_class.as_ref()
+    }
+
+    fn root_interaction_class(&self) -> Option<&InteractionClassType> {
+        self.interactions
+            .as_ref()
+            .map(|interactions| &interactions.interactions)
+    }
+
+    /// Return the object class with the provided qualified name, e.g.
+    /// `HLAobjectRoot.BaseEntity.PhysicalEntity.Platform`. The name of the
+    /// root class may be left out.
+    pub fn object_class(&self, qualified_name: &str) -> Option<&ObjectClassType> {
+        self.object_class_node(qualified_name)
+            .map(|node| node.class())
+    }
+
+    /// Return the node of the object class with the provided qualified name,
+    /// from which its superclasses and subclasses can be navigated.
+    pub fn object_class_node(
+        &self,
+        qualified_name: &str,
+    ) -> Option<ClassNode<'_, ObjectClassType>> {
+        lookup(self.root_object_class(), qualified_name)
+    }
+
+    /// Return the interaction class with the provided qualified name, e.g.
+    /// `HLAinteractionRoot.WeaponFire`. The name of the root class may be left
+    /// out.
+    pub fn interaction_class(&self, qualified_name: &str) -> Option<&InteractionClassType> {
+        self.interaction_class_node(qualified_name)
+            .map(|node| node.class())
+    }
+
+    /// Return the node of the interaction class with the provided qualified
+    /// name, from which its superclasses and subclasses can be navigated.
+    pub fn interaction_class_node(
+        &self,
+        qualified_name: &str,
+    ) -> Option<ClassNode<'_, InteractionClassType>> {
+        lookup(self.root_interaction_class(), qualified_name)
+    }
+
+    /// Return every attribute of the object class with the provided qualified
+    /// name, including those inherited from its superclasses.
+    pub fn effective_attributes(
+        &self,
+        qualified_name: &str,
+    ) -> Option<Vec<EffectiveAttribute<'_>>> {
+        self.object_class_node(qualified_name)
+            .map(|node| node.effective_members())
+    }
+
+    /// Return every parameter of the interaction class with the provided
+    /// qualified name, including those inherited from its superclasses.
+    pub fn effective_parameters(
+        &self,
+        qualified_name: &str,
+    ) -> Option<Vec<EffectiveParameter<'_>>> {
+        self.interaction_class_node(qualified_name)
+            .map(|node| node.effective_members())
+    }
+
+    /// Return the nodes of every object class, depth first from the root.
+    pub fn object_classes(&self) -> DepthFirst<'_, ObjectClassType> {
+        DepthFirst {
+            stack: self
+                .root_object_class()
+                .map(ClassNode::root)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Return the nodes of every interaction class, depth first from the root.
+    pub fn interaction_classes(&self) -> DepthFirst<'_, InteractionClassType> {
+        DepthFirst {
+            stack: self
+                .root_interaction_class()
+                .map(ClassNode::root)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Return the node of the single object class with the provided
+    /// unqualified name, e.g. `Platform`.
+    pub fn find_object_class(
+        &self,
+        name: &str,
+    ) -> Result<ClassNode<'_, ObjectClassType>, LookupError> {
+        find_unique(self.root_object_class(), name)
+    }
+
+    /// Return the node of the single interaction class with the provided
+    /// unqualified name, e.g. `WeaponFire`.
+    pub fn find_interaction_class(
+        &self,
+        name: &str,
+    ) -> Result<ClassNode<'_, InteractionClassType>, LookupError> {
+        find_unique(self.root_interaction_class(), name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object_model() -> ObjectModelType {
+        parse_str(
+            r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <objects>
+    <objectClass>
+      <name>HLAobjectRoot</name>
+      <objectClass>
+        <name>BaseEntity</name>
+        <objectClass>
+          <name>PhysicalEntity</name>
+          <objectClass>
+            <name>Platform</name>
+          </objectClass>
+          <objectClass>
+            <name>Lifeform</name>
+          </objectClass>
+        </objectClass>
+      </objectClass>
+      <objectClass>
+        <name>EmbeddedSystem</name>
+        <objectClass>
+          <name>Platform</name>
+        </objectClass>
+      </objectClass>
+    </objectClass>
+  </objects>
+  <interactions>
+    <interactionClass>
+      <name>HLAinteractionRoot</name>
+      <interactionClass>
+        <name>WeaponFire</name>
+      </interactionClass>
+    </interactionClass>
+  </interactions>
+</objectModel>"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_lookup_qualified_names() {
+        let fom = object_model();
+        let platform = fom
+            .object_class_node("HLAobjectRoot.BaseEntity.PhysicalEntity.Platform")
+            .unwrap();
+        assert_eq!("Platform", platform.class().name);
+        assert_eq!(3, platform.depth());
+        let ancestors: Vec<&str> = platform.ancestors().map(|c| c.name()).collect();
+        assert_eq!(
+            vec!["PhysicalEntity", "BaseEntity", "HLAobjectRoot"],
+            ancestors
+        );
+        assert_eq!(
+            "HLAobjectRoot.BaseEntity.PhysicalEntity",
+            platform.parent().unwrap().qualified_name()
+        );
+
+        assert!(fom
+            .object_class("BaseEntity.PhysicalEntity.Platform")
+            .is_some());
+        assert!(fom.object_class("HLAobjectRoot").is_some());
+        assert!(fom.object_class("HLAobjectRoot.Platform").is_none());
+        assert_eq!(
+            "WeaponFire",
+            fom.interaction_class("HLAinteractionRoot.WeaponFire")
+                .unwrap()
+                .name
+        );
+    }
+
+    #[test]
+    fn test_walk_class_trees() {
+        let fom = object_model();
+        let depth_first: Vec<String> = fom.object_classes().map(|n| n.qualified_name()).collect();
+        assert_eq!(
+            vec![
+                "HLAobjectRoot",
+                "HLAobjectRoot.BaseEntity",
+                "HLAobjectRoot.BaseEntity.PhysicalEntity",
+                "HLAobjectRoot.BaseEntity.PhysicalEntity.Platform",
+                "HLAobjectRoot.BaseEntity.PhysicalEntity.Lifeform",
+                "HLAobjectRoot.EmbeddedSystem",
+                "HLAobjectRoot.EmbeddedSystem.Platform",
+            ],
+            depth_first
+        );
+
+        let root = fom.object_class_node("HLAobjectRoot").unwrap();
+        let breadth_first: Vec<&str> = root.breadth_first().map(|n| n.class().name()).collect();
+        assert_eq!(
+            vec![
+                "HLAobjectRoot",
+                "BaseEntity",
+                "EmbeddedSystem",
+                "PhysicalEntity",
+                "Platform",
+                "Platform",
+                "Lifeform",
+            ],
+            breadth_first
+        );
+
+        let base_entity = fom.object_class_node("BaseEntity").unwrap();
+        let descendants: Vec<&str> = base_entity
+            .descendants()
+            .map(|n| n.class().name())
+            .collect();
+        assert_eq!(vec!["PhysicalEntity", "Platform", "Lifeform"], descendants);
+        let children: Vec<&str> = base_entity.children().map(|n| n.class().name()).collect();
+        assert_eq!(vec!["PhysicalEntity"], children);
+    }
+
+    #[test]
+    fn test_effective_members() {
+        let fom = parse_str(
+            r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <objects>
+    <objectClass>
+      <name>HLAobjectRoot</name>
+      <attribute>
+        <name>HLAprivilegeToDeleteObject</name>
+      </attribute>
+      <objectClass>
+        <name>BaseEntity</name>
+        <attribute>
+          <name>EntityType</name>
+        </attribute>
+        <attribute>
+          <name>Spatial</name>
+        </attribute>
+        <objectClass>
+          <name>Platform</name>
+          <attribute>
+            <name>Spatial</name>
+          </attribute>
+          <attribute>
+            <name>EngineSmokeOn</name>
+          </attribute>
+        </objectClass>
+      </objectClass>
+    </objectClass>
+  </objects>
+  <interactions>
+    <interactionClass>
+      <name>HLAinteractionRoot</name>
+      <interactionClass>
+        <name>WeaponFire</name>
+        <parameter>
+          <name>EventIdentifier</name>
+        </parameter>
+        <interactionClass>
+          <name>MunitionDetonation</name>
+          <parameter>
+            <name>DetonationLocation</name>
+          </parameter>
+        </interactionClass>
+      </interactionClass>
+    </interactionClass>
+  </interactions>
+</objectModel>"#,
+        )
+        .unwrap();
+
+        let attributes = fom
+            .effective_attributes("HLAobjectRoot.BaseEntity.Platform")
+            .unwrap();
+        let summary: Vec<(&str, String, Option<String>)> = attributes
+            .iter()
+            .map(|a| {
+                (
+                    a.member.name.as_str(),
+                    a.declared_by.qualified_name(),
+                    a.shadowed_by.as_ref().map(ClassNode::qualified_name),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    "HLAprivilegeToDeleteObject",
+                    String::from("HLAobjectRoot"),
+                    None
+                ),
+                ("EntityType", String::from("HLAobjectRoot.BaseEntity"), None),
+                (
+                    "Spatial",
+                    String::from("HLAobjectRoot.BaseEntity"),
+                    Some(String::from("HLAobjectRoot.BaseEntity.Platform"))
+                ),
+                (
+                    "Spatial",
+                    String::from("HLAobjectRoot.BaseEntity.Platform"),
+                    None
+                ),
+                (
+                    "EngineSmokeOn",
+                    String::from("HLAobjectRoot.BaseEntity.Platform"),
+                    None
+                ),
+            ],
+            summary
+        );
+
+        let parameters: Vec<&str> = fom
+            .effective_parameters("WeaponFire.MunitionDetonation")
+            .unwrap()
+            .iter()
+            .map(|p| p.member.name.as_str())
+            .collect();
+        assert_eq!(vec!["EventIdentifier", "DetonationLocation"], parameters);
+        assert!(fom.effective_parameters("Collision").is_none());
+    }
+
+    #[test]
+    fn test_find_unqualified_names() {
+        let fom = object_model();
+        assert_eq!(
+            "HLAobjectRoot.BaseEntity.PhysicalEntity.Lifeform",
+            fom.find_object_class("Lifeform").unwrap().qualified_name()
+        );
+        assert_eq!(
+            Err(LookupError::Ambiguous(
+                String::from("Platform"),
+                vec![
+                    String::from("HLAobjectRoot.BaseEntity.PhysicalEntity.Platform"),
+                    String::from("HLAobjectRoot.EmbeddedSystem.Platform"),
+                ]
+            )),
+            fom.find_object_class("Platform")
+        );
+        assert_eq!(
+            Err(LookupError::NotFound(String::from("Aircraft"))),
+            fom.find_object_class("Aircraft")
+        );
+        assert!(fom.find_interaction_class("WeaponFire").is_ok());
+    }
+}
@@ -0,0 +1,1066 @@
+//! The lint pack for the naming conventions of the NATO Education and
+//! Training Network (NETN) FOM, as set out in `NamingConventions.md` of the
+//! NETN FOM 3.0.
+//!
+//! The NETN modules keep many legacy datatypes, and build on those of the RPR
+//! FOM, that do not follow the conventions. The names of those are given to
+//! the pack as exceptions.
+
+use crate::lint::{Category, Level, Lint, LintContext, LintPack};
+use crate::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// The names that the lints of the NETN pack accept whatever they are.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NamingExceptions {
+    /// Object and interaction classes.
+    pub classes: HashSet<String>,
+    /// Attributes and parameters.
+    pub members: HashSet<String>,
+    /// Datatypes. The enumerators of an enumerated datatype are accepted if
+    /// the datatype is.
+    pub data_types: HashSet<String>,
+}
+
+/// The legacy datatypes of the NETN FOM 3.0 modules that do not follow the
+/// naming conventions.
+const NETN_DATA_TYPES: &[&str] = &[
+    "AidTypeEnumType",
+    "ArrayOfAgentTypeEnum",
+    "ArrayOfCommunicationNetworks",
+    "ArrayOfNames",
+    "ArrayOfRepairTypeEnum",
+    "ArrayOfResourceStatus",
+    "ArrayOfSigmas6",
+    "ArrayOfSpottedEntities",
+    "ArrayOfStringType",
+    "ArrayOfTaskIds",
+    "ArrayOfUuid",
+    "AttributeNamesType",
+    "BinArrayType",
+    "BitsPerSecond",
+    "Callsign",
+    "CommunicationNetworkArray",
+    "CommunicationNetworkTypeEnum",
+    "CommunicationServiceTypeEnum",
+    "ConnectionReceiverArray",
+    "ConnectionTypeEnum",
+    "Datetime18",
+    "DesignatedAreaCodeType",
+    "EpochTimeSecInt64",
+    "FormationDataStruct",
+    "FormationInt32",
+    "FunctionIdType",
+    "GMLidentifier",
+    "GeoLocationReferenceVariant",
+    "GeoReferenceVariant",
+    "GeodeticCircle",
+    "GeodeticLocation",
+    "GeodeticPoint",
+    "GeodeticQuadrangle",
+    "Holding",
+    "IMOType",
+    "IncomingConnectionArray",
+    "LinkStatusArray",
+    "MMSIType",
+    "ManeuverIndicatorEnumType",
+    "ManufacturerIdType",
+    "MsgIdEnumType",
+    "NETN_ArrayOfSupplyStruct",
+    "NatoStockNumberArray13",
+    "NavigationStatusEnumType",
+    "NetworkDeviceArray",
+    "NetworkDeviceReceiverCharacteristicsVariant",
+    "NetworkDeviceTransmitterCharacteristicsVariant",
+    "NoOfferReasonEnumType",
+    "PartNumberEnumType",
+    "PhysicalNetworkDescriptionVariant",
+    "PhysicalNetworkTypeEnum",
+    "QuantityInt32",
+    "QuantityUInt32",
+    "Relation",
+    "RequestedConnection",
+    "RequestedConnectionArray",
+    "SerialNumberType",
+    "ShipTypeType",
+    "SpottedEntity",
+    "SpottedEquipment",
+    "SymbolIdentifier",
+    "TimeSecInt32",
+    "TransactionId",
+    "TransferEnumType",
+    "UUID",
+    "UnitModelType",
+];
+
+/// The datatypes of the RPR FOM v2.0 that do not follow the NETN naming
+/// conventions, mostly arrays named after the RPR conventions, e.g.
+/// `WorldLocationStructLengthlessArray`.
+const RPR_DATA_TYPES: &[&str] = &[
+    "AntennaPatternVariantStructLengthlessArray",
+    "ArticulatedParameterStructLengthlessArray",
+    "AttributeValuePairStructArray1Plus",
+    "BreachStructLengthlessArray",
+    "BreachableSegmentStructLengthlessArray",
+    "BreachedStatusArray8",
+    "ClockTimeStructLengthlessArray",
+    "CoefficientsLengthlessArray1Plus",
+    "DatumIdentifierLengthlessArray",
+    "DepthMeterFloat32LengthlessArray",
+    "EntityTypeStructLengthlessArray",
+    "EnvironmentRecStructArray",
+    "ExhaustSmokeStructLengthlessArray",
+    "FixedDatumStructLengthlessArray",
+    "Float32Array1Plus",
+    "FundamentalParameterDataStructLengthlessArray",
+    "GridAxisStructLengthlessArray",
+    "GridDataStructLengthlessArray",
+    "Integer16Array1Plus",
+    "MarkingArray11",
+    "MarkingArray31",
+    "MineDielectricDifference",
+    "MineDielectricDifferenceLengthlessArray",
+    "MineFusingStructLengthlessArray",
+    "MineIdentifier",
+    "MineIdentifierLengthlessArray",
+    "MinefieldLaneMarkerStructLengthlessArray",
+    "MinefieldPaintSchemeLengthlessArray",
+    "MinefieldSensorTypeLengthlessArray",
+    "MissingRecordNumbersLengthlessArray1Plus",
+    "OctetArray",
+    "OctetArray1Plus",
+    "OctetArray2",
+    "OctetArray3",
+    "OctetArray4",
+    "OctetArray7",
+    "OctetArray8",
+    "OctetPadding32Array",
+    "OctetPadding64Array",
+    "OrientationStructLengthlessArray",
+    "PerimeterPointStructLengthlessArray",
+    "PropulsionSystemDataStructLengthlessArray",
+    "RPRUserDefinedTag",
+    "RPRboolean",
+    "RTIobjectId",
+    "RTIobjectIdArray",
+    "RecordSetStructArray1Plus",
+    "RecordStructArray",
+    "ShaftDataStructLengthlessArray1Plus",
+    "SignalDataLengthlessArray1Plus",
+    "SilentAggregateStructLengthlessArray",
+    "SilentEntityStructLengthlessArray",
+    "SupplyStructLengthlessArray",
+    "TemperatureDegreeCelsiusFloat32LengthlessArray",
+    "UnsignedInteger16Array1Plus",
+    "UnsignedInteger32LengthlessArray",
+    "UnsignedInteger64Array1Plus",
+    "UnsignedInteger8LengthlessArray",
+    "VariableDatumStructArray",
+    "VariableDatumStructLengthlessArray",
+    "VectoringNozzleSystemDataStructLengthlessArray",
+    "VelocityDecimeterPerSecondInteger16",
+    "WorldLocationStructLengthlessArray",
+];
+
+impl NamingExceptions {
+    /// Return the exceptions that the naming conventions acknowledge: the
+    /// legacy datatypes of the NETN FOM 3.0 modules, and the datatypes of the
+    /// RPR FOM v2.0 that they build on.
+    pub fn legacy() -> Self {
+        Self {
+            data_types: NETN_DATA_TYPES
+                .iter()
+                .chain(RPR_DATA_TYPES)
+                .map(|name| String::from(*name))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Accept the names that the other exceptions accept as well.
+    pub fn extend(&mut self, other: &NamingExceptions) {
+        self.classes.extend(other.classes.iter().cloned());
+        self.members.extend(other.members.iter().cloned());
+        self.data_types.extend(other.data_types.iter().cloned());
+    }
+}
+
+/// The NETN naming conventions, as a lint pack named `netn`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetnPack {
+    exceptions: Arc<NamingExceptions>,
+}
+
+impl NetnPack {
+    /// Create the pack, accepting the names of the legacy datatypes that the
+    /// naming conventions acknowledge.
+    pub fn new() -> Self {
+        Self::with_exceptions(NamingExceptions::legacy())
+    }
+
+    /// Create the pack, accepting the provided names.
+    pub fn with_exceptions(exceptions: NamingExceptions) -> Self {
+        Self {
+            exceptions: Arc::new(exceptions),
+        }
+    }
+}
+
+impl Default for NetnPack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LintPack for NetnPack {
+    fn name(&self) -> &'static str {
+        "netn"
+    }
+
+    fn lints(&self) -> Vec<Box<dyn Lint>> {
+        let exceptions = &self.exceptions;
+        vec![
+            Box::new(ClassName(exceptions.clone())),
+            Box::new(MemberName(exceptions.clone())),
+            Box::new(SimpleDataTypeName(exceptions.clone())),
+            Box::new(EnumeratedDataTypeName(exceptions.clone())),
+            Box::new(EnumeratorName(exceptions.clone())),
+            Box::new(ArrayDataTypeName(exceptions.clone())),
+            Box::new(FixedRecordDataTypeName(exceptions.clone())),
+            Box::new(VariantRecordDataTypeName(exceptions.clone())),
+        ]
+    }
+}
+
+/// Return what is wrong with the start of a name of a class, attribute or
+/// parameter, if anything: it must start with a capital letter rather than
+/// an underscore.
+fn check_start(name: &str) -> Option<&'static str> {
+    match name.chars().next() {
+        Some('_') => Some("starts with an underscore"),
+        Some(c) if !c.is_uppercase() => Some("does not start with a capital letter"),
+        _ => None,
+    }
+}
+
+/// Return the datatypes of the provided table of the object model.
+fn data_types<'a, T: 'a>(
+    fom: &'a ObjectModelType,
+    table: impl Fn(&'a DataTypesType) -> Option<&'a Vec<T>>,
+) -> impl Iterator<Item = &'a T> {
+    fom.data_types.iter().filter_map(table).flatten()
+}
+
+struct ClassName(Arc<NamingExceptions>);
+
+impl ClassName {
+    fn check_object_class(&self, node: &ClassNode<'_, ObjectClassType>, cx: &mut LintContext) {
+        let class = node.class();
+        if let Some(problem) = self.problem(&class.name, class.sharing.is_some()) {
+            let message = format!("object class name '{}' {}", class.name, problem);
+            cx.report(lint::object_class_path(node), &class.span, message);
+        }
+    }
+
+    fn check_interaction_class(
+        &self,
+        node: &ClassNode<'_, InteractionClassType>,
+        cx: &mut LintContext,
+    ) {
+        let class = node.class();
+        if let Some(problem) = self.problem(&class.name, class.sharing.is_some()) {
+            let message = format!("interaction class name '{}' {}", class.name, problem);
+            cx.report(lint::interaction_class_path(node), &class.span, message);
+        }
+    }
+
+    /// Return what is wrong with the name of a class, if it is declared by
+    /// the object model rather than restated as a superclass.
+    fn problem(&self, name: &str, declared: bool) -> Option<&'static str> {
+        if !declared || self.0.classes.contains(name) {
+            return None;
+        }
+        check_start(name)
+    }
+}
+
+impl Lint for ClassName {
+    fn id(&self) -> &'static str {
+        "netn-class-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "class names start with a capital letter"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks that the names of object and interaction classes start with a \
+capital letter, and not with an underscore, as the NETN naming conventions \
+require. Underscores may be used within a name, e.g. 'NETN_Aircraft'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        for node in fom.object_classes() {
+            self.check_object_class(&node, cx);
+        }
+        for node in fom.interaction_classes() {
+            self.check_interaction_class(&node, cx);
+        }
+    }
+}
+
+struct MemberName(Arc<NamingExceptions>);
+
+impl MemberName {
+    /// Return what is wrong with the name of an attribute or parameter.
+    fn problem(&self, name: &str) -> Option<&'static str> {
+        if self.0.members.contains(name) {
+            return None;
+        }
+        if name.chars().count() == 1 {
+            return Some("is only one character long");
+        }
+        check_start(name)
+    }
+}
+
+impl Lint for MemberName {
+    fn id(&self) -> &'static str {
+        "netn-member-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "attribute and parameter names start with a capital letter"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks that the names of attributes and parameters start with a capital \
+letter, unlike in most programming languages, and not with an underscore, and \
+that they are longer than one character, as the NETN naming conventions \
+require. E.g. 'UniqueId' rather than 'uniqueId'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        for node in fom.object_classes() {
+            let path = lint::object_class_path(&node);
+            for attribute in node.class().members() {
+                if let Some(problem) = self.problem(&attribute.name) {
+                    let path = format!("{}/attribute[{}]", path, attribute.name);
+                    let message = format!("attribute name '{}' {}", attribute.name, problem);
+                    cx.report(path, &attribute.span, message);
+                }
+            }
+        }
+        for node in fom.interaction_classes() {
+            let path = lint::interaction_class_path(&node);
+            for parameter in node.class().members() {
+                if let Some(problem) = self.problem(&parameter.name) {
+                    let path = format!("{}/parameter[{}]", path, parameter.name);
+                    let message = format!("parameter name '{}' {}", parameter.name, problem);
+                    cx.report(path, &parameter.span, message);
+                }
+            }
+        }
+    }
+}
+
+/// Return the name of a representation as it ends the name of a simple
+/// datatype, i.e. without the prefix of its standard and its byte order, and
+/// capitalized, e.g. `Float64` for `HLAfloat64BE`. The prefix is an acronym,
+/// so a single capital letter, as in `UnsignedShort`, is not one.
+fn representation_suffix(representation: &str) -> String {
+    let prefix = representation
+        .chars()
+        .take_while(char::is_ascii_uppercase)
+        .count();
+    let name = if prefix > 1 {
+        &representation[prefix..]
+    } else {
+        representation
+    };
+    let name = name
+        .strip_suffix("BE")
+        .or_else(|| name.strip_suffix("LE"))
+        .unwrap_or(name);
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+struct SimpleDataTypeName(Arc<NamingExceptions>);
+
+impl Lint for SimpleDataTypeName {
+    fn id(&self) -> &'static str {
+        "netn-simple-datatype-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "simple datatype names end with their representation"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks that the names of simple datatypes end with their representation \
+and its size, as the NETN naming conventions require. The representation is \
+named without the prefix of its standard or its byte order, e.g. 'Float64' \
+for 'HLAfloat64BE', as in 'AltitudeMeterFloat64'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        for data_type in data_types(fom, |d| d.simple_data_types.as_ref()?.simple_datas.as_ref()) {
+            let Some(representation) = &data_type.representation else {
+                continue;
+            };
+            let suffix = representation_suffix(&representation.value);
+            if self.0.data_types.contains(&data_type.name) || data_type.name.ends_with(&suffix) {
+                continue;
+            }
+            let message = format!(
+                "simple datatype name '{}' does not end with '{}', for its representation '{}'",
+                data_type.name, suffix, representation.value
+            );
+            cx.report(
+                DataTypeRef::Simple(data_type).path(),
+                &data_type.span,
+                message,
+            );
+        }
+    }
+}
+
+/// Return the size in bits of the representation, from its declaration if it
+/// is known, and from its name otherwise, e.g. 16 for `RPRunsignedInteger16BE`.
+fn representation_size(registry: &DataTypeRegistry<'_>, representation: &str) -> Option<u32> {
+    if let Some(DataTypeRef::Basic(basic)) = registry.get(representation) {
+        if let Some(size) = basic.size.as_ref().and_then(|size| size.size.value()) {
+            return Some(*size);
+        }
+    }
+    let digits: String = representation
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+struct EnumeratedDataTypeName(Arc<NamingExceptions>);
+
+impl Lint for EnumeratedDataTypeName {
+    fn id(&self) -> &'static str {
+        "netn-enumerated-datatype-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "enumerated datatype names end with 'Enum' and their size"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks that the names of enumerated datatypes end with 'Enum' followed by \
+the size of their representation in bits, as the NETN naming conventions \
+require, e.g. 'AggregateMissionEnum16' for a datatype represented by \
+'HLAinteger16BE'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        let registry = DataTypeRegistry::new(fom);
+        let enumerated_datas = data_types(fom, |d| {
+            d.enumerated_data_types.as_ref()?.enumerated_datas.as_ref()
+        });
+        for data_type in enumerated_datas {
+            if self.0.data_types.contains(&data_type.name) {
+                continue;
+            }
+            let size = data_type
+                .representation
+                .as_ref()
+                .and_then(|representation| representation_size(&registry, &representation.value));
+            let suffix = match size {
+                Some(size) => format!("Enum{}", size),
+                None => String::from("Enum"),
+            };
+            let ends_with_size = data_type.name.ends_with(&suffix)
+                || size.is_none()
+                    && data_type
+                        .name
+                        .trim_end_matches(|c: char| c.is_ascii_digit())
+                        .ends_with("Enum");
+            if !ends_with_size {
+                let message = match size {
+                    Some(_) => format!(
+                        "enumerated datatype name '{}' does not end with '{}'",
+                        data_type.name, suffix
+                    ),
+                    None => format!(
+                        "enumerated datatype name '{}' does not end with 'Enum' and its size",
+                        data_type.name
+                    ),
+                };
+                cx.report(
+                    DataTypeRef::Enumerated(data_type).path(),
+                    &data_type.span,
+                    message,
+                );
+            }
+        }
+    }
+}
+
+struct EnumeratorName(Arc<NamingExceptions>);
+
+impl Lint for EnumeratorName {
+    fn id(&self) -> &'static str {
+        "netn-enumerator-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Allow
+    }
+
+    fn description(&self) -> &'static str {
+        "enumerator names are in capital letters"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks that the names of the enumerators of enumerated datatypes are in \
+capital letters, with digits and underscores allowed, as the NETN naming \
+conventions require, e.g. 'NOT_STARTED'. Allowed by default, as the enumerators \
+of the NETN FOM 3.0 and RPR FOM v2.0 modules are in mixed case."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        let enumerated_datas = data_types(fom, |d| {
+            d.enumerated_data_types.as_ref()?.enumerated_datas.as_ref()
+        });
+        for data_type in enumerated_datas {
+            if self.0.data_types.contains(&data_type.name) {
+                continue;
+            }
+            let path = DataTypeRef::Enumerated(data_type).path();
+            for enumerator in data_type.enumerators.iter().flatten() {
+                let capitalized = enumerator
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+                if !capitalized {
+                    let path = format!("{}/enumerator[{}]", path, enumerator.name);
+                    let message = format!(
+                        "enumerator name '{}' is not in capital letters",
+                        enumerator.name
+                    );
+                    cx.report(path, &enumerator.span, message);
+                }
+            }
+        }
+    }
+}
+
+/// The words of the names of arrays that are obviously sequences, and so
+/// need not name their elements.
+const SEQUENCE_WORDS: &[&str] = &["Name", "String", "Text", "List", "Path", "Polygon"];
+
+struct ArrayDataTypeName(Arc<NamingExceptions>);
+
+impl Lint for ArrayDataTypeName {
+    fn id(&self) -> &'static str {
+        "netn-array-datatype-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "array datatype names include 'ArrayOf' and their element datatype"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks that the names of array datatypes include 'ArrayOf' followed by \
+the name of their element datatype, e.g. 'ArrayOfWorldLocationStruct', as the \
+NETN naming conventions require. Arrays that are obviously sequences, whose \
+names end with 'Name', 'String', 'Text', 'List', 'Path' or 'Polygon', need \
+not. Any name may end with the size of the array, e.g. 'Text255'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        for data_type in data_types(fom, |d| d.array_data_types.as_ref()?.array_datas.as_ref()) {
+            if self.0.data_types.contains(&data_type.name) {
+                continue;
+            }
+            let name = data_type
+                .name
+                .trim_end_matches(|c: char| c.is_ascii_digit());
+            if SEQUENCE_WORDS.iter().any(|word| name.ends_with(word)) {
+                continue;
+            }
+            let element = data_type
+                .data_type
+                .as_ref()
+                .map_or("", |element| element.value.as_str());
+            if !name.contains(&format!("ArrayOf{}", element)) {
+                let message = format!(
+                    "array datatype name '{}' does not include 'ArrayOf{}'",
+                    data_type.name, element
+                );
+                cx.report(
+                    DataTypeRef::Array(data_type).path(),
+                    &data_type.span,
+                    message,
+                );
+            }
+        }
+    }
+}
+
+struct FixedRecordDataTypeName(Arc<NamingExceptions>);
+
+impl Lint for FixedRecordDataTypeName {
+    fn id(&self) -> &'static str {
+        "netn-fixed-record-datatype-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "fixed record datatype names end with 'Struct'"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks that the names of fixed record datatypes end with 'Struct', but not \
+with 'VariantStruct', which is for variant records, as the NETN naming \
+conventions require, e.g. 'GeodeticCircleStruct'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        let fixed_record_datas = data_types(fom, |d| {
+            d.fixed_record_data_types
+                .as_ref()?
+                .fixed_record_datas
+                .as_ref()
+        });
+        for data_type in fixed_record_datas {
+            if self.0.data_types.contains(&data_type.name) {
+                continue;
+            }
+            let message = if !data_type.name.ends_with("Struct") {
+                "does not end with 'Struct'"
+            } else if data_type.name.ends_with("VariantStruct") {
+                "ends with 'VariantStruct', which is for variant records"
+            } else {
+                continue;
+            };
+            cx.report(
+                DataTypeRef::FixedRecord(data_type).path(),
+                &data_type.span,
+                format!(
+                    "fixed record datatype name '{}' {}",
+                    data_type.name, message
+                ),
+            );
+        }
+    }
+}
+
+struct VariantRecordDataTypeName(Arc<NamingExceptions>);
+
+impl Lint for VariantRecordDataTypeName {
+    fn id(&self) -> &'static str {
+        "netn-variant-record-datatype-name"
+    }
+
+    fn category(&self) -> Category {
+        Category::Naming
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn description(&self) -> &'static str {
+        "variant record datatype names end with 'VariantStruct'"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Checks that the names of variant record datatypes end with \
+'VariantStruct', as the NETN naming conventions require, e.g. \
+'PointVariantStruct'."
+    }
+
+    fn check(&self, fom: &ObjectModelType, cx: &mut LintContext) {
+        let variant_record_datas = data_types(fom, |d| {
+            d.variand_record_data_types
+                .as_ref()?
+                .variant_record_datas
+                .as_ref()
+        });
+        for data_type in variant_record_datas {
+            if self.0.data_types.contains(&data_type.name)
+                || data_type.name.ends_with("VariantStruct")
+            {
+                continue;
+            }
+            let message = format!(
+                "variant record datatype name '{}' does not end with 'VariantStruct'",
+                data_type.name
+            );
+            cx.report(
+                DataTypeRef::VariantRecord(data_type).path(),
+                &data_type.span,
+                message,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::LintRegistry;
+
+    const DOCUMENT: &str = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+    <objects>
+        <objectClass>
+            <name>HLAobjectRoot</name>
+            <objectClass>
+                <name>vehicle</name>
+                <sharing>PublishSubscribe</sharing>
+                <attribute>
+                    <name>X</name>
+                    <dataType>SpeedFloat32</dataType>
+                </attribute>
+                <attribute>
+                    <name>_Speed</name>
+                    <dataType>SpeedFloat32</dataType>
+                </attribute>
+            </objectClass>
+        </objectClass>
+    </objects>
+    <interactions>
+        <interactionClass>
+            <name>HLAinteractionRoot</name>
+            <interactionClass>
+                <name>_Collision</name>
+                <sharing>PublishSubscribe</sharing>
+                <parameter>
+                    <name>location</name>
+                    <dataType>Location</dataType>
+                </parameter>
+            </interactionClass>
+        </interactionClass>
+    </interactions>
+    <dataTypes>
+        <simpleDataTypes>
+            <simpleData>
+                <name>SpeedFloat32</name>
+                <representation>HLAfloat32BE</representation>
+            </simpleData>
+            <simpleData>
+                <name>Speed</name>
+                <representation>HLAfloat64LE</representation>
+            </simpleData>
+        </simpleDataTypes>
+        <enumeratedDataTypes>
+            <enumeratedData>
+                <name>StatusEnum16</name>
+                <representation>HLAinteger16BE</representation>
+                <enumerator>
+                    <name>NOT_STARTED</name>
+                    <value>0</value>
+                </enumerator>
+                <enumerator>
+                    <name>Finished</name>
+                    <value>1</value>
+                </enumerator>
+            </enumeratedData>
+            <enumeratedData>
+                <name>ColorEnum16</name>
+                <representation>HLAoctet</representation>
+                <enumerator>
+                    <name>RED</name>
+                    <value>0</value>
+                </enumerator>
+            </enumeratedData>
+            <enumeratedData>
+                <name>ShapeType</name>
+                <representation>RPRenumeration8BE</representation>
+                <enumerator>
+                    <name>SQUARE</name>
+                    <value>0</value>
+                </enumerator>
+            </enumeratedData>
+        </enumeratedDataTypes>
+        <arrayDataTypes>
+            <arrayData>
+                <name>ArrayOfLocationStruct</name>
+                <dataType>LocationStruct</dataType>
+                <cardinality>Dynamic</cardinality>
+                <encoding>HLAvariableArray</encoding>
+            </arrayData>
+            <arrayData>
+                <name>Text255</name>
+                <dataType>HLAunicodeChar</dataType>
+                <cardinality>255</cardinality>
+                <encoding>HLAfixedArray</encoding>
+            </arrayData>
+            <arrayData>
+                <name>Locations</name>
+                <dataType>LocationStruct</dataType>
+                <cardinality>Dynamic</cardinality>
+                <encoding>HLAvariableArray</encoding>
+            </arrayData>
+        </arrayDataTypes>
+        <fixedRecordDataTypes>
+            <fixedRecordData>
+                <name>LocationStruct</name>
+                <encoding>HLAfixedRecord</encoding>
+            </fixedRecordData>
+            <fixedRecordData>
+                <name>Location</name>
+                <encoding>HLAfixedRecord</encoding>
+            </fixedRecordData>
+            <fixedRecordData>
+                <name>PointVariantStruct</name>
+                <encoding>HLAfixedRecord</encoding>
+            </fixedRecordData>
+        </fixedRecordDataTypes>
+        <variantRecordDataTypes>
+            <variantRecordData>
+                <name>PointVariantStruct</name>
+                <discriminant>Shape</discriminant>
+                <dataType>ShapeType</dataType>
+                <encoding>HLAvariantRecord</encoding>
+            </variantRecordData>
+            <variantRecordData>
+                <name>ShapeVariant</name>
+                <discriminant>Shape</discriminant>
+                <dataType>ShapeType</dataType>
+                <encoding>HLAvariantRecord</encoding>
+            </variantRecordData>
+        </variantRecordDataTypes>
+    </dataTypes>
+</objectModel>"#;
+
+    /// Run only the lint of the pack with the provided ID over the document.
+    fn run_with(id: &str, exceptions: NamingExceptions) -> Vec<String> {
+        let mut registry = LintRegistry::empty();
+        registry.register_pack(&NetnPack::with_exceptions(exceptions));
+        let ids: Vec<&'static str> = registry.lints().map(|lint| lint.id()).collect();
+        for other in ids {
+            let level = if other == id {
+                Level::Warn
+            } else {
+                Level::Allow
+            };
+            registry.set_level(other, level).unwrap();
+        }
+        let fom = parse_str(DOCUMENT).unwrap();
+        registry.run(&fom).iter().map(ToString::to_string).collect()
+    }
+
+    fn run(id: &str) -> Vec<String> {
+        run_with(id, NamingExceptions::default())
+    }
+
+    #[test]
+    fn test_pack_lints() {
+        let pack = NetnPack::new();
+        assert_eq!("netn", pack.name());
+        let lints: Vec<(&str, Level)> = pack
+            .lints()
+            .iter()
+            .map(|lint| (lint.id(), lint.default_level()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("netn-class-name", Level::Warn),
+                ("netn-member-name", Level::Warn),
+                ("netn-simple-datatype-name", Level::Warn),
+                ("netn-enumerated-datatype-name", Level::Warn),
+                ("netn-enumerator-name", Level::Allow),
+                ("netn-array-datatype-name", Level::Warn),
+                ("netn-fixed-record-datatype-name", Level::Warn),
+                ("netn-variant-record-datatype-name", Level::Warn),
+            ],
+            lints
+        );
+        assert!(pack
+            .lints()
+            .iter()
+            .all(|lint| lint.category() == Category::Naming));
+        let mut registry = LintRegistry::new();
+        registry.register_pack(&pack);
+        assert_eq!(13, registry.lints().count());
+    }
+
+    #[test]
+    fn test_representation_suffix() {
+        assert_eq!("Float64", representation_suffix("HLAfloat64BE"));
+        assert_eq!("Integer32", representation_suffix("HLAinteger32LE"));
+        assert_eq!("Octet", representation_suffix("HLAoctet"));
+        assert_eq!("UnsignedShort", representation_suffix("UnsignedShort"));
+        assert_eq!(
+            "UnsignedInteger16",
+            representation_suffix("RPRunsignedInteger16BE")
+        );
+    }
+
+    #[test]
+    fn test_class_name() {
+        assert_eq!(
+            vec![
+                "5:13: warning: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[vehicle]: object class name 'vehicle' does not start with a capital letter [netn-class-name]",
+                "22:13: warning: /objectModel/interactions/interactionClass[HLAinteractionRoot]/interactionClass[_Collision]: interaction class name '_Collision' starts with an underscore [netn-class-name]",
+            ],
+            run("netn-class-name")
+        );
+    }
+
+    #[test]
+    fn test_member_name() {
+        assert_eq!(
+            vec![
+                "8:17: warning: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[vehicle]/attribute[X]: attribute name 'X' is only one character long [netn-member-name]",
+                "12:17: warning: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[vehicle]/attribute[_Speed]: attribute name '_Speed' starts with an underscore [netn-member-name]",
+                "25:17: warning: /objectModel/interactions/interactionClass[HLAinteractionRoot]/interactionClass[_Collision]/parameter[location]: parameter name 'location' does not start with a capital letter [netn-member-name]",
+            ],
+            run("netn-member-name")
+        );
+        let exceptions = NamingExceptions {
+            members: vec![String::from("X"), String::from("location")]
+                .into_iter()
+                .collect(),
+            ..NamingExceptions::default()
+        };
+        assert_eq!(1, run_with("netn-member-name", exceptions).len());
+    }
+
+    #[test]
+    fn test_simple_datatype_name() {
+        assert_eq!(
+            vec![
+                "38:13: warning: /objectModel/dataTypes/simpleDataTypes/simpleData[Speed]: simple datatype name 'Speed' does not end with 'Float64', for its representation 'HLAfloat64LE' [netn-simple-datatype-name]",
+            ],
+            run("netn-simple-datatype-name")
+        );
+    }
+
+    #[test]
+    fn test_enumerated_datatype_name() {
+        assert_eq!(
+            vec![
+                "56:13: warning: /objectModel/dataTypes/enumeratedDataTypes/enumeratedData[ColorEnum16]: enumerated datatype name 'ColorEnum16' does not end with 'Enum8' [netn-enumerated-datatype-name]",
+                "64:13: warning: /objectModel/dataTypes/enumeratedDataTypes/enumeratedData[ShapeType]: enumerated datatype name 'ShapeType' does not end with 'Enum8' [netn-enumerated-datatype-name]",
+            ],
+            run("netn-enumerated-datatype-name")
+        );
+    }
+
+    #[test]
+    fn test_enumerator_name() {
+        assert_eq!(
+            vec![
+                "51:17: warning: /objectModel/dataTypes/enumeratedDataTypes/enumeratedData[StatusEnum16]/enumerator[Finished]: enumerator name 'Finished' is not in capital letters [netn-enumerator-name]",
+            ],
+            run("netn-enumerator-name")
+        );
+        let exceptions = NamingExceptions {
+            data_types: vec![String::from("StatusEnum16")].into_iter().collect(),
+            ..NamingExceptions::default()
+        };
+        assert!(run_with("netn-enumerator-name", exceptions).is_empty());
+    }
+
+    #[test]
+    fn test_array_datatype_name() {
+        assert_eq!(
+            vec![
+                "86:13: warning: /objectModel/dataTypes/arrayDataTypes/arrayData[Locations]: array datatype name 'Locations' does not include 'ArrayOfLocationStruct' [netn-array-datatype-name]",
+            ],
+            run("netn-array-datatype-name")
+        );
+    }
+
+    #[test]
+    fn test_fixed_record_datatype_name() {
+        assert_eq!(
+            vec![
+                "98:13: warning: /objectModel/dataTypes/fixedRecordDataTypes/fixedRecordData[Location]: fixed record datatype name 'Location' does not end with 'Struct' [netn-fixed-record-datatype-name]",
+                "102:13: warning: /objectModel/dataTypes/fixedRecordDataTypes/fixedRecordData[PointVariantStruct]: fixed record datatype name 'PointVariantStruct' ends with 'VariantStruct', which is for variant records [netn-fixed-record-datatype-name]",
+            ],
+            run("netn-fixed-record-datatype-name")
+        );
+    }
+
+    #[test]
+    fn test_variant_record_datatype_name() {
+        assert_eq!(
+            vec![
+                "114:13: warning: /objectModel/dataTypes/variantRecordDataTypes/variantRecordData[ShapeVariant]: variant record datatype name 'ShapeVariant' does not end with 'VariantStruct' [netn-variant-record-datatype-name]",
+            ],
+            run("netn-variant-record-datatype-name")
+        );
+        let exceptions = NamingExceptions {
+            data_types: vec![String::from("ShapeVariant")].into_iter().collect(),
+            ..NamingExceptions::default()
+        };
+        assert!(run_with("netn-variant-record-datatype-name", exceptions).is_empty());
+    }
+
+    #[test]
+    fn test_legacy_exceptions() {
+        let exceptions = NamingExceptions::legacy();
+        assert!(exceptions.data_types.contains("UUID"));
+        assert!(exceptions
+            .data_types
+            .contains("WorldLocationStructLengthlessArray"));
+        assert!(exceptions.classes.is_empty());
+    }
+}
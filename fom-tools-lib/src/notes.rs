@@ -0,0 +1,244 @@
+//! Resolution of the `notes` attributes of an object model to the entries of
+//! its notes table, and the reverse.
+
+use crate::*;
+use std::any::Any;
+use std::fmt;
+
+/// A part of the object model that holds the `notes` and `idtag` attributes
+/// of its element, and of those elements within it that are not parts of the
+/// object model themselves, such as `semantics` or `updateCondition`. See
+/// `AnnotationType` for how those elements are identified.
+pub trait Annotated: Spanned + fmt::Debug {
+    fn annotations(&self) -> Option<&Vec<AnnotationType>>;
+
+    /// Return this part of the object model as `Any`, so that it can be
+    /// downcast to its type, e.g. `AttributeType`.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Return the labels of the notes that apply to the element of this part
+    /// of the object model.
+    fn note_references(&self) -> Vec<&str> {
+        self.note_references_at("")
+    }
+
+    /// Return the labels of the notes that apply to the element at the
+    /// provided path within this part of the object model, e.g. `semantics`.
+    fn note_references_at(&self, path: &str) -> Vec<&str> {
+        self.annotations()
+            .into_iter()
+            .flatten()
+            .filter(|a| a.path == path)
+            .flat_map(|a| a.notes.iter().flatten())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Return the `idtag` of the element of this part of the object model.
+    fn idtag(&self) -> Option<&str> {
+        self.annotations()
+            .into_iter()
+            .flatten()
+            .find(|a| a.path.is_empty())
+            .and_then(|a| a.idtag.as_deref())
+    }
+}
+
+macro_rules! impl_annotated {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Annotated for $t {
+                fn annotations(&self) -> Option<&Vec<AnnotationType>> {
+                    self.annotations.as_ref()
+                }
+
+                fn as_any(&self) -> &dyn Any {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+for_each_model_node!(impl_annotated);
+
+/// A part of the object model, or the parts held by a field of one, whose
+/// annotated parts can be collected in document order.
+trait AnnotatedParts {
+    fn collect_parts<'a>(&'a self, parts: &mut Vec<&'a dyn Annotated>);
+}
+
+impl<T: AnnotatedParts> AnnotatedParts for Option<T> {
+    fn collect_parts<'a>(&'a self, parts: &mut Vec<&'a dyn Annotated>) {
+        if let Some(part) = self {
+            part.collect_parts(parts);
+        }
+    }
+}
+
+impl<T: AnnotatedParts> AnnotatedParts for Vec<T> {
+    fn collect_parts<'a>(&'a self, parts: &mut Vec<&'a dyn Annotated>) {
+        for part in self {
+            part.collect_parts(parts);
+        }
+    }
+}
+
+macro_rules! impl_annotated_parts {
+    ($($t:ident { $($field:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl AnnotatedParts for $t {
+                fn collect_parts<'a>(&'a self, parts: &mut Vec<&'a dyn Annotated>) {
+                    parts.push(self);
+                    $(self.$field.collect_parts(parts);)*
+                }
+            }
+        )*
+    };
+}
+
+for_each_model_node_with_children!(impl_annotated_parts);
+
+/// A reference to a note from an element of an object model.
+#[derive(Debug, Clone, Copy)]
+pub struct NoteReference<'a> {
+    /// The part of the object model that holds the reference.
+    pub part: &'a dyn Annotated,
+    /// The path of the annotated element within the part, as for
+    /// `AnnotationType`, e.g. `semantics`, or the empty path for the element
+    /// of the part itself.
+    pub path: &'a str,
+    /// The label of the note.
+    pub label: &'a str,
+}
+
+impl ObjectModelType {
+    /// Return the entry of the notes table with the provided label.
+    pub fn note(&self, label: &str) -> Option<&NoteType> {
+        self.notes
+            .as_ref()
+            .and_then(|notes| notes.notes.as_ref())
+            .and_then(|notes| notes.iter().find(|note| note.label == label))
+    }
+
+    /// Return the notes that apply to the element of the provided part of the
+    /// object model. References to notes that are not in the notes table are
+    /// left out.
+    pub fn resolve_notes<A: Annotated + ?Sized>(&self, element: &A) -> Vec<&NoteType> {
+        self.resolve_notes_at(element, "")
+    }
+
+    /// Return the notes that apply to the element at the provided path within
+    /// the provided part of the object model. See `Annotated::note_references_at`.
+    pub fn resolve_notes_at<A: Annotated + ?Sized>(
+        &self,
+        element: &A,
+        path: &str,
+    ) -> Vec<&NoteType> {
+        element
+            .note_references_at(path)
+            .into_iter()
+            .filter_map(|label| self.note(label))
+            .collect()
+    }
+
+    /// Return every reference to a note in the object model, part by part
+    /// in document order, whether or not the note is in the notes table.
+    pub fn note_references(&self) -> Vec<NoteReference<'_>> {
+        let mut parts = Vec::new();
+        self.collect_parts(&mut parts);
+        let mut references = Vec::new();
+        for part in parts {
+            for annotation in part.annotations().into_iter().flatten() {
+                for label in annotation.notes.iter().flatten() {
+                    references.push(NoteReference {
+                        part,
+                        path: &annotation.path,
+                        label,
+                    });
+                }
+            }
+        }
+        references
+    }
+
+    /// Return the references to the note with the provided label, which name
+    /// the elements that the note applies to, in document order.
+    pub fn annotated_with(&self, label: &str) -> Vec<NoteReference<'_>> {
+        self.note_references()
+            .into_iter()
+            .filter(|reference| reference.label == label)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_notes() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <objects>
+    <objectClass notes="Note1">
+      <name>HLAobjectRoot</name>
+      <attribute notes="Note2">
+        <name>HLAprivilegeToDeleteObject</name>
+        <semantics notes="Note1 Missing">Semantics</semantics>
+      </attribute>
+    </objectClass>
+  </objects>
+  <notes>
+    <note>
+      <label>Note1</label>
+      <semantics>The first note</semantics>
+    </note>
+    <note>
+      <label>Note2</label>
+      <semantics>The second note</semantics>
+    </note>
+  </notes>
+</objectModel>"#;
+        let fom = parse_str(xml).unwrap();
+        let root = fom
+            .objects
+            .as_ref()
+            .unwrap()
+            .root_object_class
+            .as_ref()
+            .unwrap();
+        let attribute = &root.attributes.as_ref().unwrap()[0];
+
+        assert_eq!(vec!["Note1"], root.note_references());
+        assert_eq!(vec!["Note2"], attribute.note_references());
+        assert_eq!(
+            vec!["Note1", "Missing"],
+            attribute.note_references_at("semantics")
+        );
+
+        let notes = fom.resolve_notes_at(attribute, "semantics");
+        assert_eq!(1, notes.len());
+        assert_eq!(Some(String::from("The first note")), notes[0].semantics);
+
+        let annotated = fom.annotated_with("Note1");
+        assert_eq!(2, annotated.len());
+        assert_eq!(
+            Some(root),
+            annotated[0].part.as_any().downcast_ref::<ObjectClassType>()
+        );
+        assert_eq!("", annotated[0].path);
+        assert_eq!(
+            Some(attribute),
+            annotated[1].part.as_any().downcast_ref::<AttributeType>()
+        );
+        assert_eq!("semantics", annotated[1].path);
+        assert_eq!(Some(attribute.span().unwrap()), annotated[1].part.span());
+        let unresolved: Vec<NoteReference> = fom
+            .note_references()
+            .into_iter()
+            .filter(|reference| fom.note(reference.label).is_none())
+            .collect();
+        assert_eq!(1, unresolved.len());
+        assert_eq!("Missing", unresolved[0].label);
+    }
+}
@@ -0,0 +1,457 @@
+//! The regular expressions of the `pattern` facet of XML Schema simple types.
+//!
+//! XML Schema regular expressions differ from those of most regular expression
+//! libraries: they match the whole of a value rather than a part of it, have no
+//! anchors, and add the `\i` and `\c` escapes for the characters of XML names.
+//! Only the features used by the schemas of object models are supported:
+//! branches, groups, quantifiers, character classes and the single and
+//! multi-character escapes. Unicode category escapes such as `\p{Lu}` and
+//! character class subtraction are not.
+
+use std::fmt;
+
+/// A compiled `pattern` facet.
+#[derive(Debug, Clone)]
+pub(crate) struct Pattern {
+    source: String,
+    regex: Branches,
+}
+
+/// Why the text of a `pattern` facet could not be compiled.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PatternError {
+    pub(crate) pattern: String,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid pattern '{}': {}", self.pattern, self.message)
+    }
+}
+
+/// Alternative sequences of atoms, separated by `|` in the pattern.
+#[derive(Debug, Clone)]
+struct Branches(Vec<Vec<Piece>>);
+
+/// An atom and how many times it may be repeated.
+#[derive(Debug, Clone)]
+struct Piece {
+    atom: Atom,
+    min: u32,
+    max: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+enum Atom {
+    Char(char),
+    Class(Class),
+    Group(Branches),
+}
+
+/// A set of characters, such as `[a-z]`, `\d` or `.`.
+#[derive(Debug, Clone)]
+struct Class {
+    negated: bool,
+    items: Vec<ClassItem>,
+}
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+    Range(char, char),
+    Class(Class),
+    /// Any character but a line feed or carriage return, i.e. `.`.
+    NotLineEnd,
+    Digit,
+    Space,
+    /// The characters that may start an XML name, i.e. `\i`.
+    NameStart,
+    /// The characters that may appear in an XML name, i.e. `\c`.
+    NameChar,
+    /// The characters that are not punctuation, separators or others, i.e.
+    /// `\w`.
+    Word,
+}
+
+impl Class {
+    fn of(item: ClassItem, negated: bool) -> Self {
+        Self {
+            negated,
+            items: vec![item],
+        }
+    }
+
+    fn contains(&self, c: char) -> bool {
+        self.items.iter().any(|item| item.contains(c)) != self.negated
+    }
+}
+
+impl ClassItem {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            ClassItem::Range(first, last) => (*first..=*last).contains(&c),
+            ClassItem::Class(class) => class.contains(c),
+            ClassItem::NotLineEnd => c != '\n' && c != '\r',
+            ClassItem::Digit => c.is_numeric(),
+            ClassItem::Space => matches!(c, ' ' | '\t' | '\n' | '\r'),
+            ClassItem::NameStart => is_name_start_char(c),
+            ClassItem::NameChar => is_name_char(c),
+            ClassItem::Word => !(c.is_ascii_punctuation() || c.is_whitespace() || c.is_control()),
+        }
+    }
+}
+
+/// Return whether the character may start an XML name. Colons are left out, as
+/// in the names of XML Schema.
+pub(crate) fn is_name_start_char(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+/// Return whether the character may appear in an XML name after its first
+/// character. Colons are left out, as in the names of XML Schema.
+pub(crate) fn is_name_char(c: char) -> bool {
+    is_name_start_char(c) || c.is_numeric() || matches!(c, '-' | '.' | '\u{B7}')
+}
+
+impl Pattern {
+    pub(crate) fn new(source: &str) -> Result<Self, PatternError> {
+        let mut parser = Parser {
+            chars: source.chars().collect(),
+            next: 0,
+        };
+        let regex = parser.branches().and_then(|regex| {
+            if parser.next < parser.chars.len() {
+                Err(format!("unmatched ')' at offset {}", parser.next))
+            } else {
+                Ok(regex)
+            }
+        });
+        match regex {
+            Ok(regex) => Ok(Self {
+                source: String::from(source),
+                regex,
+            }),
+            Err(message) => Err(PatternError {
+                pattern: String::from(source),
+                message,
+            }),
+        }
+    }
+
+    /// The text of the facet the pattern was compiled from.
+    pub(crate) fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Return whether the pattern matches the whole of the value.
+    pub(crate) fn is_match(&self, value: &str) -> bool {
+        let value: Vec<char> = value.chars().collect();
+        match_branches(&self.regex, &value, 0, &mut |end| end == value.len())
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.next).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.next += 1;
+        c
+    }
+
+    fn branches(&mut self) -> Result<Branches, String> {
+        let mut branches = vec![self.branch()?];
+        while self.peek() == Some('|') {
+            self.next += 1;
+            branches.push(self.branch()?);
+        }
+        Ok(Branches(branches))
+    }
+
+    fn branch(&mut self) -> Result<Vec<Piece>, String> {
+        let mut pieces = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            let (min, max) = self.quantifier()?;
+            pieces.push(Piece { atom, min, max });
+        }
+        Ok(pieces)
+    }
+
+    fn atom(&mut self) -> Result<Atom, String> {
+        match self.bump() {
+            Some('(') => {
+                let branches = self.branches()?;
+                if self.bump() != Some(')') {
+                    return Err(String::from("unclosed group"));
+                }
+                Ok(Atom::Group(branches))
+            }
+            Some('[') => Ok(Atom::Class(self.class()?)),
+            Some('.') => Ok(Atom::Class(Class::of(ClassItem::NotLineEnd, false))),
+            Some('\\') => match self.escape()? {
+                Escaped::Char(c) => Ok(Atom::Char(c)),
+                Escaped::Class(class) => Ok(Atom::Class(class)),
+            },
+            Some(c @ ('*' | '+' | '?' | '{')) => Err(format!("nothing to repeat before '{}'", c)),
+            Some(']') => Err(String::from("unmatched ']'")),
+            Some(c) => Ok(Atom::Char(c)),
+            None => Err(String::from("unexpected end")),
+        }
+    }
+
+    fn quantifier(&mut self) -> Result<(u32, Option<u32>), String> {
+        match self.peek() {
+            Some('?') => {
+                self.next += 1;
+                Ok((0, Some(1)))
+            }
+            Some('*') => {
+                self.next += 1;
+                Ok((0, None))
+            }
+            Some('+') => {
+                self.next += 1;
+                Ok((1, None))
+            }
+            Some('{') => {
+                self.next += 1;
+                let min = self.number()?;
+                let max = match self.bump() {
+                    Some('}') => Some(min),
+                    Some(',') if self.peek() == Some('}') => {
+                        self.next += 1;
+                        None
+                    }
+                    Some(',') => {
+                        let max = self.number()?;
+                        if self.bump() != Some('}') {
+                            return Err(String::from("unclosed quantifier"));
+                        }
+                        if max < min {
+                            return Err(String::from("quantifier maximum below its minimum"));
+                        }
+                        Some(max)
+                    }
+                    _ => return Err(String::from("unclosed quantifier")),
+                };
+                Ok((min, max))
+            }
+            _ => Ok((1, Some(1))),
+        }
+    }
+
+    fn number(&mut self) -> Result<u32, String> {
+        let start = self.next;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.next += 1;
+        }
+        let digits: String = self.chars[start..self.next].iter().collect();
+        digits
+            .parse()
+            .map_err(|_| String::from("invalid quantifier"))
+    }
+
+    /// Read a character class expression, after its opening `[`.
+    fn class(&mut self) -> Result<Class, String> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.next += 1;
+        }
+        let mut items = Vec::new();
+        loop {
+            let first = match self.bump() {
+                None => return Err(String::from("unclosed character class")),
+                Some(']') if !items.is_empty() => return Ok(Class { negated, items }),
+                Some('-') if self.peek() == Some('[') => {
+                    return Err(String::from("character class subtraction is not supported"))
+                }
+                Some('[') => return Err(String::from("unescaped '[' in character class")),
+                Some('\\') => match self.escape()? {
+                    Escaped::Char(c) => c,
+                    Escaped::Class(class) => {
+                        items.push(ClassItem::Class(class));
+                        continue;
+                    }
+                },
+                Some(c) => c,
+            };
+            let is_range = self.peek() == Some('-')
+                && self
+                    .chars
+                    .get(self.next + 1)
+                    .is_some_and(|&c| c != ']' && c != '[');
+            if !is_range {
+                items.push(ClassItem::Range(first, first));
+                continue;
+            }
+            self.next += 1;
+            let last = match self.bump() {
+                Some('\\') => match self.escape()? {
+                    Escaped::Char(c) => c,
+                    Escaped::Class(_) => return Err(String::from("invalid range")),
+                },
+                Some(c) => c,
+                None => return Err(String::from("unclosed character class")),
+            };
+            if last < first {
+                return Err(format!("invalid range '{}-{}'", first, last));
+            }
+            items.push(ClassItem::Range(first, last));
+        }
+    }
+
+    /// Read an escape, after its `\`.
+    fn escape(&mut self) -> Result<Escaped, String> {
+        let c = self.bump().ok_or_else(|| String::from("unexpected end"))?;
+        let class = |item, negated| Ok(Escaped::Class(Class::of(item, negated)));
+        match c {
+            'n' => Ok(Escaped::Char('\n')),
+            'r' => Ok(Escaped::Char('\r')),
+            't' => Ok(Escaped::Char('\t')),
+            '\\' | '|' | '.' | '-' | '^' | '?' | '*' | '+' | '{' | '}' | '(' | ')' | '[' | ']' => {
+                Ok(Escaped::Char(c))
+            }
+            'd' => class(ClassItem::Digit, false),
+            'D' => class(ClassItem::Digit, true),
+            's' => class(ClassItem::Space, false),
+            'S' => class(ClassItem::Space, true),
+            'i' => class(ClassItem::NameStart, false),
+            'I' => class(ClassItem::NameStart, true),
+            'c' => class(ClassItem::NameChar, false),
+            'C' => class(ClassItem::NameChar, true),
+            'w' => class(ClassItem::Word, false),
+            'W' => class(ClassItem::Word, true),
+            'p' | 'P' => Err(String::from("unicode category escapes are not supported")),
+            _ => Err(format!("unknown escape '\\{}'", c)),
+        }
+    }
+}
+
+enum Escaped {
+    Char(char),
+    Class(Class),
+}
+
+/// Match the branches against the value from the provided offset, calling
+/// `then` with the offset after each way they match until it returns true.
+fn match_branches(
+    branches: &Branches,
+    value: &[char],
+    at: usize,
+    then: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    branches
+        .0
+        .iter()
+        .any(|pieces| match_pieces(pieces, value, at, then))
+}
+
+fn match_pieces(
+    pieces: &[Piece],
+    value: &[char],
+    at: usize,
+    then: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    match pieces.split_first() {
+        None => then(at),
+        Some((piece, rest)) => match_piece(piece, 0, value, at, &mut |next| {
+            match_pieces(rest, value, next, then)
+        }),
+    }
+}
+
+/// Match the piece, which has already matched `count` times, greedily.
+fn match_piece(
+    piece: &Piece,
+    count: u32,
+    value: &[char],
+    at: usize,
+    then: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    let may_repeat = piece.max.is_none_or(|max| count < max);
+    // Repeating a match of nothing can never lead anywhere new once the
+    // minimum has been reached.
+    if may_repeat
+        && match_atom(&piece.atom, value, at, &mut |next| {
+            (next != at || count < piece.min) && match_piece(piece, count + 1, value, next, then)
+        })
+    {
+        return true;
+    }
+    count >= piece.min && then(at)
+}
+
+fn match_atom(atom: &Atom, value: &[char], at: usize, then: &mut dyn FnMut(usize) -> bool) -> bool {
+    match atom {
+        Atom::Char(c) => value.get(at) == Some(c) && then(at + 1),
+        Atom::Class(class) => value.get(at).is_some_and(|&c| class.contains(c)) && then(at + 1),
+        Atom::Group(branches) => match_branches(branches, value, at, then),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, value: &str) -> bool {
+        Pattern::new(pattern).unwrap().is_match(value)
+    }
+
+    #[test]
+    fn test_patterns_of_the_omt_schemas() {
+        let dimension_value = r"\d+|\[\d+\.\.\d+\)|\[\d+\)|Excluded";
+        for value in ["0", "42", "[0..10)", "[5)", "Excluded"] {
+            assert!(matches(dimension_value, value), "{}", value);
+        }
+        for value in ["", "[0..10]", "x", "Excluded1", "[..10)"] {
+            assert!(!matches(dimension_value, value), "{}", value);
+        }
+
+        let cardinality =
+            r"(Dynamic|(\d)+|(\[(\d)+..(\d)+\]))(,(Dynamic|(\d)+|(\[(\d)+..(\d)+\])))*";
+        for value in ["Dynamic", "3", "[1..8]", "3,Dynamic,[0..2]"] {
+            assert!(matches(cardinality, value), "{}", value);
+        }
+        for value in ["", "3,", "dynamic", "[1..8", "3 ,4"] {
+            assert!(!matches(cardinality, value), "{}", value);
+        }
+
+        // A character class, rather than the grouping it appears to be.
+        let enumerator = r"HLAother|(\S)+|([(\S)+..(\S)+])";
+        assert!(matches(enumerator, "HLAother"));
+        assert!(matches(enumerator, "Red"));
+        assert!(!matches(enumerator, "Dark red"));
+    }
+
+    #[test]
+    fn test_quantifiers_and_classes() {
+        assert!(matches("a{2,3}b?", "aaa"));
+        assert!(!matches("a{2,3}b?", "aaaa"));
+        assert!(matches("[^a-c]*", "xyz"));
+        assert!(!matches("[^a-c]*", "xaz"));
+        assert!(matches(r"\i\c*", "objectClass-1"));
+        assert!(!matches(r"\i\c*", "1objectClass"));
+        assert!(matches("(a|)*b", "aab"));
+        assert!(matches("[+-]?[0-9]", "-1"));
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        for pattern in [
+            "(a", "a)", "[a", "*a", r"\p{Lu}", "[a-[b]]", "a{3,1}", r"\q",
+        ] {
+            assert!(Pattern::new(pattern).is_err(), "{}", pattern);
+        }
+    }
+}
@@ -0,0 +1,2616 @@
+//! Validation of documents against XML Schemas, such as the IEEE 1516.2-2010
+//! DIF schema that every object model document is expected to conform to.
+//!
+//! Validation is done by fom-tools itself, without libxml2 or access to a
+//! network. It checks the order and number of child elements, the attributes
+//! of each element, the values of elements and attributes against their simple
+//! types, including enumerations and patterns, IDs and references to them, and
+//! the `unique`, `key` and `keyref` constraints of the schema.
+//!
+//! Only the IEEE 1516.2-2010 schemas are bundled. They also accept documents
+//! of IEEE 1516.2-2025, in either of its namespaces, checking them as the
+//! structure of the 2010 OMT plus the extendable record encodings that 2025
+//! adds, which are among the names that the 2010 schemas accept for an
+//! encoding. That is not validation against the 2025 schemas themselves.
+//!
+//! Only the parts of XML Schema 1.0 that the schemas of the OMT use are
+//! supported. A schema that uses any other part, such as substitution groups,
+//! model group definitions, imports or derivation of complex types by
+//! restriction, is rejected when it is read, rather than being enforced in
+//! part.
+//!
+//! ```
+//! use fom_tools_lib::schema::Schema;
+//!
+//! let document = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+//!     <modelIdentification><type>FOM</type></modelIdentification>
+//!     <objects><objectClass><name>HLAobjectRoot</name><sharing>Sometimes</sharing></objectClass></objects>
+//! </objectModel>"#;
+//! let violations = Schema::dif().validate_reader(document.as_bytes()).unwrap();
+//! assert_eq!(
+//!     "3:53: /objectModel/objects/objectClass[HLAobjectRoot]/sharing: invalid value \
+//!      'Sometimes' of type 'sharingEnumerations': not one of 'Publish', 'Subscribe', \
+//!      'PublishSubscribe' or 'Neither'",
+//!     violations[0].to_string()
+//! );
+//! ```
+
+use crate::dom::{Element, Events, Position};
+use crate::pattern::{is_name_char, is_name_start_char, Pattern};
+use crate::{
+    path_segment, Error, IEEE1516_2010_NAMESPACE, IEEE1516_2025_NAMESPACE,
+    SISO_IEEE1516_2025_NAMESPACE, XSI_NAMESPACE,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::OnceLock;
+use xml::common::Position as _;
+use xml::namespace::Namespace;
+
+/// The IEEE 1516.2-2010 Data Interchange Format schema, as published with the
+/// standard.
+const DIF_SCHEMA: &str = include_str!("../data/omt/IEEE1516-DIF-2010.xsd");
+
+/// The IEEE 1516.2-2010 OMT conformance schema, a stricter version of the DIF
+/// schema.
+const OMT_SCHEMA: &str = include_str!("../data/omt/IEEE1516-OMT-2010.xsd");
+
+/// The IEEE 1516.2-2010 FDD schema, a relaxed version of the OMT conformance
+/// schema that requires only what an RTI needs.
+const FDD_SCHEMA: &str = include_str!("../data/omt/IEEE1516-FDD-2010.xsd");
+
+const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+
+/// An XML Schema that documents can be validated against.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    target_namespace: Option<String>,
+    /// Namespaces whose documents are validated as if they were in the target
+    /// namespace.
+    aliases: Vec<String>,
+    types: Vec<Type>,
+    elements: Vec<ElementDecl>,
+    global_elements: HashMap<String, ElementId>,
+}
+
+type TypeId = usize;
+type ElementId = usize;
+
+#[derive(Debug, Clone)]
+enum Type {
+    Simple(SimpleType),
+    Complex(ComplexType),
+}
+
+#[derive(Debug, Clone)]
+struct SimpleType {
+    name: String,
+    variety: Variety,
+}
+
+#[derive(Debug, Clone)]
+enum Variety {
+    Builtin(Builtin),
+    Restriction { base: TypeId, facets: Facets },
+    Union(Vec<TypeId>),
+    List(TypeId),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Facets {
+    enumeration: Vec<String>,
+    /// Alternative patterns, of which a value must match at least one.
+    patterns: Vec<Pattern>,
+    length: Option<usize>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct ComplexType {
+    name: String,
+    content: Content,
+    mixed: bool,
+    attributes: Vec<AttributeDecl>,
+    any_attribute: Option<Wildcard>,
+}
+
+#[derive(Debug, Clone)]
+enum Content {
+    Empty,
+    /// Text of the simple type, with no child elements.
+    Simple(TypeId),
+    Elements(Particle),
+}
+
+#[derive(Debug, Clone)]
+struct AttributeDecl {
+    name: String,
+    namespace: Option<String>,
+    type_id: TypeId,
+    required: bool,
+    fixed: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ElementDecl {
+    name: String,
+    namespace: Option<String>,
+    type_id: TypeId,
+    fixed: Option<String>,
+    constraints: Vec<IdentityConstraint>,
+}
+
+#[derive(Debug, Clone)]
+struct Particle {
+    min: u32,
+    /// The most times the term may occur, or None if it is unbounded.
+    max: Option<u32>,
+    term: Term,
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Element(ElementId),
+    Any(Wildcard),
+    Sequence(Vec<Particle>),
+    Choice(Vec<Particle>),
+    All(Vec<Particle>),
+}
+
+#[derive(Debug, Clone)]
+struct Wildcard {
+    namespaces: NamespaceConstraint,
+    process: ProcessContents,
+}
+
+#[derive(Debug, Clone)]
+enum NamespaceConstraint {
+    Any,
+    /// Any namespace but the target namespace, and not no namespace.
+    Other,
+    Listed(Vec<Option<String>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProcessContents {
+    Strict,
+    Lax,
+    Skip,
+}
+
+#[derive(Debug, Clone)]
+struct IdentityConstraint {
+    name: String,
+    kind: ConstraintKind,
+    /// Alternative paths to the elements the constraint applies to.
+    selector: Vec<XPath>,
+    fields: Vec<XPath>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ConstraintKind {
+    Unique,
+    Key,
+    /// A reference to the named `key` or `unique` constraint.
+    KeyRef(String),
+}
+
+/// A path of the restricted XPath subset of identity constraints, e.g.
+/// `.//ns:attribute/ns:dimensions/ns:dimension` or `@name`.
+#[derive(Debug, Clone)]
+struct XPath {
+    descendants: bool,
+    steps: Vec<NameTest>,
+    attribute: Option<NameTest>,
+}
+
+#[derive(Debug, Clone)]
+enum NameTest {
+    /// `*`
+    Any,
+    /// `prefix:*`
+    Namespace(Option<String>),
+    Name(Option<String>, String),
+}
+
+/// The built-in simple types of XML Schema that are supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Builtin {
+    AnySimpleType,
+    String,
+    NormalizedString,
+    Token,
+    Name,
+    NcName,
+    Id,
+    IdRef,
+    IdRefs,
+    NmToken,
+    NmTokens,
+    AnyUri,
+    Boolean,
+    Decimal,
+    Float,
+    Double,
+    Integer,
+    NonNegativeInteger,
+    PositiveInteger,
+    NonPositiveInteger,
+    NegativeInteger,
+    Long,
+    Int,
+    Short,
+    Byte,
+    UnsignedLong,
+    UnsignedInt,
+    UnsignedShort,
+    UnsignedByte,
+    Date,
+    Time,
+    DateTime,
+    Base64Binary,
+}
+
+const BUILTINS: [(&str, Builtin); 33] = [
+    ("anySimpleType", Builtin::AnySimpleType),
+    ("string", Builtin::String),
+    ("normalizedString", Builtin::NormalizedString),
+    ("token", Builtin::Token),
+    ("Name", Builtin::Name),
+    ("NCName", Builtin::NcName),
+    ("ID", Builtin::Id),
+    ("IDREF", Builtin::IdRef),
+    ("IDREFS", Builtin::IdRefs),
+    ("NMTOKEN", Builtin::NmToken),
+    ("NMTOKENS", Builtin::NmTokens),
+    ("anyURI", Builtin::AnyUri),
+    ("boolean", Builtin::Boolean),
+    ("decimal", Builtin::Decimal),
+    ("float", Builtin::Float),
+    ("double", Builtin::Double),
+    ("integer", Builtin::Integer),
+    ("nonNegativeInteger", Builtin::NonNegativeInteger),
+    ("positiveInteger", Builtin::PositiveInteger),
+    ("nonPositiveInteger", Builtin::NonPositiveInteger),
+    ("negativeInteger", Builtin::NegativeInteger),
+    ("long", Builtin::Long),
+    ("int", Builtin::Int),
+    ("short", Builtin::Short),
+    ("byte", Builtin::Byte),
+    ("unsignedLong", Builtin::UnsignedLong),
+    ("unsignedInt", Builtin::UnsignedInt),
+    ("unsignedShort", Builtin::UnsignedShort),
+    ("unsignedByte", Builtin::UnsignedByte),
+    ("date", Builtin::Date),
+    ("time", Builtin::Time),
+    ("dateTime", Builtin::DateTime),
+    ("base64Binary", Builtin::Base64Binary),
+];
+
+/// Why an XSD document could not be read as a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidSchema {
+    pub message: String,
+    /// Where the offending part of the XSD document starts.
+    pub position: Position,
+}
+
+impl fmt::Display for InvalidSchema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for InvalidSchema {}
+
+/// The way in which part of a document does not conform to a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+    /// The element is not allowed where it appears. Holds the names of the
+    /// elements that would have been allowed there, if any.
+    UnexpectedElement(Vec<String>),
+    /// A required child element is absent. Holds the names of the elements of
+    /// which one was required.
+    MissingElement(Vec<String>),
+    /// The element is allowed by a wildcard that requires it to be declared,
+    /// or is the root element, but the schema does not declare it.
+    UndeclaredElement,
+    /// The element has text, but only child elements are allowed.
+    UnexpectedText,
+    UnexpectedAttribute,
+    /// The named attribute is required, but absent.
+    MissingAttribute(String),
+    /// The value is not valid for the named simple type, for the reason given.
+    InvalidValue {
+        type_name: String,
+        reason: String,
+    },
+    /// The value differs from the value the schema fixes it at.
+    FixedValue(String),
+    /// Another element or attribute has the same ID.
+    DuplicateId,
+    /// No element or attribute has the referenced ID.
+    UndefinedId,
+    /// Another element selected by the named `unique` or `key` constraint has
+    /// the same value.
+    DuplicateKey(String),
+    /// An element selected by the named `key` constraint has no value for it.
+    MissingKey(String),
+    /// The value of the `keyref` constraint does not match any value of the
+    /// `key` or `unique` constraint it refers to.
+    UndefinedKey {
+        constraint: String,
+        refer: String,
+    },
+}
+
+/// A part of a document that does not conform to a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    pub kind: ViolationKind,
+    /// The location of the offending element within the document, in the form
+    /// of the path of a `FomError`.
+    pub path: String,
+    /// The offending value, or the name of the offending element or attribute.
+    pub value: Option<String>,
+    /// Where the offending element starts in the source document.
+    pub position: Position,
+}
+
+impl SchemaViolation {
+    /// Return the description of the violation, without its position and
+    /// path.
+    pub fn message(&self) -> String {
+        struct Message<'a>(&'a SchemaViolation);
+
+        impl fmt::Display for Message<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.write_message(f)
+            }
+        }
+
+        Message(self).to_string()
+    }
+
+    fn write_message(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.value.as_deref().unwrap_or_default();
+        match &self.kind {
+            ViolationKind::UnexpectedElement(expected) if expected.is_empty() => {
+                write!(f, "unexpected element '{}'", value)
+            }
+            ViolationKind::UnexpectedElement(expected) if expected.len() > MAX_LISTED => write!(
+                f,
+                "unexpected element '{}', expected {} or one of {} other elements",
+                value,
+                expected[..MAX_LISTED - 1]
+                    .iter()
+                    .map(|name| format!("'{}'", name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                expected.len() - (MAX_LISTED - 1)
+            ),
+            ViolationKind::UnexpectedElement(expected) => write!(
+                f,
+                "unexpected element '{}', expected {}",
+                value,
+                list_of(expected)
+            ),
+            ViolationKind::MissingElement(expected) => {
+                write!(f, "missing required element {}", list_of(expected))
+            }
+            ViolationKind::UndeclaredElement => {
+                write!(f, "element '{}' is not declared by the schema", value)
+            }
+            ViolationKind::UnexpectedText => write!(f, "unexpected text '{}'", value),
+            ViolationKind::UnexpectedAttribute => write!(f, "unexpected attribute '{}'", value),
+            ViolationKind::MissingAttribute(name) => {
+                write!(f, "missing required attribute '{}'", name)
+            }
+            ViolationKind::InvalidValue { type_name, reason } => write!(
+                f,
+                "invalid value '{}' of type '{}': {}",
+                value, type_name, reason
+            ),
+            ViolationKind::FixedValue(fixed) => {
+                write!(f, "value '{}' is not the fixed value '{}'", value, fixed)
+            }
+            ViolationKind::DuplicateId => write!(f, "duplicate ID '{}'", value),
+            ViolationKind::UndefinedId => write!(f, "reference to undefined ID '{}'", value),
+            ViolationKind::DuplicateKey(constraint) => {
+                write!(f, "duplicate value '{}' of '{}'", value, constraint)
+            }
+            ViolationKind::MissingKey(constraint) => write!(f, "no value for key '{}'", constraint),
+            ViolationKind::UndefinedKey { constraint, refer } => write!(
+                f,
+                "value '{}' of '{}' matches no '{}'",
+                value, constraint, refer
+            ),
+        }
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}: ", self.position, self.path)?;
+        self.write_message(f)
+    }
+}
+
+/// The most element names listed as expected in place of an unexpected one.
+const MAX_LISTED: usize = 10;
+
+/// Return the quoted values as a list for a message, e.g. `'a', 'b' or 'c'`.
+fn list_of(values: &[String]) -> String {
+    let quoted: Vec<String> = values.iter().map(|v| format!("'{}'", v)).collect();
+    match quoted.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+    }
+}
+
+impl Schema {
+    /// Return the IEEE 1516.2-2010 DIF schema, which accepts documents in both
+    /// the SISO namespace it was published with and the IEEE namespace, and
+    /// documents of IEEE 1516.2-2025, as the 2010 structure plus the
+    /// extendable record encodings.
+    pub fn dif() -> &'static Schema {
+        static DIF: OnceLock<Schema> = OnceLock::new();
+        DIF.get_or_init(|| Self::ieee1516_2010(DIF_SCHEMA))
+    }
+
+    /// Return the IEEE 1516.2-2010 OMT conformance schema, which accepts
+    /// documents in both the SISO and IEEE namespaces, and documents of IEEE
+    /// 1516.2-2025 as for `dif`. It applies to complete object models, rather
+    /// than to modules.
+    pub fn omt() -> &'static Schema {
+        static OMT: OnceLock<Schema> = OnceLock::new();
+        OMT.get_or_init(|| Self::ieee1516_2010(OMT_SCHEMA))
+    }
+
+    /// Return the IEEE 1516.2-2010 FDD schema, which accepts documents in both
+    /// the SISO and IEEE namespaces, and documents of IEEE 1516.2-2025 as for
+    /// `dif`. It applies to complete FDDs, rather than to modules.
+    pub fn fdd() -> &'static Schema {
+        static FDD: OnceLock<Schema> = OnceLock::new();
+        FDD.get_or_init(|| Self::ieee1516_2010(FDD_SCHEMA))
+    }
+
+    fn ieee1516_2010(xsd: &str) -> Schema {
+        let mut schema =
+            Schema::parse_str(xsd).expect("the IEEE 1516.2-2010 schemas are supported");
+        schema.add_namespace_alias(IEEE1516_2010_NAMESPACE);
+        // Checks 2025 documents as the 2010 structure, in place of the 2025
+        // schemas, which are not bundled.
+        schema.add_namespace_alias(IEEE1516_2025_NAMESPACE);
+        schema.add_namespace_alias(SISO_IEEE1516_2025_NAMESPACE);
+        schema
+    }
+
+    /// Read a schema from the provided XSD document.
+    pub fn parse_reader<R: Read>(r: R) -> Result<Schema, InvalidSchema> {
+        let mut events = Events::new(r);
+        let (root, namespace) = events
+            .read_root_start_with_namespace()
+            .and_then(|(root, namespace)| Ok((events.read_element(root)?, namespace)))
+            .map_err(|e| InvalidSchema {
+                message: e.msg().to_string(),
+                position: Position {
+                    line: e.position().row + 1,
+                    column: e.position().column + 1,
+                },
+            })?;
+        Loader::new(&root, &namespace)?.load(&root)
+    }
+
+    /// Read a schema from the provided XSD document.
+    pub fn parse_str(s: &str) -> Result<Schema, InvalidSchema> {
+        Self::parse_reader(s.as_bytes())
+    }
+
+    /// The namespace of the elements the schema declares.
+    pub fn target_namespace(&self) -> Option<&str> {
+        self.target_namespace.as_deref()
+    }
+
+    /// Validate documents whose elements are in the provided namespace as if
+    /// they were in the target namespace of the schema, e.g. for a schema
+    /// published in more than one namespace.
+    pub fn add_namespace_alias(&mut self, namespace: &str) {
+        self.aliases.push(String::from(namespace));
+    }
+
+    /// Return whether the schema validates documents in the provided
+    /// namespace.
+    pub fn accepts_namespace(&self, namespace: Option<&str>) -> bool {
+        namespace == self.target_namespace()
+            || namespace.is_some_and(|namespace| self.aliases.iter().any(|a| a == namespace))
+    }
+
+    /// Return every way in which the document with the provided root element
+    /// does not conform to the schema, in document order.
+    pub fn validate(&self, root: &Element) -> Vec<SchemaViolation> {
+        let mut validator = Validator::new(self, root);
+        match validator.global_element(root) {
+            Some(id) => validator.element(root, id),
+            None => validator.violations.push(SchemaViolation {
+                kind: ViolationKind::UndeclaredElement,
+                path: format!("/{}", root.name),
+                value: Some(expanded_name(root)),
+                position: root.position,
+            }),
+        }
+        validator.finish()
+    }
+
+    /// Return the values of each `key` and `unique` constraint of the root
+    /// element of the document, by the name of the constraint. The values of
+    /// constraints with more than one field are joined with `", "`, as in the
+    /// values of violations.
+    pub(crate) fn key_values(&self, root: &Element) -> HashMap<String, HashSet<String>> {
+        let mut validator = Validator::new(self, root);
+        let Some(id) = validator.global_element(root) else {
+            return HashMap::new();
+        };
+        validator.path.push(path_segment(root));
+        self.elements[id]
+            .constraints
+            .iter()
+            .filter(|constraint| !matches!(constraint.kind, ConstraintKind::KeyRef(_)))
+            .map(|constraint| {
+                let values = validator
+                    .key_table(root, constraint)
+                    .into_iter()
+                    .map(|values| values.join(", "))
+                    .collect();
+                (constraint.name.clone(), values)
+            })
+            .collect()
+    }
+
+    /// Read the document provided by the reader, and return every way in which
+    /// it does not conform to the schema.
+    pub fn validate_reader<R: Read>(&self, r: R) -> Result<Vec<SchemaViolation>, Error> {
+        Ok(self.validate(&Element::parse(r)?))
+    }
+
+    /// Read the document at the provided path, and return every way in which it
+    /// does not conform to the schema.
+    pub fn validate_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<SchemaViolation>, Error> {
+        self.validate_reader(File::open(path)?)
+    }
+
+    fn simple_type(&self, id: TypeId) -> &SimpleType {
+        match &self.types[id] {
+            Type::Simple(simple) => simple,
+            Type::Complex(_) => unreachable!("simple types are checked when read"),
+        }
+    }
+
+    fn type_name(&self, id: TypeId) -> &str {
+        match &self.types[id] {
+            Type::Simple(simple) => &simple.name,
+            Type::Complex(complex) => &complex.name,
+        }
+    }
+
+    /// Return the built-in type the simple type is ultimately derived from,
+    /// unless it is a union or list.
+    fn builtin(&self, id: TypeId) -> Option<Builtin> {
+        match self.simple_type(id).variety {
+            Variety::Builtin(builtin) => Some(builtin),
+            Variety::Restriction { base, .. } => self.builtin(base),
+            Variety::Union(_) | Variety::List(_) => None,
+        }
+    }
+
+    fn is_list(&self, id: TypeId) -> bool {
+        match self.simple_type(id).variety {
+            Variety::Builtin(builtin) => matches!(builtin, Builtin::IdRefs | Builtin::NmTokens),
+            Variety::Restriction { base, .. } => self.is_list(base),
+            Variety::Union(_) => false,
+            Variety::List(_) => true,
+        }
+    }
+
+    /// Check the value against the simple type, and return it with its
+    /// whitespace normalized as the type requires.
+    fn check_value(&self, id: TypeId, value: &str) -> Result<String, String> {
+        match &self.simple_type(id).variety {
+            Variety::Builtin(builtin) => {
+                let value = builtin.normalize(value);
+                builtin.check(&value)?;
+                Ok(value)
+            }
+            Variety::Restriction { base, facets } => {
+                let value = self.check_value(*base, value)?;
+                let length = if self.is_list(*base) {
+                    value.split(' ').filter(|item| !item.is_empty()).count()
+                } else {
+                    value.chars().count()
+                };
+                facets.check(&value, length)?;
+                Ok(value)
+            }
+            Variety::Union(members) => {
+                let mut reasons = Vec::new();
+                for member in members {
+                    match self.check_value(*member, value) {
+                        Ok(value) => return Ok(value),
+                        Err(reason) => reasons.push(reason),
+                    }
+                }
+                Err(reasons.join("; "))
+            }
+            Variety::List(item) => {
+                let value = collapse(value);
+                for item_value in value.split(' ').filter(|item| !item.is_empty()) {
+                    self.check_value(*item, item_value)?;
+                }
+                Ok(value)
+            }
+        }
+    }
+}
+
+/// Return the name of the element, preceded by its namespace in braces if it
+/// has one.
+fn expanded_name(e: &Element) -> String {
+    match &e.namespace {
+        Some(namespace) => format!("{{{}}}{}", namespace, e.name),
+        None => e.name.clone(),
+    }
+}
+
+fn collapse(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl Builtin {
+    fn name(self) -> &'static str {
+        BUILTINS
+            .iter()
+            .find(|(_, builtin)| *builtin == self)
+            .map(|(name, _)| *name)
+            .unwrap_or_default()
+    }
+
+    /// Normalize the whitespace of the value as the `whiteSpace` facet of the
+    /// type requires.
+    fn normalize(self, value: &str) -> String {
+        match self {
+            Builtin::AnySimpleType | Builtin::String => String::from(value),
+            Builtin::NormalizedString => value.replace(['\t', '\n', '\r'], " "),
+            _ => collapse(value),
+        }
+    }
+
+    fn check(self, value: &str) -> Result<(), String> {
+        let valid = match self {
+            Builtin::AnySimpleType
+            | Builtin::String
+            | Builtin::NormalizedString
+            | Builtin::Token
+            | Builtin::AnyUri => true,
+            Builtin::Name => is_name(value, true),
+            Builtin::NcName | Builtin::Id | Builtin::IdRef => is_name(value, false),
+            Builtin::IdRefs => {
+                !value.is_empty() && value.split(' ').all(|item| is_name(item, false))
+            }
+            Builtin::NmToken => is_nmtoken(value),
+            Builtin::NmTokens => !value.is_empty() && value.split(' ').all(is_nmtoken),
+            Builtin::Boolean => matches!(value, "true" | "false" | "1" | "0"),
+            Builtin::Decimal => is_decimal(value),
+            Builtin::Float | Builtin::Double => is_floating_point(value),
+            Builtin::Date => is_date(value),
+            Builtin::Time => is_time(value),
+            Builtin::DateTime => value
+                .split_once('T')
+                .is_some_and(|(date, time)| is_date_only(date) && is_time(time)),
+            Builtin::Base64Binary => is_base64(value),
+            Builtin::Integer
+            | Builtin::NonNegativeInteger
+            | Builtin::PositiveInteger
+            | Builtin::NonPositiveInteger
+            | Builtin::NegativeInteger
+            | Builtin::Long
+            | Builtin::Int
+            | Builtin::Short
+            | Builtin::Byte
+            | Builtin::UnsignedLong
+            | Builtin::UnsignedInt
+            | Builtin::UnsignedShort
+            | Builtin::UnsignedByte => return self.check_integer(value),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("not a valid xs:{}", self.name()))
+        }
+    }
+
+    fn check_integer(self, value: &str) -> Result<(), String> {
+        let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("not a valid xs:{}", self.name()));
+        }
+        let (min, max): (i128, i128) = match self {
+            Builtin::NonNegativeInteger => (0, i128::MAX),
+            Builtin::PositiveInteger => (1, i128::MAX),
+            Builtin::NonPositiveInteger => (i128::MIN, 0),
+            Builtin::NegativeInteger => (i128::MIN, -1),
+            Builtin::Long => (i64::MIN.into(), i64::MAX.into()),
+            Builtin::Int => (i32::MIN.into(), i32::MAX.into()),
+            Builtin::Short => (i16::MIN.into(), i16::MAX.into()),
+            Builtin::Byte => (i8::MIN.into(), i8::MAX.into()),
+            Builtin::UnsignedLong => (0, u64::MAX.into()),
+            Builtin::UnsignedInt => (0, u32::MAX.into()),
+            Builtin::UnsignedShort => (0, u16::MAX.into()),
+            Builtin::UnsignedByte => (0, u8::MAX.into()),
+            _ => (i128::MIN, i128::MAX),
+        };
+        let in_range = match value.parse::<i128>() {
+            Ok(n) => (min..=max).contains(&n),
+            // Only types unbounded in the direction of the value can hold
+            // integers this large.
+            Err(_) if value.starts_with('-') => min == i128::MIN,
+            Err(_) => max == i128::MAX,
+        };
+        if in_range {
+            Ok(())
+        } else {
+            Err(format!("out of the range of xs:{}", self.name()))
+        }
+    }
+}
+
+fn is_name(value: &str, colons: bool) -> bool {
+    let mut chars = value.chars();
+    chars
+        .next()
+        .is_some_and(|c| is_name_start_char(c) || (colons && c == ':'))
+        && chars.all(|c| is_name_char(c) || (colons && c == ':'))
+}
+
+fn is_nmtoken(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| is_name_char(c) || c == ':')
+}
+
+fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_decimal(value: &str) -> bool {
+    let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+    match unsigned.split_once('.') {
+        Some((whole, fraction)) => {
+            (whole.is_empty() || is_digits(whole))
+                && (fraction.is_empty() || is_digits(fraction))
+                && !(whole.is_empty() && fraction.is_empty())
+        }
+        None => is_digits(unsigned),
+    }
+}
+
+fn is_floating_point(value: &str) -> bool {
+    if matches!(value, "INF" | "-INF" | "NaN") {
+        return true;
+    }
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (value, None),
+    };
+    is_decimal(mantissa)
+        && exponent
+            .is_none_or(|exponent| is_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent)))
+}
+
+/// Return whether the value is a date without a timezone, e.g. `2010-08-31`.
+fn is_date_only(value: &str) -> bool {
+    let unsigned = value.strip_prefix('-').unwrap_or(value);
+    let parts: Vec<&str> = unsigned.rsplitn(3, '-').collect();
+    let [day, month, year] = parts[..] else {
+        return false;
+    };
+    if year.len() < 4 || !is_digits(year) || month.len() != 2 || day.len() != 2 {
+        return false;
+    }
+    let (Ok(year), Ok(month), Ok(day)) = (year.parse::<u64>(), month.parse(), day.parse::<u32>())
+    else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+/// Split the value into itself and its timezone, if it has a valid one.
+fn split_timezone(value: &str) -> Option<&str> {
+    if let Some(value) = value.strip_suffix('Z') {
+        return Some(value);
+    }
+    if value.len() > 6 {
+        let (value, zone) = value.split_at(value.len() - 6);
+        let zone = zone.as_bytes();
+        if (zone[0] == b'+' || zone[0] == b'-') && zone[3] == b':' {
+            let hours = std::str::from_utf8(&zone[1..3]).ok()?;
+            let minutes = std::str::from_utf8(&zone[4..6]).ok()?;
+            if is_digits(hours) && is_digits(minutes) {
+                return Some(value);
+            }
+        }
+    }
+    Some(value)
+}
+
+fn is_date(value: &str) -> bool {
+    // The year may start with a minus sign, which is not a timezone.
+    split_timezone(value).is_some_and(|date| is_date_only(date) || is_date_only(value))
+}
+
+fn is_time(value: &str) -> bool {
+    let Some(time) = split_timezone(value) else {
+        return false;
+    };
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+    let parts: Vec<&str> = time.split(':').collect();
+    let [hours, minutes, seconds] = parts[..] else {
+        return false;
+    };
+    [hours, minutes, seconds]
+        .iter()
+        .all(|part| part.len() == 2 && is_digits(part))
+        && fraction.is_none_or(is_digits)
+        && hours < "24"
+        && minutes < "60"
+        && seconds < "60"
+}
+
+fn is_base64(value: &str) -> bool {
+    let data: Vec<u8> = value.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let padding = data.iter().rev().take_while(|&&b| b == b'=').count();
+    data.len().is_multiple_of(4)
+        && padding <= 2
+        && data[..data.len() - padding]
+            .iter()
+            .all(|&b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+impl Facets {
+    /// Check the value, whose length in characters or list items is provided,
+    /// against the facets.
+    fn check(&self, value: &str, length: usize) -> Result<(), String> {
+        if !self.enumeration.is_empty() && !self.enumeration.iter().any(|v| v == value) {
+            return Err(format!("not one of {}", list_of(&self.enumeration)));
+        }
+        if !self.patterns.is_empty() && !self.patterns.iter().any(|p| p.is_match(value)) {
+            let patterns: Vec<String> = self
+                .patterns
+                .iter()
+                .map(|p| String::from(p.source()))
+                .collect();
+            return Err(format!("does not match the pattern {}", list_of(&patterns)));
+        }
+        if let Some(expected) = self.length.filter(|&expected| length != expected) {
+            return Err(format!("length is not {}", expected));
+        }
+        if let Some(min) = self.min_length.filter(|&min| length < min) {
+            return Err(format!("length is less than {}", min));
+        }
+        if let Some(max) = self.max_length.filter(|&max| length > max) {
+            return Err(format!("length is more than {}", max));
+        }
+        Ok(())
+    }
+}
+
+/// Reads the definitions of an XSD document into a `Schema`. Named types are
+/// read when they are first referred to, so that a complex type derived from
+/// another can copy the content it extends.
+struct Loader<'a> {
+    namespace: &'a Namespace,
+    target_namespace: Option<String>,
+    elements_qualified: bool,
+    attributes_qualified: bool,
+    type_definitions: HashMap<&'a str, &'a Element>,
+    attribute_groups: HashMap<&'a str, &'a Element>,
+    types: Vec<Option<Type>>,
+    type_ids: HashMap<&'a str, TypeId>,
+    builtin_ids: HashMap<Builtin, TypeId>,
+    any_type: TypeId,
+    elements: Vec<Option<ElementDecl>>,
+    global_elements: HashMap<String, ElementId>,
+}
+
+fn invalid(e: &Element, message: String) -> InvalidSchema {
+    InvalidSchema {
+        message,
+        position: e.position,
+    }
+}
+
+/// Return the child elements of the XSD element that define something, i.e.
+/// all but annotations.
+fn definitions(e: &Element) -> impl Iterator<Item = &Element> {
+    e.child_elements()
+        .filter(|child| child.name != "annotation")
+}
+
+fn required_attribute<'e>(e: &'e Element, name: &str) -> Result<&'e str, InvalidSchema> {
+    e.get_attribute(name)
+        .ok_or_else(|| invalid(e, format!("'{}' has no '{}' attribute", e.name, name)))
+}
+
+impl<'a> Loader<'a> {
+    fn new(root: &'a Element, namespace: &'a Namespace) -> Result<Self, InvalidSchema> {
+        if root.name != "schema" || root.namespace.as_deref() != Some(XSD_NAMESPACE) {
+            return Err(invalid(root, String::from("not an XML Schema document")));
+        }
+        let mut loader = Self {
+            namespace,
+            target_namespace: root.get_attribute("targetNamespace").map(String::from),
+            elements_qualified: root.get_attribute("elementFormDefault") == Some("qualified"),
+            attributes_qualified: root.get_attribute("attributeFormDefault") == Some("qualified"),
+            type_definitions: HashMap::new(),
+            attribute_groups: HashMap::new(),
+            types: Vec::new(),
+            type_ids: HashMap::new(),
+            builtin_ids: HashMap::new(),
+            any_type: 0,
+            elements: Vec::new(),
+            global_elements: HashMap::new(),
+        };
+        let any = Wildcard {
+            namespaces: NamespaceConstraint::Any,
+            process: ProcessContents::Lax,
+        };
+        loader.types.push(Some(Type::Complex(ComplexType {
+            name: String::from("xs:anyType"),
+            content: Content::Elements(Particle {
+                min: 0,
+                max: None,
+                term: Term::Any(any.clone()),
+            }),
+            mixed: true,
+            attributes: Vec::new(),
+            any_attribute: Some(any),
+        })));
+        Ok(loader)
+    }
+
+    fn load(mut self, root: &'a Element) -> Result<Schema, InvalidSchema> {
+        let mut global_elements = Vec::new();
+        for e in definitions(root) {
+            match e.name.as_str() {
+                "complexType" | "simpleType" => {
+                    self.type_definitions
+                        .insert(required_attribute(e, "name")?, e);
+                }
+                "attributeGroup" => {
+                    self.attribute_groups
+                        .insert(required_attribute(e, "name")?, e);
+                }
+                "element" => {
+                    let name = required_attribute(e, "name")?;
+                    self.global_elements
+                        .insert(String::from(name), self.elements.len());
+                    self.elements.push(None);
+                    global_elements.push(e);
+                }
+                _ => return Err(self.unsupported(e)),
+            }
+        }
+        for (id, e) in global_elements.into_iter().enumerate() {
+            let decl = self.element_declaration(e, true)?;
+            self.elements[id] = Some(decl);
+        }
+        let names: Vec<&str> = self.type_definitions.keys().copied().collect();
+        for name in names {
+            self.named_type(root, name)?;
+        }
+        Ok(Schema {
+            target_namespace: self.target_namespace,
+            aliases: Vec::new(),
+            // Every type and element is defined once it has been allocated,
+            // or the schema is rejected.
+            types: self.types.into_iter().map(Option::unwrap).collect(),
+            elements: self.elements.into_iter().map(Option::unwrap).collect(),
+            global_elements: self.global_elements,
+        })
+    }
+
+    fn unsupported(&self, e: &Element) -> InvalidSchema {
+        invalid(e, format!("'{}' is not supported here", e.name))
+    }
+
+    /// Resolve the qualified name, e.g. `xs:string`, to its namespace and local
+    /// name, using the namespace prefixes in scope at the root of the schema.
+    fn resolve(&self, e: &Element, qname: &str) -> Result<(Option<String>, String), InvalidSchema> {
+        let (prefix, local) = qname.split_once(':').unwrap_or(("", qname));
+        match self.namespace.get(prefix) {
+            Some(namespace) if !namespace.is_empty() => {
+                Ok((Some(String::from(namespace)), String::from(local)))
+            }
+            _ if prefix.is_empty() => Ok((None, String::from(local))),
+            _ => Err(invalid(
+                e,
+                format!("undeclared namespace prefix '{}'", prefix),
+            )),
+        }
+    }
+
+    fn is_target(&self, namespace: &Option<String>) -> bool {
+        *namespace == self.target_namespace
+    }
+
+    /// Return the type with the provided qualified name.
+    fn type_reference(&mut self, e: &'a Element, qname: &str) -> Result<TypeId, InvalidSchema> {
+        let (namespace, local) = self.resolve(e, qname)?;
+        if namespace.as_deref() == Some(XSD_NAMESPACE) {
+            if local == "anyType" {
+                return Ok(self.any_type);
+            }
+            let builtin = BUILTINS
+                .iter()
+                .find(|(name, _)| *name == local)
+                .map(|(_, builtin)| *builtin)
+                .ok_or_else(|| invalid(e, format!("unsupported built-in type '{}'", qname)))?;
+            return Ok(self.builtin_type(builtin));
+        }
+        if !self.is_target(&namespace) {
+            return Err(invalid(
+                e,
+                format!("type '{}' is not in the target namespace", qname),
+            ));
+        }
+        self.named_type(e, &local)
+    }
+
+    fn named_type(&mut self, e: &Element, name: &str) -> Result<TypeId, InvalidSchema> {
+        if let Some(&id) = self.type_ids.get(name) {
+            return Ok(id);
+        }
+        let (&name, &definition) = self
+            .type_definitions
+            .get_key_value(name)
+            .ok_or_else(|| invalid(e, format!("undefined type '{}'", name)))?;
+        let id = self.types.len();
+        self.types.push(None);
+        self.type_ids.insert(name, id);
+        let defined = if definition.name == "complexType" {
+            Type::Complex(self.complex_type(definition, String::from(name))?)
+        } else {
+            Type::Simple(self.simple_type(definition, String::from(name))?)
+        };
+        self.types[id] = Some(defined);
+        Ok(id)
+    }
+
+    /// Add an anonymous type to the schema.
+    fn add_type(&mut self, defined: Type) -> TypeId {
+        self.types.push(Some(defined));
+        self.types.len() - 1
+    }
+
+    /// Return the type with the provided ID, which must already be defined.
+    fn defined_type(&self, e: &Element, id: TypeId) -> Result<&Type, InvalidSchema> {
+        self.types[id]
+            .as_ref()
+            .ok_or_else(|| invalid(e, String::from("circular type derivation")))
+    }
+
+    fn simple_type_reference(
+        &mut self,
+        e: &'a Element,
+        qname: &str,
+    ) -> Result<TypeId, InvalidSchema> {
+        let id = self.type_reference(e, qname)?;
+        match self.defined_type(e, id)? {
+            Type::Simple(_) => Ok(id),
+            Type::Complex(_) => Err(invalid(e, format!("'{}' is not a simple type", qname))),
+        }
+    }
+
+    /// Return the simple type of the `type`, `base` or `itemType` attribute of
+    /// the element, or the one it defines inline.
+    fn simple_type_of(&mut self, e: &'a Element, attribute: &str) -> Result<TypeId, InvalidSchema> {
+        if let Some(qname) = e.get_attribute(attribute) {
+            return self.simple_type_reference(e, qname);
+        }
+        match definitions(e).find(|child| child.name == "simpleType") {
+            Some(definition) => {
+                let name = format!("anonymous type of '{}'", e.name);
+                let simple = self.simple_type(definition, name)?;
+                Ok(self.add_type(Type::Simple(simple)))
+            }
+            None => Err(invalid(e, format!("'{}' has no simple type", e.name))),
+        }
+    }
+
+    fn simple_type(&mut self, e: &'a Element, name: String) -> Result<SimpleType, InvalidSchema> {
+        let derivation = definitions(e)
+            .next()
+            .ok_or_else(|| invalid(e, String::from("empty simple type")))?;
+        let variety = match derivation.name.as_str() {
+            "restriction" => {
+                let base = self.simple_type_of(derivation, "base")?;
+                let facets = self.facets(derivation)?;
+                Variety::Restriction { base, facets }
+            }
+            "union" => {
+                let mut members = Vec::new();
+                for qname in derivation
+                    .get_attribute("memberTypes")
+                    .unwrap_or_default()
+                    .split_whitespace()
+                {
+                    members.push(self.simple_type_reference(derivation, qname)?);
+                }
+                for member in definitions(derivation) {
+                    let name = format!("anonymous member of '{}'", name);
+                    let simple = self.simple_type(member, name)?;
+                    members.push(self.add_type(Type::Simple(simple)));
+                }
+                Variety::Union(members)
+            }
+            "list" => Variety::List(self.simple_type_of(derivation, "itemType")?),
+            _ => return Err(self.unsupported(derivation)),
+        };
+        Ok(SimpleType { name, variety })
+    }
+
+    fn facets(&self, restriction: &Element) -> Result<Facets, InvalidSchema> {
+        let mut facets = Facets::default();
+        for facet in definitions(restriction) {
+            if facet.name == "simpleType" {
+                continue;
+            }
+            let value = required_attribute(facet, "value")?;
+            let length = || {
+                value
+                    .parse()
+                    .map_err(|_| invalid(facet, format!("invalid length '{}'", value)))
+            };
+            match facet.name.as_str() {
+                "enumeration" => facets.enumeration.push(String::from(value)),
+                "pattern" => facets
+                    .patterns
+                    .push(Pattern::new(value).map_err(|e| invalid(facet, e.to_string()))?),
+                "length" => facets.length = Some(length()?),
+                "minLength" => facets.min_length = Some(length()?),
+                "maxLength" => facets.max_length = Some(length()?),
+                _ => return Err(self.unsupported(facet)),
+            }
+        }
+        Ok(facets)
+    }
+
+    fn complex_type(&mut self, e: &'a Element, name: String) -> Result<ComplexType, InvalidSchema> {
+        let mut complex = ComplexType {
+            name,
+            content: Content::Empty,
+            mixed: e.get_attribute("mixed") == Some("true"),
+            attributes: Vec::new(),
+            any_attribute: None,
+        };
+        for child in definitions(e) {
+            match child.name.as_str() {
+                "simpleContent" | "complexContent" => self.derived_content(child, &mut complex)?,
+                "sequence" | "choice" | "all" => {
+                    complex.content = Content::Elements(self.particle(child)?)
+                }
+                _ => {
+                    self.attribute_use(child, &mut complex.attributes, &mut complex.any_attribute)?
+                }
+            }
+        }
+        if complex.mixed && matches!(complex.content, Content::Empty) {
+            complex.content = Content::Elements(Particle {
+                min: 1,
+                max: Some(1),
+                term: Term::Sequence(Vec::new()),
+            });
+        }
+        Ok(complex)
+    }
+
+    /// Read the `simpleContent` or `complexContent` of a complex type, which
+    /// may extend the content and attributes of another type.
+    fn derived_content(
+        &mut self,
+        e: &'a Element,
+        complex: &mut ComplexType,
+    ) -> Result<(), InvalidSchema> {
+        if e.get_attribute("mixed") == Some("true") {
+            complex.mixed = true;
+        }
+        let extension = definitions(e)
+            .next()
+            .filter(|derivation| derivation.name == "extension")
+            .ok_or_else(|| invalid(e, String::from("only derivation by extension is supported")))?;
+        let base = self.type_reference(extension, required_attribute(extension, "base")?)?;
+        let (content, attributes, any_attribute) = match self.defined_type(extension, base)? {
+            Type::Simple(_) => (Content::Simple(base), Vec::new(), None),
+            Type::Complex(base) => (
+                base.content.clone(),
+                base.attributes.clone(),
+                base.any_attribute.clone(),
+            ),
+        };
+        complex.attributes = attributes;
+        complex.any_attribute = any_attribute;
+        let mut extended = None;
+        for child in definitions(extension) {
+            match child.name.as_str() {
+                "sequence" | "choice" | "all" => extended = Some(self.particle(child)?),
+                _ => {
+                    self.attribute_use(child, &mut complex.attributes, &mut complex.any_attribute)?
+                }
+            }
+        }
+        let is_simple = e.name == "simpleContent";
+        complex.content = match (content, extended) {
+            (Content::Simple(simple), None) if is_simple => Content::Simple(simple),
+            (Content::Empty, None) if !is_simple => Content::Empty,
+            (Content::Empty, Some(particle)) if !is_simple => Content::Elements(particle),
+            (Content::Elements(base), None) if !is_simple => Content::Elements(base),
+            (Content::Elements(base), Some(particle)) if !is_simple => {
+                Content::Elements(Particle {
+                    min: 1,
+                    max: Some(1),
+                    term: Term::Sequence(vec![base, particle]),
+                })
+            }
+            _ => {
+                return Err(invalid(
+                    extension,
+                    String::from("invalid extension of its base"),
+                ))
+            }
+        };
+        Ok(())
+    }
+
+    /// Read an `attribute`, `attributeGroup` reference or `anyAttribute`.
+    fn attribute_use(
+        &mut self,
+        e: &'a Element,
+        attributes: &mut Vec<AttributeDecl>,
+        any_attribute: &mut Option<Wildcard>,
+    ) -> Result<(), InvalidSchema> {
+        match e.name.as_str() {
+            "attribute" => {
+                if e.get_attribute("ref").is_some() {
+                    return Err(invalid(
+                        e,
+                        String::from("attribute references are not supported"),
+                    ));
+                }
+                let name = required_attribute(e, "name")?;
+                let type_id = if e.get_attribute("type").is_some()
+                    || definitions(e).any(|child| child.name == "simpleType")
+                {
+                    self.simple_type_of(e, "type")?
+                } else {
+                    self.builtin_type(Builtin::AnySimpleType)
+                };
+                let qualified = match e.get_attribute("form") {
+                    Some(form) => form == "qualified",
+                    None => self.attributes_qualified,
+                };
+                let decl = AttributeDecl {
+                    name: String::from(name),
+                    namespace: self.target_namespace.clone().filter(|_| qualified),
+                    type_id,
+                    required: e.get_attribute("use") == Some("required"),
+                    fixed: e.get_attribute("fixed").map(String::from),
+                };
+                attributes.retain(|a| a.name != decl.name || a.namespace != decl.namespace);
+                if e.get_attribute("use") != Some("prohibited") {
+                    attributes.push(decl);
+                }
+            }
+            "attributeGroup" => {
+                let (namespace, name) = self.resolve(e, required_attribute(e, "ref")?)?;
+                let group = self
+                    .attribute_groups
+                    .get(name.as_str())
+                    .copied()
+                    .filter(|_| self.is_target(&namespace))
+                    .ok_or_else(|| invalid(e, format!("undefined attribute group '{}'", name)))?;
+                // A group that refers to itself would otherwise never end.
+                self.attribute_groups.remove(name.as_str());
+                for child in definitions(group) {
+                    self.attribute_use(child, attributes, any_attribute)?;
+                }
+                self.attribute_groups
+                    .insert(required_attribute(group, "name")?, group);
+            }
+            "anyAttribute" => *any_attribute = Some(self.wildcard(e)?),
+            _ => return Err(self.unsupported(e)),
+        }
+        Ok(())
+    }
+
+    fn builtin_type(&mut self, builtin: Builtin) -> TypeId {
+        if let Some(&id) = self.builtin_ids.get(&builtin) {
+            return id;
+        }
+        let id = self.add_type(Type::Simple(SimpleType {
+            name: format!("xs:{}", builtin.name()),
+            variety: Variety::Builtin(builtin),
+        }));
+        self.builtin_ids.insert(builtin, id);
+        id
+    }
+
+    fn wildcard(&self, e: &Element) -> Result<Wildcard, InvalidSchema> {
+        let namespaces = match e.get_attribute("namespace").unwrap_or("##any") {
+            "##any" => NamespaceConstraint::Any,
+            "##other" => NamespaceConstraint::Other,
+            listed => NamespaceConstraint::Listed(
+                listed
+                    .split_whitespace()
+                    .map(|namespace| match namespace {
+                        "##targetNamespace" => self.target_namespace.clone(),
+                        "##local" => None,
+                        namespace => Some(String::from(namespace)),
+                    })
+                    .collect(),
+            ),
+        };
+        let process = match e.get_attribute("processContents").unwrap_or("strict") {
+            "strict" => ProcessContents::Strict,
+            "lax" => ProcessContents::Lax,
+            "skip" => ProcessContents::Skip,
+            other => return Err(invalid(e, format!("invalid processContents '{}'", other))),
+        };
+        Ok(Wildcard {
+            namespaces,
+            process,
+        })
+    }
+
+    fn particle(&mut self, e: &'a Element) -> Result<Particle, InvalidSchema> {
+        let occurs = |name: &str, default: Option<u32>| match e.get_attribute(name) {
+            None => Ok(default),
+            Some("unbounded") if name == "maxOccurs" => Ok(None),
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| invalid(e, format!("invalid {} '{}'", name, value))),
+        };
+        let min = occurs("minOccurs", Some(1))?.unwrap_or(1);
+        let max = occurs("maxOccurs", Some(1))?;
+        let term = match e.name.as_str() {
+            "element" => match e.get_attribute("ref") {
+                Some(qname) => {
+                    let (namespace, name) = self.resolve(e, qname)?;
+                    let id = self
+                        .global_elements
+                        .get(&name)
+                        .copied()
+                        .filter(|_| self.is_target(&namespace))
+                        .ok_or_else(|| invalid(e, format!("undefined element '{}'", qname)))?;
+                    Term::Element(id)
+                }
+                None => {
+                    let decl = self.element_declaration(e, false)?;
+                    self.elements.push(Some(decl));
+                    Term::Element(self.elements.len() - 1)
+                }
+            },
+            "any" => Term::Any(self.wildcard(e)?),
+            "sequence" | "choice" | "all" => {
+                let mut particles = Vec::new();
+                for child in definitions(e) {
+                    particles.push(self.particle(child)?);
+                }
+                match e.name.as_str() {
+                    "sequence" => Term::Sequence(particles),
+                    "choice" => Term::Choice(particles),
+                    _ => Term::All(particles),
+                }
+            }
+            _ => return Err(self.unsupported(e)),
+        };
+        Ok(Particle { min, max, term })
+    }
+
+    fn element_declaration(
+        &mut self,
+        e: &'a Element,
+        global: bool,
+    ) -> Result<ElementDecl, InvalidSchema> {
+        if e.get_attribute("substitutionGroup").is_some()
+            || e.get_attribute("abstract") == Some("true")
+        {
+            return Err(invalid(
+                e,
+                String::from("substitution groups are not supported"),
+            ));
+        }
+        let qualified = match e.get_attribute("form") {
+            Some(form) => form == "qualified",
+            None => global || self.elements_qualified,
+        };
+        let mut type_id = match e.get_attribute("type") {
+            Some(qname) => Some(self.type_reference(e, qname)?),
+            None => None,
+        };
+        let mut constraints = Vec::new();
+        for child in definitions(e) {
+            match child.name.as_str() {
+                "complexType" => {
+                    let name = format!("anonymous type of '{}'", required_attribute(e, "name")?);
+                    let complex = self.complex_type(child, name)?;
+                    type_id = Some(self.add_type(Type::Complex(complex)));
+                }
+                "simpleType" => type_id = Some(self.simple_type_of(e, "type")?),
+                "unique" | "key" | "keyref" => constraints.push(self.identity_constraint(child)?),
+                _ => return Err(self.unsupported(child)),
+            }
+        }
+        for constraint in &constraints {
+            if let ConstraintKind::KeyRef(refer) = &constraint.kind {
+                let refers_to_key = constraints
+                    .iter()
+                    .any(|c| c.name == *refer && c.kind != constraint.kind);
+                if !refers_to_key {
+                    return Err(invalid(
+                        e,
+                        format!(
+                            "'{}' does not refer to a key of the same element",
+                            constraint.name
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(ElementDecl {
+            name: String::from(required_attribute(e, "name")?),
+            namespace: self.target_namespace.clone().filter(|_| qualified),
+            type_id: type_id.unwrap_or(self.any_type),
+            fixed: e.get_attribute("fixed").map(String::from),
+            constraints,
+        })
+    }
+
+    fn identity_constraint(&self, e: &Element) -> Result<IdentityConstraint, InvalidSchema> {
+        let kind = match e.name.as_str() {
+            "unique" => ConstraintKind::Unique,
+            "key" => ConstraintKind::Key,
+            _ => ConstraintKind::KeyRef(self.resolve(e, required_attribute(e, "refer")?)?.1),
+        };
+        let mut selector = Vec::new();
+        let mut fields = Vec::new();
+        for child in definitions(e) {
+            let xpath = required_attribute(child, "xpath")?;
+            match child.name.as_str() {
+                "selector" => selector = self.xpaths(child, xpath, false)?,
+                "field" => {
+                    let mut alternatives = self.xpaths(child, xpath, true)?;
+                    if alternatives.len() != 1 {
+                        return Err(invalid(child, String::from("unsupported field path")));
+                    }
+                    fields.push(alternatives.remove(0));
+                }
+                _ => return Err(self.unsupported(child)),
+            }
+        }
+        Ok(IdentityConstraint {
+            name: String::from(required_attribute(e, "name")?),
+            kind,
+            selector,
+            fields,
+        })
+    }
+
+    /// Read the alternative paths of a `selector` or `field`.
+    fn xpaths(&self, e: &Element, xpath: &str, field: bool) -> Result<Vec<XPath>, InvalidSchema> {
+        let unsupported = || invalid(e, format!("unsupported path '{}'", xpath));
+        let mut paths = Vec::new();
+        for alternative in xpath.split('|') {
+            let alternative = alternative.trim();
+            let (descendants, rest) = match alternative.strip_prefix(".//") {
+                Some(rest) => (true, rest),
+                None => (false, alternative.strip_prefix("./").unwrap_or(alternative)),
+            };
+            let mut path = XPath {
+                descendants,
+                steps: Vec::new(),
+                attribute: None,
+            };
+            let steps: Vec<&str> = rest.split('/').map(str::trim).collect();
+            for (i, step) in steps.iter().enumerate() {
+                let step = step.strip_prefix("child::").unwrap_or(step);
+                if step == "." {
+                    continue;
+                }
+                if let Some(attribute) = step.strip_prefix('@') {
+                    if !field || i + 1 != steps.len() {
+                        return Err(unsupported());
+                    }
+                    path.attribute = Some(self.name_test(e, attribute, true)?);
+                } else if step.is_empty() {
+                    return Err(unsupported());
+                } else {
+                    path.steps.push(self.name_test(e, step, false)?);
+                }
+            }
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    fn name_test(
+        &self,
+        e: &Element,
+        test: &str,
+        attribute: bool,
+    ) -> Result<NameTest, InvalidSchema> {
+        if test == "*" {
+            return Ok(NameTest::Any);
+        }
+        let valid = test
+            .split(':')
+            .all(|part| part == "*" || is_name(part, false));
+        if !valid || (attribute && test.contains('*')) {
+            return Err(invalid(e, format!("unsupported name test '{}'", test)));
+        }
+        // Unprefixed names in paths are in no namespace, not the default one.
+        let resolved = match test.split_once(':') {
+            Some((prefix, local)) => (self.resolve(e, &format!("{}:{}", prefix, local))?.0, local),
+            None => (None, test),
+        };
+        Ok(match resolved {
+            (namespace, "*") => NameTest::Namespace(namespace),
+            (namespace, local) => NameTest::Name(namespace, String::from(local)),
+        })
+    }
+}
+
+/// The target namespace of a schema, and the namespace of the document being
+/// validated that stands for it.
+#[derive(Debug, Clone, Copy)]
+struct Namespaces<'a> {
+    target: Option<&'a str>,
+    actual: Option<&'a str>,
+}
+
+impl Namespaces<'_> {
+    /// Return whether a name in the declared namespace of the schema matches
+    /// one in the namespace of the document.
+    fn matches(&self, declared: Option<&str>, namespace: Option<&str>) -> bool {
+        if declared.is_some() && declared == self.target {
+            namespace == self.actual
+        } else {
+            declared == namespace
+        }
+    }
+
+    fn allows(&self, wildcard: &Wildcard, namespace: Option<&str>) -> bool {
+        match &wildcard.namespaces {
+            NamespaceConstraint::Any => true,
+            NamespaceConstraint::Other => namespace.is_some() && namespace != self.actual,
+            NamespaceConstraint::Listed(listed) => listed
+                .iter()
+                .any(|declared| self.matches(declared.as_deref(), namespace)),
+        }
+    }
+
+    fn name_test(&self, test: &NameTest, name: &str, namespace: Option<&str>) -> bool {
+        match test {
+            NameTest::Any => true,
+            NameTest::Namespace(declared) => self.matches(declared.as_deref(), namespace),
+            NameTest::Name(declared, local) => {
+                local == name && self.matches(declared.as_deref(), namespace)
+            }
+        }
+    }
+}
+
+/// What a child element was matched with in the content of its parent.
+#[derive(Debug, Clone, Copy)]
+enum Matched<'s> {
+    Element(ElementId),
+    Wildcard(&'s Wildcard),
+}
+
+/// Matches the child elements of an element against the particle of its
+/// content. As the particles of a schema must match elements unambiguously,
+/// each child is matched with the first particle that can take it, and never
+/// reconsidered. A required particle that no child matches is recorded as
+/// missing, and matching continues as if it had been matched.
+struct Matcher<'s, 'e> {
+    schema: &'s Schema,
+    namespaces: Namespaces<'s>,
+    children: Vec<&'e Element>,
+    next: usize,
+    matched: Vec<Matched<'s>>,
+    missing: Vec<Vec<String>>,
+    /// The names of the elements that could have been matched at the child
+    /// `expected_at`, the furthest any particle got.
+    expected: Vec<String>,
+    expected_at: usize,
+}
+
+impl<'s, 'e> Matcher<'s, 'e> {
+    fn current(&self) -> Option<&'e Element> {
+        self.children.get(self.next).copied()
+    }
+
+    fn particle(&mut self, particle: &'s Particle) {
+        let mut count = 0;
+        while particle.max.is_none_or(|max| count < max) {
+            match self.current() {
+                Some(child) if self.starts_with(&particle.term, child) => {
+                    let before = self.next;
+                    self.term(&particle.term);
+                    count += 1;
+                    if self.next == before {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        if particle.max.is_none_or(|max| count < max) {
+            self.expect(&particle.term);
+        }
+        if count < particle.min && !self.is_emptiable(&particle.term) {
+            match &particle.term {
+                // Report what is missing from within the group.
+                Term::Sequence(_) | Term::All(_) => self.term(&particle.term),
+                term => {
+                    let mut names = Vec::new();
+                    self.first_names(term, &mut names);
+                    self.missing.push(names);
+                }
+            }
+        }
+    }
+
+    fn term(&mut self, term: &'s Term) {
+        match term {
+            Term::Element(id) => {
+                self.matched.push(Matched::Element(*id));
+                self.next += 1;
+            }
+            Term::Any(wildcard) => {
+                self.matched.push(Matched::Wildcard(wildcard));
+                self.next += 1;
+            }
+            Term::Sequence(particles) => {
+                for particle in particles {
+                    self.particle(particle);
+                }
+            }
+            Term::Choice(particles) => {
+                let chosen = self
+                    .current()
+                    .and_then(|child| particles.iter().find(|p| self.starts_with(&p.term, child)));
+                if let Some(particle) = chosen {
+                    self.particle(particle);
+                }
+            }
+            Term::All(particles) => {
+                let mut remaining: Vec<&Particle> = particles.iter().collect();
+                while let Some(child) = self.current() {
+                    match remaining
+                        .iter()
+                        .position(|p| self.starts_with(&p.term, child))
+                    {
+                        Some(i) => {
+                            let particle = remaining.remove(i);
+                            self.term(&particle.term);
+                        }
+                        None => break,
+                    }
+                }
+                for particle in remaining {
+                    self.expect(&particle.term);
+                    if particle.min > 0 {
+                        let mut names = Vec::new();
+                        self.first_names(&particle.term, &mut names);
+                        self.missing.push(names);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Return whether the term can match the child as its first element.
+    fn starts_with(&self, term: &Term, child: &Element) -> bool {
+        match term {
+            Term::Element(id) => {
+                let decl = &self.schema.elements[*id];
+                decl.name == child.name
+                    && self
+                        .namespaces
+                        .matches(decl.namespace.as_deref(), child.namespace.as_deref())
+            }
+            Term::Any(wildcard) => self.namespaces.allows(wildcard, child.namespace.as_deref()),
+            Term::Sequence(particles) => {
+                for particle in particles {
+                    if self.starts_with(&particle.term, child) {
+                        return true;
+                    }
+                    if particle.min > 0 && !self.is_emptiable(&particle.term) {
+                        return false;
+                    }
+                }
+                false
+            }
+            Term::Choice(particles) | Term::All(particles) => particles
+                .iter()
+                .any(|particle| self.starts_with(&particle.term, child)),
+        }
+    }
+
+    /// Return whether the term can match no elements at all.
+    fn is_emptiable(&self, term: &Term) -> bool {
+        let emptiable =
+            |particle: &Particle| particle.min == 0 || self.is_emptiable(&particle.term);
+        match term {
+            Term::Element(_) | Term::Any(_) => false,
+            Term::Sequence(particles) | Term::All(particles) => particles.iter().all(emptiable),
+            Term::Choice(particles) => particles.iter().any(emptiable),
+        }
+    }
+
+    /// Add the names of the elements that could start the term to the
+    /// names of those that would have been matched by the current child.
+    fn expect(&mut self, term: &Term) {
+        if self.expected_at != self.next {
+            self.expected.clear();
+            self.expected_at = self.next;
+        }
+        let mut names = std::mem::take(&mut self.expected);
+        self.first_names(term, &mut names);
+        self.expected = names;
+    }
+
+    fn first_names(&self, term: &Term, names: &mut Vec<String>) {
+        let mut add = |name: &String| {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        };
+        match term {
+            Term::Element(id) => add(&self.schema.elements[*id].name),
+            // Wildcards are left out, as they have no name to expect.
+            Term::Any(_) => {}
+            Term::Sequence(particles) => {
+                for particle in particles {
+                    self.first_names(&particle.term, names);
+                    if particle.min > 0 && !self.is_emptiable(&particle.term) {
+                        break;
+                    }
+                }
+            }
+            Term::Choice(particles) | Term::All(particles) => {
+                for particle in particles {
+                    self.first_names(&particle.term, names);
+                }
+            }
+        }
+    }
+}
+
+struct Validator<'s> {
+    schema: &'s Schema,
+    namespaces: Namespaces<'s>,
+    path: Vec<String>,
+    violations: Vec<SchemaViolation>,
+    ids: HashSet<String>,
+    /// The IDs referred to, and the paths and positions of the references.
+    id_references: Vec<(String, String, Position)>,
+}
+
+impl<'s> Validator<'s> {
+    /// Create a validator of the document with the provided root element.
+    /// Documents in a namespace the schema does not accept are validated as
+    /// if they were in its target namespace, so that every element is
+    /// reported as undeclared.
+    fn new(schema: &'s Schema, root: &'s Element) -> Self {
+        let namespace = if schema.accepts_namespace(root.namespace.as_deref()) {
+            root.namespace.as_deref()
+        } else {
+            schema.target_namespace()
+        };
+        Self {
+            schema,
+            namespaces: Namespaces {
+                target: schema.target_namespace(),
+                actual: namespace,
+            },
+            path: Vec::new(),
+            violations: Vec::new(),
+            ids: HashSet::new(),
+            id_references: Vec::new(),
+        }
+    }
+
+    fn path(&self) -> String {
+        format!("/{}", self.path.join("/"))
+    }
+
+    fn report(&mut self, kind: ViolationKind, e: &Element, value: Option<String>) {
+        let path = self.path();
+        self.violations.push(SchemaViolation {
+            kind,
+            path,
+            value,
+            position: e.position,
+        });
+    }
+
+    /// Record a problem with the child of the element currently being
+    /// validated.
+    fn report_child(&mut self, kind: ViolationKind, child: &Element, value: Option<String>) {
+        self.path.push(path_segment(child));
+        self.report(kind, child, value);
+        self.path.pop();
+    }
+
+    fn report_value(
+        &mut self,
+        kind: ViolationKind,
+        e: &Element,
+        attribute: Option<&str>,
+        value: &str,
+    ) {
+        match attribute {
+            Some(name) => {
+                self.path.push(format!("@{}", name));
+                self.report(kind, e, Some(String::from(value)));
+                self.path.pop();
+            }
+            None => self.report(kind, e, Some(String::from(value))),
+        }
+    }
+
+    fn finish(mut self) -> Vec<SchemaViolation> {
+        for (id, path, position) in std::mem::take(&mut self.id_references) {
+            if !self.ids.contains(&id) {
+                self.violations.push(SchemaViolation {
+                    kind: ViolationKind::UndefinedId,
+                    path,
+                    value: Some(id),
+                    position,
+                });
+            }
+        }
+        self.violations.sort_by_key(|v| v.position);
+        self.violations
+    }
+
+    fn global_element(&self, e: &Element) -> Option<ElementId> {
+        self.schema
+            .global_elements
+            .get(&e.name)
+            .copied()
+            .filter(|&id| {
+                let declared = self.schema.elements[id].namespace.as_deref();
+                self.namespaces.matches(declared, e.namespace.as_deref())
+            })
+    }
+
+    fn element(&mut self, e: &Element, id: ElementId) {
+        let schema = self.schema;
+        let decl = &schema.elements[id];
+        self.path.push(path_segment(e));
+        match &schema.types[decl.type_id] {
+            Type::Simple(_) => {
+                self.attributes(e, &[], None);
+                self.simple_content(e, decl.type_id, decl.fixed.as_deref());
+            }
+            Type::Complex(complex) => {
+                self.attributes(e, &complex.attributes, complex.any_attribute.as_ref());
+                match &complex.content {
+                    Content::Empty => {
+                        self.no_child_elements(e);
+                        self.no_text(e);
+                    }
+                    Content::Simple(simple) => {
+                        self.simple_content(e, *simple, decl.fixed.as_deref())
+                    }
+                    Content::Elements(particle) => {
+                        if !complex.mixed {
+                            self.no_text(e);
+                        }
+                        self.children(e, particle);
+                    }
+                }
+            }
+        }
+        self.identity_constraints(e, decl);
+        self.path.pop();
+    }
+
+    fn simple_content(&mut self, e: &Element, simple: TypeId, fixed: Option<&str>) {
+        self.no_child_elements(e);
+        let text = e.get_text().unwrap_or_default();
+        self.value(e, None, simple, &text, fixed);
+    }
+
+    fn no_child_elements(&mut self, e: &Element) {
+        for child in e.child_elements() {
+            let value = Some(child.name.clone());
+            self.report_child(ViolationKind::UnexpectedElement(Vec::new()), child, value);
+        }
+    }
+
+    fn no_text(&mut self, e: &Element) {
+        if let Some(text) = e.get_text().filter(|text| !text.trim().is_empty()) {
+            self.report(
+                ViolationKind::UnexpectedText,
+                e,
+                Some(String::from(text.trim())),
+            );
+        }
+    }
+
+    fn attributes(
+        &mut self,
+        e: &Element,
+        declared: &'s [AttributeDecl],
+        any_attribute: Option<&Wildcard>,
+    ) {
+        for attribute in &e.attributes {
+            let namespace = attribute.namespace.as_deref();
+            if namespace == Some(XSI_NAMESPACE) {
+                continue;
+            }
+            let decl = declared.iter().find(|decl| {
+                decl.name == attribute.name
+                    && self
+                        .namespaces
+                        .matches(decl.namespace.as_deref(), namespace)
+            });
+            match decl {
+                Some(decl) => self.value(
+                    e,
+                    Some(&attribute.name),
+                    decl.type_id,
+                    &attribute.value,
+                    decl.fixed.as_deref(),
+                ),
+                None if any_attribute.is_some_and(|w| self.namespaces.allows(w, namespace)) => {}
+                None => {
+                    let name = attribute.name.clone();
+                    self.report_value(ViolationKind::UnexpectedAttribute, e, Some(&name), &name);
+                }
+            }
+        }
+        for decl in declared.iter().filter(|decl| decl.required) {
+            let present = e.attributes.iter().any(|attribute| {
+                attribute.name == decl.name
+                    && self
+                        .namespaces
+                        .matches(decl.namespace.as_deref(), attribute.namespace.as_deref())
+            });
+            if !present {
+                self.report(ViolationKind::MissingAttribute(decl.name.clone()), e, None);
+            }
+        }
+    }
+
+    /// Check the value of the element, or of its named attribute, against
+    /// the simple type.
+    fn value(
+        &mut self,
+        e: &Element,
+        attribute: Option<&str>,
+        simple: TypeId,
+        value: &str,
+        fixed: Option<&str>,
+    ) {
+        let schema = self.schema;
+        let normalized = match schema.check_value(simple, value) {
+            Ok(normalized) => normalized,
+            Err(reason) => {
+                let kind = ViolationKind::InvalidValue {
+                    type_name: String::from(schema.type_name(simple)),
+                    reason,
+                };
+                self.report_value(kind, e, attribute, value);
+                return;
+            }
+        };
+        if let Some(fixed) = fixed {
+            if schema.check_value(simple, fixed).as_deref() != Ok(normalized.as_str()) {
+                let kind = ViolationKind::FixedValue(String::from(fixed));
+                self.report_value(kind, e, attribute, value);
+            }
+        }
+        match schema.builtin(simple) {
+            Some(Builtin::Id) if !self.ids.insert(normalized.clone()) => {
+                self.report_value(ViolationKind::DuplicateId, e, attribute, &normalized);
+            }
+            Some(Builtin::IdRef | Builtin::IdRefs) => {
+                let path = match attribute {
+                    Some(name) => format!("{}/@{}", self.path(), name),
+                    None => self.path(),
+                };
+                for id in normalized.split(' ') {
+                    self.id_references
+                        .push((String::from(id), path.clone(), e.position));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn children(&mut self, e: &Element, particle: &'s Particle) {
+        let children: Vec<&Element> = e.child_elements().collect();
+        // Children that could not be matched, and the names of the elements
+        // that were expected in their place. They are left out and the
+        // content matched again until every child left is matched.
+        let mut unexpected: Vec<(usize, Vec<String>)> = Vec::new();
+        let (matcher, included) = loop {
+            let included: Vec<usize> = (0..children.len())
+                .filter(|i| !unexpected.iter().any(|(skipped, _)| skipped == i))
+                .collect();
+            let mut matcher = Matcher {
+                schema: self.schema,
+                namespaces: self.namespaces,
+                children: included.iter().map(|&i| children[i]).collect(),
+                next: 0,
+                matched: Vec::new(),
+                missing: Vec::new(),
+                expected: Vec::new(),
+                expected_at: 0,
+            };
+            matcher.particle(particle);
+            if matcher.next == included.len() {
+                break (matcher, included);
+            }
+            let expected = if matcher.expected_at == matcher.next {
+                matcher.expected
+            } else {
+                Vec::new()
+            };
+            unexpected.push((included[matcher.next], expected));
+        };
+        for names in matcher.missing {
+            self.report(ViolationKind::MissingElement(names), e, None);
+        }
+        let mut matched: Vec<Option<Matched>> = vec![None; children.len()];
+        for (i, m) in included.into_iter().zip(matcher.matched) {
+            matched[i] = Some(m);
+        }
+        for (i, expected) in unexpected {
+            let child = children[i];
+            let value = Some(child.name.clone());
+            self.report_child(ViolationKind::UnexpectedElement(expected), child, value);
+            // Problems within the child are still worth reporting, if it is
+            // declared elsewhere in the content.
+            matched[i] = self
+                .declaration_by_name(&particle.term, child)
+                .or_else(|| self.global_element(child))
+                .map(Matched::Element);
+        }
+        for (child, m) in children.into_iter().zip(matched) {
+            match m {
+                Some(Matched::Element(id)) => self.element(child, id),
+                Some(Matched::Wildcard(wildcard)) => self.wildcard_child(child, wildcard),
+                None => {}
+            }
+        }
+    }
+
+    fn declaration_by_name(&self, term: &Term, child: &Element) -> Option<ElementId> {
+        match term {
+            Term::Element(id) => {
+                let decl = &self.schema.elements[*id];
+                let matches = decl.name == child.name
+                    && self
+                        .namespaces
+                        .matches(decl.namespace.as_deref(), child.namespace.as_deref());
+                Some(*id).filter(|_| matches)
+            }
+            Term::Any(_) => None,
+            Term::Sequence(particles) | Term::Choice(particles) | Term::All(particles) => particles
+                .iter()
+                .find_map(|particle| self.declaration_by_name(&particle.term, child)),
+        }
+    }
+
+    fn wildcard_child(&mut self, child: &Element, wildcard: &Wildcard) {
+        if wildcard.process == ProcessContents::Skip {
+            return;
+        }
+        match self.global_element(child) {
+            Some(id) => self.element(child, id),
+            None if wildcard.process == ProcessContents::Strict => {
+                let value = Some(expanded_name(child));
+                self.report_child(ViolationKind::UndeclaredElement, child, value);
+            }
+            None => {}
+        }
+    }
+
+    fn identity_constraints(&mut self, e: &Element, decl: &'s ElementDecl) {
+        let mut tables: HashMap<&str, HashSet<Vec<String>>> = HashMap::new();
+        let (keys, references): (Vec<_>, Vec<_>) = decl
+            .constraints
+            .iter()
+            .partition(|c| !matches!(c.kind, ConstraintKind::KeyRef(_)));
+        for constraint in keys {
+            let table = self.key_table(e, constraint);
+            tables.insert(&constraint.name, table);
+        }
+        for constraint in references {
+            let ConstraintKind::KeyRef(refer) = &constraint.kind else {
+                continue;
+            };
+            for (node, path) in self.select(e, &constraint.selector) {
+                let Some(values) = self.fields(node, &constraint.fields) else {
+                    continue;
+                };
+                if !tables
+                    .get(refer.as_str())
+                    .is_some_and(|t| t.contains(&values))
+                {
+                    let kind = ViolationKind::UndefinedKey {
+                        constraint: constraint.name.clone(),
+                        refer: refer.clone(),
+                    };
+                    self.report_selected(kind, node, path, Some(values.join(", ")));
+                }
+            }
+        }
+    }
+
+    /// Return the values of the `key` or `unique` constraint of the element,
+    /// and record the elements it selects that have no value or a duplicate
+    /// one.
+    fn key_table(&mut self, e: &Element, constraint: &IdentityConstraint) -> HashSet<Vec<String>> {
+        let mut table = HashSet::new();
+        for (node, path) in self.select(e, &constraint.selector) {
+            match self.fields(node, &constraint.fields) {
+                Some(values) if !table.insert(values.clone()) => {
+                    let kind = ViolationKind::DuplicateKey(constraint.name.clone());
+                    self.report_selected(kind, node, path, Some(values.join(", ")));
+                }
+                Some(_) => {}
+                None if constraint.kind == ConstraintKind::Key => {
+                    let kind = ViolationKind::MissingKey(constraint.name.clone());
+                    self.report_selected(kind, node, path, None);
+                }
+                None => {}
+            }
+        }
+        table
+    }
+
+    /// Record a problem with an element selected by an identity constraint of
+    /// the element currently being validated, with the provided path from it.
+    fn report_selected(
+        &mut self,
+        kind: ViolationKind,
+        node: &Element,
+        path: Vec<String>,
+        value: Option<String>,
+    ) {
+        let depth = self.path.len();
+        self.path.extend(path);
+        self.report(kind, node, value);
+        self.path.truncate(depth);
+    }
+
+    /// Return the elements selected by the alternative paths from the
+    /// element, each with its path from the element.
+    fn select<'e>(&self, e: &'e Element, paths: &[XPath]) -> Vec<(&'e Element, Vec<String>)> {
+        let mut selected: Vec<(&'e Element, Vec<String>)> = Vec::new();
+        for path in paths {
+            let mut current = vec![(e, Vec::new())];
+            if path.descendants {
+                let mut i = 0;
+                while i < current.len() {
+                    let (node, node_path) = current[i].clone();
+                    for child in node.child_elements() {
+                        let mut child_path = node_path.clone();
+                        child_path.push(path_segment(child));
+                        current.push((child, child_path));
+                    }
+                    i += 1;
+                }
+            }
+            for step in &path.steps {
+                current = current
+                    .into_iter()
+                    .flat_map(|(node, node_path)| {
+                        node.child_elements()
+                            .filter(|child| {
+                                self.namespaces.name_test(
+                                    step,
+                                    &child.name,
+                                    child.namespace.as_deref(),
+                                )
+                            })
+                            .map(move |child| {
+                                let mut child_path = node_path.clone();
+                                child_path.push(path_segment(child));
+                                (child, child_path)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+            }
+            for (node, node_path) in current {
+                if !selected.iter().any(|(other, _)| std::ptr::eq(*other, node)) {
+                    selected.push((node, node_path));
+                }
+            }
+        }
+        selected
+    }
+
+    /// Return the values of the fields of the selected element, or None if it
+    /// lacks any of them.
+    fn fields(&self, node: &Element, fields: &[XPath]) -> Option<Vec<String>> {
+        fields
+            .iter()
+            .map(|field| {
+                let selected = self.select(node, std::slice::from_ref(field));
+                let (e, _) = selected.first()?;
+                match &field.attribute {
+                    Some(test) => e
+                        .attributes
+                        .iter()
+                        .find(|a| {
+                            self.namespaces
+                                .name_test(test, &a.name, a.namespace.as_deref())
+                        })
+                        .map(|a| collapse(&a.value)),
+                    None => Some(collapse(&e.get_text().unwrap_or_default())),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(document: &str) -> Vec<String> {
+        Schema::dif()
+            .validate_reader(document.as_bytes())
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_dif_schema_accepts_a_valid_module() {
+        let document = r#"<?xml version="1.0"?>
+<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://standards.ieee.org/IEEE1516-2010 http://standards.ieee.org/downloads/1516/1516.2-2010/IEEE1516-DIF-2010.xsd">
+    <modelIdentification>
+        <name>Valid</name>
+        <type>FOM</type>
+        <modificationDate>2010-08-31</modificationDate>
+        <securityClassification>Unclassified</securityClassification>
+        <description>A valid module</description>
+        <poc>
+            <pocType>Primary author</pocType>
+        </poc>
+    </modelIdentification>
+    <serviceUtilization>
+        <connect section="4.2" isUsed="true" isCallback="false"/>
+        <updateAttributeValues section="6.10" isUsed="true" isCallback="false"/>
+    </serviceUtilization>
+    <objects>
+        <objectClass>
+            <name>HLAobjectRoot</name>
+            <objectClass notes="Note1">
+                <name>Vehicle</name>
+                <sharing>PublishSubscribe</sharing>
+                <attribute>
+                    <name>Speed</name>
+                    <dataType>HLAfloat64BE</dataType>
+                    <dimensions>
+                        <dimension>Region</dimension>
+                    </dimensions>
+                </attribute>
+            </objectClass>
+        </objectClass>
+    </objects>
+    <dataTypes>
+        <arrayDataTypes>
+            <arrayData>
+                <name>Speeds</name>
+                <dataType>HLAfloat64BE</dataType>
+                <cardinality>[1..8]</cardinality>
+                <encoding>HLAvariableArray</encoding>
+            </arrayData>
+        </arrayDataTypes>
+    </dataTypes>
+    <notes>
+        <note>
+            <label>Note1</label>
+            <semantics>A note</semantics>
+        </note>
+    </notes>
+</objectModel>"#;
+        assert_eq!(Vec::<String>::new(), validate(document));
+    }
+
+    #[test]
+    fn test_dif_schema_reports_every_violation() {
+        let document = r#"<objectModel xmlns="http://www.sisostds.org/schemas/IEEE1516-2010" idtag="Model">
+    <modelIdentification>
+        <name>Invalid</name>
+        <type>FOM</type>
+        <modificationDate>2010-02-30</modificationDate>
+        <description></description>
+    </modelIdentification>
+    <serviceUtilization>
+        <connect section="4.3" isUsed="maybe" isCallback="false"/>
+        <connect section="4.2" isUsed="true" isCallback="false"/>
+    </serviceUtilization>
+    <objects>
+        <objectClass>
+            <name>HLAobjectRoot</name>
+            <objectClass notes="Missing">
+                <semantics>A vehicle</semantics>
+                <name>Vehicle</name>
+                <attribute>
+                    <name>Speed</name>
+                    <sharing>Sometimes</sharing>
+                </attribute>
+                <attribute>
+                    <name>Speed</name>
+                    <colour>Red</colour>
+                </attribute>
+            </objectClass>
+            <objectClass>Text<name>Vehicle</name></objectClass>
+        </objectClass>
+    </objects>
+    <dataTypes>
+        <arrayDataTypes>
+            <arrayData>
+                <name>Speeds</name>
+                <cardinality>[1..]</cardinality>
+            </arrayData>
+        </arrayDataTypes>
+    </dataTypes>
+    <notes>
+        <note><label>Model</label></note>
+    </notes>
+</objectModel>"#;
+        assert_eq!(
+            vec![
+                "5:9: /objectModel/modelIdentification[Invalid]/modificationDate: invalid value '2010-02-30' of type 'xs:date': not a valid xs:date",
+                "6:9: /objectModel/modelIdentification[Invalid]/description: invalid value '' of type 'nonEmptyString': length is less than 1",
+                "9:9: /objectModel/serviceUtilization/connect/@section: value '4.3' is not the fixed value '4.2'",
+                "9:9: /objectModel/serviceUtilization/connect/@isUsed: invalid value 'maybe' of type 'xs:boolean': not a valid xs:boolean",
+                "10:9: /objectModel/serviceUtilization/connect: unexpected element 'connect', expected 'disconnect', 'connectionLost', 'createFederationExecution', 'destroyFederationExecution', 'listFederationExecutions', 'reportFederationExecutions', 'joinFederationExecution', 'resignFederationExecution', 'registerFederationSynchronizationPoint' or one of 158 other elements",
+                "15:13: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]: missing required element 'name'",
+                "15:13: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/@notes: reference to undefined ID 'Missing'",
+                "17:17: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/name: unexpected element 'name', expected 'attribute' or 'objectClass'",
+                "20:21: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/attribute[Speed]/sharing: invalid value 'Sometimes' of type 'sharingEnumerations': not one of 'Publish', 'Subscribe', 'PublishSubscribe' or 'Neither'",
+                "22:17: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/attribute[Speed]: duplicate value 'Speed' of 'attributeName'",
+                "24:21: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]/attribute[Speed]/colour: unexpected element 'colour', expected 'dataType', 'updateType', 'updateCondition', 'ownership', 'sharing', 'dimensions', 'transportation', 'order' or 'semantics'",
+                "27:13: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]: unexpected text 'Text'",
+                "27:13: /objectModel/objects/objectClass[HLAobjectRoot]/objectClass[Vehicle]: duplicate value 'Vehicle' of 'className'",
+                "34:17: /objectModel/dataTypes/arrayDataTypes/arrayData[Speeds]/cardinality: invalid value '[1..]' of type 'cardinalityPattern': does not match the pattern '(Dynamic|(\\d)+|(\\[(\\d)+..(\\d)+\\]))(,(Dynamic|(\\d)+|(\\[(\\d)+..(\\d)+\\])))*'",
+                "39:15: /objectModel/notes/note[Model]/label: duplicate ID 'Model'",
+            ],
+            validate(document)
+        );
+    }
+
+    #[test]
+    fn test_dif_schema_checks_2025_documents_as_2010_structure() {
+        let document = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2025">
+    <objects>
+        <objectClass>
+            <name>HLAobjectRoot</name>
+            <sharing>Bogus</sharing>
+        </objectClass>
+    </objects>
+    <dataTypes>
+        <fixedRecordDataTypes>
+            <fixedRecordData>
+                <name>Position</name>
+                <encoding>HLAextendableFixedRecord</encoding>
+            </fixedRecordData>
+        </fixedRecordDataTypes>
+    </dataTypes>
+</objectModel>"#;
+        assert_eq!(
+            vec!["5:13: /objectModel/objects/objectClass[HLAobjectRoot]/sharing: invalid value 'Bogus' of type 'sharingEnumerations': not one of 'Publish', 'Subscribe', 'PublishSubscribe' or 'Neither'"],
+            validate(document)
+        );
+        assert!(Schema::omt().accepts_namespace(Some(SISO_IEEE1516_2025_NAMESPACE)));
+    }
+
+    #[test]
+    fn test_dif_schema_rejects_other_documents() {
+        assert_eq!(
+            vec!["1:1: /objectModel: element '{http://standards.ieee.org/IEEE1516-2000}objectModel' is not declared by the schema"],
+            validate(r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2000"/>"#)
+        );
+        assert_eq!(
+            vec!["1:1: /fom: element 'fom' is not declared by the schema"],
+            validate("<fom/>")
+        );
+    }
+
+    const SCHEMA: &str = r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:t="urn:test" targetNamespace="urn:test" elementFormDefault="qualified">
+    <xs:element name="root">
+        <xs:complexType>
+            <xs:sequence>
+                <xs:choice maxOccurs="unbounded">
+                    <xs:element name="item" type="t:itemType"/>
+                    <xs:element name="link">
+                        <xs:complexType>
+                            <xs:attribute name="to" type="xs:NCName" use="required"/>
+                        </xs:complexType>
+                    </xs:element>
+                </xs:choice>
+                <xs:element name="settings" minOccurs="0">
+                    <xs:complexType>
+                        <xs:all>
+                            <xs:element name="size" type="t:size"/>
+                            <xs:element name="tags" type="t:tags" minOccurs="0"/>
+                        </xs:all>
+                    </xs:complexType>
+                </xs:element>
+            </xs:sequence>
+        </xs:complexType>
+        <xs:key name="itemKey">
+            <xs:selector xpath="t:item"/>
+            <xs:field xpath="@id"/>
+        </xs:key>
+        <xs:keyref name="linkRef" refer="t:itemKey">
+            <xs:selector xpath=".//t:link"/>
+            <xs:field xpath="@to"/>
+        </xs:keyref>
+    </xs:element>
+    <xs:complexType name="itemType">
+        <xs:simpleContent>
+            <xs:extension base="xs:string">
+                <xs:attribute name="id" type="xs:NCName"/>
+            </xs:extension>
+        </xs:simpleContent>
+    </xs:complexType>
+    <xs:simpleType name="size">
+        <xs:restriction base="xs:unsignedShort">
+            <xs:pattern value="\d{1,3}"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="tags">
+        <xs:restriction>
+            <xs:simpleType>
+                <xs:list itemType="xs:NCName"/>
+            </xs:simpleType>
+            <xs:maxLength value="2"/>
+        </xs:restriction>
+    </xs:simpleType>
+</xs:schema>"#;
+
+    #[test]
+    fn test_choices_all_groups_keys_and_lists() {
+        let schema = Schema::parse_str(SCHEMA).unwrap();
+        let validate = |document: &str| -> Vec<String> {
+            schema
+                .validate_reader(document.as_bytes())
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        };
+        let valid = r#"<root xmlns="urn:test">
+    <item id="a">A</item><link to="a"/><item id="b">B</item>
+    <settings><tags>x y</tags><size>12</size></settings>
+</root>"#;
+        assert_eq!(Vec::<String>::new(), validate(valid));
+
+        let invalid = r#"<root xmlns="urn:test">
+    <item>A</item><link to="c"/><link/><item id="b">B</item><item id="b"/>
+    <settings><tags>x y z</tags><tags>x</tags></settings>
+</root>"#;
+        assert_eq!(
+            vec![
+                "2:5: /root/item: no value for key 'itemKey'",
+                "2:19: /root/link: value 'c' of 'linkRef' matches no 'itemKey'",
+                "2:33: /root/link: missing required attribute 'to'",
+                "2:61: /root/item: duplicate value 'b' of 'itemKey'",
+                "3:5: /root/settings: missing required element 'size'",
+                "3:15: /root/settings/tags: invalid value 'x y z' of type 'tags': length is more than 2",
+                "3:33: /root/settings/tags: unexpected element 'tags', expected 'size'",
+            ],
+            validate(invalid)
+        );
+
+        assert_eq!(
+            vec!["1:62: /root/settings/size: invalid value '1000' of type 'size': does not match the pattern '\\d{1,3}'"],
+            validate(r#"<root xmlns="urn:test"><link to="a"/><item id="a"/><settings><size>1000</size></settings></root>"#)
+        );
+        assert_eq!(
+            vec!["1:1: /root: missing required element 'item' or 'link'"],
+            validate(r#"<root xmlns="urn:test"></root>"#)
+        );
+    }
+
+    #[test]
+    fn test_builtin_types() {
+        let valid =
+            |builtin: Builtin, value: &str| builtin.check(&builtin.normalize(value)).is_ok();
+        assert!(valid(Builtin::Short, " -32768 "));
+        assert!(!valid(Builtin::Short, "32768"));
+        assert!(valid(
+            Builtin::NonNegativeInteger,
+            "+123456789012345678901234567890123456789012"
+        ));
+        assert!(!valid(
+            Builtin::NonNegativeInteger,
+            "-123456789012345678901234567890123456789012"
+        ));
+        assert!(!valid(Builtin::UnsignedByte, "1.0"));
+        assert!(valid(Builtin::Decimal, "-.5"));
+        assert!(!valid(Builtin::Decimal, "."));
+        assert!(valid(Builtin::Double, "1.5E-3"));
+        assert!(valid(Builtin::Double, "INF"));
+        assert!(valid(Builtin::Date, "2012-02-29"));
+        assert!(valid(Builtin::Date, "2010-08-31+01:00"));
+        assert!(!valid(Builtin::Date, "2010-8-31"));
+        assert!(valid(Builtin::DateTime, "2010-08-31T23:59:59.5Z"));
+        assert!(!valid(Builtin::DateTime, "2010-08-31T24:00:00"));
+        assert!(valid(Builtin::NcName, "HLAobjectRoot"));
+        assert!(!valid(Builtin::NcName, "a:b"));
+        assert!(valid(Builtin::IdRefs, "Note1  Note2"));
+        assert!(valid(Builtin::Base64Binary, "SExB"));
+        assert!(!valid(Builtin::Base64Binary, "SEx"));
+        assert!(valid(Builtin::Boolean, "1"));
+        assert!(!valid(Builtin::Boolean, "yes"));
+    }
+
+    #[test]
+    fn test_unsupported_schemas_are_rejected() {
+        let unsupported = |definition: &str| {
+            let xsd = format!(
+                r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}</xs:schema>"#,
+                definition
+            );
+            Schema::parse_str(&xsd).unwrap_err().message
+        };
+        assert_eq!(
+            "'import' is not supported here",
+            unsupported(r#"<xs:import namespace="urn:other"/>"#)
+        );
+        assert_eq!(
+            "'minInclusive' is not supported here",
+            unsupported(
+                r#"<xs:simpleType name="t"><xs:restriction base="xs:int"><xs:minInclusive value="1"/></xs:restriction></xs:simpleType>"#
+            )
+        );
+        assert_eq!(
+            "undefined type 'missing'",
+            unsupported(r#"<xs:element name="e" type="missing"/>"#)
+        );
+        assert_eq!(
+            "unsupported built-in type 'xs:QName'",
+            unsupported(r#"<xs:element name="e" type="xs:QName"/>"#)
+        );
+    }
+}
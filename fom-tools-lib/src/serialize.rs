@@ -0,0 +1,1143 @@
+//! Conversion of an object model back into an XML document object model, and
+//! writing of it as an XML document. The elements are produced in the order
+//! required by the IEEE 1516.2-2010 DIF schema, so that the document can be
+//! read back into an identical object model.
+
+use crate::dom::{Attribute, Element, Node, WriteOptions};
+use crate::*;
+use std::io::Write;
+
+/// Conversion of a part of the object model into an XML element with the
+/// provided name. The elements are not given a namespace; the namespace of the
+/// document is applied once the whole document has been converted.
+trait ToElement {
+    fn to_element(&self, name: &str) -> Element;
+}
+
+/// Conversion of a part of the object model that is written as the text
+/// content of an element.
+trait ToText {
+    fn to_text(&self) -> &str;
+}
+
+impl<T: ToText> ToElement for T {
+    fn to_element(&self, name: &str) -> Element {
+        text_element(name, self.to_text())
+    }
+}
+
+impl ToText for String {
+    fn to_text(&self) -> &str {
+        self
+    }
+}
+
+impl<T> ToText for TypedValue<T> {
+    fn to_text(&self) -> &str {
+        self.text()
+    }
+}
+
+fn text_element(name: &str, text: &str) -> Element {
+    let mut e = Element::new(name);
+    if !text.is_empty() {
+        e.children.push(Node::Text(String::from(text)));
+    }
+    e
+}
+
+fn bool_text(value: bool) -> &'static str {
+    if value {
+        "true"
+    } else {
+        "false"
+    }
+}
+
+fn set_attribute(e: &mut Element, name: &str, value: &str) {
+    e.attributes.push(Attribute {
+        name: String::from(name),
+        namespace: None,
+        value: String::from(value),
+    });
+}
+
+/// Append a child element converted from the provided part of the object
+/// model, if there is one.
+fn push_child<T: ToElement>(e: &mut Element, name: &str, child: Option<&T>) {
+    if let Some(child) = child {
+        e.children.push(Node::Element(child.to_element(name)));
+    }
+}
+
+/// Append a child element for each of the provided parts of the object model.
+fn push_children<T: ToElement>(e: &mut Element, name: &str, children: Option<&Vec<T>>) {
+    for child in children.into_iter().flatten() {
+        push_child(e, name, Some(child));
+    }
+}
+
+/// Set the `notes` and `idtag` attributes of the annotated elements. See
+/// `AnnotationType` for how the annotated elements are identified.
+fn apply_annotations(e: &mut Element, annotations: Option<&Vec<AnnotationType>>) {
+    for annotation in annotations.into_iter().flatten() {
+        if let Some(annotated) = find_descendant(e, &annotation.path) {
+            if let Some(notes) = &annotation.notes {
+                set_attribute(annotated, "notes", &notes.join(" "));
+            }
+            if let Some(idtag) = &annotation.idtag {
+                set_attribute(annotated, "idtag", idtag);
+            }
+        }
+    }
+}
+
+/// Return the descendant of the element at the path of an annotation, if the
+/// path is well-formed and there is one. Indices start at one, so a path with
+/// the index `[0]` selects no element.
+fn find_descendant<'a>(e: &'a mut Element, path: &str) -> Option<&'a mut Element> {
+    if path.is_empty() {
+        return Some(e);
+    }
+    let (segment, rest) = match path.find('/') {
+        Some(i) => (&path[..i], &path[i + 1..]),
+        None => (path, ""),
+    };
+    let (name, index) = match segment.split_once('[') {
+        Some((name, index)) => (name, index.strip_suffix(']')?.parse::<usize>().ok()?),
+        None => (segment, 1),
+    };
+    let child = e
+        .children
+        .iter_mut()
+        .filter_map(|node| match node {
+            Node::Element(child) if child.name == name => Some(child),
+            _ => None,
+        })
+        .nth(index.checked_sub(1)?)?;
+    find_descendant(child, rest)
+}
+
+/// Put the provided element, and all of its descendants, in the namespace.
+fn set_namespace(e: &mut Element, namespace: &Option<String>) {
+    e.namespace = namespace.clone();
+    for child in e.children.iter_mut() {
+        if let Node::Element(child) = child {
+            set_namespace(child, namespace);
+        }
+    }
+}
+
+/// Convert an object model into the root `objectModel` element of an OMT
+/// document. Converting the element back with `parse_object_model` gives an
+/// object model equal to the provided one.
+pub fn serialize_object_model(fom: &ObjectModelType) -> Element {
+    let mut e = fom.to_element("objectModel");
+    set_namespace(&mut e, &fom.namespace);
+    if let Some(schema_location) = &fom.schema_location {
+        let value: Vec<&str> = schema_location
+            .iter()
+            .flat_map(|l| vec![l.namespace.as_str(), l.location.as_str()])
+            .collect();
+        e.attributes.push(Attribute {
+            name: String::from("schemaLocation"),
+            namespace: Some(String::from(XSI_NAMESPACE)),
+            value: value.join(" "),
+        });
+    }
+    e
+}
+
+/// Write an object model as an OMT document to the provided writer, as
+/// directed by the provided options.
+pub fn write_object_model_with_options<W: Write>(
+    fom: &ObjectModelType,
+    w: W,
+    options: &WriteOptions,
+) -> Result<(), Error> {
+    Ok(serialize_object_model(fom).write(w, options)?)
+}
+
+/// Write an object model as an OMT document to the provided writer, indented
+/// with four spaces.
+pub fn write_object_model<W: Write>(fom: &ObjectModelType, w: W) -> Result<(), Error> {
+    write_object_model_with_options(fom, w, &WriteOptions::default())
+}
+
+/// Write an object model as an OMT document to the file at the provided path,
+/// replacing the file if it exists.
+pub fn write_file<P: AsRef<Path>>(
+    fom: &ObjectModelType,
+    path: P,
+    options: &WriteOptions,
+) -> Result<(), Error> {
+    let mut w = std::io::BufWriter::new(File::create(path)?);
+    write_object_model_with_options(fom, &mut w, options)?;
+    Ok(w.flush()?)
+}
+
+/// Return an object model as the text of an OMT document.
+pub fn to_xml_string(fom: &ObjectModelType, options: &WriteOptions) -> Result<String, Error> {
+    let mut xml = Vec::new();
+    write_object_model_with_options(fom, &mut xml, options)?;
+    // The writer only produces UTF-8.
+    Ok(String::from_utf8(xml).expect("written XML is UTF-8"))
+}
+
+impl From<&ObjectModelType> for Element {
+    fn from(fom: &ObjectModelType) -> Self {
+        serialize_object_model(fom)
+    }
+}
+
+impl ToElement for ObjectModelType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(
+            &mut e,
+            "modelIdentification",
+            self.model_identification.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "serviceUtilization",
+            self.service_utilization.as_ref(),
+        );
+        push_child(&mut e, "objects", self.objects.as_ref());
+        push_child(&mut e, "interactions", self.interactions.as_ref());
+        push_child(&mut e, "dimensions", self.dimensions.as_ref());
+        push_child(&mut e, "time", self.time.as_ref());
+        push_child(&mut e, "tags", self.tags.as_ref());
+        push_child(&mut e, "synchronizations", self.synchronizations.as_ref());
+        push_child(&mut e, "transportations", self.transportations.as_ref());
+        push_child(&mut e, "switches", self.switches.as_ref());
+        push_child(&mut e, "updateRates", self.update_rates.as_ref());
+        push_child(&mut e, "dataTypes", self.data_types.as_ref());
+        push_child(&mut e, "notes", self.notes.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for ModelIdentificationType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", self.name.as_ref());
+        push_child(&mut e, "type", self.model_type.as_ref());
+        push_child(&mut e, "version", self.version.as_ref());
+        push_child(&mut e, "modificationDate", self.modification_date.as_ref());
+        push_child(
+            &mut e,
+            "securityClassification",
+            self.security_classification.as_ref(),
+        );
+        push_children(
+            &mut e,
+            "releaseRestriction",
+            self.release_restriction.as_ref(),
+        );
+        push_child(&mut e, "purpose", self.purpose.as_ref());
+        push_child(
+            &mut e,
+            "applicationDomain",
+            self.application_domain.as_ref(),
+        );
+        push_child(&mut e, "description", self.description.as_ref());
+        push_child(&mut e, "useLimitation", self.use_limitation.as_ref());
+        push_children(&mut e, "useHistory", self.use_history.as_ref());
+        push_children(&mut e, "keyword", self.keywords.as_ref());
+        push_children(&mut e, "poc", self.poc.as_ref());
+        push_children(&mut e, "reference", self.references.as_ref());
+        push_child(&mut e, "other", self.other.as_ref());
+        push_child(&mut e, "glyph", self.glyph.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for ModelType {
+    fn to_text(&self) -> &str {
+        match self {
+            ModelType::FOM => "FOM",
+            ModelType::SOM => "SOM",
+            ModelType::Other(text) => text,
+        }
+    }
+}
+
+impl ToText for SecurityClassificationType {
+    fn to_text(&self) -> &str {
+        match self {
+            SecurityClassificationType::Unclassified => "Unclassified",
+            SecurityClassificationType::Confidential => "Confidential",
+            SecurityClassificationType::Secret => "Secret",
+            SecurityClassificationType::TopSecret => "Top Secret",
+            SecurityClassificationType::Other(text) => text,
+        }
+    }
+}
+
+impl ToText for ApplicationDomainType {
+    fn to_text(&self) -> &str {
+        match self {
+            ApplicationDomainType::Analysis => "Analysis",
+            ApplicationDomainType::Training => "Training",
+            ApplicationDomainType::TestAndEvaluation => "Test and Evaluation",
+            ApplicationDomainType::Engineering => "Engineering",
+            ApplicationDomainType::Acquisition => "Acquisition",
+            ApplicationDomainType::Other(text) => text,
+        }
+    }
+}
+
+impl ToElement for KeywordType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "taxonomy", self.taxonomy.as_ref());
+        push_child(&mut e, "keywordValue", self.keyword_value.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for PocType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "pocType", self.poc_type.as_ref());
+        push_child(&mut e, "pocName", self.poc_name.as_ref());
+        push_child(&mut e, "pocOrg", self.poc_org.as_ref());
+        push_children(&mut e, "pocTelephone", self.poc_telephones.as_ref());
+        push_children(&mut e, "pocEmail", self.poc_emails.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for PocTypeType {
+    fn to_text(&self) -> &str {
+        match self {
+            PocTypeType::PrimaryAuthor => "Primary author",
+            PocTypeType::Contributor => "Contributor",
+            PocTypeType::Proponent => "Proponent",
+            PocTypeType::Sponsor => "Sponsor",
+            PocTypeType::ReleaseAuthority => "Release authority",
+            PocTypeType::TechnicalPoc => "Technical POC",
+            PocTypeType::Other(text) => text,
+        }
+    }
+}
+
+impl ToElement for IdReferenceType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "type", self.reference_type.as_ref());
+        push_child(&mut e, "identification", self.identification.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for GlyphType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = text_element(name, self.image.as_deref().unwrap_or(""));
+        if let Some(href) = &self.href {
+            set_attribute(&mut e, "href", href);
+        }
+        if let Some(glyph_type) = &self.glyph_type {
+            set_attribute(&mut e, "type", glyph_type.to_text());
+        }
+        if let Some(height) = &self.height {
+            set_attribute(&mut e, "height", height);
+        }
+        if let Some(width) = &self.width {
+            set_attribute(&mut e, "width", width);
+        }
+        if let Some(alt) = &self.alt {
+            set_attribute(&mut e, "alt", alt);
+        }
+        e
+    }
+}
+
+impl ToText for GlyphTypeType {
+    fn to_text(&self) -> &str {
+        match self {
+            GlyphTypeType::Bitmap => "BITMAP",
+            GlyphTypeType::Jpg => "JPG",
+            GlyphTypeType::Gif => "GIF",
+            GlyphTypeType::Png => "PNG",
+            GlyphTypeType::Tiff => "TIFF",
+            GlyphTypeType::Other(text) => text,
+        }
+    }
+}
+
+impl ToElement for ServiceUtiliizationType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        for (service, info) in self.all_services() {
+            push_child(&mut e, service, info.as_ref());
+        }
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for ServiceInfoType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        set_attribute(&mut e, "section", &self.section.to_string());
+        set_attribute(&mut e, "isCallback", bool_text(self.is_callback));
+        set_attribute(&mut e, "isUsed", bool_text(self.is_used));
+        e
+    }
+}
+
+impl ToElement for ObjectsType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "objectClass", self.root_object_class.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for ObjectClassType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "sharing", self.sharing.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        push_children(&mut e, "attribute", self.attributes.as_ref());
+        push_children(&mut e, "objectClass", self.object_classes.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for SharingType {
+    fn to_text(&self) -> &str {
+        match self {
+            SharingType::Publish => "Publish",
+            SharingType::Subscribe => "Subscribe",
+            SharingType::PublishSubscribe => "PublishSubscribe",
+            SharingType::Neither => "Neither",
+        }
+    }
+}
+
+/// Append a `dimensions` element holding a `dimension` element for each of
+/// the provided dimensions, if there are any.
+fn push_dimensions(e: &mut Element, dimensions: Option<&Vec<ReferenceType>>) {
+    if let Some(dimensions) = dimensions {
+        let mut dimensions_element = Element::new("dimensions");
+        push_children(&mut dimensions_element, "dimension", Some(dimensions));
+        e.children.push(Node::Element(dimensions_element));
+    }
+}
+
+impl ToElement for AttributeType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_child(&mut e, "updateType", self.update_type.as_ref());
+        push_child(&mut e, "updateCondition", self.update_condition.as_ref());
+        push_child(&mut e, "ownership", self.onwership.as_ref());
+        push_child(&mut e, "sharing", self.sharing.as_ref());
+        push_dimensions(&mut e, self.dimensions.as_ref());
+        push_child(&mut e, "transportation", self.transportation.as_ref());
+        push_child(&mut e, "order", self.order.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for ReferenceType {
+    fn to_text(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToText for UpdateType {
+    fn to_text(&self) -> &str {
+        match self {
+            UpdateType::Static => "Static",
+            UpdateType::Periodic => "Periodic",
+            UpdateType::Conditional => "Conditional",
+            UpdateType::Na => "NA",
+            UpdateType::Other(text) => text,
+        }
+    }
+}
+
+impl ToText for OwnershipType {
+    fn to_text(&self) -> &str {
+        match self {
+            OwnershipType::Divest => "Divest",
+            OwnershipType::Acquire => "Acquire",
+            OwnershipType::DivestAcquire => "DivestAcquire",
+            OwnershipType::NoTransfer => "NoTransfer",
+            OwnershipType::Other(text) => text,
+        }
+    }
+}
+
+impl ToText for OrderType {
+    fn to_text(&self) -> &str {
+        match self {
+            OrderType::Receive => "Receive",
+            OrderType::TimeStamp => "TimeStamp",
+        }
+    }
+}
+
+impl ToElement for InteractionsType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "interactionClass", Some(&self.interactions));
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for InteractionClassType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "sharing", self.sharing.as_ref());
+        push_dimensions(&mut e, self.dimensions.as_ref());
+        push_child(&mut e, "transportation", self.transportation.as_ref());
+        push_child(&mut e, "order", self.order.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        push_children(&mut e, "parameter", self.parameters.as_ref());
+        push_children(
+            &mut e,
+            "interactionClass",
+            self.interaction_classes.as_ref(),
+        );
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for ParameterType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for DimensionsType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "dimension", self.dimensions.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for DimensionType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_child(&mut e, "upperBound", self.upper_bound.as_ref());
+        push_child(&mut e, "normalization", self.normalization.as_ref());
+        push_child(
+            &mut e,
+            "value",
+            self.value.as_ref().map(|v| v.to_string()).as_ref(),
+        );
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for TimeType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "timeStamp", self.time_stamp.as_ref());
+        push_child(&mut e, "lookahead", self.lookahead.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for TimeTypeType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "dataType", Some(&self.data_type));
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for TagsType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "updateReflectTag", self.update_reflect_tag.as_ref());
+        push_child(&mut e, "sendReceiveTag", self.send_receive_tag.as_ref());
+        push_child(&mut e, "deleteRemoveTag", self.delete_remove_tag.as_ref());
+        push_child(
+            &mut e,
+            "divestitureRequestTag",
+            self.divestiture_request_tag.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "divestitureCompletionTag",
+            self.divestiture_completion_tag.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "acquisitionRequestTag",
+            self.acquisition_request_tag.as_ref(),
+        );
+        push_child(&mut e, "requestUpdateTag", self.request_update_tag.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for TagType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for SynchronizationsType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(
+            &mut e,
+            "synchronizationPoint",
+            self.synchronization_points.as_ref(),
+        );
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for SynchronizationPointType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "label", Some(&self.label));
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_child(&mut e, "capability", self.capability.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for CapabilityType {
+    fn to_text(&self) -> &str {
+        match self {
+            CapabilityType::Register => "Register",
+            CapabilityType::Achieve => "Achieve",
+            CapabilityType::RegisterAchieve => "RegisterAchieve",
+            CapabilityType::NoSynch => "NoSynch",
+            CapabilityType::Na => "NA",
+        }
+    }
+}
+
+impl ToElement for TransportationsType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "transportation", self.transportations.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for TransportationType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "reliable", self.reliable.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for ReliableType {
+    fn to_text(&self) -> &str {
+        match self {
+            ReliableType::Yes => "Yes",
+            ReliableType::No => "No",
+        }
+    }
+}
+
+impl ToElement for SwitchesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "autoProvide", self.auto_provide.as_ref());
+        push_child(
+            &mut e,
+            "conveyRegionDesignatorSets",
+            self.convey_region_designator_sets.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "conveyProducingFederate",
+            self.convey_producing_federate.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "attributeScopeAdvisory",
+            self.attribute_scope_advisory.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "attributeRelevanceAdvisory",
+            self.attribute_relevance_advisory.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "objectClassRelevanceAdvisory",
+            self.object_class_relevance_advisory.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "interactionRelevanceAdvisory",
+            self.interaction_relevance_advisory.as_ref(),
+        );
+        push_child(&mut e, "serviceReporting", self.service_reporting.as_ref());
+        push_child(
+            &mut e,
+            "exceptionReporting",
+            self.exception_reporting.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "delaySubscriptionEvaluation",
+            self.delay_subscription_evaluation.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "automaticResignAction",
+            self.automatic_resign_action.as_ref(),
+        );
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for SwitchType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        set_attribute(&mut e, "isEnabled", bool_text(self.is_enabled));
+        e
+    }
+}
+
+impl ToElement for ResignSwitchType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        let resign_action = match self {
+            ResignSwitchType::UnconditionallyDivestAttributes => "UnconditionallyDivestAttributes",
+            ResignSwitchType::DeleteObjects => "DeleteObjects",
+            ResignSwitchType::CancelPendingOwnershipAcquisitions => {
+                "CancelPendingOwnershipAcquisitions"
+            }
+            ResignSwitchType::DeleteObjectsThenDivest => "DeleteObjectsThenDivest",
+            ResignSwitchType::CancelThenDeleteThenDivest => "CancelThenDeleteThenDivest",
+            ResignSwitchType::NoAction => "NoAction",
+        };
+        set_attribute(&mut e, "resignAction", resign_action);
+        e
+    }
+}
+
+impl ToElement for UpdateRatesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "updateRate", self.update_rates.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for UpdateRateType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "rate", self.rate.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for RateType {
+    fn to_text(&self) -> &str {
+        self.value.text()
+    }
+}
+
+impl ToElement for DataTypesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(
+            &mut e,
+            "basicDataRepresentations",
+            self.basic_data_representations.as_ref(),
+        );
+        push_child(&mut e, "simpleDataTypes", self.simple_data_types.as_ref());
+        push_child(
+            &mut e,
+            "enumeratedDataTypes",
+            self.enumerated_data_types.as_ref(),
+        );
+        push_child(&mut e, "arrayDataTypes", self.array_data_types.as_ref());
+        push_child(
+            &mut e,
+            "fixedRecordDataTypes",
+            self.fixed_record_data_types.as_ref(),
+        );
+        push_child(
+            &mut e,
+            "variantRecordDataTypes",
+            self.variand_record_data_types.as_ref(),
+        );
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for BasicDataRepresentationsType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "basicData", self.basic_datas.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for BasicDataType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "size", self.size.as_ref());
+        push_child(&mut e, "interpretation", self.interpretation.as_ref());
+        push_child(&mut e, "endian", self.endian.as_ref());
+        push_child(&mut e, "encoding", self.encoding.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for SizeType {
+    fn to_text(&self) -> &str {
+        self.size.text()
+    }
+}
+
+impl ToText for EndianType {
+    fn to_text(&self) -> &str {
+        match self {
+            EndianType::Big => "Big",
+            EndianType::Little => "Little",
+        }
+    }
+}
+
+impl ToElement for SimpleDataTypesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "simpleData", self.simple_datas.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for SimpleDataType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "representation", self.representation.as_ref());
+        push_child(&mut e, "units", self.units.as_ref());
+        push_child(&mut e, "resolution", self.resolution.as_ref());
+        push_child(&mut e, "accuracy", self.accuracy.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for EnumeratedDataTypesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "enumeratedData", self.enumerated_datas.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for EnumeratedDataType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "representation", self.representation.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        push_children(&mut e, "enumerator", self.enumerators.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for EnumeratorType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_children(&mut e, "value", Some(&self.value));
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for ArrayDataTypesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "arrayData", self.array_datas.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for ArrayDataType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_child(&mut e, "cardinality", self.cardinality.as_ref());
+        push_child(&mut e, "encoding", self.encoding.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for ArrayDataTypeEncodingType {
+    fn to_text(&self) -> &str {
+        match self {
+            ArrayDataTypeEncodingType::HlaFixedArray => "HLAfixedArray",
+            ArrayDataTypeEncodingType::HlaVariableArray => "HLAvariableArray",
+            ArrayDataTypeEncodingType::Other(text) => text,
+        }
+    }
+}
+
+impl ToElement for FixedRecordDataTypesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "fixedRecordData", self.fixed_record_datas.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for FixedRecordDataType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "encoding", self.encoding.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        push_children(&mut e, "field", self.fields.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for FixedRecordEncodingType {
+    fn to_text(&self) -> &str {
+        match self {
+            FixedRecordEncodingType::HlaFixedRecord => "HLAfixedRecord",
+            FixedRecordEncodingType::HlaExtendableFixedRecord => "HLAextendableFixedRecord",
+            FixedRecordEncodingType::Other(text) => text,
+        }
+    }
+}
+
+impl ToElement for FieldType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for VariantRecordDataTypesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(
+            &mut e,
+            "variantRecordData",
+            self.variant_record_datas.as_ref(),
+        );
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for VariantRecordDataType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "name", Some(&self.name));
+        push_child(&mut e, "discriminant", self.discriminant.as_ref());
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_children(&mut e, "alternative", self.alternatives.as_ref());
+        push_child(&mut e, "encoding", self.encoding.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for AlternativeType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "enumerator", self.enumerator.as_ref());
+        push_child(&mut e, "name", self.name.as_ref());
+        push_child(&mut e, "dataType", self.data_type.as_ref());
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToText for VariantRecordEncodingType {
+    fn to_text(&self) -> &str {
+        match self {
+            VariantRecordEncodingType::HlaVariantRecord => "HLAvariantRecord",
+            VariantRecordEncodingType::HlaExtendableVariantRecord => "HLAextendableVariantRecord",
+            VariantRecordEncodingType::Other(text) => text,
+        }
+    }
+}
+
+impl ToElement for NotesType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_children(&mut e, "note", self.notes.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+impl ToElement for NoteType {
+    fn to_element(&self, name: &str) -> Element {
+        let mut e = Element::new(name);
+        push_child(&mut e, "label", Some(&self.label));
+        push_child(&mut e, "semantics", self.semantics.as_ref());
+        apply_annotations(&mut e, self.annotations.as_ref());
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_object_model_applies_annotations() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <dataTypes>
+    <enumeratedDataTypes>
+      <enumeratedData notes="Note1">
+        <name>Color</name>
+        <representation>HLAinteger32BE</representation>
+        <enumerator>
+          <name>Red</name>
+          <value>1</value>
+          <value notes="Note2 Note3">2</value>
+        </enumerator>
+      </enumeratedData>
+    </enumeratedDataTypes>
+  </dataTypes>
+</objectModel>"#;
+        let fom = parse_str(xml).unwrap();
+        let e = serialize_object_model(&fom);
+
+        let enumerated_data = e
+            .get_child("dataTypes")
+            .and_then(|e| e.get_child("enumeratedDataTypes"))
+            .and_then(|e| e.get_child("enumeratedData"))
+            .unwrap();
+        assert_eq!(Some("Note1"), enumerated_data.get_attribute("notes"));
+        assert_eq!(
+            Some(&String::from(IEEE1516_2010_NAMESPACE)),
+            enumerated_data.namespace.as_ref()
+        );
+
+        let values: Vec<Option<&str>> = enumerated_data
+            .get_child("enumerator")
+            .unwrap()
+            .child_elements()
+            .filter(|e| e.name == "value")
+            .map(|e| e.get_attribute("notes"))
+            .collect();
+        assert_eq!(vec![None, Some("Note2 Note3")], values);
+
+        assert!(fom.same_model(&parse_object_model(&e).unwrap()));
+    }
+
+    #[test]
+    fn test_annotations_at_invalid_paths_are_not_applied() {
+        let mut e = Element::new("enumerator");
+        e.children.push(Node::Element(Element::new("value")));
+        for path in ["value[0]", "value[2]", "value[", "value[x]", "name"] {
+            assert!(find_descendant(&mut e, path).is_none(), "{}", path);
+        }
+        assert!(find_descendant(&mut e, "value[1]").is_some());
+
+        let annotations = vec![AnnotationType {
+            path: String::from("value[0]"),
+            notes: Some(vec![String::from("Note1")]),
+            idtag: None,
+        }];
+        apply_annotations(&mut e, Some(&annotations));
+        assert_eq!(None, e.get_child("value").unwrap().get_attribute("notes"));
+    }
+
+    #[test]
+    fn test_to_xml_string_declares_namespaces() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xsi:schemaLocation="http://standards.ieee.org/IEEE1516-2010 IEEE1516-DIF-2010.xsd">
+  <switches>
+    <autoProvide isEnabled="true"/>
+  </switches>
+</objectModel>"#;
+        let fom = parse_str(xml).unwrap();
+
+        let written = to_xml_string(&fom, &WriteOptions::default()).unwrap();
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://standards.ieee.org/IEEE1516-2010 IEEE1516-DIF-2010.xsd">
+    <switches>
+        <autoProvide isEnabled="true"/>
+    </switches>
+</objectModel>"#,
+            written
+        );
+        assert!(fom.same_model(&parse_str(&written).unwrap()));
+    }
+}
@@ -0,0 +1,237 @@
+//! Where in their source documents the parts of an object model came from.
+
+use crate::dom::Position;
+use crate::*;
+use std::fmt;
+
+/// The extent of the element that a part of the object model was converted
+/// from within its source document. A span names its own file, so it remains
+/// valid when the part is moved into an object model read from elsewhere.
+///
+/// The default span, whose positions are zero, is that of a part that was not
+/// read from a document. As the parts of the object model compare their spans
+/// along with everything else, `ObjectModelType::same_model` is provided to
+/// compare object models regardless of where they were read from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceSpan {
+    /// The file the document was read from, if it was read from a file.
+    pub file: Option<Arc<Path>>,
+    /// Where the start tag of the element begins.
+    pub start: Position,
+    /// The position just past the end tag of the element.
+    pub end: Position,
+}
+
+impl fmt::Display for SourceSpan {
+    /// Format the span as `file:line:column`, the form understood by most
+    /// editors, leaving out the file when it is not known.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}", self.start)
+    }
+}
+
+/// A part of the object model that knows where in its source document it was
+/// read from. Parts that were not read from a document, such as those of an
+/// object model converted from a document object model built in memory, have
+/// no span.
+pub trait Spanned {
+    fn source_span(&self) -> &SourceSpan;
+
+    /// Return the span of this part of the object model, if it is known.
+    fn span(&self) -> Option<&SourceSpan> {
+        let span = self.source_span();
+        if span.start == Position::default() {
+            None
+        } else {
+            Some(span)
+        }
+    }
+}
+
+macro_rules! impl_spanned {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Spanned for $t {
+                fn source_span(&self) -> &SourceSpan {
+                    &self.span
+                }
+            }
+        )*
+    };
+}
+
+for_each_model_node!(impl_spanned);
+
+impl ObjectModelType {
+    /// Return whether the object models are the same, apart from where their
+    /// parts were read from, e.g. an object model read from a document and
+    /// the object model read back after writing it elsewhere.
+    pub fn same_model(&self, other: &ObjectModelType) -> bool {
+        let mut model = self.clone();
+        let mut other = other.clone();
+        model.clear_spans();
+        other.clear_spans();
+        model == other
+    }
+}
+
+/// A part of the object model, or the parts held by a field of one, whose
+/// spans can be reset to the default.
+trait ClearSpans {
+    fn clear_spans(&mut self);
+}
+
+impl<T: ClearSpans> ClearSpans for Option<T> {
+    fn clear_spans(&mut self) {
+        if let Some(part) = self {
+            part.clear_spans();
+        }
+    }
+}
+
+impl<T: ClearSpans> ClearSpans for Vec<T> {
+    fn clear_spans(&mut self) {
+        for part in self {
+            part.clear_spans();
+        }
+    }
+}
+
+macro_rules! impl_clear_spans {
+    ($($t:ident { $($field:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl ClearSpans for $t {
+                fn clear_spans(&mut self) {
+                    self.span = SourceSpan::default();
+                    $(self.$field.clear_spans();)*
+                }
+            }
+        )*
+    };
+}
+
+for_each_model_node_with_children!(impl_clear_spans);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_records_spans() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010">
+  <objects>
+    <objectClass>
+      <name>HLAobjectRoot</name>
+      <attribute>
+        <name>HLAprivilegeToDeleteObject</name>
+      </attribute>
+    </objectClass>
+  </objects>
+</objectModel>"#;
+        let fom = parse_str(xml).unwrap();
+        let root = fom
+            .objects
+            .as_ref()
+            .unwrap()
+            .root_object_class
+            .as_ref()
+            .unwrap();
+        let span = root.span().unwrap();
+        assert_eq!(None, span.file);
+        assert_eq!(Position { line: 3, column: 5 }, span.start);
+        assert_eq!(
+            Position {
+                line: 8,
+                column: 19
+            },
+            span.end
+        );
+
+        let attribute = &root.attributes.as_ref().unwrap()[0];
+        let span = attribute.span().unwrap();
+        assert_eq!(Position { line: 5, column: 7 }, span.start);
+        assert_eq!(
+            Position {
+                line: 7,
+                column: 19
+            },
+            span.end
+        );
+        assert_eq!("5:7", span.to_string());
+    }
+
+    #[test]
+    fn test_parse_file_records_file() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("omt")
+            .join("HLAstandardMIM.xml");
+        let fom = parse_file(&path).unwrap();
+        let span = fom.span().unwrap();
+        assert_eq!(Some(path.as_path()), span.file.as_deref());
+        assert!(span.to_string().ends_with("HLAstandardMIM.xml:8:1"));
+
+        // Spans survive the object model being cloned, and only
+        // `same_model` ignores them.
+        let objects = fom.objects.clone().unwrap();
+        assert_eq!(fom.objects.as_ref().unwrap().span(), objects.span());
+        let reparsed = parse_object_model(&serialize_object_model(&fom)).unwrap();
+        assert_eq!(None, reparsed.span());
+        assert_ne!(fom, reparsed);
+        assert!(fom.same_model(&reparsed));
+    }
+
+    #[test]
+    fn test_spans_survive_merging() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mim_path = root.join("data").join("omt").join("HLAstandardMIM.xml");
+        let restaurant_path = root
+            .join("..")
+            .join("examples")
+            .join("RestaurantFOMmodule.xml");
+        let mut merged = parse_file(&mim_path).unwrap();
+        let restaurant = parse_file(&restaurant_path).unwrap();
+
+        // Move the simple datatypes of the restaurant module into the MIM.
+        let simple_datas = |fom: &ObjectModelType| {
+            fom.data_types
+                .as_ref()
+                .and_then(|data_types| data_types.simple_data_types.as_ref())
+                .and_then(|simple_data_types| simple_data_types.simple_datas.clone())
+                .unwrap()
+        };
+        let moved = simple_datas(&restaurant);
+        let simple_data_types = merged
+            .data_types
+            .as_mut()
+            .unwrap()
+            .simple_data_types
+            .as_mut()
+            .unwrap();
+        simple_data_types
+            .simple_datas
+            .get_or_insert_with(Vec::new)
+            .extend(moved.iter().cloned());
+
+        // Each part keeps the span of the document it was read from.
+        let merged_simple_datas = simple_datas(&merged);
+        let (mim_simple_datas, restaurant_simple_datas) =
+            merged_simple_datas.split_at(merged_simple_datas.len() - moved.len());
+        for simple_data in mim_simple_datas {
+            let span = simple_data.span().unwrap();
+            assert_eq!(Some(mim_path.as_path()), span.file.as_deref());
+        }
+        for (simple_data, original) in restaurant_simple_datas.iter().zip(&moved) {
+            let span = simple_data.span().unwrap();
+            assert_eq!(Some(restaurant_path.as_path()), span.file.as_deref());
+            assert_eq!(original.span(), Some(span));
+        }
+        assert_eq!(
+            Some(mim_path.as_path()),
+            merged.span().unwrap().file.as_deref()
+        );
+    }
+}
@@ -0,0 +1,432 @@
+//! Conversion of an OMT document into an object model as the document is read,
+//! the `Streaming` parse backend.
+//!
+//! The `Dom` backend reads the whole document into elements before converting
+//! any of it, and the elements take several times the memory of the document
+//! itself. Most of a large module, such as the RPR enumerations, is in the
+//! rows of its datatype tables, and in its notes. Here each of those rows is
+//! read into an element of its own, converted and dropped, so that only one
+//! row is held at a time. Enumerated datatypes, which may have thousands of
+//! enumerators, are converted an enumerator at a time in the same way. The
+//! rest of the document, whose object and interaction class trees cannot be
+//! converted before they end, is read into elements as before.
+//!
+//! The conversion of each part of the document records its problems in a
+//! context of its own, and those of the datatypes and notes are reported
+//! after those of the rest of the document, as the `Dom` backend does. The
+//! check of enumerator values against their representations needs every
+//! datatype, so only the values to check are kept until the end.
+//!
+//! IEEE 1516.2-2000 documents are rewritten as a whole before conversion, and
+//! are read by the `Dom` backend.
+
+use crate::dom::{Element, Events, ParseError};
+use crate::*;
+
+/// The tables of `dataTypes` whose rows are converted as they are read.
+const DATA_TYPE_TABLES: &[&str] = &[
+    "basicDataRepresentations",
+    "simpleDataTypes",
+    "enumeratedDataTypes",
+    "arrayDataTypes",
+    "fixedRecordDataTypes",
+    "variantRecordDataTypes",
+];
+
+/// What is kept of the rows for the checks made once the whole object model
+/// is known.
+struct Checks {
+    version: Option<SchemaVersion>,
+    enumerator_values: Vec<EnumeratorValue>,
+    added_values: Vec<FomError>,
+}
+
+impl Checks {
+    /// Keep the warnings about values added by later versions of the schema
+    /// in the provided element, whose path relative to `objectModel` is the
+    /// provided one, to report once the whole object model is known.
+    fn check_added_values(&mut self, cx: &Context, e: &Element, path: &str) {
+        if let Some(version) = self.version {
+            // Kept apart so that they are reported after the enumerator values,
+            // as the `Dom` backend reports them.
+            let mut added = part_context(cx);
+            version::check_added_values(&mut added, e, path, version);
+            self.added_values.append(&mut added.warnings);
+        }
+    }
+}
+
+/// Reads the rest of a row of a table whose start tag was the last event read,
+/// and whose path relative to `objectModel` is the provided one, and converts
+/// it.
+type ReadRow<R, Row> =
+    fn(&mut Events<R>, &mut Context, &mut Checks, Element, &str) -> Result<Option<Row>, ParseError>;
+
+/// Return a context for a part of the document, at the same place in the
+/// document as the provided one but with no problems recorded.
+fn part_context(cx: &Context) -> Context {
+    let mut part = Context::new();
+    part.path = cx.path.clone();
+    part.file = cx.file.clone();
+    part
+}
+
+/// Read an object model from the provided reader, as directed by the provided
+/// options, converting the document as it is read.
+pub(crate) fn parse_reader<R: Read>(r: R, options: &ParseOptions) -> Result<Parsed, Error> {
+    let mut events = Events::new(r);
+    let root = events.read_root_start()?;
+    if legacy::is_ieee1516_2000(&root) {
+        let root = events.read_element(root)?;
+        events.read_to_end()?;
+        return Ok(parse_object_model_with_options(&root, options)?);
+    }
+
+    let format = omt_format(&root);
+    let mut cx = Context::new();
+    cx.file = options.source_file.as_deref().map(Arc::from);
+    cx.enter(&root);
+    check_namespace(&mut cx, &root, options);
+    let mut checks = Checks {
+        version: root
+            .namespace
+            .as_deref()
+            .and_then(SchemaVersion::from_namespace),
+        enumerator_values: Vec::new(),
+        added_values: Vec::new(),
+    };
+
+    let namespace = root.namespace.clone();
+    let mut data_types_cx = part_context(&cx);
+    let mut notes_cx = part_context(&cx);
+    let mut data_types = None;
+    let mut notes = None;
+    let mut streamed: Vec<String> = Vec::new();
+    let root = events.read_children(root, |events, _, child| {
+        if child.namespace != namespace || !["dataTypes", "notes"].contains(&child.name.as_str()) {
+            return Ok(Some(child));
+        }
+        // Only the first of each is converted, as by the `Dom` backend.
+        if streamed.contains(&child.name) {
+            events.read_element(child)?;
+            return Ok(None);
+        }
+        streamed.push(child.name.clone());
+        if child.name == "dataTypes" {
+            data_types = read_data_types(events, &mut data_types_cx, &mut checks, child)?;
+        } else {
+            notes = read_table(
+                events,
+                &mut notes_cx,
+                &mut checks,
+                child,
+                "notes",
+                "note",
+                read_row,
+                |t: &mut NotesType, rows| t.notes = rows,
+            )?;
+        }
+        Ok(None)
+    })?;
+    events.read_to_end()?;
+
+    let fom = ObjectModelType::from_element(&root, &mut cx).map(|mut fom| {
+        fom.data_types = data_types;
+        fom.notes = notes;
+        fom
+    });
+    for part in [data_types_cx, notes_cx] {
+        cx.errors.extend(part.errors);
+        cx.warnings.extend(part.warnings);
+    }
+    if let Some(fom) = &fom {
+        check_enumerator_values(&mut cx, checks.enumerator_values, fom);
+        if let Some(version) = fom.schema_version() {
+            cx.warnings.append(&mut checks.added_values);
+            version::check_schema_version(&mut cx, &root, version);
+        }
+    }
+    cx.leave();
+    Ok(into_parsed(cx, fom, format)?)
+}
+
+/// Read the `dataTypes` element whose start tag was the last event read,
+/// converting the rows of its tables as they are read.
+fn read_data_types<R: Read>(
+    events: &mut Events<R>,
+    cx: &mut Context,
+    checks: &mut Checks,
+    e: Element,
+) -> Result<Option<DataTypesType>, ParseError> {
+    cx.enter(&e);
+    let namespace = e.namespace.clone();
+    let mut basic_data_representations = None;
+    let mut simple_data_types = None;
+    let mut enumerated_data_types = None;
+    let mut array_data_types = None;
+    let mut fixed_record_data_types = None;
+    let mut variant_record_data_types = None;
+    let mut streamed: Vec<String> = Vec::new();
+    let e = events.read_children(e, |events, _, child| {
+        if child.namespace != namespace || !DATA_TYPE_TABLES.contains(&child.name.as_str()) {
+            return Ok(Some(child));
+        }
+        if streamed.contains(&child.name) {
+            events.read_element(child)?;
+            return Ok(None);
+        }
+        streamed.push(child.name.clone());
+        let path = format!("dataTypes/{}", child.name);
+        match child.name.as_str() {
+            "basicDataRepresentations" => {
+                basic_data_representations = read_table(
+                    events,
+                    cx,
+                    checks,
+                    child,
+                    &path,
+                    "basicData",
+                    read_row,
+                    |t: &mut BasicDataRepresentationsType, rows| t.basic_datas = rows,
+                )?
+            }
+            "simpleDataTypes" => {
+                simple_data_types = read_table(
+                    events,
+                    cx,
+                    checks,
+                    child,
+                    &path,
+                    "simpleData",
+                    read_row,
+                    |t: &mut SimpleDataTypesType, rows| t.simple_datas = rows,
+                )?
+            }
+            "enumeratedDataTypes" => {
+                enumerated_data_types = read_table(
+                    events,
+                    cx,
+                    checks,
+                    child,
+                    &path,
+                    "enumeratedData",
+                    read_enumerated_data,
+                    |t: &mut EnumeratedDataTypesType, rows| t.enumerated_datas = rows,
+                )?
+            }
+            "arrayDataTypes" => {
+                array_data_types = read_table(
+                    events,
+                    cx,
+                    checks,
+                    child,
+                    &path,
+                    "arrayData",
+                    read_row,
+                    |t: &mut ArrayDataTypesType, rows| t.array_datas = rows,
+                )?
+            }
+            "fixedRecordDataTypes" => {
+                fixed_record_data_types = read_table(
+                    events,
+                    cx,
+                    checks,
+                    child,
+                    &path,
+                    "fixedRecordData",
+                    read_row,
+                    |t: &mut FixedRecordDataTypesType, rows| t.fixed_record_datas = rows,
+                )?
+            }
+            _ => {
+                variant_record_data_types = read_table(
+                    events,
+                    cx,
+                    checks,
+                    child,
+                    &path,
+                    "variantRecordData",
+                    read_row,
+                    |t: &mut VariantRecordDataTypesType, rows| t.variant_record_datas = rows,
+                )?
+            }
+        }
+        Ok(None)
+    })?;
+    let data_types = DataTypesType::from_element(&e, cx).map(|mut data_types| {
+        data_types.basic_data_representations = basic_data_representations;
+        data_types.simple_data_types = simple_data_types;
+        data_types.enumerated_data_types = enumerated_data_types;
+        data_types.array_data_types = array_data_types;
+        data_types.fixed_record_data_types = fixed_record_data_types;
+        data_types.variand_record_data_types = variant_record_data_types;
+        data_types
+    });
+    cx.leave();
+    Ok(data_types)
+}
+
+/// Read the table whose start tag was the last event read, and whose path
+/// relative to `objectModel` is the provided one, converting each of its rows
+/// with the provided function as it is read. The rows are given to the
+/// converted table by the other provided function.
+#[allow(clippy::too_many_arguments)]
+fn read_table<R: Read, T: FromElement, Row>(
+    events: &mut Events<R>,
+    cx: &mut Context,
+    checks: &mut Checks,
+    e: Element,
+    path: &str,
+    row_name: &str,
+    read_row: ReadRow<R, Row>,
+    set_rows: fn(&mut T, Option<Vec<Row>>),
+) -> Result<Option<T>, ParseError> {
+    cx.enter(&e);
+    let namespace = e.namespace.clone();
+    let row_path = format!("{}/{}", path, row_name);
+    let mut rows = Vec::new();
+    let e = events.read_children(e, |events, _, child| {
+        if child.name != row_name || child.namespace != namespace {
+            return Ok(Some(child));
+        }
+        rows.extend(read_row(events, cx, checks, child, &row_path)?);
+        Ok(None)
+    })?;
+    let table = T::from_element(&e, cx).map(|mut table| {
+        set_rows(&mut table, map_vec_to_option(rows));
+        table
+    });
+    cx.leave();
+    Ok(table)
+}
+
+/// Read the rest of a row whole, and convert it.
+fn read_row<R: Read, Row: FromElement>(
+    events: &mut Events<R>,
+    cx: &mut Context,
+    checks: &mut Checks,
+    e: Element,
+    path: &str,
+) -> Result<Option<Row>, ParseError> {
+    let row = events.read_element(e)?;
+    checks.check_added_values(cx, &row, path);
+    Ok(convert_element(cx, &row))
+}
+
+/// Read the rest of an `enumeratedData` row, converting each of its
+/// enumerators as it is read, as some enumerated datatypes have thousands.
+fn read_enumerated_data<R: Read>(
+    events: &mut Events<R>,
+    cx: &mut Context,
+    checks: &mut Checks,
+    e: Element,
+    path: &str,
+) -> Result<Option<EnumeratedDataType>, ParseError> {
+    let namespace = e.namespace.clone();
+    let enumerator_path = format!("{}/enumerator", path);
+    // The name and representation of the datatype, which are needed to
+    // convert and check its enumerators, come before the enumerators.
+    let mut representation = None;
+    let mut entered = false;
+    let mut enumerators = Vec::new();
+    let e = events.read_children(e, |events, e, child| {
+        if child.name != "enumerator" || child.namespace != namespace {
+            return Ok(Some(child));
+        }
+        if !entered {
+            cx.enter(e);
+            representation = get_text_of_child_element(e, "representation");
+            entered = true;
+        }
+        let enumerator = events.read_element(child)?;
+        checks.check_added_values(cx, &enumerator, &enumerator_path);
+        if let Some(representation) = &representation {
+            collect_enumerator_values(
+                cx,
+                representation,
+                &enumerator,
+                &mut checks.enumerator_values,
+            );
+        }
+        enumerators.extend(convert_element(cx, &enumerator));
+        Ok(None)
+    })?;
+    if entered {
+        cx.leave();
+    }
+    checks.check_added_values(cx, &e, path);
+    cx.enter(&e);
+    let enumerated_data = EnumeratedDataType::from_element(&e, cx).map(|mut enumerated_data| {
+        enumerated_data.enumerators = map_vec_to_option(enumerators);
+        enumerated_data
+    });
+    cx.leave();
+    Ok(enumerated_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_with(xml: &str, backend: ParseBackend) -> Result<Parsed, Error> {
+        let options = ParseOptions {
+            backend,
+            ..ParseOptions::default()
+        };
+        parse_reader_with_options(xml.as_bytes(), &options)
+    }
+
+    #[test]
+    fn test_streaming_reports_the_problems_of_the_dom_backend() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2010" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="IEEE1516-DIF-2010.xsd">
+  <objects>
+    <objectClass>
+      <name>HLAobjectRoot</name>
+      <sharing>Neither</sharing>
+    </objectClass>
+  </objects>
+  <dataTypes>
+    <simpleDataTypes notes="n1">
+      <simpleData>
+        <name>Count</name>
+        <representation>HLAinteger16BE</representation>
+      </simpleData>
+    </simpleDataTypes>
+    <enumeratedDataTypes>
+      <enumeratedData>
+        <name>Kind</name>
+        <representation>HLAoctet</representation>
+        <enumerator>
+          <name>Big</name>
+          <value>300</value>
+        </enumerator>
+      </enumeratedData>
+    </enumeratedDataTypes>
+    <fixedRecordDataTypes>
+      <fixedRecordData>
+        <name>Position</name>
+        <encoding>HLAextendableFixedRecord</encoding>
+      </fixedRecordData>
+    </fixedRecordDataTypes>
+  </dataTypes>
+  <notes>
+    <note>
+      <label>n1</label>
+      <semantics>A note</semantics>
+    </note>
+  </notes>
+</objectModel>"#;
+        let dom = parse_with(xml, ParseBackend::Dom).unwrap();
+        let streamed = parse_with(xml, ParseBackend::Streaming).unwrap();
+        assert_eq!(4, dom.warnings.len());
+        assert_eq!(dom.warnings, streamed.warnings);
+        assert_eq!(dom.object_model, streamed.object_model);
+
+        let xml = xml
+            .replace("<name>Count</name>", "")
+            .replace("Neither", "Sometimes");
+        let dom = parse_with(&xml, ParseBackend::Dom).unwrap_err();
+        let streamed = parse_with(&xml, ParseBackend::Streaming).unwrap_err();
+        assert_eq!(dom.to_string(), streamed.to_string());
+    }
+}
@@ -0,0 +1,579 @@
+//! A reader for the subset of TOML used by the `fom-check.toml` configuration
+//! files of `fom check`.
+//!
+//! Supported are comments, tables, arrays of tables, bare, quoted and dotted
+//! keys, and values that are basic or literal strings, integers, booleans,
+//! arrays or inline tables. Floats, dates and times, and multi-line strings
+//! are not.
+
+use std::fmt;
+
+/// A TOML value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+    Table(Table),
+    /// The tables of an array of tables, e.g. of `[[override]]`.
+    Tables(Vec<Table>),
+}
+
+impl Value {
+    /// Return the name of the type of the value, for messages.
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "a string",
+            Value::Integer(_) => "an integer",
+            Value::Boolean(_) => "a boolean",
+            Value::Array(_) => "an array",
+            Value::Table(_) | Value::Tables(_) => "a table",
+        }
+    }
+}
+
+/// The entries of a table, in the order they were written.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Table {
+    pub(crate) entries: Vec<Entry>,
+}
+
+/// A key of a table and its value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) key: String,
+    pub(crate) value: Value,
+    /// The line, from 1, on which the key was first written.
+    pub(crate) line: usize,
+}
+
+impl Table {
+    fn get_mut(&mut self, key: &str) -> Option<&mut Entry> {
+        self.entries.iter_mut().find(|entry| entry.key == key)
+    }
+}
+
+/// Why a document could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TomlError {
+    /// The line, from 1, on which the problem was found.
+    pub(crate) line: usize,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for TomlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.line, self.message)
+    }
+}
+
+/// Read the document into its root table.
+pub(crate) fn parse(source: &str) -> Result<Table, TomlError> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        next: 0,
+        line: 1,
+    };
+    parser.document().map_err(|message| TomlError {
+        line: parser.line,
+        message,
+    })
+}
+
+struct Parser {
+    chars: Vec<char>,
+    next: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.next).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.next += 1;
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    /// Skip spaces and tabs, and a comment up to the end of the line.
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.bump();
+        }
+        if self.peek() == Some('#') {
+            while !matches!(self.peek(), None | Some('\n')) {
+                self.bump();
+            }
+        }
+    }
+
+    /// Skip spaces, comments and line ends.
+    fn skip_lines(&mut self) {
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                Some('\n') => {
+                    self.bump();
+                }
+                Some('\r') if self.chars.get(self.next + 1) == Some(&'\n') => {
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Expect the end of a line, after an optional comment.
+    fn end_of_line(&mut self) -> Result<(), String> {
+        self.skip_spaces();
+        match self.peek() {
+            None | Some('\n') => Ok(()),
+            Some('\r') if self.chars.get(self.next + 1) == Some(&'\n') => Ok(()),
+            Some(c) => Err(format!("expected the end of the line, found '{}'", c)),
+        }
+    }
+
+    fn document(&mut self) -> Result<Table, String> {
+        let mut root = Table::default();
+        // The path of the table that key/value pairs are added to, and the
+        // paths of the tables defined by headers so far, with the number of
+        // tables in each array of tables passed through.
+        let mut current: Vec<String> = Vec::new();
+        let mut defined: Vec<Vec<String>> = Vec::new();
+        loop {
+            self.skip_lines();
+            match self.peek() {
+                None => return Ok(root),
+                Some('[') => {
+                    self.bump();
+                    let array = self.peek() == Some('[');
+                    if array {
+                        self.bump();
+                    }
+                    self.skip_spaces();
+                    let keys = self.keys()?;
+                    let close = if array { "]]" } else { "]" };
+                    for c in close.chars() {
+                        if self.bump() != Some(c) {
+                            return Err(format!("expected '{}' to close the table header", close));
+                        }
+                    }
+                    self.end_of_line()?;
+                    let line = self.line;
+                    let identity = header_table(&mut root, &keys, array, line)?;
+                    if !array {
+                        if defined.contains(&identity) {
+                            return Err(format!("the table '{}' is defined twice", keys.join(".")));
+                        }
+                        defined.push(identity);
+                    }
+                    current = keys;
+                }
+                Some(_) => {
+                    let line = self.line;
+                    let keys = self.keys()?;
+                    if self.bump() != Some('=') {
+                        return Err(format!("expected '=' after the key '{}'", keys.join(".")));
+                    }
+                    self.skip_spaces();
+                    let value = self.value()?;
+                    self.end_of_line()?;
+                    let table = table_at(&mut root, &current);
+                    insert(table, &keys, value, line)?;
+                }
+            }
+        }
+    }
+
+    /// Read a dotted key, and the spaces after it.
+    fn keys(&mut self) -> Result<Vec<String>, String> {
+        let mut keys = vec![self.key()?];
+        self.skip_spaces();
+        while self.peek() == Some('.') {
+            self.bump();
+            self.skip_spaces();
+            keys.push(self.key()?);
+            self.skip_spaces();
+        }
+        Ok(keys)
+    }
+
+    fn key(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some('"') => self.basic_string(),
+            Some('\'') => self.literal_string(),
+            _ => {
+                let start = self.next;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    self.bump();
+                }
+                if start == self.next {
+                    return Err(match self.peek() {
+                        Some(c) => format!("expected a key, found '{}'", c),
+                        None => String::from("expected a key"),
+                    });
+                }
+                Ok(self.chars[start..self.next].iter().collect())
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('"') => {
+                if self.chars[self.next..].starts_with(&['"', '"', '"']) {
+                    return Err(String::from("multi-line strings are not supported"));
+                }
+                self.basic_string().map(Value::String)
+            }
+            Some('\'') => {
+                if self.chars[self.next..].starts_with(&['\'', '\'', '\'']) {
+                    return Err(String::from("multi-line strings are not supported"));
+                }
+                self.literal_string().map(Value::String)
+            }
+            Some('[') => self.array(),
+            Some('{') => self.inline_table(),
+            Some(c) if c.is_ascii_alphanumeric() || c == '+' || c == '-' => {
+                let start = self.next;
+                while self.peek().is_some_and(|c| {
+                    c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '_' | '.' | ':')
+                }) {
+                    self.bump();
+                }
+                let word: String = self.chars[start..self.next].iter().collect();
+                match word.as_str() {
+                    "true" => Ok(Value::Boolean(true)),
+                    "false" => Ok(Value::Boolean(false)),
+                    _ => word
+                        .replace('_', "")
+                        .parse()
+                        .map(Value::Integer)
+                        .map_err(|_| format!("unsupported value '{}'", word)),
+                }
+            }
+            Some(c) => Err(format!("expected a value, found '{}'", c)),
+            None => Err(String::from("expected a value")),
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, String> {
+        self.bump();
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(value),
+                Some('\\') => value.push(self.escape()?),
+                Some('\n') | None => return Err(String::from("unclosed string")),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, String> {
+        match self.bump() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some(c @ ('u' | 'U')) => {
+                let length = if c == 'u' { 4 } else { 8 };
+                let digits: String = (0..length).filter_map(|_| self.bump()).collect();
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid escape '\\{}{}'", c, digits))
+            }
+            Some(c) => Err(format!("invalid escape '\\{}'", c)),
+            None => Err(String::from("unclosed string")),
+        }
+    }
+
+    fn literal_string(&mut self) -> Result<String, String> {
+        self.bump();
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                Some('\'') => return Ok(value),
+                Some('\n') | None => return Err(String::from("unclosed string")),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.bump();
+        let mut values = Vec::new();
+        loop {
+            self.skip_lines();
+            if self.peek() == Some(']') {
+                self.bump();
+                return Ok(Value::Array(values));
+            }
+            values.push(self.value()?);
+            self.skip_lines();
+            match self.bump() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(values)),
+                _ => return Err(String::from("expected ',' or ']' in the array")),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Value, String> {
+        self.bump();
+        let mut table = Table::default();
+        self.skip_spaces();
+        if self.peek() == Some('}') {
+            self.bump();
+            return Ok(Value::Table(table));
+        }
+        loop {
+            self.skip_spaces();
+            let line = self.line;
+            let keys = self.keys()?;
+            if self.bump() != Some('=') {
+                return Err(format!("expected '=' after the key '{}'", keys.join(".")));
+            }
+            self.skip_spaces();
+            let value = self.value()?;
+            insert(&mut table, &keys, value, line)?;
+            self.skip_spaces();
+            match self.bump() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Table(table)),
+                _ => return Err(String::from("expected ',' or '}' in the inline table")),
+            }
+        }
+    }
+}
+
+/// Return the table at the path, which the header of the table or of an array
+/// of tables has created. Paths through arrays of tables lead to their last
+/// table.
+fn table_at<'a>(root: &'a mut Table, path: &[String]) -> &'a mut Table {
+    let mut table = root;
+    for key in path {
+        let entry = table
+            .get_mut(key)
+            .expect("the tables of headers are created as they are read");
+        table = match &mut entry.value {
+            Value::Table(child) => child,
+            Value::Tables(children) => children.last_mut().expect("arrays of tables are not empty"),
+            _ => unreachable!("headers only lead through tables"),
+        };
+    }
+    table
+}
+
+/// Create the table of a header, or add a table to the array of tables of one,
+/// and return the identity of the table: its path, with the number of tables
+/// in each array of tables passed through.
+fn header_table(
+    root: &mut Table,
+    keys: &[String],
+    array: bool,
+    line: usize,
+) -> Result<Vec<String>, String> {
+    let mut table = root;
+    let mut identity = Vec::new();
+    for (i, key) in keys.iter().enumerate() {
+        let last = i + 1 == keys.len();
+        if table.get_mut(key).is_none() {
+            let value = if last && array {
+                Value::Tables(Vec::new())
+            } else {
+                Value::Table(Table::default())
+            };
+            table.entries.push(Entry {
+                key: key.clone(),
+                value,
+                line,
+            });
+        }
+        let entry = table.get_mut(key).expect("the entry was just added");
+        identity.push(key.clone());
+        let already_defined = || format!("the key '{}' is already defined", keys[..=i].join("."));
+        table = match &mut entry.value {
+            Value::Tables(children) => {
+                if last && array {
+                    children.push(Table::default());
+                } else if last {
+                    return Err(already_defined());
+                }
+                identity.push(children.len().to_string());
+                children.last_mut().expect("arrays of tables are not empty")
+            }
+            Value::Table(child) if !(last && array) => child,
+            _ => return Err(already_defined()),
+        };
+    }
+    Ok(identity)
+}
+
+/// Add the value to the table under the dotted key.
+fn insert(table: &mut Table, keys: &[String], value: Value, line: usize) -> Result<(), String> {
+    let (key, parents) = keys.split_last().expect("keys are not empty");
+    let mut table = table;
+    for (i, parent) in parents.iter().enumerate() {
+        if table.get_mut(parent).is_none() {
+            table.entries.push(Entry {
+                key: parent.clone(),
+                value: Value::Table(Table::default()),
+                line,
+            });
+        }
+        table = match &mut table
+            .get_mut(parent)
+            .expect("the entry was just added")
+            .value
+        {
+            Value::Table(child) => child,
+            _ => {
+                return Err(format!(
+                    "the key '{}' is already defined",
+                    keys[..=i].join(".")
+                ))
+            }
+        };
+    }
+    if table.get_mut(key).is_some() {
+        return Err(format!("the key '{}' is already defined", keys.join(".")));
+    }
+    table.entries.push(Entry {
+        key: key.clone(),
+        value,
+        line,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get<'a>(table: &'a Table, key: &str) -> &'a Value {
+        &table
+            .entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .unwrap()
+            .value
+    }
+
+    #[test]
+    fn test_values() {
+        let table = parse(
+            r#"# A comment
+string = "a \"quoted\" \u00e9"  # another
+literal = 'C:\modules'
+integer = -1_000
+boolean = true
+array = [
+    "one",  # the first
+    'two',
+]
+inline = { level = "deny", nested.key = 1 }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            &Value::String(String::from("a \"quoted\" \u{e9}")),
+            get(&table, "string")
+        );
+        assert_eq!(
+            &Value::String(String::from("C:\\modules")),
+            get(&table, "literal")
+        );
+        assert_eq!(&Value::Integer(-1000), get(&table, "integer"));
+        assert_eq!(&Value::Boolean(true), get(&table, "boolean"));
+        assert_eq!(
+            &Value::Array(vec![
+                Value::String(String::from("one")),
+                Value::String(String::from("two")),
+            ]),
+            get(&table, "array")
+        );
+        let Value::Table(inline) = get(&table, "inline") else {
+            panic!("not a table");
+        };
+        assert_eq!(&Value::String(String::from("deny")), get(inline, "level"));
+        let Value::Table(nested) = get(inline, "nested") else {
+            panic!("not a table");
+        };
+        assert_eq!(&Value::Integer(1), get(nested, "key"));
+        assert_eq!(10, table.entries[5].line);
+    }
+
+    #[test]
+    fn test_tables() {
+        let table = parse(
+            r#"top = 1
+
+[files]
+include = ["*.xml"]
+
+[[override]]
+files = ["a.xml"]
+[override.lints]
+"missing-semantics" = "allow"
+
+[[override]]
+files = ["b.xml"]
+"#,
+        )
+        .unwrap();
+        let keys: Vec<&str> = table
+            .entries
+            .iter()
+            .map(|entry| entry.key.as_str())
+            .collect();
+        assert_eq!(vec!["top", "files", "override"], keys);
+        let Value::Tables(overrides) = get(&table, "override") else {
+            panic!("not an array of tables");
+        };
+        assert_eq!(2, overrides.len());
+        let Value::Table(lints) = get(&overrides[0], "lints") else {
+            panic!("not a table");
+        };
+        assert_eq!(
+            &Value::String(String::from("allow")),
+            get(lints, "missing-semantics")
+        );
+        assert_eq!(1, overrides[1].entries.len());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source: &str| parse(source).unwrap_err().to_string();
+        assert_eq!("2: the key 'a' is already defined", error("a = 1\na = 2"));
+        assert_eq!(
+            "3: the table 'files' is defined twice",
+            error("[files]\n\n[files]")
+        );
+        assert_eq!("1: unsupported value '1.5'", error("a = 1.5"));
+        assert_eq!("1: unclosed string", error("a = \"one"));
+        assert_eq!(
+            "1: multi-line strings are not supported",
+            error("a = \"\"\"one\"\"\"")
+        );
+        assert_eq!(
+            "1: expected the end of the line, found 'b'",
+            error("a = 1 b = 2")
+        );
+        assert_eq!("1: expected a key, found '='", error("= 1"));
+        assert_eq!("2: the key 'a' is already defined", error("a = 1\n[a]"));
+    }
+}
@@ -0,0 +1,238 @@
+//! The versions of the IEEE 1516.2 OMT schema that object model documents may
+//! be written against, and the upgrade of object models between them.
+//!
+//! IEEE 1516.2-2025, the OMT of HLA 4, is supported as the structure of the
+//! 2010 OMT plus the extendable record encodings that 2025 adds,
+//! `HLAextendableFixedRecord` and `HLAextendableVariantRecord`, whose records
+//! may gain fields or alternatives in later versions of a FOM without breaking
+//! federates built against earlier ones. Documents of either version are
+//! converted into the same object model. The 2025 schemas are not bundled, so
+//! anything else that they change is not known to fom-tools.
+
+use crate::dom::Element;
+use crate::*;
+use std::fmt;
+
+/// A version of the OMT schema, identified by the namespace of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SchemaVersion {
+    Ieee1516_2010,
+    Ieee1516_2025,
+}
+
+impl SchemaVersion {
+    /// Return the version of the schema whose namespace, as published by
+    /// either IEEE or SISO, is the provided one.
+    pub fn from_namespace(namespace: &str) -> Option<Self> {
+        match namespace {
+            IEEE1516_2010_NAMESPACE | SISO_IEEE1516_2010_NAMESPACE => {
+                Some(SchemaVersion::Ieee1516_2010)
+            }
+            IEEE1516_2025_NAMESPACE | SISO_IEEE1516_2025_NAMESPACE => {
+                Some(SchemaVersion::Ieee1516_2025)
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the namespace of the schema as published by IEEE.
+    pub fn namespace(&self) -> &'static str {
+        match self {
+            SchemaVersion::Ieee1516_2010 => IEEE1516_2010_NAMESPACE,
+            SchemaVersion::Ieee1516_2025 => IEEE1516_2025_NAMESPACE,
+        }
+    }
+
+    /// Return the namespace of the schema as published by SISO.
+    pub fn siso_namespace(&self) -> &'static str {
+        match self {
+            SchemaVersion::Ieee1516_2010 => SISO_IEEE1516_2010_NAMESPACE,
+            SchemaVersion::Ieee1516_2025 => SISO_IEEE1516_2025_NAMESPACE,
+        }
+    }
+
+    /// Return the year of the version, as used in the names of its schema
+    /// files, e.g. `IEEE1516-DIF-2010.xsd`.
+    pub fn year(&self) -> &'static str {
+        match self {
+            SchemaVersion::Ieee1516_2010 => "2010",
+            SchemaVersion::Ieee1516_2025 => "2025",
+        }
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IEEE 1516.2-{}", self.year())
+    }
+}
+
+impl ObjectModelType {
+    /// Return the version of the OMT schema of the document this object model
+    /// was read from, or is written as, if its namespace is that of one.
+    pub fn schema_version(&self) -> Option<SchemaVersion> {
+        self.namespace
+            .as_deref()
+            .and_then(SchemaVersion::from_namespace)
+    }
+}
+
+/// The values of elements, by their path relative to `objectModel`, that were
+/// added to the OMT by a later version of the schema.
+const ADDED_VALUES: &[(&str, &str, SchemaVersion)] = &[
+    (
+        "dataTypes/fixedRecordDataTypes/fixedRecordData/encoding",
+        "HLAextendableFixedRecord",
+        SchemaVersion::Ieee1516_2025,
+    ),
+    (
+        "dataTypes/variantRecordDataTypes/variantRecordData/encoding",
+        "HLAextendableVariantRecord",
+        SchemaVersion::Ieee1516_2025,
+    ),
+];
+
+/// Warn about the values in the provided document that were added by a
+/// version of the schema later than that of the document.
+pub(crate) fn check_schema_version(cx: &mut Context, e: &Element, version: SchemaVersion) {
+    for child in e.child_elements() {
+        check_added_values(cx, child, &child.name, version);
+    }
+}
+
+/// Warn about the values in the provided element, and in its descendants, that
+/// were added by a version of the schema later than the provided one. The path
+/// is that of the element relative to `objectModel`.
+pub(crate) fn check_added_values(
+    cx: &mut Context,
+    e: &Element,
+    path: &str,
+    version: SchemaVersion,
+) {
+    cx.enter(e);
+    let text = get_element_text(e);
+    let added = ADDED_VALUES.iter().find(|(added_path, value, added_in)| {
+        *added_path == path && text.trim() == *value && *added_in > version
+    });
+    if let Some((_, _, added_in)) = added {
+        cx.warning(
+            FomErrorKind::RequiresSchemaVersion(*added_in),
+            e,
+            Some(text.clone()),
+        );
+    }
+    for child in e.child_elements() {
+        check_added_values(cx, child, &format!("{}/{}", path, child.name), version);
+    }
+    cx.leave();
+}
+
+/// Return the provided 2010 object model as a 2025 object model, as far as
+/// fom-tools supports 2025 (see the module documentation). The upgrade moves
+/// the document into the 2025 namespace, as published by the same body, and
+/// points the schema locations of the 2010 schema files at the files of the
+/// same name for 2025, e.g. `IEEE1516-DIF-2025.xsd`, which are not bundled
+/// with fom-tools. Nothing else is changed: in particular, records keep their
+/// encodings rather than becoming extendable. Object models of other versions
+/// are returned unchanged.
+pub fn upgrade_to_2025(fom: &ObjectModelType) -> ObjectModelType {
+    let mut upgraded = fom.clone();
+    let upgrade_namespace = |namespace: &str| match namespace {
+        IEEE1516_2010_NAMESPACE => Some(IEEE1516_2025_NAMESPACE),
+        SISO_IEEE1516_2010_NAMESPACE => Some(SISO_IEEE1516_2025_NAMESPACE),
+        _ => None,
+    };
+    let namespace = match fom.namespace.as_deref().and_then(upgrade_namespace) {
+        Some(namespace) => namespace,
+        None => return upgraded,
+    };
+    upgraded.namespace = Some(String::from(namespace));
+    for location in upgraded.schema_location.iter_mut().flatten() {
+        if let Some(namespace) = upgrade_namespace(&location.namespace) {
+            location.namespace = String::from(namespace);
+            location.location = location.location.replace("-2010.xsd", "-2025.xsd");
+        }
+    }
+    upgraded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_2025_document() {
+        let xml = r#"<objectModel xmlns="http://standards.ieee.org/IEEE1516-2025">
+  <dataTypes>
+    <fixedRecordDataTypes>
+      <fixedRecordData>
+        <name>Position</name>
+        <encoding>HLAextendableFixedRecord</encoding>
+      </fixedRecordData>
+    </fixedRecordDataTypes>
+    <variantRecordDataTypes>
+      <variantRecordData>
+        <name>Event</name>
+        <encoding>HLAextendableVariantRecord</encoding>
+      </variantRecordData>
+    </variantRecordDataTypes>
+  </dataTypes>
+</objectModel>"#;
+        let parsed = parse_reader_with_options(xml.as_bytes(), &ParseOptions::default()).unwrap();
+        assert!(parsed.warnings.is_empty());
+        assert_eq!(SourceFormat::Ieee1516_2025, parsed.format);
+        let fom = parsed.object_model;
+        assert_eq!(Some(SchemaVersion::Ieee1516_2025), fom.schema_version());
+        let data_types = fom.data_types.as_ref().unwrap();
+        let fixed_record = &data_types
+            .fixed_record_data_types
+            .as_ref()
+            .unwrap()
+            .fixed_record_datas
+            .as_ref()
+            .unwrap()[0];
+        assert_eq!(
+            Some(FixedRecordEncodingType::HlaExtendableFixedRecord),
+            fixed_record.encoding
+        );
+
+        // Written back in the 2025 namespace.
+        let e = serialize_object_model(&fom);
+        assert_eq!(Some(IEEE1516_2025_NAMESPACE), e.namespace.as_deref());
+        assert!(fom.same_model(&parse_object_model(&e).unwrap()));
+
+        // The extendable encodings are not part of the 2010 OMT.
+        let xml = xml.replace("IEEE1516-2025", "IEEE1516-2010");
+        let parsed = parse_reader_with_options(xml.as_bytes(), &ParseOptions::default()).unwrap();
+        assert_eq!(SourceFormat::Ieee1516_2010, parsed.format);
+        let warnings: Vec<String> = parsed.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            vec![
+                "6:9: /objectModel/dataTypes/fixedRecordDataTypes/fixedRecordData[Position]/encoding: 'HLAextendableFixedRecord' requires IEEE 1516.2-2025",
+                "12:9: /objectModel/dataTypes/variantRecordDataTypes/variantRecordData[Event]/encoding: 'HLAextendableVariantRecord' requires IEEE 1516.2-2025",
+            ],
+            warnings
+        );
+    }
+
+    #[test]
+    fn test_upgrade_to_2025() {
+        let fom = standard_mim();
+        assert_eq!(Some(SchemaVersion::Ieee1516_2010), fom.schema_version());
+        let upgraded = upgrade_to_2025(fom);
+        assert_eq!(
+            Some(SchemaVersion::Ieee1516_2025),
+            upgraded.schema_version()
+        );
+        assert_eq!(
+            Some(SISO_IEEE1516_2025_NAMESPACE),
+            upgraded.namespace.as_deref()
+        );
+        for location in upgraded.schema_location.iter().flatten() {
+            assert_eq!(SISO_IEEE1516_2025_NAMESPACE, location.namespace);
+            assert!(!location.location.contains("2010"));
+        }
+        assert_eq!(fom.objects, upgraded.objects);
+        assert_eq!(upgraded, upgrade_to_2025(&upgraded));
+    }
+}
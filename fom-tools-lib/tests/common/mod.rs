@@ -0,0 +1,26 @@
+//! Helpers shared by the integration tests.
+
+use std::path::{Path, PathBuf};
+
+/// Return the paths of the object model documents bundled with the crate and
+/// of the examples, in sorted order.
+pub fn bundled_documents() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut documents = Vec::new();
+    let mut directories = vec![
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("data"),
+        root.join("examples"),
+    ];
+    while let Some(directory) = directories.pop() {
+        for entry in directory.read_dir().unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension() == Some("xml".as_ref()) {
+                documents.push(path);
+            }
+        }
+    }
+    documents.sort();
+    documents
+}
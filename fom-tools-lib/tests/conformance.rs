@@ -0,0 +1,63 @@
+//! Checks the bundled object models, each as a whole, for conformance with
+//! IEEE 1516.2-2010.
+
+use fom_tools_lib::conformance::{check_conformance, Profile, Severity};
+use fom_tools_lib::dom::Element;
+use std::fs::File;
+use std::path::Path;
+
+fn read_modules(directories: &[&str]) -> Vec<Element> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut paths = Vec::new();
+    for directory in directories {
+        for entry in root.join(directory).read_dir().unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension() == Some("xml".as_ref()) {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| Element::parse(File::open(path).unwrap()).unwrap())
+        .collect()
+}
+
+fn check(modules: &[Element], profile: Profile) -> Vec<String> {
+    let modules: Vec<&Element> = modules.iter().collect();
+    check_conformance(&modules, profile)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn test_rpr_and_netn_foms_conform() {
+    let modules = read_modules(&[
+        "fom-tools-lib/data/modules/RPR-FOM_v2.0",
+        "fom-tools-lib/data/modules/NETN-FOM-3.0",
+    ]);
+    assert_eq!(26, modules.len());
+    assert_eq!(Vec::<String>::new(), check(&modules, Profile::Omt));
+    assert_eq!(Vec::<String>::new(), check(&modules, Profile::Fdd));
+}
+
+#[test]
+fn test_netn_fom_refers_to_the_rpr_fom() {
+    let modules = read_modules(&["fom-tools-lib/data/modules/NETN-FOM-3.0"]);
+    assert!(check(&modules, Profile::Omt)
+        .iter()
+        .any(|finding| finding.contains("value 'WorldLocationStruct' of 'dataTypeRef'")));
+}
+
+#[test]
+fn test_restaurant_examples_only_have_warnings() {
+    let modules = read_modules(&["examples"]);
+    let modules: Vec<&Element> = modules.iter().collect();
+    let findings = check_conformance(&modules, Profile::Omt);
+    assert!(!findings.is_empty());
+    assert!(findings
+        .iter()
+        .all(|finding| finding.severity == Severity::Warning));
+}
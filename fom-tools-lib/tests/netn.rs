@@ -0,0 +1,42 @@
+//! Checks the bundled NETN and RPR modules against the NETN naming
+//! conventions.
+
+use fom_tools_lib::lint::LintRegistry;
+use fom_tools_lib::netn::{NamingExceptions, NetnPack};
+use std::path::Path;
+
+fn lint_modules(directories: &[&str], pack: &NetnPack) -> Vec<String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+    let mut registry = LintRegistry::empty();
+    registry.register_pack(pack);
+    let mut diagnostics = Vec::new();
+    for directory in directories {
+        for entry in root.join(directory).read_dir().unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension() == Some("xml".as_ref()) {
+                let fom = fom_tools_lib::parse_file(&path).unwrap();
+                for diagnostic in registry.run(&fom) {
+                    diagnostics.push(format!("{}:{}", path.display(), diagnostic));
+                }
+            }
+        }
+    }
+    diagnostics
+}
+
+#[test]
+fn test_netn_and_rpr_foms_follow_the_conventions_with_legacy_exceptions() {
+    let directories = ["modules/RPR-FOM_v2.0", "modules/NETN-FOM-3.0"];
+    assert_eq!(
+        Vec::<String>::new(),
+        lint_modules(&directories, &NetnPack::new())
+    );
+}
+
+#[test]
+fn test_legacy_datatypes_break_the_conventions() {
+    let pack = NetnPack::with_exceptions(NamingExceptions::default());
+    let diagnostics = lint_modules(&["modules/NETN-FOM-3.0"], &pack);
+    assert!(diagnostics.iter().any(|diagnostic| diagnostic
+        .ends_with("fixed record datatype name 'GeodeticPoint' does not end with 'Struct' [netn-fixed-record-datatype-name]")));
+}
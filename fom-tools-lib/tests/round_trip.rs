@@ -0,0 +1,148 @@
+//! Reads every bundled object model document, converts the object model back
+//! into XML and checks that nothing in the document was lost on the way, both
+//! when converting to a document object model and when writing a document.
+//! Also checks that the streaming parse backend reads every bundled document
+//! as the DOM backend does.
+
+mod common;
+
+use common::bundled_documents;
+use fom_tools_lib::dom::{Element, Node};
+use fom_tools_lib::{
+    parse_file_with_options, parse_object_model, parse_str, serialize_object_model, to_xml_string,
+    ParseBackend, ParseOptions, WriteOptions,
+};
+use std::fs::File;
+
+/// An element reduced to what the object model is expected to keep: its name
+/// and namespace, its attributes in a canonical order with whitespace
+/// normalised, its trimmed text when it has no child elements, and its child
+/// elements in the same namespace.
+#[derive(Debug, PartialEq)]
+struct Normalized {
+    name: String,
+    namespace: Option<String>,
+    attributes: Vec<(Option<String>, String, String)>,
+    text: String,
+    children: Vec<Normalized>,
+}
+
+fn normalize(e: &Element) -> Normalized {
+    let mut attributes: Vec<(Option<String>, String, String)> = e
+        .attributes
+        .iter()
+        .map(|a| {
+            let value: Vec<&str> = a.value.split_whitespace().collect();
+            (a.namespace.clone(), a.name.clone(), value.join(" "))
+        })
+        .collect();
+    attributes.sort();
+
+    let children: Vec<Normalized> = e
+        .child_elements()
+        .filter(|child| child.namespace == e.namespace)
+        .map(normalize)
+        .collect();
+    let text = if children.is_empty() {
+        let text: Vec<&str> = e
+            .children
+            .iter()
+            .filter_map(|node| match node {
+                Node::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        text.concat().trim().to_string()
+    } else {
+        String::new()
+    };
+
+    Normalized {
+        name: e.name.clone(),
+        namespace: e.namespace.clone(),
+        attributes,
+        text,
+        children,
+    }
+}
+
+/// Return the path to the first difference between the two elements.
+fn first_difference(expected: &Normalized, actual: &Normalized, path: &str) -> Option<String> {
+    let path = format!("{}/{}", path, expected.name);
+    if expected.name != actual.name
+        || expected.namespace != actual.namespace
+        || expected.attributes != actual.attributes
+        || expected.text != actual.text
+    {
+        return Some(format!(
+            "{}: expected {:?}, got {:?}",
+            path, expected, actual
+        ));
+    }
+    for (i, expected_child) in expected.children.iter().enumerate() {
+        match actual.children.get(i) {
+            Some(actual_child) => {
+                if let Some(difference) = first_difference(expected_child, actual_child, &path) {
+                    return Some(difference);
+                }
+            }
+            None => return Some(format!("{}: missing {}", path, expected_child.name)),
+        }
+    }
+    if actual.children.len() > expected.children.len() {
+        return Some(format!(
+            "{}: unexpected {}",
+            path,
+            actual.children[expected.children.len()].name
+        ));
+    }
+    None
+}
+
+#[test]
+fn test_round_trip_bundled_documents() {
+    let documents = bundled_documents();
+    assert!(documents.len() > 40);
+
+    for document in documents {
+        let source = Element::parse(File::open(&document).unwrap()).unwrap();
+        let fom = parse_object_model(&source)
+            .unwrap_or_else(|errors| panic!("{}: {}", document.display(), errors));
+
+        let serialized = serialize_object_model(&fom);
+        if let Some(difference) = first_difference(&normalize(&source), &normalize(&serialized), "")
+        {
+            panic!("{}: {}", document.display(), difference);
+        }
+
+        let reparsed = parse_object_model(&serialized)
+            .unwrap_or_else(|errors| panic!("{}: {}", document.display(), errors));
+        assert!(fom.same_model(&reparsed), "{}", document.display());
+
+        let written = to_xml_string(&fom, &WriteOptions::default()).unwrap();
+        let reread =
+            parse_str(&written).unwrap_or_else(|e| panic!("{}: {}", document.display(), e));
+        assert!(fom.same_model(&reread), "{}", document.display());
+    }
+}
+
+#[test]
+fn test_streaming_matches_dom_on_bundled_documents() {
+    for document in bundled_documents() {
+        let dom = parse_file_with_options(&document, &ParseOptions::default())
+            .unwrap_or_else(|e| panic!("{}: {}", document.display(), e));
+        let options = ParseOptions {
+            backend: ParseBackend::Streaming,
+            ..ParseOptions::default()
+        };
+        let streamed = parse_file_with_options(&document, &options)
+            .unwrap_or_else(|e| panic!("{}: {}", document.display(), e));
+        assert_eq!(dom.warnings, streamed.warnings, "{}", document.display());
+        assert_eq!(
+            dom.object_model,
+            streamed.object_model,
+            "{}",
+            document.display()
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Validates every bundled object model document against the IEEE 1516.2-2010
+//! DIF schema, which each of them is published as conforming to.
+
+mod common;
+
+use common::bundled_documents;
+use fom_tools_lib::schema::Schema;
+
+#[test]
+fn test_bundled_documents_conform_to_the_dif_schema() {
+    let documents = bundled_documents();
+    assert!(documents.len() > 40);
+
+    for document in documents {
+        let violations: Vec<String> = Schema::dif()
+            .validate_file(&document)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            Vec::<String>::new(),
+            violations,
+            "{} does not conform to the DIF schema",
+            document.display()
+        );
+    }
+}